@@ -0,0 +1,25 @@
+//! Generates the gRPC service from `proto/ads1263.proto` (`grpc` feature)
+//! and sets up the Node.js addon build (`node` feature)
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    grpc();
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
+
+#[cfg(feature = "grpc")]
+fn grpc() {
+    println!("cargo:rerun-if-changed=proto/ads1263.proto");
+    println!("cargo:rerun-if-env-changed=PROTOC");
+    // Use the vendored protoc unless PROTOC points to one, e.g. on 32-bit ARM
+    if std::env::var_os("PROTOC").is_none() {
+        let protoc = protoc_bin_vendored::protoc_bin_path()
+            .expect("no vendored protoc for this host; install protobuf-compiler and set PROTOC");
+        // SAFETY: the build script is single-threaded
+        unsafe { std::env::set_var("PROTOC", protoc) };
+    }
+    tonic_prost_build::compile_protos("proto/ads1263.proto")
+        .expect("failed to compile proto/ads1263.proto");
+}
@@ -0,0 +1,1062 @@
+//! Background acquisition engine
+//!
+//! [`Acquisition`] moves the driver onto a dedicated thread that runs the
+//! DRDY-synchronized scan loop and delivers each scan, as timestamped
+//! [`Sample`]s, over a bounded channel. When the consumer falls behind, the
+//! channel fills up and the sampler blocks until there is room again. Alternatively the sampler can
+//! write into a [`RingBuffer`] that never blocks it, or fill fixed-size
+//! [`Block`]s that are handed over whole while the next one fills
+//! ([`BlockAcquisition`]).
+//!
+//! With a single channel the multiplexer is set once and every conversion
+//! is read at the data rate; conversions the sampler misses while blocked
+//! are counted (see [`Acquisition::dropped`]).
+//!
+//! An acquisition can be paused and resumed without tearing down the
+//! thread or its buffers; pauses, like watchdog recoveries, are reported as
+//! [`AcquisitionEvent`]s so gaps in the data can be explained. The ADC1
+//! configuration or the scanned channels can be changed the same way
+//! ([`Acquisition::reconfigure`]); samples carry the configuration
+//! generation they were taken with.
+//!
+//! Start one with [`Ads1263::start_acquisition`].
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+#[cfg(feature = "realtime")]
+use crate::realtime::RealtimeConfig;
+use crate::registers::Adc1Config;
+use crate::ring::RingBuffer;
+use crate::sample::Sample;
+use crate::scan::ScanPlan;
+use crate::sink::Sink;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Longest a paused sampler sleeps before re-checking its state
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+
+/// Settings for a background acquisition
+#[derive(Debug, Clone)]
+pub struct AcquisitionConfig {
+    /// Number of scans buffered between the sampler and the consumer
+    pub capacity: usize,
+    /// Stop after this many scans; `None` runs until stopped
+    pub max_scans: Option<u64>,
+    /// Name of the sampler thread
+    pub thread_name: String,
+    /// Recover from transient faults instead of stopping; `None` stops on
+    /// the first error
+    pub watchdog: Option<WatchdogConfig>,
+    /// Scheduling applied to the sampler thread
+    #[cfg(feature = "realtime")]
+    pub realtime: Option<RealtimeConfig>,
+}
+
+impl Default for AcquisitionConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 64,
+            max_scans: None,
+            thread_name: "ads1263-sampler".into(),
+            watchdog: None,
+            #[cfg(feature = "realtime")]
+            realtime: None,
+        }
+    }
+}
+
+/// Fault recovery settings of an acquisition
+///
+/// Reads failing with a [transient](Ads1263Error::is_transient) error
+/// (stalled DRDY, SPI or CRC errors) are skipped. After `max_errors` in a
+/// row the sampler re-initializes the device with its cached configuration
+/// ([`Ads1263::recover`]), retrying up to `max_attempts` times
+/// `retry_delay` apart, and reports the outcome as an [`AcquisitionEvent`].
+/// Other errors stop the acquisition as without a watchdog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Consecutive failed reads that trigger a re-initialization
+    pub max_errors: u32,
+    /// Re-initialization attempts before the acquisition stops
+    pub max_attempts: u32,
+    /// Pause between re-initialization attempts
+    pub retry_delay: Duration,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            max_errors: 3,
+            max_attempts: 5,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Something that happened to a running acquisition besides delivering scans
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcquisitionEvent {
+    /// The watchdog re-initialized the device; the scans in between are missing
+    Recovered {
+        /// When the device was running again
+        at: Instant,
+        /// The error that triggered the recovery
+        reason: String,
+        /// Re-initialization attempts needed
+        attempts: u32,
+    },
+    /// The watchdog gave up; the acquisition stops with this error
+    RecoveryFailed {
+        /// When the last attempt failed
+        at: Instant,
+        /// The error of the last attempt
+        reason: String,
+    },
+    /// Sampling was paused; no scans follow until [`Resumed`](Self::Resumed)
+    Paused {
+        /// When conversions were stopped
+        at: Instant,
+    },
+    /// Sampling was resumed after a pause
+    Resumed {
+        /// When conversions were restarted
+        at: Instant,
+    },
+    /// A [`Reconfiguration`] was applied; later samples carry `generation`
+    Reconfigured {
+        /// When the new configuration took effect
+        at: Instant,
+        /// The new configuration generation
+        generation: u32,
+    },
+}
+
+/// Change applied to a running acquisition between two scans
+///
+/// A plan reprograms gain and data rate per step, and those settings stay
+/// in effect; send an [`Adc1`](Self::Adc1) change after switching back to
+/// plain channels to set them explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reconfiguration {
+    /// Write a new ADC1 configuration and restart conversions
+    Adc1(Adc1Config),
+    /// Scan these channels with the current ADC1 configuration
+    Channels(Vec<u8>),
+    /// Scan with per-step gain, data rate, and settling
+    Plan(ScanPlan),
+}
+
+/// What the sampler scans
+enum ScanSource {
+    Channels(Vec<u8>),
+    Plan(ScanPlan),
+}
+
+impl ScanSource {
+    /// Samples per scan
+    fn len(&self) -> usize {
+        match self {
+            ScanSource::Channels(channels) => channels.len(),
+            ScanSource::Plan(plan) => plan.len(),
+        }
+    }
+}
+
+/// Sampler ends of the event and reconfiguration channels
+struct Control {
+    events: Sender<AcquisitionEvent>,
+    changes: Receiver<Reconfiguration>,
+}
+
+/// State shared between an acquisition handle and its sampler thread
+struct Shared {
+    running: AtomicBool,
+    paused: AtomicBool,
+    /// Conversions missed by the sampler (single-channel acquisitions)
+    dropped: AtomicU64,
+    /// Configuration generation of the samples being produced
+    generation: AtomicU32,
+}
+
+/// Handle to a running background acquisition
+///
+/// Each message holds one scan of the configured channels in order, one
+/// [`Sample`] per channel. The sampler stops after the first error (which
+/// is delivered as the last message), after `max_scans` scans, on
+/// [`stop`](Self::stop), or when the handle is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, DataRate, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let acq = adc.start_acquisition(&[0, 1, 2], AcquisitionConfig::default())?;
+/// for scan in acq.iter().take(100) {
+///     for sample in scan? {
+///         println!("ch{} = {} at {:?}", sample.channel, sample.raw, sample.timestamp);
+///     }
+/// }
+/// let adc = acq.join()?; // driver is returned for reuse
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct Acquisition {
+    receiver: Option<Receiver<Result<Vec<Sample>>>>,
+    shared: Arc<Shared>,
+    events: Receiver<AcquisitionEvent>,
+    changes: Sender<Reconfiguration>,
+    worker: Option<JoinHandle<Ads1263>>,
+}
+
+impl Acquisition {
+    /// Spawn the sampler thread delivering scans over the channel
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the thread cannot be created
+    pub(crate) fn start(adc: Ads1263, channels: &[u8], config: AcquisitionConfig) -> Result<Self> {
+        let (tx, rx) = mpsc::sync_channel(config.capacity);
+        Self::spawn(adc, channels, config, rx, move |scan| {
+            tx.send(scan.map(<[Sample]>::to_vec)).is_ok()
+        })
+        .map_err(|(e, _)| e)
+    }
+
+    /// Spawn the sampler thread writing scans into a ring buffer
+    ///
+    /// Only the error that stops the sampler is sent over the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::ChannelCountMismatch` if the ring's frame
+    /// length differs from the number of channels
+    pub(crate) fn start_ring(
+        adc: Ads1263,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        ring: Arc<RingBuffer>,
+    ) -> Result<Self> {
+        if ring.frame_len() != channels.len() {
+            return Err(Ads1263Error::ChannelCountMismatch {
+                expected: ring.frame_len(),
+                got: channels.len(),
+            });
+        }
+        let (tx, rx) = mpsc::sync_channel(1);
+        let mut values = Vec::with_capacity(channels.len());
+        Self::spawn(adc, channels, config, rx, move |scan| match scan {
+            // The ring's frame length is fixed; a reconfiguration must keep the channel count
+            Ok(samples) if samples.len() != ring.frame_len() => {
+                let _ = tx.send(Err(Ads1263Error::ChannelCountMismatch {
+                    expected: ring.frame_len(),
+                    got: samples.len(),
+                }));
+                false
+            }
+            Ok(samples) => {
+                values.clear();
+                values.extend(samples.iter().map(|s| s.raw as u32));
+                ring.push(&values);
+                true
+            }
+            Err(e) => {
+                let _ = tx.send(Err(e));
+                false
+            }
+        })
+        .map_err(|(e, _)| e)
+    }
+
+    /// Spawn the sampler thread writing scans into a sink
+    ///
+    /// Only the error that stops the sampler (including a failed write) is
+    /// sent over the channel.
+    pub(crate) fn start_sink(
+        adc: Ads1263,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        sink: impl Sink + 'static,
+    ) -> Result<Self> {
+        Self::try_start_sink(adc, channels, config, sink).map_err(|(e, _)| e)
+    }
+
+    /// Like [`start_sink`](Self::start_sink), but hands the driver back if
+    /// the sampler thread cannot be created
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn try_start_sink(
+        adc: Ads1263,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        mut sink: impl Sink + 'static,
+    ) -> std::result::Result<Self, (Ads1263Error, Ads1263)> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        Self::spawn(adc, channels, config, rx, move |scan| {
+            match scan.and_then(|samples| sink.write(samples)) {
+                Ok(()) => true,
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    false
+                }
+            }
+        })
+    }
+
+    /// Spawn the sampler thread filling blocks of `block_scans` scans
+    ///
+    /// Only the error that stops the sampler is sent over the channel.
+    pub(crate) fn start_blocks(
+        adc: Ads1263,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        block_scans: usize,
+    ) -> Result<BlockAcquisition> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let (block_tx, block_rx) = mpsc::sync_channel(config.capacity);
+        let mut filler = BlockFiller::new(channels.len(), block_scans, block_tx);
+        let acquisition = Self::spawn(adc, channels, config, rx, move |scan| match scan {
+            Ok(samples) => filler.push(samples),
+            Err(e) => {
+                let _ = tx.send(Err(e));
+                false
+            }
+        })
+        .map_err(|(e, _)| e)?;
+        Ok(BlockAcquisition {
+            acquisition,
+            blocks: Some(block_rx),
+        })
+    }
+
+    /// Spawn the sampler thread; `deliver` returns false to stop it
+    ///
+    /// The driver is handed back with the error if the thread cannot be
+    /// created.
+    #[allow(clippy::result_large_err)]
+    fn spawn(
+        adc: Ads1263,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        receiver: Receiver<Result<Vec<Sample>>>,
+        deliver: impl FnMut(Result<&[Sample]>) -> bool + Send + 'static,
+    ) -> std::result::Result<Self, (Ads1263Error, Ads1263)> {
+        let shared = Arc::new(Shared {
+            running: AtomicBool::new(true),
+            paused: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            generation: AtomicU32::new(0),
+        });
+        let source = ScanSource::Channels(channels.to_vec());
+        let state = shared.clone();
+        let max_scans = config.max_scans;
+        let (event_tx, events) = mpsc::channel();
+        let (changes, change_rx) = mpsc::channel();
+        let control = Control {
+            events: event_tx,
+            changes: change_rx,
+        };
+        let watchdog = Watchdog::new(config.watchdog);
+
+        // The driver only moves to the thread once it exists
+        let (handoff, driver) = mpsc::sync_channel(1);
+        let run = move || {
+            let adc = driver.recv().expect("driver handed over after spawn");
+            sample_loop(adc, source, max_scans, &state, &control, watchdog, deliver)
+        };
+
+        #[cfg(feature = "realtime")]
+        let worker = match &config.realtime {
+            Some(rt) => rt.spawn(&config.thread_name, run),
+            None => thread::Builder::new().name(config.thread_name).spawn(run),
+        };
+        #[cfg(not(feature = "realtime"))]
+        let worker = thread::Builder::new().name(config.thread_name).spawn(run);
+        let worker = match worker {
+            Ok(worker) => worker,
+            Err(e) => return Err((e.into(), adc)),
+        };
+        // The thread waits for the driver before anything else, so the
+        // receiver is still alive
+        let _ = handoff.send(adc);
+
+        log::info!("Acquisition started");
+        Ok(Self {
+            receiver: Some(receiver),
+            shared,
+            events,
+            changes,
+            worker: Some(worker),
+        })
+    }
+
+    /// true while the sampler thread is still producing scans
+    pub fn is_running(&self) -> bool {
+        self.shared.running.load(Ordering::Acquire)
+    }
+
+    /// Conversions the sampler has missed so far
+    ///
+    /// Only counted for single-channel acquisitions, where conversions
+    /// follow each other at the data rate. Per-sample counts are in
+    /// [`Sample::dropped_since_last`].
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Events since the last call, e.g. watchdog recoveries
+    ///
+    /// Does not block.
+    pub fn events(&self) -> impl Iterator<Item = AcquisitionEvent> + '_ {
+        self.events.try_iter()
+    }
+
+    /// Pause sampling after the current scan
+    ///
+    /// The sampler stops ADC1 conversions, disconnects the input
+    /// multiplexer, and reports [`AcquisitionEvent::Paused`]; the thread,
+    /// buffers, and any sink stay in place. Scans already buffered can
+    /// still be received.
+    pub fn pause(&self) {
+        self.shared.paused.store(true, Ordering::Release);
+    }
+
+    /// Resume a paused acquisition
+    ///
+    /// Conversions restart and [`AcquisitionEvent::Resumed`] is reported
+    /// before the next scan.
+    pub fn resume(&self) {
+        self.shared.paused.store(false, Ordering::Release);
+        self.wake();
+    }
+
+    /// true if sampling is paused or a pause was requested
+    pub fn is_paused(&self) -> bool {
+        self.shared.paused.load(Ordering::Acquire)
+    }
+
+    /// Apply a new configuration after the current scan
+    ///
+    /// The sampler finishes the scan in progress, reprograms the device,
+    /// bumps the configuration generation, and reports
+    /// [`AcquisitionEvent::Reconfigured`]; every later [`Sample`] carries the
+    /// new [`generation`](Sample::generation). A paused acquisition applies
+    /// the change once resumed. If the device rejects the change, the
+    /// acquisition stops with that error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::WorkerTerminated` if the sampler has stopped
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ads1263::{AcquisitionConfig, Ads1263, DataRate, Hal, Reconfiguration};
+    ///
+    /// let mut adc = Ads1263::new(Hal::new()?);
+    /// adc.init_adc1(DataRate::Sps1200)?;
+    /// let config = adc.adc1_config();
+    ///
+    /// let acq = adc.start_acquisition(&[0, 1], AcquisitionConfig::default())?;
+    /// acq.reconfigure(Reconfiguration::Adc1(ads1263::Adc1Config {
+    ///     data_rate: DataRate::Sps400,
+    ///     ..config
+    /// }))?;
+    /// acq.reconfigure(Reconfiguration::Channels(vec![0, 1, 2, 3]))?;
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn reconfigure(&self, change: Reconfiguration) -> Result<()> {
+        if !self.is_running() {
+            return Err(Ads1263Error::WorkerTerminated);
+        }
+        self.changes
+            .send(change)
+            .map_err(|_| Ads1263Error::WorkerTerminated)
+    }
+
+    /// Configuration generation of the samples currently produced
+    ///
+    /// Starts at 0 and is incremented by every applied [`reconfigure`](Self::reconfigure).
+    pub fn generation(&self) -> u32 {
+        self.shared.generation.load(Ordering::Acquire)
+    }
+
+    /// Ask the sampler to stop after the current scan
+    ///
+    /// Scans already buffered can still be received.
+    pub fn stop(&self) {
+        self.shared.running.store(false, Ordering::Release);
+        self.wake();
+    }
+
+    /// Wake a paused sampler so it sees a state change
+    fn wake(&self) {
+        if let Some(worker) = &self.worker {
+            worker.thread().unpark();
+        }
+    }
+
+    /// Wait for the next scan
+    ///
+    /// # Returns
+    ///
+    /// `None` once the sampler has stopped and the buffer is empty
+    pub fn recv(&self) -> Option<Result<Vec<Sample>>> {
+        self.receiver.as_ref()?.recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next scan
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if no scan arrived in time and
+    /// `Ads1263Error::WorkerTerminated` if the sampler has stopped
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Vec<Sample>> {
+        let receiver = self
+            .receiver
+            .as_ref()
+            .ok_or(Ads1263Error::WorkerTerminated)?;
+        match receiver.recv_timeout(timeout) {
+            Ok(scan) => scan,
+            Err(RecvTimeoutError::Timeout) => Err(Ads1263Error::Timeout),
+            Err(RecvTimeoutError::Disconnected) => Err(Ads1263Error::WorkerTerminated),
+        }
+    }
+
+    /// Take the next scan if one is buffered
+    pub fn try_recv(&self) -> Option<Result<Vec<Sample>>> {
+        self.receiver.as_ref()?.try_recv().ok()
+    }
+
+    /// Blocking iterator over scans until the sampler stops
+    pub fn iter(&self) -> impl Iterator<Item = Result<Vec<Sample>>> + '_ {
+        self.receiver.iter().flat_map(|rx| rx.iter())
+    }
+
+    /// Stop the sampler and recover the driver
+    ///
+    /// Scans still buffered are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::WorkerTerminated` if the sampler thread panicked
+    pub fn join(mut self) -> Result<Ads1263> {
+        self.stop();
+        // Unblock a sampler waiting for room in a full channel
+        self.receiver = None;
+        let worker = self.worker.take().ok_or(Ads1263Error::WorkerTerminated)?;
+        worker.join().map_err(|_| Ads1263Error::WorkerTerminated)
+    }
+}
+
+impl Drop for Acquisition {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Consecutive scans from a [`BlockAcquisition`]
+///
+/// Dereferences to the samples, scan after scan. Dropping the block hands
+/// its buffer back to the sampler for reuse.
+pub struct Block {
+    samples: Vec<Sample>,
+    channels: usize,
+    generation: u32,
+    recycle: Sender<Vec<Sample>>,
+}
+
+impl Block {
+    /// Number of scans in the block
+    pub fn scans(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    /// Samples per scan
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Configuration generation shared by all samples of the block
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Iterate over the scans, one slice of samples per scan
+    pub fn iter_scans(&self) -> impl Iterator<Item = &[Sample]> {
+        self.samples.chunks_exact(self.channels)
+    }
+
+    /// Take the samples out of the block instead of recycling the buffer
+    pub fn into_vec(mut self) -> Vec<Sample> {
+        std::mem::take(&mut self.samples)
+    }
+}
+
+impl Deref for Block {
+    type Target = [Sample];
+
+    fn deref(&self) -> &[Sample] {
+        &self.samples
+    }
+}
+
+impl Drop for Block {
+    fn drop(&mut self) {
+        if self.samples.capacity() > 0 {
+            let mut buffer = std::mem::take(&mut self.samples);
+            buffer.clear();
+            let _ = self.recycle.send(buffer);
+        }
+    }
+}
+
+/// Sampler side of a block acquisition
+///
+/// Two buffers are allocated up front: one fills while the other is with
+/// the consumer. More are only allocated if the consumer holds on to
+/// blocks. A partially filled block is delivered when the sampler stops
+/// and when the configuration changes, so a block never mixes generations.
+struct BlockFiller {
+    current: Vec<Sample>,
+    block_scans: usize,
+    block_len: usize,
+    channels: usize,
+    generation: u32,
+    blocks: SyncSender<Block>,
+    recycle: Sender<Vec<Sample>>,
+    free: Receiver<Vec<Sample>>,
+}
+
+impl BlockFiller {
+    fn new(channels: usize, block_scans: usize, blocks: SyncSender<Block>) -> Self {
+        let channels = channels.max(1);
+        let block_scans = block_scans.max(1);
+        let block_len = block_scans * channels;
+        let (recycle, free) = mpsc::channel();
+        let _ = recycle.send(Vec::with_capacity(block_len));
+        Self {
+            current: Vec::with_capacity(block_len),
+            block_scans,
+            block_len,
+            channels,
+            generation: 0,
+            blocks,
+            recycle,
+            free,
+        }
+    }
+
+    /// Append a scan; returns false once the consumer is gone
+    fn push(&mut self, scan: &[Sample]) -> bool {
+        let generation = scan.first().map_or(self.generation, |s| s.generation);
+        if generation != self.generation {
+            // Close the block of the previous configuration
+            if !self.current.is_empty() && !self.send() {
+                return false;
+            }
+            self.generation = generation;
+            self.channels = scan.len().max(1);
+            self.block_len = self.block_scans * self.channels;
+        }
+        self.current.extend_from_slice(scan);
+        if self.current.len() < self.block_len {
+            return true;
+        }
+        self.send()
+    }
+
+    /// Hand the current block to the consumer and continue in a free buffer
+    fn send(&mut self) -> bool {
+        let next = self
+            .free
+            .try_recv()
+            .unwrap_or_else(|_| Vec::with_capacity(self.block_len));
+        let full = std::mem::replace(&mut self.current, next);
+        // Blocks only when all queued blocks are still unread
+        self.blocks.send(self.block(full)).is_ok()
+    }
+
+    fn block(&self, samples: Vec<Sample>) -> Block {
+        Block {
+            samples,
+            channels: self.channels,
+            generation: self.generation,
+            recycle: self.recycle.clone(),
+        }
+    }
+}
+
+impl Drop for BlockFiller {
+    fn drop(&mut self) {
+        if !self.current.is_empty() {
+            let rest = std::mem::take(&mut self.current);
+            // Returns once the consumer reads a block or drops the queue
+            let _ = self.blocks.send(self.block(rest));
+        }
+    }
+}
+
+/// Handle to a running block acquisition
+///
+/// The sampler fills one [`Block`] of scans while the consumer processes
+/// the previous one, so capture is gap-free as long as the consumer keeps
+/// up on average; up to `capacity` full blocks are queued to absorb
+/// slower periods. Stopping works as for [`Acquisition`]; the error that
+/// stops the sampler is returned after the last block.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, DataRate, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps38400)?;
+///
+/// let acq = adc.start_block_acquisition(&[0], 4096, AcquisitionConfig::default())?;
+/// for block in acq.iter().take(100) {
+///     let block = block?;
+///     let sum: i64 = block.iter().map(|s| i64::from(s.raw)).sum();
+///     println!("mean {:.1}", sum as f64 / block.len() as f64);
+/// }
+/// println!("{} conversions missed", acq.dropped());
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct BlockAcquisition {
+    acquisition: Acquisition,
+    blocks: Option<Receiver<Block>>,
+}
+
+impl BlockAcquisition {
+    /// true while the sampler thread is still producing scans
+    pub fn is_running(&self) -> bool {
+        self.acquisition.is_running()
+    }
+
+    /// Conversions the sampler has missed so far; see [`Acquisition::dropped`]
+    pub fn dropped(&self) -> u64 {
+        self.acquisition.dropped()
+    }
+
+    /// Events since the last call; see [`Acquisition::events`]
+    pub fn events(&self) -> impl Iterator<Item = AcquisitionEvent> + '_ {
+        self.acquisition.events()
+    }
+
+    /// Pause sampling; see [`Acquisition::pause`]
+    ///
+    /// The block being filled is continued after [`resume`](Self::resume).
+    pub fn pause(&self) {
+        self.acquisition.pause();
+    }
+
+    /// Resume a paused acquisition
+    pub fn resume(&self) {
+        self.acquisition.resume();
+    }
+
+    /// true if sampling is paused or a pause was requested
+    pub fn is_paused(&self) -> bool {
+        self.acquisition.is_paused()
+    }
+
+    /// Apply a new configuration; see [`Acquisition::reconfigure`]
+    ///
+    /// The block being filled is delivered early, so every block holds
+    /// samples of a single configuration.
+    pub fn reconfigure(&self, change: Reconfiguration) -> Result<()> {
+        self.acquisition.reconfigure(change)
+    }
+
+    /// Configuration generation of the samples currently produced
+    pub fn generation(&self) -> u32 {
+        self.acquisition.generation()
+    }
+
+    /// Ask the sampler to stop after the current scan
+    ///
+    /// The partially filled block is still delivered.
+    pub fn stop(&self) {
+        self.acquisition.stop();
+    }
+
+    /// Wait for the next block
+    ///
+    /// # Returns
+    ///
+    /// `None` once the sampler has stopped and all blocks were received
+    pub fn recv(&self) -> Option<Result<Block>> {
+        match self.blocks.as_ref()?.recv() {
+            Ok(block) => Some(Ok(block)),
+            Err(_) => self.stop_error(),
+        }
+    }
+
+    /// Wait up to `timeout` for the next block
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if no block arrived in time,
+    /// `Ads1263Error::WorkerTerminated` if the sampler has stopped, or the
+    /// error that stopped it
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Block> {
+        let blocks = self.blocks.as_ref().ok_or(Ads1263Error::WorkerTerminated)?;
+        match blocks.recv_timeout(timeout) {
+            Ok(block) => Ok(block),
+            Err(RecvTimeoutError::Timeout) => Err(Ads1263Error::Timeout),
+            Err(RecvTimeoutError::Disconnected) => match self.stop_error() {
+                Some(Err(e)) => Err(e),
+                _ => Err(Ads1263Error::WorkerTerminated),
+            },
+        }
+    }
+
+    /// Take the next block if one is ready
+    pub fn try_recv(&self) -> Option<Result<Block>> {
+        self.blocks.as_ref()?.try_recv().ok().map(Ok)
+    }
+
+    /// Blocking iterator over blocks until the sampler stops
+    pub fn iter(&self) -> impl Iterator<Item = Result<Block>> + '_ {
+        std::iter::from_fn(|| self.recv())
+    }
+
+    /// Stop the sampler and recover the driver
+    ///
+    /// Blocks still queued are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::WorkerTerminated` if the sampler thread panicked
+    pub fn join(mut self) -> Result<Ads1263> {
+        self.stop();
+        // Unblock a sampler waiting for room in a full queue
+        self.blocks = None;
+        self.acquisition.receiver = None;
+        let worker = self
+            .acquisition
+            .worker
+            .take()
+            .ok_or(Ads1263Error::WorkerTerminated)?;
+        worker.join().map_err(|_| Ads1263Error::WorkerTerminated)
+    }
+
+    /// Error that stopped the sampler, if any
+    fn stop_error(&self) -> Option<Result<Block>> {
+        let error = self.acquisition.try_recv()?.err()?;
+        Some(Err(error))
+    }
+}
+
+/// Scan until stopped, the scan limit is reached, delivery fails, or a read
+/// fails and the watchdog (if any) cannot recover
+fn sample_loop(
+    mut adc: Ads1263,
+    mut source: ScanSource,
+    max_scans: Option<u64>,
+    shared: &Shared,
+    control: &Control,
+    mut watchdog: Watchdog,
+    mut deliver: impl FnMut(Result<&[Sample]>) -> bool,
+) -> Ads1263 {
+    let mut samples = Vec::with_capacity(source.len());
+    let mut scans = 0u64;
+    // Single channel: route the multiplexer on the first read and after faults, pauses,
+    // or reconfigurations
+    let mut route = true;
+    while shared.running.load(Ordering::Acquire) && max_scans.is_none_or(|max| scans < max) {
+        if shared.paused.load(Ordering::Acquire) {
+            if let Err(e) = pause(&mut adc, shared, &control.events) {
+                deliver(Err(e));
+                break;
+            }
+            route = true;
+            continue;
+        }
+        if let Ok(change) = control.changes.try_recv() {
+            if let Err(e) = reconfigure(&mut adc, &mut source, change, shared, &control.events) {
+                deliver(Err(e));
+                break;
+            }
+            samples.reserve(source.len());
+            route = true;
+            continue;
+        }
+        let scan = match &source {
+            // Keep the multiplexer so conversions run back-to-back
+            ScanSource::Channels(channels) if channels.len() == 1 => {
+                read_continuous(&mut adc, channels[0], route).map(|sample| {
+                    shared
+                        .dropped
+                        .fetch_add(u64::from(sample.dropped_since_last), Ordering::Relaxed);
+                    samples.clear();
+                    samples.push(sample);
+                })
+            }
+            ScanSource::Channels(channels) => adc.get_samples_into(channels, &mut samples),
+            ScanSource::Plan(plan) => adc.scan_samples_into(plan, &mut samples),
+        };
+        let scan = match scan {
+            Ok(()) => {
+                route = false;
+                watchdog.reset();
+                let generation = shared.generation.load(Ordering::Relaxed);
+                for sample in &mut samples {
+                    sample.generation = generation;
+                }
+                Ok(samples.as_slice())
+            }
+            Err(e) => match watchdog.handle(&mut adc, e, &shared.running, &control.events) {
+                Ok(()) => {
+                    route = true;
+                    continue;
+                }
+                Err(e) => Err(e),
+            },
+        };
+        let failed = scan.is_err();
+        if !deliver(scan) || failed {
+            break;
+        }
+        scans += 1;
+    }
+    shared.running.store(false, Ordering::Release);
+    log::info!("Acquisition stopped after {} scans", scans);
+    adc
+}
+
+/// Apply a configuration change between scans and start a new generation
+fn reconfigure(
+    adc: &mut Ads1263,
+    source: &mut ScanSource,
+    change: Reconfiguration,
+    shared: &Shared,
+    events: &Sender<AcquisitionEvent>,
+) -> Result<()> {
+    match change {
+        Reconfiguration::Adc1(config) => adc.configure_adc1(config)?,
+        Reconfiguration::Channels(channels) => *source = ScanSource::Channels(channels),
+        Reconfiguration::Plan(plan) => *source = ScanSource::Plan(plan),
+    }
+    let generation = shared.generation.fetch_add(1, Ordering::AcqRel) + 1;
+    log::info!("Acquisition reconfigured (generation {})", generation);
+    let _ = events.send(AcquisitionEvent::Reconfigured {
+        at: Instant::now(),
+        generation,
+    });
+    Ok(())
+}
+
+/// Stop conversions until resumed or stopped
+fn pause(adc: &mut Ads1263, shared: &Shared, events: &Sender<AcquisitionEvent>) -> Result<()> {
+    adc.stop_adc1()?;
+    adc.release_mux()?;
+    log::info!("Acquisition paused");
+    let _ = events.send(AcquisitionEvent::Paused { at: Instant::now() });
+
+    while shared.paused.load(Ordering::Acquire) && shared.running.load(Ordering::Acquire) {
+        thread::park_timeout(PAUSE_POLL);
+    }
+    if !shared.running.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    adc.start_adc1()?;
+    log::info!("Acquisition resumed");
+    let _ = events.send(AcquisitionEvent::Resumed { at: Instant::now() });
+    Ok(())
+}
+
+/// Read the next conversion of a single channel, routing it first if asked
+fn read_continuous(adc: &mut Ads1263, channel: u8, route: bool) -> Result<Sample> {
+    if route {
+        adc.select_channel(channel)?;
+    }
+    adc.read_next_sample(channel)
+}
+
+/// Sampler-side fault handling
+struct Watchdog {
+    config: Option<WatchdogConfig>,
+    /// Consecutive failed reads
+    errors: u32,
+}
+
+impl Watchdog {
+    fn new(config: Option<WatchdogConfig>) -> Self {
+        Self { config, errors: 0 }
+    }
+
+    /// Note a successful read
+    fn reset(&mut self) {
+        self.errors = 0;
+    }
+
+    /// Handle a failed read
+    ///
+    /// # Errors
+    ///
+    /// Returns the error to stop the acquisition with: `error` itself if it
+    /// is not recoverable, or the last re-initialization error
+    fn handle(
+        &mut self,
+        adc: &mut Ads1263,
+        error: Ads1263Error,
+        running: &AtomicBool,
+        events: &Sender<AcquisitionEvent>,
+    ) -> Result<()> {
+        let Some(config) = self.config else {
+            return Err(error);
+        };
+        if !error.is_transient() {
+            return Err(error);
+        }
+        self.errors += 1;
+        log::warn!(
+            "Acquisition read failed ({}/{}): {}",
+            self.errors,
+            config.max_errors,
+            error
+        );
+        if self.errors < config.max_errors {
+            return Ok(());
+        }
+
+        let reason = error.to_string();
+        let mut last = error;
+        for attempt in 1..=config.max_attempts.max(1) {
+            if attempt > 1 {
+                thread::sleep(config.retry_delay);
+            }
+            if !running.load(Ordering::Acquire) {
+                return Ok(());
+            }
+            match adc.recover() {
+                Ok(()) => {
+                    log::warn!("Device recovered after {} attempt(s)", attempt);
+                    self.errors = 0;
+                    let _ = events.send(AcquisitionEvent::Recovered {
+                        at: Instant::now(),
+                        reason,
+                        attempts: attempt,
+                    });
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!("Recovery attempt {} failed: {}", attempt, e);
+                    last = e;
+                }
+            }
+        }
+        let _ = events.send(AcquisitionEvent::RecoveryFailed {
+            at: Instant::now(),
+            reason: last.to_string(),
+        });
+        Err(last)
+    }
+}
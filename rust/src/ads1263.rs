@@ -12,9 +12,143 @@
 //! - DAC outputs for sensor biasing
 //! - RTD measurement support
 
+use crate::acquisition::{Acquisition, AcquisitionConfig, BlockAcquisition};
+use crate::burst::{Burst, BurstTiming};
+use crate::chip_gpio::ChipGpio;
+use crate::dual::{DualScan, DualScanConfig};
 use crate::error::{Ads1263Error, Result};
 use crate::hal::Hal;
+use crate::overrun::{OverrunDetector, OverrunStats};
+use crate::protocol::{self, ADC2_POLL_INTERVAL_US, ADC2_TIMEOUT_MS, RESET_HOLD_MS};
 use crate::registers::*;
+use crate::ring::RingBuffer;
+use crate::rtd::{RtdConfig, RtdElement, RtdPins};
+use crate::sample::{Sample, Samples, SamplesConfig};
+use crate::scan::ScanPlan;
+use crate::sensors::{SensorSetup, gain_factor};
+use crate::sink::Sink;
+use crate::stats::{Stats, StatsAccumulator};
+use crate::trigger::{Capture, Trigger, TriggerConfig};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// DRDY timeout as a multiple of the expected conversion period
+const DRDY_TIMEOUT_FACTOR: u32 = 4;
+
+/// Extra DRDY timeout allowance for scheduling latency
+const DRDY_TIMEOUT_MARGIN: Duration = Duration::from_millis(20);
+
+/// Clock speeds tried in order by [`Ads1263::probe_spi_clock`]
+const SPI_PROBE_STEPS: [u32; 7] = [
+    1_000_000, 2_000_000, 3_000_000, 4_000_000, 5_000_000, 6_000_000, 8_000_000,
+];
+
+/// INPMUX value with both inputs floating
+const INPMUX_FLOATING: u8 = 0xFF;
+
+/// Patterns written to a scratch register while probing the SPI clock
+const SPI_PROBE_PATTERNS: [u8; 4] = [0x55, 0xAA, 0x00, 0xFF];
+
+/// Conversions averaged by an ADC1 calibration
+const CALIBRATION_CONVERSIONS: u32 = 16;
+
+/// FSCAL value for unity gain
+const FSCAL_UNITY: u32 = 0x40_0000;
+
+/// How ADC1 conversions are started and stopped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConversionControl {
+    /// START1/STOP1 opcodes over SPI (default)
+    #[default]
+    Command,
+    /// Toggling the START pin, for deterministic start timing
+    Pin,
+}
+
+/// How an RTD is connected to the excitation and sense inputs
+///
+/// [`RtdPins`] assigns the inputs and [`RtdConfig`] the whole circuit; on
+/// the HAT's RTD circuit, IDAC1 on AIN3
+/// excites the RTD, AIN7 and AIN6 sense it, and the reference resistor
+/// between AIN4 and AIN5 makes the measurement ratiometric.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RtdWiring {
+    /// One current through the RTD; lead resistance adds to the reading
+    TwoWire,
+    /// A second, matched current from IDAC2 on AINCOM through the
+    /// compensation lead cancels the lead resistance (default); see
+    /// [`Ads1263::read_rtd_config`] for the IDAC mismatch
+    #[default]
+    ThreeWire,
+    /// One current through the force leads, measured on separate sense
+    /// leads that carry no current (Kelvin connection)
+    FourWire,
+}
+
+impl RtdWiring {
+    /// Number of excitation currents flowing through the reference resistor
+    pub(crate) fn currents(self) -> f64 {
+        match self {
+            RtdWiring::ThreeWire => 2.0,
+            RtdWiring::TwoWire | RtdWiring::FourWire => 1.0,
+        }
+    }
+
+    /// RTD resistance of a raw conversion at `gain`, with `r_ref` in ohms
+    pub fn resistance(self, raw: u32, r_ref: f64, gain: Gain) -> f64 {
+        (raw as f64 / 2147483647.0) * self.currents() * r_ref / gain_factor(gain)
+    }
+}
+
+/// ADC1 calibration command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calibration {
+    /// Offset with the inputs shorted internally
+    SelfOffset,
+    /// Offset of the selected input, which must be at zero
+    SystemOffset,
+    /// Gain of the selected input, which must be at full scale
+    SystemGain,
+}
+
+impl Calibration {
+    fn command(self) -> Command {
+        match self {
+            Calibration::SelfOffset => Command::SelfOCal1,
+            Calibration::SystemOffset => Command::SysOCal1,
+            Calibration::SystemGain => Command::SysGCal1,
+        }
+    }
+}
+
+/// ADC1 offset and full-scale calibration registers
+///
+/// The device subtracts `offset` from each conversion and scales the
+/// result by `full_scale / 0x400000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adc1Calibration {
+    /// Signed 24-bit offset correction (`OFCAL`)
+    pub offset: i32,
+    /// Unsigned 24-bit full-scale correction (`FSCAL`), 0x400000 for unity
+    pub full_scale: u32,
+}
+
+impl Adc1Calibration {
+    /// Gain correction factor of `full_scale`
+    pub fn gain(&self) -> f64 {
+        f64::from(self.full_scale) / f64::from(FSCAL_UNITY)
+    }
+}
+
+impl Default for Adc1Calibration {
+    /// Reset values: no offset, unity gain
+    fn default() -> Self {
+        Self {
+            offset: 0,
+            full_scale: FSCAL_UNITY,
+        }
+    }
+}
 
 /// ADS1263 ADC Driver
 ///
@@ -39,6 +173,11 @@ use crate::registers::*;
 pub struct Ads1263 {
     hal: Hal,
     scan_mode: InputMode,
+    adc1: Adc1Config,
+    control: ConversionControl,
+    overrun: OverrunDetector,
+    /// Sequence number of the next sample
+    sequence: u64,
 }
 
 impl Ads1263 {
@@ -55,9 +194,23 @@ impl Ads1263 {
         Self {
             hal,
             scan_mode: InputMode::SingleEnded,
+            adc1: Adc1Config::default(),
+            control: ConversionControl::default(),
+            overrun: OverrunDetector::default(),
+            sequence: 0,
         }
     }
 
+    /// Get a shared reference to the hardware abstraction layer
+    pub fn hal(&self) -> &Hal {
+        &self.hal
+    }
+
+    /// Get a mutable reference to the hardware abstraction layer
+    pub fn hal_mut(&mut self) -> &mut Hal {
+        &mut self.hal
+    }
+
     // ========================================================================
     // Low-level operations
     // ========================================================================
@@ -68,11 +221,11 @@ impl Ads1263 {
     fn reset(&mut self) {
         log::debug!("Performing hardware reset");
         self.hal.set_rst(true);
-        self.hal.delay_ms(300);
+        self.hal.delay_ms(u64::from(RESET_HOLD_MS));
         self.hal.set_rst(false);
-        self.hal.delay_ms(300);
+        self.hal.delay_ms(u64::from(RESET_HOLD_MS));
         self.hal.set_rst(true);
-        self.hal.delay_ms(300);
+        self.hal.delay_ms(u64::from(RESET_HOLD_MS));
     }
 
     /// Send a command to the ADC
@@ -81,10 +234,7 @@ impl Ads1263 {
     ///
     /// * `cmd` - Command to send
     fn write_cmd(&mut self, cmd: Command) -> Result<()> {
-        self.hal.set_cs(false);
-        self.hal.spi_write_byte(cmd as u8)?;
-        self.hal.set_cs(true);
-        Ok(())
+        self.hal.spi_frame(&[cmd as u8], &mut [0u8])
     }
 
     /// Write to a register
@@ -93,13 +243,10 @@ impl Ads1263 {
     ///
     /// * `reg` - Register to write to
     /// * `data` - Data byte to write
-    fn write_reg(&mut self, reg: Register, data: u8) -> Result<()> {
-        self.hal.set_cs(false);
-        self.hal.spi_write_byte(Command::WReg as u8 | reg as u8)?;
-        self.hal.spi_write_byte(0x00)?; // Number of registers to write minus 1
-        self.hal.spi_write_byte(data)?;
-        self.hal.set_cs(true);
-        Ok(())
+    pub(crate) fn write_reg(&mut self, reg: Register, data: u8) -> Result<()> {
+        // Command, number of registers to write minus 1, data
+        let command = [Command::WReg as u8 | reg as u8, 0x00, data];
+        self.hal.spi_frame(&command, &mut [0u8; 3])
     }
 
     /// Read from a register
@@ -111,13 +258,12 @@ impl Ads1263 {
     /// # Returns
     ///
     /// The register value
-    fn read_reg(&mut self, reg: Register) -> Result<u8> {
-        self.hal.set_cs(false);
-        self.hal.spi_write_byte(Command::RReg as u8 | reg as u8)?;
-        self.hal.spi_write_byte(0x00)?; // Number of registers to read minus 1
-        let data = self.hal.spi_read_byte()?;
-        self.hal.set_cs(true);
-        Ok(data)
+    pub(crate) fn read_reg(&mut self, reg: Register) -> Result<u8> {
+        // Command, number of registers to read minus 1, then clock out the value
+        let command = [Command::RReg as u8 | reg as u8, 0x00, 0x00];
+        let mut response = [0u8; 3];
+        self.hal.spi_frame(&command, &mut response)?;
+        Ok(response[2])
     }
 
     /// Write to a register and verify the write
@@ -146,33 +292,6 @@ impl Ads1263 {
         }
     }
 
-    // ========================================================================
-    // Checksum validation
-    // ========================================================================
-
-    /// Verify checksum for ADC data
-    ///
-    /// The ADS1263 appends a CRC byte to each data read.
-    ///
-    /// # Arguments
-    ///
-    /// * `val` - The data value to check
-    /// * `crc` - The CRC byte received from the ADC
-    ///
-    /// # Returns
-    ///
-    /// true if checksum is valid, false otherwise
-    fn checksum(val: u32, crc: u8) -> bool {
-        let mut sum: u8 = 0;
-        let mut v = val;
-        while v != 0 {
-            sum = sum.wrapping_add((v & 0xFF) as u8);
-            v >>= 8;
-        }
-        sum = sum.wrapping_add(0x9B);
-        sum == crc
-    }
-
     // ========================================================================
     // Public configuration methods
     // ========================================================================
@@ -184,7 +303,30 @@ impl Ads1263 {
     /// The chip ID (should be 1 for ADS1263)
     pub fn read_chip_id(&mut self) -> Result<u8> {
         let id = self.read_reg(Register::Id)?;
-        Ok(id >> 5)
+        Ok(protocol::chip_id(id))
+    }
+
+    /// Read a single register
+    ///
+    /// Decode the value with [`Register::fields`].
+    pub fn read_register(&mut self, reg: Register) -> Result<u8> {
+        self.read_reg(reg)
+    }
+
+    /// Read all registers in one transfer, indexed by address
+    ///
+    /// The values line up with [`Register::ALL`].
+    pub fn read_registers(&mut self) -> Result<[u8; 27]> {
+        // Command, number of registers to read minus 1, then clock out the values
+        let mut command = [0u8; 29];
+        command[0] = Command::RReg as u8 | Register::Id as u8;
+        command[1] = (Register::ALL.len() - 1) as u8;
+        let mut response = [0u8; 29];
+        self.hal.spi_frame(&command, &mut response)?;
+
+        let mut values = [0u8; 27];
+        values.copy_from_slice(&response[2..]);
+        Ok(values)
     }
 
     /// Set the input mode (single-ended or differential)
@@ -203,39 +345,98 @@ impl Ads1263 {
     }
 
     // ========================================================================
-    // ADC1 Configuration
+    // SPI clock tuning
     // ========================================================================
 
-    /// Configure ADC1 with specified parameters
+    /// Find the fastest SPI clock with reliable register access
+    ///
+    /// Steps the clock up from 1 MHz and, at each speed, checks the chip ID
+    /// and writes test patterns to the `OFCAL0` register. The first failing
+    /// speed ends the probe and the clock backs off to the previous one. The
+    /// original `OFCAL0` value is restored afterwards.
     ///
     /// # Arguments
     ///
-    /// * `gain` - PGA gain setting
-    /// * `drate` - Data rate setting
-    /// * `delay` - Conversion delay setting
-    fn config_adc1(&mut self, gain: Gain, drate: DataRate, delay: Delay) -> Result<()> {
-        // MODE2: PGA bypassed (0x80) | gain | data rate
-        let mode2 = 0x80 | ((gain as u8) << 4) | (drate as u8);
-        self.write_reg_verify(Register::Mode2, mode2, "REG_MODE2")?;
+    /// * `max_hz` - Upper limit for the probe; speeds above
+    ///   [`SpiConfig::MAX_CLOCK_SPEED`](crate::SpiConfig::MAX_CLOCK_SPEED) are never tried
+    ///
+    /// # Returns
+    ///
+    /// The clock speed left in effect
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InitFailed` if register access fails at every
+    /// probed speed; the original clock is restored
+    pub fn probe_spi_clock(&mut self, max_hz: u32) -> Result<u32> {
+        let original = self.hal.spi_clock();
+        // Read at the current (known good) clock
+        let saved = self.read_reg(Register::OfCal0)?;
+        let mut good = None;
+
+        for &hz in SPI_PROBE_STEPS.iter().filter(|&&hz| hz <= max_hz) {
+            if self.hal.set_spi_clock(hz).is_err() {
+                break;
+            }
+            if self.spi_link_ok() {
+                log::debug!("SPI probe passed at {} Hz", hz);
+                good = Some(hz);
+            } else {
+                log::info!("SPI probe failed at {} Hz", hz);
+                break;
+            }
+        }
 
-        // REFMUX: VDD, VSS as reference (0x24)
-        let refmux = ReferenceSource::AvddAvss as u8;
-        self.write_reg_verify(Register::RefMux, refmux, "REG_REFMUX")?;
+        let hz = good.unwrap_or(original);
+        self.hal.set_spi_clock(hz)?;
+        self.write_reg(Register::OfCal0, saved)?;
 
-        // MODE0: Conversion delay
-        let mode0 = delay as u8;
-        self.write_reg_verify(Register::Mode0, mode0, "REG_MODE0")?;
+        if good.is_none() {
+            log::error!("SPI register access failed at every probed speed");
+            return Err(Ads1263Error::InitFailed);
+        }
+        log::info!("SPI clock tuned to {} Hz", hz);
+        Ok(hz)
+    }
+
+    /// Check chip ID and register readback at the current SPI clock
+    fn spi_link_ok(&mut self) -> bool {
+        let check = |adc: &mut Self| -> Result<bool> {
+            if adc.read_chip_id()? != 1 {
+                return Ok(false);
+            }
+            for &pattern in &SPI_PROBE_PATTERNS {
+                adc.write_reg(Register::OfCal0, pattern)?;
+                if adc.read_reg(Register::OfCal0)? != pattern {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        check(self).unwrap_or(false)
+    }
 
-        // MODE1: Digital filter - FIR (0x84)
-        let mode1 = DigitalFilter::Fir as u8;
-        self.write_reg_verify(Register::Mode1, mode1, "REG_MODE1")?;
+    // ========================================================================
+    // ADC1 Configuration
+    // ========================================================================
 
+    /// Write an ADC1 configuration to the device
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Gain, data rate, filter, delay, and reference to apply
+    fn config_adc1(&mut self, config: Adc1Config) -> Result<()> {
+        for (reg, data, name) in protocol::adc1_writes(&config) {
+            self.write_reg_verify(reg, data, name)?;
+        }
+        self.adc1 = config;
         Ok(())
     }
 
     /// Initialize ADC1 with specified data rate
     ///
-    /// Performs hardware reset, verifies chip ID, and configures ADC1.
+    /// Performs hardware reset, verifies chip ID, and configures ADC1 with
+    /// unity gain, FIR filter, 35 µs delay, and AVDD/AVSS reference.
     ///
     /// # Arguments
     ///
@@ -245,21 +446,178 @@ impl Ads1263 {
     ///
     /// Returns `Ads1263Error::InvalidChipId` if chip ID is not 1
     pub fn init_adc1(&mut self, rate: DataRate) -> Result<()> {
+        self.init_adc1_with(Adc1Config {
+            data_rate: rate,
+            ..Adc1Config::default()
+        })
+    }
+
+    /// Initialize ADC1 with a full configuration
+    ///
+    /// Performs hardware reset, verifies chip ID, and configures ADC1.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChipId` if chip ID is not 1
+    pub fn init_adc1_with(&mut self, config: Adc1Config) -> Result<()> {
         self.reset();
 
-        let chip_id = self.read_chip_id()?;
-        if chip_id == 1 {
-            log::info!("Chip ID verified: {}", chip_id);
-        } else {
-            log::error!("Invalid chip ID: {} (expected 1)", chip_id);
-            return Err(Ads1263Error::InvalidChipId(chip_id));
+        protocol::verify_chip_id(self.read_chip_id()?)?;
+
+        self.configure_adc1(config)?;
+
+        log::info!("ADC1 initialized with data rate {:?}", config.data_rate);
+        Ok(())
+    }
+
+    /// Reconfigure ADC1 without resetting the device
+    ///
+    /// Stops conversions, writes the configuration, and restarts them.
+    pub fn configure_adc1(&mut self, config: Adc1Config) -> Result<()> {
+        self.stop_adc1()?;
+        self.config_adc1(config)?;
+        self.start_adc1()
+    }
+
+    /// Reset the device and restore the cached configuration
+    ///
+    /// Performs the same hardware reset and chip ID check as
+    /// [`init_adc1_with`](Self::init_adc1_with), rewrites the last ADC1
+    /// configuration, and restarts conversions with the current
+    /// [`ConversionControl`]. Used to recover from a stalled DRDY or a
+    /// corrupted register state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChipId` if the device does not answer
+    pub fn recover(&mut self) -> Result<()> {
+        log::warn!("Re-initializing device");
+        let config = self.adc1;
+        self.init_adc1_with(config)?;
+        self.overrun.restart();
+        Ok(())
+    }
+
+    /// Get the ADC1 configuration last written to the device
+    pub fn adc1_config(&self) -> Adc1Config {
+        self.adc1
+    }
+
+    /// Expected time from a conversion (re)start to settled ADC1 data
+    ///
+    /// Derived from the configured data rate, digital filter latency, and
+    /// conversion delay.
+    pub fn conversion_period(&self) -> Duration {
+        let config = self.adc1;
+        config.delay.duration() + config.data_rate.period() * config.filter.settling_periods()
+    }
+
+    /// Time after which a DRDY wait is considered failed
+    ///
+    /// A small multiple of [`conversion_period`](Self::conversion_period)
+    /// plus a margin for scheduling latency.
+    pub fn drdy_timeout(&self) -> Duration {
+        self.conversion_period() * DRDY_TIMEOUT_FACTOR + DRDY_TIMEOUT_MARGIN
+    }
+
+    /// Wait for DRDY with a timeout derived from the ADC1 configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::DrdyTimeout` with the expected and observed wait
+    pub(crate) fn wait_conversion(&mut self) -> Result<()> {
+        let expected = self.conversion_period();
+        let start = Instant::now();
+        match self.hal.wait_drdy_within(self.drdy_timeout()) {
+            Err(Ads1263Error::Timeout) => Err(Ads1263Error::DrdyTimeout {
+                expected,
+                waited: start.elapsed(),
+            }),
+            result => result,
         }
+    }
 
-        self.write_cmd(Command::Stop1)?;
-        self.config_adc1(Gain::Gain1, rate, Delay::Delay35us)?;
-        self.write_cmd(Command::Start1)?;
+    // ========================================================================
+    // Calibration
+    // ========================================================================
 
-        log::info!("ADC1 initialized with data rate {:?}", rate);
+    /// Run an ADC1 calibration and return the resulting registers
+    ///
+    /// Restarts conversions and waits for the device to average
+    /// 16 conversions. For the system calibrations, route the input with
+    /// [`select_channel`](Self::select_channel) first and apply zero or
+    /// full scale to it. The result stays in effect until the next reset;
+    /// store it and restore it with
+    /// [`set_adc1_calibration`](Self::set_adc1_calibration).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::DrdyTimeout` if the calibration does not finish
+    pub fn calibrate_adc1(&mut self, calibration: Calibration) -> Result<Adc1Calibration> {
+        self.start_adc1()?;
+        // DRDY goes high when the calibration starts and low once it is done
+        self.write_cmd(calibration.command())?;
+        let expected = self.conversion_period() * CALIBRATION_CONVERSIONS;
+        let start = Instant::now();
+        match self
+            .hal
+            .wait_drdy_within(self.drdy_timeout() * CALIBRATION_CONVERSIONS)
+        {
+            Err(Ads1263Error::Timeout) => {
+                return Err(Ads1263Error::DrdyTimeout {
+                    expected,
+                    waited: start.elapsed(),
+                });
+            }
+            result => result?,
+        }
+        self.overrun.restart();
+        let result = self.adc1_calibration()?;
+        log::info!(
+            "ADC1 {:?} calibration: offset {}, gain {:.6}",
+            calibration,
+            result.offset,
+            result.gain()
+        );
+        Ok(result)
+    }
+
+    /// Read the ADC1 calibration registers
+    pub fn adc1_calibration(&mut self) -> Result<Adc1Calibration> {
+        let offset = u32::from_le_bytes([
+            self.read_reg(Register::OfCal0)?,
+            self.read_reg(Register::OfCal1)?,
+            self.read_reg(Register::OfCal2)?,
+            0,
+        ]);
+        let full_scale = u32::from_le_bytes([
+            self.read_reg(Register::FsCal0)?,
+            self.read_reg(Register::FsCal1)?,
+            self.read_reg(Register::FsCal2)?,
+            0,
+        ]);
+        Ok(Adc1Calibration {
+            // Sign-extend the 24-bit offset
+            offset: ((offset << 8) as i32) >> 8,
+            full_scale,
+        })
+    }
+
+    /// Write the ADC1 calibration registers, e.g. a stored calibration
+    pub fn set_adc1_calibration(&mut self, calibration: Adc1Calibration) -> Result<()> {
+        let offset = (calibration.offset as u32).to_le_bytes();
+        let full_scale = calibration.full_scale.to_le_bytes();
+        self.write_reg(Register::OfCal0, offset[0])?;
+        self.write_reg(Register::OfCal1, offset[1])?;
+        self.write_reg(Register::OfCal2, offset[2])?;
+        self.write_reg(Register::FsCal0, full_scale[0])?;
+        self.write_reg(Register::FsCal1, full_scale[1])?;
+        self.write_reg(Register::FsCal2, full_scale[2])?;
+        log::info!(
+            "ADC1 calibration set: offset {}, gain {:.6}",
+            calibration.offset,
+            calibration.gain()
+        );
         Ok(())
     }
 
@@ -269,17 +627,18 @@ impl Ads1263 {
 
     /// Configure ADC2 with specified parameters
     fn config_adc2(&mut self, gain: Adc2Gain, drate: Adc2DataRate, delay: Delay) -> Result<()> {
-        // ADC2CFG: VAVDD/VAVSS reference (0x20) | data rate | gain
-        let adc2cfg = 0x20 | ((drate as u8) << 6) | (gain as u8);
-        self.write_reg_verify(Register::Adc2Cfg, adc2cfg, "REG_ADC2CFG")?;
-
-        // MODE0: Conversion delay
-        let mode0 = delay as u8;
-        self.write_reg_verify(Register::Mode0, mode0, "REG_MODE0")?;
-
+        for (reg, data, name) in protocol::adc2_writes(gain, drate, delay) {
+            self.write_reg_verify(reg, data, name)?;
+        }
         Ok(())
     }
 
+    /// Write ADC2 gain and data rate without touching ADC1's settings
+    pub(crate) fn set_adc2_rate(&mut self, gain: Adc2Gain, drate: Adc2DataRate) -> Result<()> {
+        let adc2cfg = protocol::adc2_cfg(gain, drate);
+        self.write_reg_verify(Register::Adc2Cfg, adc2cfg, "REG_ADC2CFG")
+    }
+
     /// Initialize ADC2 with specified data rate
     ///
     /// # Arguments
@@ -292,13 +651,7 @@ impl Ads1263 {
     pub fn init_adc2(&mut self, rate: Adc2DataRate) -> Result<()> {
         self.reset();
 
-        let chip_id = self.read_chip_id()?;
-        if chip_id == 1 {
-            log::info!("Chip ID verified: {}", chip_id);
-        } else {
-            log::error!("Invalid chip ID: {} (expected 1)", chip_id);
-            return Err(Ads1263Error::InvalidChipId(chip_id));
-        }
+        protocol::verify_chip_id(self.read_chip_id()?)?;
 
         self.write_cmd(Command::Stop2)?;
         self.config_adc2(Adc2Gain::Gain1, rate, Delay::Delay35us)?;
@@ -313,49 +666,28 @@ impl Ads1263 {
 
     /// Set single-ended input channel for ADC1
     fn set_channel(&mut self, channel: u8) -> Result<()> {
-        if channel > 10 {
-            return Err(Ads1263Error::InvalidChannel(channel, 10));
-        }
-        // INPMUX: channel as positive, VCOM (0x0A) as negative
-        let inpmux = (channel << 4) | 0x0A;
+        let inpmux = protocol::channel_mux(InputMode::SingleEnded, channel)?;
         self.write_reg(Register::InpMux, inpmux)?;
         Ok(())
     }
 
     /// Set differential input channel for ADC1
     fn set_diff_channel(&mut self, channel: u8) -> Result<()> {
-        let inpmux = match channel {
-            0 => (0 << 4) | 1, // AIN0 - AIN1
-            1 => (2 << 4) | 3, // AIN2 - AIN3
-            2 => (4 << 4) | 5, // AIN4 - AIN5
-            3 => (6 << 4) | 7, // AIN6 - AIN7
-            4 => (8 << 4) | 9, // AIN8 - AIN9
-            _ => return Err(Ads1263Error::InvalidChannel(channel, 4)),
-        };
+        let inpmux = protocol::channel_mux(InputMode::Differential, channel)?;
         self.write_reg(Register::InpMux, inpmux)?;
         Ok(())
     }
 
     /// Set single-ended input channel for ADC2
     fn set_channel_adc2(&mut self, channel: u8) -> Result<()> {
-        if channel > 10 {
-            return Err(Ads1263Error::InvalidChannel(channel, 10));
-        }
-        let inpmux = (channel << 4) | 0x0A;
+        let inpmux = protocol::channel_mux(InputMode::SingleEnded, channel)?;
         self.write_reg(Register::Adc2Mux, inpmux)?;
         Ok(())
     }
 
     /// Set differential input channel for ADC2
     fn set_diff_channel_adc2(&mut self, channel: u8) -> Result<()> {
-        let inpmux = match channel {
-            0 => (0 << 4) | 1,
-            1 => (2 << 4) | 3,
-            2 => (4 << 4) | 5,
-            3 => (6 << 4) | 7,
-            4 => (8 << 4) | 9,
-            _ => return Err(Ads1263Error::InvalidChannel(channel, 4)),
-        };
+        let inpmux = protocol::channel_mux(InputMode::Differential, channel)?;
         self.write_reg(Register::Adc2Mux, inpmux)?;
         Ok(())
     }
@@ -365,66 +697,177 @@ impl Ads1263 {
     // ========================================================================
 
     /// Read raw ADC1 data (32-bit)
-    fn read_adc1_data(&mut self) -> Result<u32> {
-        self.hal.set_cs(false);
-
-        // Wait for valid status
-        let _status = loop {
-            self.hal.spi_write_byte(Command::RData1 as u8)?;
-            let s = self.hal.spi_read_byte()?;
-            if (s & 0x40) != 0 {
-                break s;
-            }
-        };
+    ///
+    /// Must be called after DRDY has signalled a completed conversion; the
+    /// frame is fetched exactly once and the status byte only verifies it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoNewData` if the status byte reports no new conversion
+    pub(crate) fn read_adc1_data(&mut self) -> Result<u32> {
+        let (status, data, _) = self.read_adc1_frame()?;
+        if !status.adc1_new_data() {
+            log::warn!("ADC1 status 0x{:02X} reports no new data", status.bits());
+            return Err(Ads1263Error::NoNewData);
+        }
+        Ok(data)
+    }
+
+    /// Read the ADC1 conversion DRDY signalled at `timestamp` as a sample
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoNewData` if the status byte reports no new conversion
+    pub(crate) fn read_adc1_sample(&mut self, channel: u8, timestamp: Instant) -> Result<Sample> {
+        let (status, data, crc_ok) = self.read_adc1_frame()?;
+        if !status.adc1_new_data() {
+            log::warn!("ADC1 status 0x{:02X} reports no new data", status.bits());
+            return Err(Ads1263Error::NoNewData);
+        }
+        let sequence = self.sequence;
+        self.sequence += 1;
+        Ok(Sample {
+            raw: data as i32,
+            channel,
+            timestamp,
+            status,
+            crc_ok,
+            dropped_since_last: 0,
+            sequence,
+            generation: 0,
+        })
+    }
+
+    /// Read the next conversion of the channel already routed to ADC1
+    ///
+    /// Does not touch the multiplexer, so conversions follow each other at
+    /// the data rate. Lost conversions are counted and reported in
+    /// [`Sample::dropped_since_last`].
+    pub(crate) fn read_next_sample(&mut self, channel: u8) -> Result<Sample> {
+        self.wait_conversion()?;
+        self.read_next_sample_at(channel, Instant::now())
+    }
+
+    /// Read the conversion DRDY signalled at `timestamp` in a continuous sequence
+    ///
+    /// Like [`read_next_sample`](Self::read_next_sample) for callers that
+    /// waited for DRDY themselves.
+    pub(crate) fn read_next_sample_at(
+        &mut self,
+        channel: u8,
+        timestamp: Instant,
+    ) -> Result<Sample> {
+        let mut sample = self.read_adc1_sample(channel, timestamp)?;
+        sample.dropped_since_last = self.overrun.record(timestamp, self.adc1.data_rate.period());
+        Ok(sample)
+    }
 
-        // Read 4 data bytes + CRC
-        let b0 = self.hal.spi_read_byte()?;
-        let b1 = self.hal.spi_read_byte()?;
-        let b2 = self.hal.spi_read_byte()?;
-        let b3 = self.hal.spi_read_byte()?;
-        let crc = self.hal.spi_read_byte()?;
+    /// Counters of conversions lost during continuous reads
+    ///
+    /// Covers [`capture`](Self::capture) and single-channel acquisitions;
+    /// see [`OverrunStats`].
+    pub fn overrun_stats(&self) -> OverrunStats {
+        self.overrun.stats()
+    }
+
+    /// Reset the missed-conversion counters
+    pub fn reset_overrun_stats(&mut self) {
+        self.overrun.reset();
+    }
 
-        self.hal.set_cs(true);
+    /// Read a single ADC1 data frame in one SPI transaction
+    ///
+    /// The frame is RDATA1, status, 4 data bytes, and CRC. The status byte
+    /// is not polled; it is returned as-is for the caller to inspect along
+    /// with the result of the CRC check.
+    fn read_adc1_frame(&mut self) -> Result<(StatusFlags, u32, bool)> {
+        let command = [Command::RData1 as u8, 0, 0, 0, 0, 0, 0];
+        let mut frame = [0u8; 7];
 
-        let data =
-            ((b0 as u32) << 24) | ((b1 as u32) << 16) | ((b2 as u32) << 8) | (b3 as u32);
+        self.hal.spi_frame(&command, &mut frame)?;
 
-        if !Self::checksum(data, crc) {
-            log::warn!("ADC1 checksum error: data=0x{:08X}, crc=0x{:02X}", data, crc);
+        let data = u32::from_be_bytes([frame[2], frame[3], frame[4], frame[5]]);
+        let crc = frame[6];
+        let crc_ok = protocol::checksum(data, crc);
+        if !crc_ok {
+            log::warn!(
+                "ADC1 checksum error: data=0x{:08X}, crc=0x{:02X}",
+                data,
+                crc
+            );
         }
 
-        Ok(data)
+        Ok((StatusFlags::from_bits(frame[1]), data, crc_ok))
     }
 
     /// Read raw ADC2 data (24-bit)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if no conversion is ready within
+    /// 500 ms
     fn read_adc2_data(&mut self) -> Result<u32> {
-        self.hal.set_cs(false);
-
-        // Wait for valid status
+        // ADC2 has no DRDY output, so the status byte is the only ready signal
+        let timeout = Duration::from_millis(u64::from(ADC2_TIMEOUT_MS));
+        let start = Instant::now();
         loop {
-            self.hal.spi_write_byte(Command::RData2 as u8)?;
-            let s = self.hal.spi_read_byte()?;
-            if (s & 0x80) != 0 {
-                break;
+            let (status, data, _) = self.read_adc2_frame()?;
+            if status.adc2_new_data() {
+                return Ok(data);
+            }
+            if start.elapsed() >= timeout {
+                log::warn!("No ADC2 conversion within {:?}", timeout);
+                return Err(Ads1263Error::Timeout);
             }
+            self.hal.delay_us(u64::from(ADC2_POLL_INTERVAL_US));
         }
+    }
 
-        // Read 3 data bytes + padding + CRC (ADC2 is 24-bit)
-        let b0 = self.hal.spi_read_byte()?;
-        let b1 = self.hal.spi_read_byte()?;
-        let b2 = self.hal.spi_read_byte()?;
-        let _b3 = self.hal.spi_read_byte()?; // Padding
-        let crc = self.hal.spi_read_byte()?;
+    /// Fetch an ADC2 conversion of `channel` if one is ready
+    ///
+    /// Polls the status byte once. The sample's raw value is the
+    /// sign-extended 24-bit code, timestamped when it was found.
+    pub(crate) fn poll_adc2_sample(&mut self, channel: u8) -> Result<Option<Sample>> {
+        let (status, data, crc_ok) = self.read_adc2_frame()?;
+        if !status.adc2_new_data() {
+            return Ok(None);
+        }
+        let sequence = self.sequence;
+        self.sequence += 1;
+        Ok(Some(Sample {
+            raw: ((data << 8) as i32) >> 8,
+            channel,
+            timestamp: Instant::now(),
+            status,
+            crc_ok,
+            dropped_since_last: 0,
+            sequence,
+            generation: 0,
+        }))
+    }
 
-        self.hal.set_cs(true);
+    /// Read a single ADC2 data frame in one SPI transaction
+    ///
+    /// The frame is RDATA2, status, 3 data bytes, padding, and CRC
+    /// (ADC2 is 24-bit).
+    fn read_adc2_frame(&mut self) -> Result<(StatusFlags, u32, bool)> {
+        let command = [Command::RData2 as u8, 0, 0, 0, 0, 0, 0];
+        let mut frame = [0u8; 7];
 
-        let data = ((b0 as u32) << 16) | ((b1 as u32) << 8) | (b2 as u32);
+        self.hal.spi_frame(&command, &mut frame)?;
 
-        if !Self::checksum(data, crc) {
-            log::warn!("ADC2 checksum error: data=0x{:06X}, crc=0x{:02X}", data, crc);
+        let data = u32::from_be_bytes([0, frame[2], frame[3], frame[4]]);
+        let crc = frame[6];
+        let crc_ok = protocol::checksum(data, crc);
+        if !crc_ok {
+            log::warn!(
+                "ADC2 checksum error: data=0x{:06X}, crc=0x{:02X}",
+                data,
+                crc
+            );
         }
 
-        Ok(data)
+        Ok((StatusFlags::from_bits(frame[1]), data, crc_ok))
     }
 
     // ========================================================================
@@ -441,23 +884,92 @@ impl Ads1263 {
     ///
     /// Raw 32-bit ADC value
     pub fn get_channel_value(&mut self, channel: u8) -> Result<u32> {
+        self.select_channel(channel)?;
+        self.wait_conversion()?;
+        self.read_adc1_data()
+    }
+
+    /// Get an ADC1 channel value as a timestamped sample
+    ///
+    /// Like [`get_channel_value`](Self::get_channel_value), but also returns
+    /// the time DRDY signalled the conversion, the status byte, and whether
+    /// the CRC matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub fn get_sample(&mut self, channel: u8) -> Result<Sample> {
+        self.select_channel(channel)?;
+        self.wait_conversion()?;
+        self.read_adc1_sample(channel, Instant::now())
+    }
+
+    /// Read multiple channels from ADC1 as timestamped samples
+    ///
+    /// `samples` is cleared first and receives one sample per channel in
+    /// order; reusing it across scans avoids allocating.
+    pub fn get_samples_into(&mut self, channels: &[u8], samples: &mut Vec<Sample>) -> Result<()> {
+        samples.clear();
+        samples.reserve(channels.len());
+        for &ch in channels {
+            samples.push(self.get_sample(ch)?);
+        }
+        Ok(())
+    }
+
+    /// Try to read an ADC1 conversion without blocking
+    ///
+    /// Checks DRDY exactly once and, if a conversion is pending, fetches it
+    /// with a single RDATA1 frame. The input is whatever was last routed with
+    /// [`select_channel`](Self::select_channel); conversions must be running
+    /// (see [`start_adc1`](Self::start_adc1)).
+    ///
+    /// # Returns
+    ///
+    /// `Some((value, status))` with the signed conversion result, or `None`
+    /// if no new conversion is ready
+    pub fn try_read_adc1(&mut self) -> Result<Option<(i32, StatusFlags)>> {
+        if self.hal.read_drdy() {
+            return Ok(None);
+        }
+
+        let (status, data, _) = self.read_adc1_frame()?;
+        if !status.adc1_new_data() {
+            return Ok(None);
+        }
+
+        Ok(Some((data as i32, status)))
+    }
+
+    /// Route an ADC1 channel to the input multiplexer for the current mode
+    ///
+    /// Changing the multiplexer restarts the conversion in progress.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub fn select_channel(&mut self, channel: u8) -> Result<()> {
+        self.overrun.restart();
         match self.scan_mode {
             InputMode::SingleEnded => {
                 if channel > 10 {
                     return Err(Ads1263Error::InvalidChannel(channel, 10));
                 }
-                self.set_channel(channel)?;
+                self.set_channel(channel)
             }
             InputMode::Differential => {
                 if channel > 4 {
                     return Err(Ads1263Error::InvalidChannel(channel, 4));
                 }
-                self.set_diff_channel(channel)?;
+                self.set_diff_channel(channel)
             }
         }
+    }
 
-        self.hal.wait_drdy()?;
-        self.read_adc1_data()
+    /// Disconnect both ADC1 inputs by setting the multiplexer to floating
+    pub fn release_mux(&mut self) -> Result<()> {
+        self.overrun.restart();
+        self.write_reg(Register::InpMux, INPMUX_FLOATING)
     }
 
     /// Get ADC2 channel value (raw 24-bit)
@@ -470,6 +982,13 @@ impl Ads1263 {
     ///
     /// Raw 24-bit ADC value (stored in u32)
     pub fn get_channel_value_adc2(&mut self, channel: u8) -> Result<u32> {
+        self.select_channel_adc2(channel)?;
+        self.write_cmd(Command::Start2)?;
+        self.read_adc2_data()
+    }
+
+    /// Route a channel to ADC2 in the current input mode
+    pub(crate) fn select_channel_adc2(&mut self, channel: u8) -> Result<()> {
         match self.scan_mode {
             InputMode::SingleEnded => {
                 if channel > 10 {
@@ -484,9 +1003,7 @@ impl Ads1263 {
                 self.set_diff_channel_adc2(channel)?;
             }
         }
-
-        self.write_cmd(Command::Start2)?;
-        self.read_adc2_data()
+        Ok(())
     }
 
     /// Read multiple channels from ADC1
@@ -499,13 +1016,378 @@ impl Ads1263 {
     ///
     /// Vector of raw 32-bit values in the same order as input channels
     pub fn get_all(&mut self, channels: &[u8]) -> Result<Vec<u32>> {
-        let mut values = Vec::with_capacity(channels.len());
-        for &ch in channels {
-            values.push(self.get_channel_value(ch)?);
+        let mut values = vec![0u32; channels.len()];
+        self.get_all_into(channels, &mut values)?;
+        Ok(values)
+    }
+
+    /// Read multiple channels from ADC1 into a caller-provided buffer
+    ///
+    /// Allocation-free variant of [`get_all`](Self::get_all) for the hot
+    /// acquisition path.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Slice of channel numbers to read
+    /// * `values` - Output buffer; `values[i]` receives the value of `channels[i]`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::BufferTooSmall` if `values` is shorter than `channels`
+    pub fn get_all_into(&mut self, channels: &[u8], values: &mut [u32]) -> Result<()> {
+        if values.len() < channels.len() {
+            return Err(Ads1263Error::BufferTooSmall {
+                needed: channels.len(),
+                got: values.len(),
+            });
         }
+        for (value, &ch) in values.iter_mut().zip(channels) {
+            *value = self.get_channel_value(ch)?;
+        }
+        Ok(())
+    }
+
+    /// Run a scan plan with per-step gain, data rate, and settling
+    ///
+    /// # Returns
+    ///
+    /// Vector of raw 32-bit values, one per step
+    pub fn scan(&mut self, plan: &ScanPlan) -> Result<Vec<u32>> {
+        let mut values = vec![0u32; plan.len()];
+        self.run_scan(plan, &mut values)?;
         Ok(values)
     }
 
+    /// Run a scan plan into a caller-provided buffer
+    ///
+    /// MODE2 is only rewritten when a step's gain or data rate differs from
+    /// the current ADC1 configuration, and INPMUX only when the input differs
+    /// from the previous step. When a step reprograms anything and has a
+    /// settling delay, the conversion is restarted after the delay so the
+    /// result reflects the settled input. The plan's last gain and data rate
+    /// stay in effect afterwards (see [`adc1_config`](Self::adc1_config)).
+    ///
+    /// # Arguments
+    ///
+    /// * `plan` - Steps to execute, in order
+    /// * `values` - Output buffer; `values[i]` receives the result of step `i`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::BufferTooSmall` if `values` is shorter than the
+    /// plan, or `Ads1263Error::InvalidChannel` for a step with an invalid input
+    pub fn run_scan(&mut self, plan: &ScanPlan, values: &mut [u32]) -> Result<()> {
+        if values.len() < plan.len() {
+            return Err(Ads1263Error::BufferTooSmall {
+                needed: plan.len(),
+                got: values.len(),
+            });
+        }
+        let mut values = values.iter_mut();
+        self.run_plan(plan, |adc, _| {
+            if let Some(value) = values.next() {
+                *value = adc.read_adc1_data()?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Run a scan plan, collecting timestamped samples
+    ///
+    /// Like [`run_scan`](Self::run_scan); each sample's channel is the
+    /// step's channel (or differential pair) number.
+    pub fn scan_samples_into(&mut self, plan: &ScanPlan, samples: &mut Vec<Sample>) -> Result<()> {
+        samples.clear();
+        samples.reserve(plan.len());
+        self.run_plan(plan, |adc, channel| {
+            samples.push(adc.read_adc1_sample(channel, Instant::now())?);
+            Ok(())
+        })
+    }
+
+    /// Execute the steps of a plan, calling `read` once each conversion is ready
+    fn run_plan(
+        &mut self,
+        plan: &ScanPlan,
+        mut read: impl FnMut(&mut Self, u8) -> Result<()>,
+    ) -> Result<()> {
+        self.overrun.restart();
+        let mut inpmux = None;
+        for step in plan.steps() {
+            let (mode, channel) = step.input.mode_channel();
+            let mux = protocol::channel_mux(mode, channel)?;
+            let mut reprogrammed = false;
+
+            if step.gain != self.adc1.gain || step.data_rate != self.adc1.data_rate {
+                self.write_reg(
+                    Register::Mode2,
+                    protocol::adc1_mode2(step.gain, step.data_rate),
+                )?;
+                self.adc1.gain = step.gain;
+                self.adc1.data_rate = step.data_rate;
+                reprogrammed = true;
+            }
+            if inpmux != Some(mux) {
+                self.write_reg(Register::InpMux, mux)?;
+                inpmux = Some(mux);
+                reprogrammed = true;
+            }
+            if reprogrammed && !step.settling.is_zero() {
+                self.hal.delay_us(step.settling.as_micros() as u64);
+                self.start_adc1()?;
+            }
+
+            self.wait_conversion()?;
+            read(self, channel)?;
+        }
+        Ok(())
+    }
+
+    /// Scan ADC1 channels continuously on a background thread
+    ///
+    /// Moves the driver onto the sampler thread; [`Acquisition::join`]
+    /// returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Channel numbers to scan, in order
+    /// * `config` - Buffering, scan limit, and thread settings
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sampler thread cannot be spawned
+    pub fn start_acquisition(
+        self,
+        channels: &[u8],
+        config: AcquisitionConfig,
+    ) -> Result<Acquisition> {
+        Acquisition::start(self, channels, config)
+    }
+
+    /// Scan ADC1 channels continuously into a ring buffer
+    ///
+    /// Like [`start_acquisition`](Self::start_acquisition), but each scan is
+    /// pushed to `ring`, applying its [`OverflowPolicy`](crate::OverflowPolicy),
+    /// so a slow consumer never stalls the sampler. The acquisition's
+    /// channel only carries the error that stops the sampler.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::ChannelCountMismatch` if the ring's frame length
+    /// differs from the number of channels
+    pub fn start_acquisition_ring(
+        self,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        ring: Arc<RingBuffer>,
+    ) -> Result<Acquisition> {
+        Acquisition::start_ring(self, channels, config, ring)
+    }
+
+    /// Scan ADC1 channels continuously into a sink
+    ///
+    /// Like [`start_acquisition`](Self::start_acquisition), but the sampler
+    /// thread writes every scan to `sink` itself. The sink is dropped (and
+    /// so flushed) when the sampler stops; the acquisition's channel only
+    /// carries the error that stops it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sampler thread cannot be spawned
+    pub fn start_acquisition_sink(
+        self,
+        channels: &[u8],
+        config: AcquisitionConfig,
+        sink: impl Sink + 'static,
+    ) -> Result<Acquisition> {
+        Acquisition::start_sink(self, channels, config, sink)
+    }
+
+    /// Scan ADC1 channels continuously into double-buffered blocks
+    ///
+    /// Like [`start_acquisition`](Self::start_acquisition), but scans are
+    /// collected into blocks of `block_scans` scans that are handed over
+    /// whole while the sampler fills the next one. `config.capacity` is the
+    /// number of full blocks that can be queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sampler thread cannot be spawned
+    pub fn start_block_acquisition(
+        self,
+        channels: &[u8],
+        block_scans: usize,
+        config: AcquisitionConfig,
+    ) -> Result<BlockAcquisition> {
+        Acquisition::start_blocks(self, channels, config, block_scans)
+    }
+
+    /// Scan channels until a trigger fires and return the capture
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Channel numbers to scan, in order
+    /// * `config` - Trigger condition, pre/post-trigger lengths, and timeout
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if the trigger does not fire within
+    /// `config.timeout`, or any error from reading the channels
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+    /// use ads1263::{Edge, TriggerCondition, TriggerConfig};
+    ///
+    /// // 100 scans before and 400 from the moment channel 0 rises through 0 V
+    /// let capture = adc.capture_triggered(&[0, 1], TriggerConfig {
+    ///     condition: TriggerCondition::Level { channel: 0, level: 0, edge: Edge::Rising },
+    ///     pre_trigger: 100,
+    ///     post_trigger: 400,
+    ///     timeout: Some(std::time::Duration::from_secs(10)),
+    /// })?;
+    /// println!("Triggered at scan {}", capture.trigger_index);
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn capture_triggered(&mut self, channels: &[u8], config: TriggerConfig) -> Result<Capture> {
+        let mut trigger = Trigger::new(config, channels.len())?;
+        let mut values = vec![0u32; channels.len()];
+        let start = Instant::now();
+        loop {
+            self.get_all_into(channels, &mut values)?;
+            if let Some(capture) = trigger.feed(&values) {
+                return Ok(capture);
+            }
+            if !trigger.is_triggered() && config.timeout.is_some_and(|t| start.elapsed() >= t) {
+                log::warn!("Trigger did not fire within {:?}", config.timeout);
+                return Err(Ads1263Error::Timeout);
+            }
+        }
+    }
+
+    /// Iterate over the conversions of one channel
+    ///
+    /// The channel is routed once and each item is the next conversion at
+    /// the configured data rate, as for [`capture`](Self::capture). The
+    /// iterator borrows the driver until it is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    /// * `config` - Sample limit and error handling
+    pub fn samples(&mut self, channel: u8, config: SamplesConfig) -> Samples<'_> {
+        Samples::new(self, channel, config)
+    }
+
+    /// Sample one channel fast on ADC1 while ADC2 cycles through others
+    ///
+    /// ADC1 converts `config.fast_channel` back-to-back at its configured
+    /// data rate; ADC2 round-robins `config.slow_channels` at its own rate.
+    /// Both are merged into one stream in timestamp order. ADC1 must be
+    /// initialized; ADC2 is configured here.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChannel` for a channel outside the
+    /// current input mode, or an SPI error while setting up
+    pub fn dual_scan(&mut self, config: DualScanConfig) -> Result<DualScan<'_>> {
+        DualScan::new(self, config)
+    }
+
+    /// Use the chip's own GPIO pins (AIN3 to AIN9 and AINCOM)
+    ///
+    /// See [`ChipGpio`] for the pin numbering.
+    pub fn gpio(&mut self) -> ChipGpio<'_> {
+        ChipGpio::new(self)
+    }
+
+    /// Collect `n` consecutive conversions from one channel
+    ///
+    /// Routes the channel once and then reads every conversion at the
+    /// configured data rate without touching the multiplexer, so no
+    /// conversion is spent on settling after the first. Conversions missed
+    /// because the reader fell behind show up in
+    /// [`Sample::dropped_since_last`] and [`overrun_stats`](Self::overrun_stats).
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    /// * `n` - Number of conversions to collect
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+    /// let burst = adc.capture(0, 10_000)?;
+    /// println!("{:.1} SPS, {} late", burst.timing.sps(), burst.timing.late);
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn capture(&mut self, channel: u8, n: usize) -> Result<Burst> {
+        let mut samples = Vec::with_capacity(n);
+        let timing = self.capture_into(channel, n, &mut samples)?;
+        Ok(Burst { samples, timing })
+    }
+
+    /// Collect `n` consecutive conversions into a reusable buffer
+    ///
+    /// Allocation-free variant of [`capture`](Self::capture) once `samples`
+    /// has capacity for `n`; it is cleared first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoNewData` if a frame read after DRDY carries
+    /// no new conversion, or any error from waiting for DRDY
+    pub fn capture_into(
+        &mut self,
+        channel: u8,
+        n: usize,
+        samples: &mut Vec<Sample>,
+    ) -> Result<BurstTiming> {
+        samples.clear();
+        samples.reserve(n);
+        self.select_channel(channel)?;
+
+        for _ in 0..n {
+            samples.push(self.read_next_sample(channel)?);
+        }
+
+        let timing = BurstTiming::from_samples(samples, self.adc1.data_rate.period());
+        log::debug!(
+            "Burst of {} samples on channel {}: {:?}",
+            n,
+            channel,
+            timing
+        );
+        Ok(timing)
+    }
+
+    /// Measure statistics of one channel over a fixed time
+    ///
+    /// Reads the channel back-to-back, as for [`capture`](Self::capture),
+    /// until `duration` has elapsed. Values are signed raw codes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+    /// let stats = adc.measure_stats(0, std::time::Duration::from_secs(2))?;
+    /// println!("noise: {:.1} codes rms, {:.0} p-p", stats.std_dev, stats.peak_to_peak());
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn measure_stats(&mut self, channel: u8, duration: Duration) -> Result<Stats> {
+        let mut acc = StatsAccumulator::new();
+        self.select_channel(channel)?;
+        let start = Instant::now();
+        while start.elapsed() < duration {
+            let sample = self.read_next_sample(channel)?;
+            acc.push(f64::from(sample.raw));
+        }
+        let stats = acc.stats();
+        log::debug!("Channel {} over {:?}: {:?}", channel, duration, stats);
+        Ok(stats)
+    }
+
     /// Read all 10 channels from ADC2
     ///
     /// # Returns
@@ -513,8 +1395,8 @@ impl Ads1263 {
     /// Array of 10 raw 24-bit values
     pub fn get_all_adc2(&mut self) -> Result<[u32; 10]> {
         let mut values = [0u32; 10];
-        for i in 0..10 {
-            values[i] = self.get_channel_value_adc2(i as u8)?;
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = self.get_channel_value_adc2(i as u8)?;
             self.write_cmd(Command::Stop2)?;
         }
         Ok(values)
@@ -564,6 +1446,204 @@ impl Ads1263 {
         }
     }
 
+    // ========================================================================
+    // Arbitrary inputs and internal monitors
+    // ========================================================================
+
+    /// Route any two multiplexer inputs to ADC1, e.g. AIN0 against AIN3
+    ///
+    /// Changing the multiplexer restarts the conversion in progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChannel` for an AIN above 9
+    pub fn select_inputs(&mut self, positive: MuxInput, negative: MuxInput) -> Result<()> {
+        for input in [positive, negative] {
+            if let MuxInput::Ain(n) = input
+                && n > 9
+            {
+                return Err(Ads1263Error::InvalidChannel(n, 9));
+            }
+        }
+        let inpmux = (positive.bits() << 4) | negative.bits();
+        self.overrun.restart();
+        self.write_reg(Register::InpMux, inpmux)
+    }
+
+    /// Convert two multiplexer inputs once
+    ///
+    /// Like [`get_channel_value`](Self::get_channel_value) for inputs
+    /// outside the channel numbering; conversions must be running.
+    ///
+    /// # Returns
+    ///
+    /// Signed 32-bit conversion result
+    pub fn read_inputs(&mut self, positive: MuxInput, negative: MuxInput) -> Result<i32> {
+        self.select_inputs(positive, negative)?;
+        self.wait_conversion()?;
+        Ok(self.read_adc1_data()? as i32)
+    }
+
+    /// Collect `n` consecutive conversions of two multiplexer inputs
+    ///
+    /// Like [`capture`](Self::capture) for inputs outside the channel
+    /// numbering: routes them once and reads every conversion at the
+    /// configured data rate, e.g. with both inputs on AINCOM to measure
+    /// noise.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+    /// use ads1263::MuxInput;
+    ///
+    /// let codes = adc.capture_inputs(MuxInput::AinCom, MuxInput::AinCom, 1000)?;
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn capture_inputs(
+        &mut self,
+        positive: MuxInput,
+        negative: MuxInput,
+        n: usize,
+    ) -> Result<Vec<i32>> {
+        let mut codes = Vec::with_capacity(n);
+        self.select_inputs(positive, negative)?;
+        for _ in 0..n {
+            self.wait_conversion()?;
+            codes.push(self.read_adc1_data()? as i32);
+        }
+        Ok(codes)
+    }
+
+    /// Convert once with a sensor's inputs, gain, data rate, and reference
+    ///
+    /// Enables VBIAS for the conversion if the setup asks for it. Restores
+    /// the ADC1 configuration and restarts conversions afterwards,
+    /// like [`read_monitor`](Self::read_monitor). Route a channel again
+    /// before the next read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChannel` for an AIN above 9, or the
+    /// error of the conversion
+    pub fn read_sensor(&mut self, setup: &SensorSetup) -> Result<i32> {
+        let saved = self.adc1;
+        self.stop_adc1()?;
+        self.config_adc1(Adc1Config {
+            gain: setup.gain,
+            data_rate: setup.data_rate,
+            delay: setup.delay,
+            reference: setup.reference,
+            ..saved
+        })?;
+        let power = if setup.vbias {
+            Some(self.read_reg(Register::Power)?)
+        } else {
+            None
+        };
+        if let Some(power) = power {
+            self.write_reg(Register::Power, power | 0x02)?;
+        }
+        let result = self
+            .select_inputs(setup.positive, setup.negative)
+            .and_then(|()| self.start_adc1())
+            .and_then(|()| self.wait_conversion())
+            .and_then(|()| self.read_adc1_data());
+        if let Some(power) = power {
+            self.write_reg(Register::Power, power)?;
+        }
+        self.configure_adc1(saved)?;
+
+        let code = result? as i32;
+        log::debug!(
+            "Sensor {:?}-{:?}: code {} ({:.9} V)",
+            setup.positive,
+            setup.negative,
+            code,
+            setup.volts(code)
+        );
+        Ok(code)
+    }
+
+    /// Read an input as a fraction of the sensor excitation
+    ///
+    /// Same as [`read_sensor`](Self::read_sensor), with the setup's
+    /// reference taken to be the excitation of the sensor (see
+    /// [`SensorSetup::ratiometric`]). The result is dimensionless and
+    /// independent of the excitation voltage, divided by the PGA gain.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the reference is the
+    /// internal 2.5 V reference, or the error of
+    /// [`read_sensor`](Self::read_sensor)
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+    /// use ads1263::{Gain, MuxInput, ReferenceSource, SensorSetup};
+    ///
+    /// // Bridge excited from AIN0/AIN1, output on AIN2/AIN3
+    /// let bridge = SensorSetup::new(MuxInput::Ain(2), MuxInput::Ain(3))
+    ///     .gain(Gain::Gain64)
+    ///     .ratiometric(ReferenceSource::ExternalAin01);
+    /// println!("{:.6} mV/V", adc.read_ratio(&bridge)? * 1000.0);
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn read_ratio(&mut self, setup: &SensorSetup) -> Result<f64> {
+        if !setup.reference.is_ratiometric() {
+            return Err(Ads1263Error::InvalidConfig(format!(
+                "ratiometric read: reference {:?} is not a sensor excitation",
+                setup.reference
+            )));
+        }
+        let code = self.read_sensor(setup)?;
+        Ok(setup.ratio(code))
+    }
+
+    /// Read an internal monitor: die temperature or a supply voltage
+    ///
+    /// Converts once at unity PGA gain, 20 SPS, against the internal 2.5 V
+    /// reference, then restores the ADC1 configuration and restarts
+    /// conversions. Route a channel again before the next read.
+    ///
+    /// # Returns
+    ///
+    /// The temperature in °C or the supply in volts, see [`Monitor::unit`]
+    pub fn read_monitor(&mut self, monitor: Monitor) -> Result<f64> {
+        let saved = self.adc1;
+        let config = Adc1Config {
+            gain: Gain::Gain1,
+            data_rate: DataRate::Sps20,
+            reference: ReferenceSource::Internal2_5V,
+            ..saved
+        };
+
+        self.stop_adc1()?;
+        // The temperature sensor needs the PGA, so don't bypass it at unity gain
+        self.write_reg(Register::Mode2, DataRate::Sps20 as u8)?;
+        self.write_reg(Register::RefMux, ReferenceSource::Internal2_5V as u8)?;
+        let power = self.read_reg(Register::Power)?;
+        self.write_reg(Register::Power, power | 0x01)?;
+        self.adc1 = config;
+        self.select_inputs(monitor.input(), monitor.input())?;
+        self.start_adc1()?;
+        let result = self.wait_conversion().and_then(|()| self.read_adc1_data());
+        self.configure_adc1(saved)?;
+
+        let volts = Self::raw_to_voltage_adc1(result?, 2.5);
+        let value = monitor.convert(volts);
+        log::debug!(
+            "{:?} monitor: {:.6} V -> {:.3} {}",
+            monitor,
+            volts,
+            value,
+            monitor.unit()
+        );
+        Ok(value)
+    }
+
     // ========================================================================
     // RTD (Resistance Temperature Detector) support
     // ========================================================================
@@ -571,6 +1651,8 @@ impl Ads1263 {
     /// Configure and read RTD measurement
     ///
     /// Sets up the ADC for RTD measurement using the internal current sources.
+    /// Same as [`read_rtd_wired`](Self::read_rtd_wired) with the HAT's
+    /// 3-wire circuit at 250 µA.
     ///
     /// # Arguments
     ///
@@ -582,58 +1664,229 @@ impl Ads1263 {
     ///
     /// Raw 32-bit ADC value
     pub fn read_rtd(&mut self, delay: Delay, gain: Gain, drate: DataRate) -> Result<u32> {
+        self.read_rtd_wired(RtdWiring::ThreeWire, IdacCurrent::Ua250, delay, gain, drate)
+    }
+
+    /// Configure and read an RTD measurement with the given wiring and
+    /// excitation current
+    ///
+    /// Convert the result with [`RtdWiring::resistance`]. The current
+    /// cancels out of the ratiometric result, but the voltage it develops
+    /// across the reference resistor must stay within the IDAC compliance
+    /// and the reference input range.
+    ///
+    /// # Returns
+    ///
+    /// Raw 32-bit ADC value
+    pub fn read_rtd_wired(
+        &mut self,
+        wiring: RtdWiring,
+        idac: IdacCurrent,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<u32> {
+        let config = RtdConfig::default().wiring(wiring).idac(idac);
+        self.convert_rtd(&config, delay, gain, drate)
+    }
+
+    /// Read the lead-compensated resistance of an RTD on the HAT's RTD
+    /// circuit in ohms
+    ///
+    /// Same as [`read_rtd_config`](Self::read_rtd_config) with the
+    /// HAT's [`RtdConfig`], converted with [`RtdConfig::resistance`].
+    ///
+    /// # Arguments
+    ///
+    /// * `wiring` - Lead connection
+    /// * `idac` - Excitation current
+    /// * `r_ref` - Reference resistor in ohms
+    /// * `delay` - Conversion delay
+    /// * `gain` - PGA gain
+    /// * `drate` - Data rate
+    pub fn read_rtd_resistance(
+        &mut self,
+        wiring: RtdWiring,
+        idac: IdacCurrent,
+        r_ref: f64,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<f64> {
+        let config = RtdConfig::default().wiring(wiring).idac(idac).r_ref(r_ref);
+        let raw = self.read_rtd_config(&config, delay, gain, drate)?;
+        Ok(config.resistance(raw, gain))
+    }
+
+    /// Configure and read an RTD measurement on the given pins
+    ///
+    /// Same as [`read_rtd_config`](Self::read_rtd_config) with `pins` on
+    /// the HAT's reference resistor between AIN4 and AIN5. Convert the
+    /// result with [`RtdWiring::resistance`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the pins do not fit the
+    /// wiring or use a reference input, see [`RtdConfig::validate`]
+    ///
+    /// # Returns
+    ///
+    /// Raw 32-bit ADC value
+    pub fn read_rtd_pins(
+        &mut self,
+        wiring: RtdWiring,
+        pins: &RtdPins,
+        idac: IdacCurrent,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<u32> {
+        let config = RtdConfig::default().wiring(wiring).pins(*pins).idac(idac);
+        self.read_rtd_config(&config, delay, gain, drate)
+    }
+
+    /// Configure and read an RTD measurement on the given circuit
+    ///
+    /// 2- and 4-wire take one conversion. 3-wire takes a second one with
+    /// the IDAC outputs swapped between the excitation and compensation
+    /// leads and returns the mean of both, so a mismatch between the two
+    /// currents no longer leaves an offset proportional to the lead
+    /// resistance; with equal leads, the lead resistance cancels
+    /// completely. Convert the result with [`RtdConfig::resistance`].
+    ///
+    /// The IDACs are turned off and the ADC1 configuration is restored
+    /// afterwards, like [`read_sensor`](Self::read_sensor). Route a channel
+    /// again before the next read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the circuit is not
+    /// consistent, see [`RtdConfig::validate`]
+    ///
+    /// # Returns
+    ///
+    /// Raw 32-bit ADC value
+    pub fn read_rtd_config(
+        &mut self,
+        config: &RtdConfig,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<u32> {
+        config.validate()?;
+        let raw = self.convert_rtd(config, delay, gain, drate)?;
+        if config.wiring != RtdWiring::ThreeWire {
+            return Ok(raw);
+        }
+
+        let swapped = config.pins(config.pins.swapped());
+        let swapped = self.convert_rtd(&swapped, delay, gain, drate)?;
+        log::debug!("RTD 3-wire: raw {}, IDACs swapped {}", raw, swapped);
+        let mean = (i64::from(raw as i32) + i64::from(swapped as i32)) / 2;
+        Ok(mean as i32 as u32)
+    }
+
+    /// One RTD conversion
+    ///
+    /// Turns the IDACs off, restores the ADC1 configuration, and restarts
+    /// conversions afterwards, like [`read_sensor`](Self::read_sensor).
+    fn convert_rtd(
+        &mut self,
+        config: &RtdConfig,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<u32> {
+        let saved = self.adc1;
+        self.stop_adc1()?;
+        let result = self.excite_rtd(config, delay, gain, drate);
+        // IDACMAG: both currents off; IDACMUX: both outputs unconnected
+        self.write_reg(Register::IdacMag, 0x00)?;
+        self.write_reg(Register::IdacMux, 0xBB)?;
+        self.configure_adc1(saved)?;
+        result
+    }
+
+    /// Set up the RTD circuit and read one conversion
+    fn excite_rtd(
+        &mut self,
+        config: &RtdConfig,
+        delay: Delay,
+        gain: Gain,
+        drate: DataRate,
+    ) -> Result<u32> {
+        let RtdConfig {
+            wiring, pins, idac, ..
+        } = *config;
+
         // MODE0 (CHOP OFF)
         self.write_reg(Register::Mode0, delay as u8)?;
         self.hal.delay_ms(1);
 
-        // IDACMUX: IDAC2 to AINCOM, IDAC1 to AIN3
-        self.write_reg(Register::IdacMux, (0x0A << 4) | 0x03)?;
+        // IDACMUX: IDAC1 to the excitation pin; IDAC2 to the compensation
+        // pin for 3-wire, else unconnected
+        // IDACMAG: IDAC2 off unless it compensates the leads
+        let (idac2_pin, idac2) = match wiring {
+            RtdWiring::ThreeWire => (pins.compensation.bits(), idac),
+            RtdWiring::TwoWire | RtdWiring::FourWire => (0x0B, IdacCurrent::Off),
+        };
+        self.write_reg(Register::IdacMux, (idac2_pin << 4) | pins.excitation.bits())?;
         self.hal.delay_ms(1);
-
-        // IDACMAG: IDAC2 = IDAC1 = 250µA
-        self.write_reg(Register::IdacMag, (0x03 << 4) | 0x03)?;
+        self.write_reg(Register::IdacMag, ((idac2 as u8) << 4) | idac as u8)?;
         self.hal.delay_ms(1);
 
         // MODE2: gain | data rate
         let mode2 = ((gain as u8) << 4) | (drate as u8);
         self.write_reg(Register::Mode2, mode2)?;
         self.hal.delay_ms(1);
-
-        // INPMUX: AINP = AIN7, AINN = AIN6
-        self.write_reg(Register::InpMux, (0x07 << 4) | 0x06)?;
+        self.adc1.gain = gain;
+        self.adc1.data_rate = drate;
+        self.adc1.delay = delay;
+
+        // INPMUX: sense inputs
+        self.overrun.restart();
+        self.write_reg(
+            Register::InpMux,
+            (pins.positive.bits() << 4) | pins.negative.bits(),
+        )?;
         self.hal.delay_ms(1);
 
-        // REFMUX: AIN4, AIN5
-        self.write_reg(Register::RefMux, (0x03 << 3) | 0x03)?;
+        // REFMUX: external reference across the reference resistor
+        self.write_reg(Register::RefMux, config.reference as u8)?;
         self.hal.delay_ms(1);
 
         // Read one conversion
-        self.write_cmd(Command::Start1)?;
+        self.start_adc1()?;
         self.hal.delay_ms(10);
-        self.hal.wait_drdy()?;
+        self.wait_conversion()?;
         let value = self.read_adc1_data()?;
-        self.write_cmd(Command::Stop1)?;
+        self.stop_adc1()?;
 
         Ok(value)
     }
 
     /// Convert RTD raw value to resistance
     ///
+    /// For conversions at unity gain; see [`RtdWiring::resistance`] for
+    /// other gains.
+    ///
     /// # Arguments
     ///
-    /// * `raw` - Raw ADC value from `read_rtd()`
+    /// * `raw` - Raw ADC value from `read_rtd()` at `Gain::Gain1`
     /// * `r_ref` - Reference resistor value in ohms (e.g., 2000.0 for 2kΩ)
     ///
     /// # Returns
     ///
     /// Resistance in ohms
     pub fn rtd_to_resistance(raw: u32, r_ref: f64) -> f64 {
-        (raw as f64 / 2147483647.0) * 2.0 * r_ref
+        RtdWiring::ThreeWire.resistance(raw, r_ref, Gain::Gain1)
     }
 
     /// Convert PT100 resistance to temperature
     ///
-    /// Uses simplified linear approximation for PT100 sensors.
+    /// Uses simplified linear approximation for PT100 sensors, 3 °C off at
+    /// 200 °C and more toward the ends of the range; kept for compatibility,
+    /// see [`pt100_to_celsius_cvd`](Self::pt100_to_celsius_cvd).
     ///
     /// # Arguments
     ///
@@ -647,6 +1900,37 @@ impl Ads1263 {
         (resistance / 100.0 - 1.0) / 0.00385
     }
 
+    /// Convert PT100 resistance to temperature with the Callendar–Van
+    /// Dusen equation
+    ///
+    /// Same as [`RtdElement::PT100`]`.celsius(resistance)`; see
+    /// [`RtdElement`] for other elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `resistance` - Resistance in ohms
+    ///
+    /// # Returns
+    ///
+    /// Temperature in degrees Celsius
+    pub fn pt100_to_celsius_cvd(resistance: f64) -> f64 {
+        RtdElement::PT100.celsius(resistance)
+    }
+
+    /// PT100 resistance at a temperature, by the Callendar–Van Dusen
+    /// equation with the IEC 60751 coefficients
+    ///
+    /// # Arguments
+    ///
+    /// * `celsius` - Temperature in degrees Celsius
+    ///
+    /// # Returns
+    ///
+    /// Resistance in ohms
+    pub fn celsius_to_pt100(celsius: f64) -> f64 {
+        RtdElement::PT100.resistance(celsius)
+    }
+
     // ========================================================================
     // DAC control
     // ========================================================================
@@ -683,9 +1967,36 @@ impl Ads1263 {
         Ok(())
     }
 
+    /// Select how ADC1 conversions are started and stopped
+    ///
+    /// Switching modes stops ADC1; call [`start_adc1`](Self::start_adc1)
+    /// to resume.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoStartPin` if `Pin` is requested but the HAL
+    /// has no START pin
+    pub fn set_conversion_control(&mut self, control: ConversionControl) -> Result<()> {
+        if control == ConversionControl::Pin && !self.hal.has_start_pin() {
+            return Err(Ads1263Error::NoStartPin);
+        }
+        self.stop_adc1()?;
+        self.control = control;
+        log::info!("ADC1 conversion control set to {:?}", control);
+        Ok(())
+    }
+
+    /// Get the ADC1 conversion control mode
+    pub fn conversion_control(&self) -> ConversionControl {
+        self.control
+    }
+
     /// Stop ADC1 conversions
     pub fn stop_adc1(&mut self) -> Result<()> {
-        self.write_cmd(Command::Stop1)
+        match self.control {
+            ConversionControl::Command => self.write_cmd(Command::Stop1),
+            ConversionControl::Pin => self.hal.set_start(false),
+        }
     }
 
     /// Stop ADC2 conversions
@@ -694,8 +2005,19 @@ impl Ads1263 {
     }
 
     /// Start ADC1 conversions
+    ///
+    /// With [`ConversionControl::Pin`] the START pin is pulsed low then
+    /// driven high, so the first conversion begins at a known instant.
     pub fn start_adc1(&mut self) -> Result<()> {
-        self.write_cmd(Command::Start1)
+        match self.control {
+            ConversionControl::Command => self.write_cmd(Command::Start1),
+            ConversionControl::Pin => {
+                // Minimum START low pulse is 4 tCLK (~0.5 µs)
+                self.hal.set_start(false)?;
+                self.hal.delay_us(1);
+                self.hal.set_start(true)
+            }
+        }
     }
 
     /// Start ADC2 conversions
@@ -0,0 +1,300 @@
+//! Threshold alarms with hysteresis and debounce
+//!
+//! [`AlarmMonitor`] checks each scan against per-channel high/low limits.
+//! A limit must be exceeded for at least the debounce time before the
+//! alarm is raised, and the value must come back past the limit by the
+//! hysteresis (again for the debounce time) before it clears, so noise
+//! around a limit does not produce a flood of events.
+//!
+//! Limits are signed raw ADC1 codes and channels are indices within a scan,
+//! as for [`Trigger`](crate::Trigger).
+
+use std::time::{Duration, Instant};
+
+/// Which limit an alarm refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlarmKind {
+    /// Value above the high limit
+    High,
+    /// Value below the low limit
+    Low,
+}
+
+/// Limits for one channel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlarmLimits {
+    /// Raise when the value exceeds this
+    pub high: Option<i32>,
+    /// Raise when the value falls below this
+    pub low: Option<i32>,
+    /// Distance back inside a limit required to clear the alarm
+    pub hysteresis: i32,
+    /// Time a condition must persist before the alarm changes state
+    pub debounce: Duration,
+}
+
+/// Alarm state change
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmEvent {
+    /// A limit has been exceeded for the debounce time
+    Raised {
+        /// Index of the value within a scan
+        channel: usize,
+        /// Limit that was exceeded
+        kind: AlarmKind,
+        /// Value that completed the debounce
+        value: i32,
+        /// Time of the scan that raised the alarm
+        at: Instant,
+    },
+    /// The value has been back inside the limit for the debounce time
+    Cleared {
+        /// Index of the value within a scan
+        channel: usize,
+        /// Limit that was cleared
+        kind: AlarmKind,
+        /// Value that completed the debounce
+        value: i32,
+        /// Time of the scan that cleared the alarm
+        at: Instant,
+    },
+}
+
+/// Debounce state of one limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum LimitState {
+    #[default]
+    Normal,
+    /// Exceeded since the given instant, not yet debounced
+    Raising(Instant),
+    Active,
+    /// Back inside since the given instant, not yet debounced
+    Clearing(Instant),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ChannelAlarm {
+    channel: usize,
+    limits: AlarmLimits,
+    high: LimitState,
+    low: LimitState,
+}
+
+/// Callback invoked for each alarm event
+type AlarmCallback = Box<dyn FnMut(&AlarmEvent) + Send>;
+
+/// Watches scans for limit violations
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{AlarmEvent, AlarmLimits, AlarmMonitor};
+/// use std::time::Duration;
+///
+/// let mut alarms = AlarmMonitor::new();
+/// alarms.set_limits(0, AlarmLimits {
+///     high: Some(1_500_000_000),
+///     hysteresis: 10_000_000,
+///     debounce: Duration::from_millis(50),
+///     ..AlarmLimits::default()
+/// });
+/// alarms.on_event(|event| {
+///     if let AlarmEvent::Raised { channel, .. } = event {
+///         eprintln!("Channel {} over limit!", channel);
+///     }
+/// });
+///
+/// let mut values = [0u32; 2];
+/// loop {
+///     adc.get_all_into(&[0, 1], &mut values)?;
+///     alarms.check(&values);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Default)]
+pub struct AlarmMonitor {
+    channels: Vec<ChannelAlarm>,
+    callbacks: Vec<AlarmCallback>,
+    /// Events from the last `check`, reused between scans
+    events: Vec<AlarmEvent>,
+}
+
+impl AlarmMonitor {
+    /// Create a monitor with no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set or replace the limits for a channel
+    ///
+    /// Replacing limits resets the channel's alarm state.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Index of the value within a scan
+    /// * `limits` - High/low limits, hysteresis, and debounce time
+    pub fn set_limits(&mut self, channel: usize, limits: AlarmLimits) {
+        let alarm = ChannelAlarm {
+            channel,
+            limits,
+            high: LimitState::Normal,
+            low: LimitState::Normal,
+        };
+        match self.channels.iter_mut().find(|a| a.channel == channel) {
+            Some(existing) => *existing = alarm,
+            None => self.channels.push(alarm),
+        }
+        log::debug!("Alarm limits for channel {}: {:?}", channel, limits);
+    }
+
+    /// Remove the limits for a channel
+    pub fn remove_limits(&mut self, channel: usize) {
+        self.channels.retain(|a| a.channel != channel);
+    }
+
+    /// Register a callback for every alarm event
+    ///
+    /// Callbacks run synchronously inside [`check`](Self::check).
+    pub fn on_event(&mut self, callback: impl FnMut(&AlarmEvent) + Send + 'static) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    /// true if the alarm for `channel` and `kind` is currently raised
+    pub fn is_active(&self, channel: usize, kind: AlarmKind) -> bool {
+        self.channels
+            .iter()
+            .find(|a| a.channel == channel)
+            .is_some_and(|a| {
+                let state = match kind {
+                    AlarmKind::High => a.high,
+                    AlarmKind::Low => a.low,
+                };
+                matches!(state, LimitState::Active | LimitState::Clearing(_))
+            })
+    }
+
+    /// Check a scan taken now; see [`check_at`](Self::check_at)
+    pub fn check(&mut self, scan: &[u32]) -> &[AlarmEvent] {
+        self.check_at(scan, Instant::now())
+    }
+
+    /// Check a scan taken at `at` against all limits
+    ///
+    /// Channels beyond the end of the scan are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The events this scan produced, after the callbacks have run
+    pub fn check_at(&mut self, scan: &[u32], at: Instant) -> &[AlarmEvent] {
+        self.events.clear();
+        for alarm in &mut self.channels {
+            let Some(&raw) = scan.get(alarm.channel) else {
+                continue;
+            };
+            let value = raw as i32;
+            let limits = alarm.limits;
+
+            if let Some(high) = limits.high {
+                let exceeded = value > high;
+                let inside = value < high.saturating_sub(limits.hysteresis);
+                if let Some(raised) = step(&mut alarm.high, exceeded, inside, at, limits.debounce) {
+                    self.events
+                        .push(event(raised, alarm.channel, AlarmKind::High, value, at));
+                }
+            }
+            if let Some(low) = limits.low {
+                let exceeded = value < low;
+                let inside = value > low.saturating_add(limits.hysteresis);
+                if let Some(raised) = step(&mut alarm.low, exceeded, inside, at, limits.debounce) {
+                    self.events
+                        .push(event(raised, alarm.channel, AlarmKind::Low, value, at));
+                }
+            }
+        }
+
+        for event in &self.events {
+            match event {
+                AlarmEvent::Raised {
+                    channel,
+                    kind,
+                    value,
+                    ..
+                } => {
+                    log::warn!("Alarm raised: channel {} {:?} ({})", channel, kind, value)
+                }
+                AlarmEvent::Cleared {
+                    channel,
+                    kind,
+                    value,
+                    ..
+                } => {
+                    log::info!("Alarm cleared: channel {} {:?} ({})", channel, kind, value)
+                }
+            }
+            for callback in &mut self.callbacks {
+                callback(event);
+            }
+        }
+        &self.events
+    }
+}
+
+/// Advance one limit's debounce state
+///
+/// Returns `Some(true)` when the alarm is raised and `Some(false)` when it clears.
+fn step(
+    state: &mut LimitState,
+    exceeded: bool,
+    inside: bool,
+    at: Instant,
+    debounce: Duration,
+) -> Option<bool> {
+    let settled = |since: Instant| at.saturating_duration_since(since) >= debounce;
+    match *state {
+        LimitState::Normal if exceeded => {
+            *state = LimitState::Raising(at);
+            if settled(at) {
+                *state = LimitState::Active;
+                return Some(true);
+            }
+        }
+        LimitState::Raising(since) if exceeded && settled(since) => {
+            *state = LimitState::Active;
+            return Some(true);
+        }
+        LimitState::Raising(_) if !exceeded => *state = LimitState::Normal,
+        LimitState::Active if inside => {
+            *state = LimitState::Clearing(at);
+            if settled(at) {
+                *state = LimitState::Normal;
+                return Some(false);
+            }
+        }
+        LimitState::Clearing(since) if inside && settled(since) => {
+            *state = LimitState::Normal;
+            return Some(false);
+        }
+        LimitState::Clearing(_) if !inside => *state = LimitState::Active,
+        _ => {}
+    }
+    None
+}
+
+fn event(raised: bool, channel: usize, kind: AlarmKind, value: i32, at: Instant) -> AlarmEvent {
+    if raised {
+        AlarmEvent::Raised {
+            channel,
+            kind,
+            value,
+            at,
+        }
+    } else {
+        AlarmEvent::Cleared {
+            channel,
+            kind,
+            value,
+            at,
+        }
+    }
+}
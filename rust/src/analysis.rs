@@ -0,0 +1,243 @@
+//! Spectrum analysis of captured blocks
+//!
+//! [`Spectrum::compute`] applies a [`Window`] to a block of values and
+//! returns the single-sided amplitude spectrum, from which the strongest
+//! tones, THD, and SNR can be read off, e.g. to find mains pickup or clock
+//! spurs in a [`Burst`](crate::Burst) without exporting the data first.
+//!
+//! Requires the `analysis` feature.
+
+use crate::sample::Sample;
+use rustfft::FftPlanner;
+use rustfft::num_complex::Complex;
+use std::f64::consts::PI;
+
+/// Window function applied before the FFT
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Window {
+    /// No window; only for signals periodic in the block
+    Rectangular,
+    /// Hann window (default), a good general-purpose choice
+    #[default]
+    Hann,
+    /// Hamming window
+    Hamming,
+    /// 4-term Blackman-Harris window, for high dynamic range
+    BlackmanHarris,
+}
+
+impl Window {
+    /// Window coefficient `n` of a block of `len`
+    fn coefficient(self, n: usize, len: usize) -> f64 {
+        if len < 2 {
+            return 1.0;
+        }
+        let x = 2.0 * PI * n as f64 / (len - 1) as f64;
+        match self {
+            Window::Rectangular => 1.0,
+            Window::Hann => 0.5 - 0.5 * x.cos(),
+            Window::Hamming => 0.54 - 0.46 * x.cos(),
+            Window::BlackmanHarris => {
+                0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos() - 0.01168 * (3.0 * x).cos()
+            }
+        }
+    }
+
+    /// Bins on each side of a tone that hold its main lobe
+    fn lobe_bins(self) -> usize {
+        match self {
+            Window::Rectangular => 1,
+            Window::Hann | Window::Hamming => 2,
+            Window::BlackmanHarris => 4,
+        }
+    }
+}
+
+/// Single-sided amplitude spectrum
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    /// Frequency spacing of the bins in Hz
+    pub bin_width: f64,
+    /// Peak amplitude per bin, in the units of the input; bin 0 is DC
+    pub amplitudes: Vec<f64>,
+    /// Number of values the spectrum was computed from
+    pub block_len: usize,
+    /// Window the spectrum was computed with
+    pub window: Window,
+}
+
+impl Spectrum {
+    /// Compute the spectrum of a block of values
+    ///
+    /// Amplitudes are corrected for the window's coherent gain, so a sine
+    /// of amplitude A centred on a bin shows as A. Non-finite values are
+    /// treated as 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Block of equally spaced values
+    /// * `sample_rate` - Rate of `values` in Hz
+    /// * `window` - Window function
+    pub fn compute(values: &[f64], sample_rate: f64, window: Window) -> Self {
+        let len = values.len();
+        if len == 0 {
+            return Self {
+                bin_width: 0.0,
+                amplitudes: Vec::new(),
+                block_len: 0,
+                window,
+            };
+        }
+
+        let mut gain = 0.0;
+        let mut buffer: Vec<Complex<f64>> = values
+            .iter()
+            .enumerate()
+            .map(|(n, &v)| {
+                let w = window.coefficient(n, len);
+                gain += w;
+                let v = if v.is_finite() { v } else { 0.0 };
+                Complex::new(v * w, 0.0)
+            })
+            .collect();
+        FftPlanner::new().plan_fft_forward(len).process(&mut buffer);
+
+        let amplitudes = buffer[..len / 2 + 1]
+            .iter()
+            .enumerate()
+            .map(|(k, c)| {
+                // DC and Nyquist have no mirror image in the discarded half
+                let scale = if k == 0 || 2 * k == len { 1.0 } else { 2.0 };
+                c.norm() * scale / gain
+            })
+            .collect();
+
+        Self {
+            bin_width: sample_rate / len as f64,
+            amplitudes,
+            block_len: len,
+            window,
+        }
+    }
+
+    /// Compute the spectrum of the raw values of a capture
+    pub fn from_samples(samples: &[Sample], sample_rate: f64, window: Window) -> Self {
+        let values: Vec<f64> = samples.iter().map(|s| f64::from(s.raw)).collect();
+        Self::compute(&values, sample_rate, window)
+    }
+
+    /// Centre frequency of a bin in Hz
+    pub fn frequency(&self, bin: usize) -> f64 {
+        bin as f64 * self.bin_width
+    }
+
+    /// Strongest tone outside DC as (frequency, amplitude)
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        self.fundamental_bin()
+            .map(|bin| (self.frequency(bin), self.amplitudes[bin]))
+    }
+
+    /// The `count` strongest local maxima outside DC as (frequency, amplitude)
+    ///
+    /// Sorted by descending amplitude.
+    pub fn peaks(&self, count: usize) -> Vec<(f64, f64)> {
+        let a = &self.amplitudes;
+        let mut peaks: Vec<usize> = (self.window.lobe_bins() + 1..a.len())
+            .filter(|&k| a[k] > a[k - 1] && a.get(k + 1).is_none_or(|&next| a[k] >= next))
+            .collect();
+        peaks.sort_by(|&x, &y| a[y].total_cmp(&a[x]));
+        peaks
+            .into_iter()
+            .take(count)
+            .map(|k| (self.frequency(k), a[k]))
+            .collect()
+    }
+
+    /// Total harmonic distortion in dB relative to the fundamental
+    ///
+    /// The fundamental is the strongest tone outside DC; harmonics 2 to
+    /// `harmonics` + 1 are included, folded back below Nyquist where they alias.
+    pub fn thd_db(&self, harmonics: usize) -> Option<f64> {
+        let fundamental = self.fundamental_bin()?;
+        let harmonic_power: f64 = self
+            .harmonic_bins(fundamental, harmonics)
+            .map(|bin| self.tone_power(bin))
+            .sum();
+        Some(10.0 * (harmonic_power / self.tone_power(fundamental)).log10())
+    }
+
+    /// Signal-to-noise ratio in dB
+    ///
+    /// Noise is everything except DC, the fundamental, and harmonics 2 to
+    /// `harmonics` + 1.
+    pub fn snr_db(&self, harmonics: usize) -> Option<f64> {
+        let fundamental = self.fundamental_bin()?;
+        let lobe = self.window.lobe_bins();
+        let mut excluded = vec![false; self.amplitudes.len()];
+        let mut exclude = |center: usize| {
+            let end = (center + lobe + 1).min(excluded.len());
+            for flag in &mut excluded[center.saturating_sub(lobe)..end] {
+                *flag = true;
+            }
+        };
+        exclude(0);
+        exclude(fundamental);
+        for bin in self.harmonic_bins(fundamental, harmonics) {
+            exclude(bin);
+        }
+
+        let noise: f64 = self
+            .amplitudes
+            .iter()
+            .zip(&excluded)
+            .filter(|&(_, &skip)| !skip)
+            .map(|(a, _)| a * a)
+            .sum();
+        Some(10.0 * (self.tone_power(fundamental) / noise).log10())
+    }
+
+    /// Bin of the strongest tone, skipping the DC lobe
+    fn fundamental_bin(&self) -> Option<usize> {
+        let start = self.window.lobe_bins() + 1;
+        (start..self.amplitudes.len())
+            .max_by(|&x, &y| self.amplitudes[x].total_cmp(&self.amplitudes[y]))
+    }
+
+    /// Bins of harmonics 2..=harmonics+1, folded below Nyquist
+    fn harmonic_bins(&self, fundamental: usize, harmonics: usize) -> impl Iterator<Item = usize> {
+        // Bins of the full (two-sided) spectrum
+        let full = self.block_len;
+        (2..harmonics + 2).map(move |h| {
+            let bin = (h * fundamental) % full.max(1);
+            if bin > full / 2 { full - bin } else { bin }
+        })
+    }
+
+    /// Power of a tone, summed over its main lobe
+    fn tone_power(&self, center: usize) -> f64 {
+        let lobe = self.window.lobe_bins();
+        let end = (center + lobe + 1).min(self.amplitudes.len());
+        self.amplitudes[center.saturating_sub(lobe)..end]
+            .iter()
+            .map(|a| a * a)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn harmonics_fold_at_odd_block_lengths() {
+        let len = 101;
+        let values: Vec<f64> = (0..len)
+            .map(|n| (2.0 * std::f64::consts::PI * 40.0 * n as f64 / len as f64).sin())
+            .collect();
+        let spectrum = Spectrum::compute(&values, 1000.0, Window::Rectangular);
+        assert_eq!(spectrum.fundamental_bin(), Some(40));
+        // 80 and 120 fold to 101 - 80 and 120 - 101
+        let bins: Vec<usize> = spectrum.harmonic_bins(40, 2).collect();
+        assert_eq!(bins, [21, 19]);
+    }
+}
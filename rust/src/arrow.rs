@@ -0,0 +1,242 @@
+//! Apache Arrow / Parquet export
+//!
+//! [`ParquetSink`] collects samples into Arrow record batches and writes
+//! them as a Snappy-compressed Parquet file that pandas, Polars, DuckDB,
+//! or Spark read directly:
+//!
+//! | Column | Arrow type |
+//! |--------|------------|
+//! | `timestamp` | `Timestamp(Microsecond, "UTC")` |
+//! | `channel` | `Dictionary(UInt8, Utf8)`, the [`ChannelMap`] name |
+//! | `raw` | `Int32`, ADC1 code |
+//! | `value` | `Float64`, converted to the channel's unit |
+//! | `sequence` | `UInt64`, acquisition sequence number |
+//!
+//! Channel units and the reference voltage are stored in the schema
+//! metadata.
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use arrow_array::builder::StringDictionaryBuilder;
+use arrow_array::types::UInt8Type;
+use arrow_array::{
+    ArrayRef, Float64Array, Int32Array, RecordBatch, TimestampMicrosecondArray, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+/// Default number of rows per record batch
+const BATCH_ROWS: usize = 65536;
+
+/// Sink writing samples to a Parquet file
+///
+/// Samples are buffered and written a record batch at a time. The file's
+/// footer is written by [`finish`](Self::finish) or on drop; a file cut
+/// short without it cannot be read.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelInfo, ChannelMap, DataRate, Hal, ParquetSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let sink = ParquetSink::create("capture.parquet", map)?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct ParquetSink<W: Write + Send> {
+    writer: Option<ArrowWriter<W>>,
+    schema: SchemaRef,
+    map: ChannelMap,
+    clock: WallClock,
+    batch_rows: usize,
+    pending: Vec<Sample>,
+}
+
+impl ParquetSink<File> {
+    /// Create (or truncate) a Parquet file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created
+    pub fn create(path: impl AsRef<Path>, map: ChannelMap) -> Result<Self> {
+        Self::new(File::create(path)?, map)
+    }
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    /// Write to `writer`, naming and converting channels with `map`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Parquet` if the file header cannot be written
+    pub fn new(writer: W, map: ChannelMap) -> Result<Self> {
+        let schema = schema(&map);
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        let writer = ArrowWriter::try_new(writer, schema.clone(), Some(props))?;
+        Ok(Self {
+            writer: Some(writer),
+            schema,
+            map,
+            clock: WallClock::new(),
+            batch_rows: BATCH_ROWS,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the number of rows per record batch (default 65536)
+    pub fn batch_rows(mut self, rows: usize) -> Self {
+        self.batch_rows = rows.max(1);
+        self
+    }
+
+    /// Arrow schema of the written batches
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Write buffered samples and the file footer, and return the writer
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Parquet` if writing fails
+    pub fn finish(mut self) -> Result<W> {
+        self.write_batch()?;
+        let writer = self.writer.take().expect("writer present until finished");
+        Ok(writer.into_inner()?)
+    }
+
+    /// Write the pending samples as one record batch
+    fn write_batch(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        self.clock.update();
+
+        let mut channel = StringDictionaryBuilder::<UInt8Type>::new();
+        for sample in &self.pending {
+            channel.append(self.map.name(sample.channel))?;
+        }
+        let timestamp: TimestampMicrosecondArray = self
+            .pending
+            .iter()
+            .map(|s| {
+                let utc = self.clock.to_utc(s.timestamp);
+                utc.duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros() as i64
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let raw: Int32Array = self
+            .pending
+            .iter()
+            .map(|s| s.raw)
+            .collect::<Vec<_>>()
+            .into();
+        let value: Float64Array = self
+            .pending
+            .iter()
+            .map(|s| self.map.convert_sample(s))
+            .collect::<Vec<_>>()
+            .into();
+        let sequence: UInt64Array = self
+            .pending
+            .iter()
+            .map(|s| s.sequence)
+            .collect::<Vec<_>>()
+            .into();
+
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(timestamp.with_timezone("UTC")),
+            Arc::new(channel.finish()),
+            Arc::new(raw),
+            Arc::new(value),
+            Arc::new(sequence),
+        ];
+        writer.write(&RecordBatch::try_new(self.schema.clone(), columns)?)?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> Sink for ParquetSink<W> {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.pending.extend_from_slice(samples);
+        if self.pending.len() >= self.batch_rows {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Write buffered samples and close the current row group
+    fn flush(&mut self) -> Result<()> {
+        self.write_batch()?;
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> Drop for ParquetSink<W> {
+    fn drop(&mut self) {
+        let result = self.write_batch().and_then(|()| match self.writer.take() {
+            Some(writer) => writer.close().map(drop).map_err(Into::into),
+            None => Ok(()),
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to finish Parquet file: {}", e);
+        }
+    }
+}
+
+/// Schema of the written batches, with units in the metadata
+fn schema(map: &ChannelMap) -> SchemaRef {
+    let fields = vec![
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new(
+            "channel",
+            DataType::Dictionary(Box::new(DataType::UInt8), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new("raw", DataType::Int32, false),
+        Field::new("value", DataType::Float64, false),
+        Field::new("sequence", DataType::UInt64, false),
+    ];
+    let mut metadata: HashMap<String, String> = map
+        .iter()
+        .map(|(_, info)| (format!("unit.{}", info.name), info.unit.clone()))
+        .collect();
+    metadata.insert("reference".to_string(), map.reference().to_string());
+    Arc::new(Schema::new_with_metadata(fields, metadata))
+}
@@ -0,0 +1,262 @@
+//! Async front-end for the ADS1263 driver
+//!
+//! Wraps the blocking [`Ads1263`] driver for use inside a tokio runtime.
+//! SPI transactions run on tokio's blocking thread pool and DRDY is awaited
+//! through a GPIO edge interrupt, so no executor thread is blocked while a
+//! conversion is in progress.
+//!
+//! Requires the `tokio` feature.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::handle::Ads1263Handle;
+use crate::registers::{Adc2DataRate, DataRate, InputMode};
+use crate::sample::Sample;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{Mutex, MutexGuard, Notify, mpsc};
+
+/// Async ADS1263 driver
+///
+/// Cloning is cheap; all clones share the same device. Reads hold the
+/// device from selecting the input until the result is fetched, so
+/// concurrent reads on different clones never see each other's channel.
+/// Blocking [`handle`](Self::handle)s bypass that ordering and must not
+/// change the input while an async read is pending.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, Ads1263Async, DataRate, Hal};
+///
+/// async fn read() -> ads1263::Result<u32> {
+///     let adc = Ads1263Async::new(Ads1263::new(Hal::new()?))?;
+///     adc.init_adc1(DataRate::Sps400).await?;
+///     adc.read_channel(0).await
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Ads1263Async {
+    adc: Ads1263Handle,
+    drdy: Arc<Notify>,
+    session: Arc<Mutex<()>>,
+}
+
+impl Ads1263Async {
+    /// Create an async driver from a blocking driver instance
+    ///
+    /// Installs a DRDY edge callback that wakes pending reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the DRDY interrupt cannot be configured
+    pub fn new(mut adc: Ads1263) -> Result<Self> {
+        let drdy = Arc::new(Notify::new());
+        let notify = drdy.clone();
+        adc.hal_mut()
+            .set_drdy_callback(move |_| notify.notify_waiters())?;
+
+        Ok(Self {
+            adc: Ads1263Handle::new(adc),
+            drdy,
+            session: Arc::new(Mutex::new(())),
+        })
+    }
+
+    /// Get a blocking handle to the same device
+    pub fn handle(&self) -> Ads1263Handle {
+        self.adc.clone()
+    }
+
+    /// Recover the blocking driver
+    ///
+    /// Fails and returns `self` if other clones of this handle still exist.
+    pub fn try_into_inner(self) -> std::result::Result<Ads1263, Self> {
+        let Self { adc, drdy, session } = self;
+        match adc.try_into_inner() {
+            Ok(mut adc) => {
+                if let Err(e) = adc.hal_mut().clear_drdy_callback() {
+                    log::warn!("Failed to remove DRDY callback: {}", e);
+                }
+                Ok(adc)
+            }
+            Err(adc) => Err(Self { adc, drdy, session }),
+        }
+    }
+
+    /// Hold the device across the select, wait, and read of one conversion
+    pub(crate) async fn session(&self) -> MutexGuard<'_, ()> {
+        self.session.lock().await
+    }
+
+    /// Run a blocking driver operation on tokio's blocking thread pool
+    pub(crate) async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Ads1263) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let adc = self.adc.clone();
+        match tokio::task::spawn_blocking(move || adc.with(f)).await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(Ads1263Error::WorkerTerminated),
+        }
+    }
+
+    /// Set the input mode (single-ended or differential)
+    pub fn set_mode(&self, mode: InputMode) {
+        self.adc.set_mode(mode);
+    }
+
+    /// Get the current input mode
+    pub fn get_mode(&self) -> InputMode {
+        self.adc.get_mode()
+    }
+
+    /// Initialize ADC1 with specified data rate
+    ///
+    /// See [`Ads1263::init_adc1`].
+    pub async fn init_adc1(&self, rate: DataRate) -> Result<()> {
+        self.run(move |adc| adc.init_adc1(rate)).await
+    }
+
+    /// Initialize ADC2 with specified data rate
+    ///
+    /// See [`Ads1263::init_adc2`].
+    pub async fn init_adc2(&self, rate: Adc2DataRate) -> Result<()> {
+        self.run(move |adc| adc.init_adc2(rate)).await
+    }
+
+    /// Await DRDY going low (data ready)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::DrdyTimeout` if no conversion completes within
+    /// [`Ads1263::drdy_timeout`]
+    pub async fn wait_drdy(&self) -> Result<()> {
+        let mut notified = std::pin::pin!(self.drdy.notified());
+        notified.as_mut().enable();
+
+        // A conversion may have completed before the waiter was registered
+        let (ready, expected, timeout) = {
+            let adc = self.adc.lock();
+            (
+                !adc.hal().read_drdy(),
+                adc.conversion_period(),
+                adc.drdy_timeout(),
+            )
+        };
+        if ready {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        tokio::time::timeout(timeout, notified).await.map_err(|_| {
+            let waited = start.elapsed();
+            log::error!(
+                "Timeout awaiting DRDY: expected {:?}, waited {:?}",
+                expected,
+                waited
+            );
+            Ads1263Error::DrdyTimeout { expected, waited }
+        })
+    }
+
+    /// Read an ADC1 channel value (raw 32-bit)
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub async fn read_channel(&self, channel: u8) -> Result<u32> {
+        let _session = self.session().await;
+        self.run(move |adc| adc.select_channel(channel)).await?;
+        self.wait_drdy().await?;
+        self.run(|adc| adc.read_adc1_data()).await
+    }
+
+    /// Read an ADC1 channel as a timestamped sample
+    ///
+    /// The timestamp is taken when the DRDY wait completes.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub async fn read_sample(&self, channel: u8) -> Result<Sample> {
+        let _session = self.session().await;
+        self.run(move |adc| adc.select_channel(channel)).await?;
+        self.wait_drdy().await?;
+        let timestamp = Instant::now();
+        self.run(move |adc| adc.read_adc1_sample(channel, timestamp))
+            .await
+    }
+
+    /// Await the next conversion of the channel already routed to ADC1
+    ///
+    /// Counts missed conversions like [`Ads1263::samples`]. The caller
+    /// holds the [`session`](Self::session) that selected the channel.
+    #[cfg(feature = "stream")]
+    pub(crate) async fn read_next_sample(&self, channel: u8) -> Result<Sample> {
+        self.wait_drdy().await?;
+        let timestamp = Instant::now();
+        self.run(move |adc| adc.read_next_sample_at(channel, timestamp))
+            .await
+    }
+
+    /// Read multiple ADC1 channels
+    ///
+    /// # Returns
+    ///
+    /// Vector of raw 32-bit values in the same order as input channels
+    pub async fn read_all(&self, channels: &[u8]) -> Result<Vec<u32>> {
+        let mut values = Vec::with_capacity(channels.len());
+        for &ch in channels {
+            values.push(self.read_channel(ch).await?);
+        }
+        Ok(values)
+    }
+
+    /// Read multiple ADC1 channels as timestamped samples
+    pub async fn read_samples(&self, channels: &[u8]) -> Result<Vec<Sample>> {
+        let mut samples = Vec::with_capacity(channels.len());
+        for &ch in channels {
+            samples.push(self.read_sample(ch).await?);
+        }
+        Ok(samples)
+    }
+
+    /// Continuously scan ADC1 channels on a background task
+    ///
+    /// Each message holds one scan of `channels`, one [`Sample`] per
+    /// channel. The task stops after the first error or when the receiver
+    /// is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Channel numbers to scan
+    /// * `capacity` - Number of scans buffered before the reader applies backpressure
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime or if `capacity` is 0
+    pub fn stream(
+        &self,
+        channels: Vec<u8>,
+        capacity: usize,
+    ) -> mpsc::Receiver<Result<Vec<Sample>>> {
+        let (tx, rx) = mpsc::channel(capacity);
+        let adc = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let scan = adc.read_samples(&channels).await;
+                let failed = scan.is_err();
+                if tx.send(scan).await.is_err() || failed {
+                    break;
+                }
+            }
+            log::debug!("Async scan stream stopped");
+        });
+
+        rx
+    }
+}
@@ -0,0 +1,194 @@
+//! Multiple ADS1263 boards on one SPI bus
+//!
+//! Each board needs its own CS, DRDY, and RST pins (stacked HATs must be
+//! re-jumpered so they don't share them); SCLK, DIN, and DOUT are shared.
+//! Because chip select is driven manually per device, the boards can share
+//! SPI0 as long as they are accessed one at a time, which [`Ads1263Bank`]
+//! guarantees by owning all of them.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::hal::{Hal, PinConfig, SpiConfig};
+use crate::registers::{DataRate, InputMode};
+
+/// Address of a channel on a specific device in a bank
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BankChannel {
+    /// Device index within the bank
+    pub device: usize,
+    /// Channel number on that device
+    pub channel: u8,
+}
+
+impl BankChannel {
+    /// Create a new bank channel address
+    pub const fn new(device: usize, channel: u8) -> Self {
+        Self { device, channel }
+    }
+}
+
+/// A set of ADS1263 devices sharing one SPI bus
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263Bank, BankChannel, DataRate, PinConfig};
+///
+/// let mut bank = Ads1263Bank::with_pins(&[
+///     PinConfig::default(),
+///     PinConfig { rst: 23, cs: 24, drdy: 25, ..PinConfig::default() },
+/// ])?;
+/// bank.init_adc1(DataRate::Sps400)?;
+///
+/// // Channels 0-4 on both boards, device-major
+/// let values = bank.scan(&[0, 1, 2, 3, 4])?;
+///
+/// // Or pick individual inputs
+/// let pair = bank.get_all(&[BankChannel::new(0, 3), BankChannel::new(1, 7)])?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct Ads1263Bank {
+    devices: Vec<Ads1263>,
+}
+
+impl Ads1263Bank {
+    /// Create a bank from already constructed drivers
+    pub fn new(devices: Vec<Ads1263>) -> Self {
+        Self { devices }
+    }
+
+    /// Create a bank with one device per pin configuration on the default SPI bus
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if GPIO or SPI initialization fails for any device
+    pub fn with_pins(pins: &[PinConfig]) -> Result<Self> {
+        Self::with_config(pins, SpiConfig::default())
+    }
+
+    /// Create a bank with one device per pin configuration on a custom SPI bus
+    ///
+    /// All devices share a single SPI device handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if GPIO or SPI initialization fails for any device
+    pub fn with_config(pins: &[PinConfig], spi_config: SpiConfig) -> Result<Self> {
+        let spi = Hal::open_shared_spi(spi_config)?;
+        let devices = pins
+            .iter()
+            .map(|&pin_config| {
+                Hal::with_shared_spi(pin_config, spi_config, spi.clone()).map(Ads1263::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        log::info!("Bank created with {} devices", devices.len());
+        Ok(Self { devices })
+    }
+
+    /// Number of devices in the bank
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// true if the bank has no devices
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Get a device by index
+    pub fn device(&mut self, index: usize) -> Option<&mut Ads1263> {
+        self.devices.get_mut(index)
+    }
+
+    /// Iterate over all devices
+    pub fn devices_mut(&mut self) -> impl Iterator<Item = &mut Ads1263> {
+        self.devices.iter_mut()
+    }
+
+    /// Release the individual drivers
+    pub fn into_devices(self) -> Vec<Ads1263> {
+        self.devices
+    }
+
+    /// Set the input mode on every device
+    pub fn set_mode(&mut self, mode: InputMode) {
+        for adc in &mut self.devices {
+            adc.set_mode(mode);
+        }
+    }
+
+    /// Initialize ADC1 on every device
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first device that fails to initialize
+    pub fn init_adc1(&mut self, rate: DataRate) -> Result<()> {
+        for (i, adc) in self.devices.iter_mut().enumerate() {
+            log::info!("Initializing bank device {}", i);
+            adc.init_adc1(rate)?;
+        }
+        Ok(())
+    }
+
+    /// Read individual channels across devices
+    ///
+    /// # Returns
+    ///
+    /// Raw 32-bit values in the same order as `channels`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidDevice` if a device index is out of range
+    pub fn get_all(&mut self, channels: &[BankChannel]) -> Result<Vec<u32>> {
+        let count = self.devices.len();
+        channels
+            .iter()
+            .map(|ch| {
+                self.devices
+                    .get_mut(ch.device)
+                    .ok_or(Ads1263Error::InvalidDevice(ch.device, count))?
+                    .get_channel_value(ch.channel)
+            })
+            .collect()
+    }
+
+    /// Read the same channels on every device
+    ///
+    /// # Returns
+    ///
+    /// Raw 32-bit values, device-major: all of device 0's channels, then
+    /// device 1's, and so on
+    pub fn scan(&mut self, channels: &[u8]) -> Result<Vec<u32>> {
+        let mut values = vec![0u32; channels.len() * self.devices.len()];
+        self.scan_into(channels, &mut values)?;
+        Ok(values)
+    }
+
+    /// Read the same channels on every device into a caller-provided buffer
+    ///
+    /// Allocation-free variant of [`scan`](Self::scan).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::BufferTooSmall` if `values` cannot hold every reading
+    pub fn scan_into(&mut self, channels: &[u8], values: &mut [u32]) -> Result<()> {
+        let needed = channels.len() * self.devices.len();
+        if values.len() < needed {
+            return Err(Ads1263Error::BufferTooSmall {
+                needed,
+                got: values.len(),
+            });
+        }
+        if channels.is_empty() {
+            return Ok(());
+        }
+        for (adc, chunk) in self
+            .devices
+            .iter_mut()
+            .zip(values.chunks_mut(channels.len()))
+        {
+            adc.get_all_into(channels, chunk)?;
+        }
+        Ok(())
+    }
+}
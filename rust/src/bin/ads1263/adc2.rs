@@ -0,0 +1,46 @@
+//! `adc2`: live values of the 24-bit auxiliary ADC
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263, Ads1263Config, Hal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Print all ten ADC2 inputs until Ctrl+C, overwriting the previous lines
+///
+/// With `--json`, prints one `{"inputs": [{"input", "raw", "volts"}]}` line
+/// per scan instead.
+pub fn run(config: &Ads1263Config, running: &AtomicBool, json: bool) -> ads1263::Result<()> {
+    let mut adc = Ads1263::new(Hal::with_config(config.pins, config.spi)?);
+    adc.set_mode(config.mode);
+    adc.init_adc2(config.adc2.unwrap_or_default().data_rate)?;
+
+    let reference = config.map.reference();
+    while running.load(Ordering::SeqCst) {
+        let values = adc.get_all_adc2()?;
+
+        if json {
+            let inputs = values.iter().enumerate().map(|(i, &raw)| {
+                Object::new()
+                    .int("input", i)
+                    .int("raw", raw)
+                    .num("volts", Ads1263::raw_to_voltage_adc2(raw, reference))
+                    .finish()
+            });
+            let document = Object::new().value("inputs", &json::array(inputs));
+            println!("{}", document.finish());
+            continue;
+        }
+
+        for (i, &raw) in values.iter().enumerate() {
+            let voltage = Ads1263::raw_to_voltage_adc2(raw, reference);
+            println!("IN{} {:>12.6} V", i, voltage);
+        }
+
+        // Move cursor up 10 lines
+        print!("\x1B[10A");
+    }
+    if !json {
+        print!("\x1B[10B");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//! `benchmark`: achieved sample rate and read latency
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263, Ads1263Config, SamplesConfig, WaitStrategy};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Conversions read per run
+    #[arg(short = 'n', long, default_value_t = 500)]
+    samples: usize,
+    /// SPI clocks to try, e.g. `1M,4M,8M`
+    #[arg(long, value_delimiter = ',', value_parser = parse_hz, default_value = "1M,2M,4M,8M")]
+    clocks: Vec<u32>,
+    /// DRDY wait strategies to try
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "interrupt,spin,sleep"
+    )]
+    waits: Vec<Wait>,
+    /// Read methods to try
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "single,continuous,poll"
+    )]
+    reads: Vec<Read>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Wait {
+    /// Sleep until the DRDY interrupt
+    Interrupt,
+    /// Poll DRDY continuously
+    Spin,
+    /// Poll DRDY every 100 µs
+    Sleep,
+}
+
+impl Wait {
+    fn strategy(self) -> WaitStrategy {
+        match self {
+            Wait::Interrupt => WaitStrategy::Interrupt,
+            Wait::Spin => WaitStrategy::BusySpin,
+            Wait::Sleep => WaitStrategy::Sleep {
+                poll_interval: Duration::from_micros(100),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Read {
+    /// Route the channel for every conversion
+    Single,
+    /// Stream conversions of the routed channel
+    Continuous,
+    /// Busy-poll for new conversions without waiting on DRDY
+    Poll,
+}
+
+/// Timing of one run
+struct Run {
+    /// Time each read took
+    reads: Vec<Duration>,
+    elapsed: Duration,
+    dropped: u64,
+}
+
+/// Run every combination of clock, wait strategy, and read method and
+/// print one line per run
+///
+/// SPS is compared with the configured data rate for the streaming reads
+/// and with the rate single conversions can reach, including filter
+/// settling, for `single`. Latencies are per read. `poll` busy-waits by
+/// itself, so it runs once per clock.
+///
+/// With `--json`, prints `{"results": [{"clock_hz", "wait", "read", "sps",
+/// "expected_sps", "p50_us", "p90_us", "p99_us", "max_us", "dropped"}]}`;
+/// `wait` is `null` for `poll`.
+pub fn run(config: &Ads1263Config, args: &Args, json: bool) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+    let channel = config.channels[0];
+    let n = args.samples.max(1);
+    let continuous_sps = config.adc1.data_rate.sps();
+    let single_sps = 1.0 / adc.conversion_period().as_secs_f64();
+
+    let mut results = Vec::new();
+    if !json {
+        println!(
+            "{:>8} {:<9} {:<10} {:>9} {:>7} {:>8} {:>8} {:>8} {:>8} {:>7}",
+            "CLOCK",
+            "WAIT",
+            "READ",
+            "SPS",
+            "OF RATE",
+            "P50 µs",
+            "P90 µs",
+            "P99 µs",
+            "MAX µs",
+            "DROPPED"
+        );
+    }
+    for &hz in &args.clocks {
+        adc.hal_mut().set_spi_clock(hz)?;
+        for &read in &args.reads {
+            let waits: Vec<Option<Wait>> = match read {
+                Read::Poll => vec![None],
+                _ => args.waits.iter().copied().map(Some).collect(),
+            };
+            for wait in waits {
+                if let Some(wait) = wait {
+                    adc.hal_mut().set_wait_strategy(wait.strategy())?;
+                }
+                let run = measure(&mut adc, channel, read, n)?;
+
+                let sps = run.reads.len() as f64 / run.elapsed.as_secs_f64();
+                let rate = match read {
+                    Read::Single => single_sps,
+                    Read::Continuous | Read::Poll => continuous_sps,
+                };
+                let mut micros: Vec<f64> =
+                    run.reads.iter().map(|d| d.as_secs_f64() * 1e6).collect();
+                micros.sort_by(f64::total_cmp);
+                let wait_name = wait.map(|w| format!("{:?}", w).to_lowercase());
+                let read_name = format!("{:?}", read).to_lowercase();
+                let max = micros.last().copied().unwrap_or(0.0);
+                if json {
+                    let result = Object::new()
+                        .int("clock_hz", hz)
+                        .opt("wait", wait_name.as_deref(), json::string)
+                        .str("read", &read_name)
+                        .num("sps", sps)
+                        .num("expected_sps", rate)
+                        .num("p50_us", percentile(&micros, 50.0))
+                        .num("p90_us", percentile(&micros, 90.0))
+                        .num("p99_us", percentile(&micros, 99.0))
+                        .num("max_us", max)
+                        .int("dropped", run.dropped);
+                    results.push(result.finish());
+                    continue;
+                }
+                println!(
+                    "{:>6.1}M {:<9} {:<10} {:>9.1} {:>6.1}% {:>8.0} {:>8.0} {:>8.0} {:>8.0} {:>7}",
+                    f64::from(hz) / 1e6,
+                    wait_name.as_deref().unwrap_or("-"),
+                    read_name,
+                    sps,
+                    sps / rate * 100.0,
+                    percentile(&micros, 50.0),
+                    percentile(&micros, 90.0),
+                    percentile(&micros, 99.0),
+                    max,
+                    run.dropped
+                );
+            }
+        }
+    }
+    if json {
+        let document = Object::new().value("results", &json::array(results));
+        println!("{}", document.finish());
+    }
+    Ok(())
+}
+
+/// Read `n` conversions of `channel` with one read method
+fn measure(adc: &mut Ads1263, channel: u8, read: Read, n: usize) -> ads1263::Result<Run> {
+    let mut reads = Vec::with_capacity(n);
+    adc.reset_overrun_stats();
+    let start = Instant::now();
+    match read {
+        Read::Single => {
+            for _ in 0..n {
+                let t = Instant::now();
+                adc.get_channel_value(channel)?;
+                reads.push(t.elapsed());
+            }
+        }
+        Read::Continuous => {
+            let mut samples = adc.samples(channel, SamplesConfig::default());
+            for _ in 0..n {
+                let t = Instant::now();
+                samples.next().transpose()?;
+                reads.push(t.elapsed());
+            }
+        }
+        Read::Poll => {
+            adc.select_channel(channel)?;
+            let mut t = Instant::now();
+            while reads.len() < n {
+                if adc.try_read_adc1()?.is_some() {
+                    reads.push(t.elapsed());
+                    t = Instant::now();
+                }
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    Ok(Run {
+        reads,
+        elapsed,
+        dropped: adc.overrun_stats().dropped,
+    })
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Clock in Hz, with an optional `k` or `M` suffix
+fn parse_hz(value: &str) -> Result<u32, String> {
+    let value = value.trim();
+    let (number, scale) = match value.strip_suffix(['M', 'm']) {
+        Some(number) => (number, 1e6),
+        None => match value.strip_suffix(['k', 'K']) {
+            Some(number) => (number, 1e3),
+            None => (value, 1.0),
+        },
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|n| n * scale)
+        .filter(|hz| *hz >= 1.0 && *hz <= f64::from(u32::MAX))
+        .map(|hz| hz.round() as u32)
+        .ok_or_else(|| format!("invalid clock '{}', e.g. `8M` or `500k`", value))
+}
@@ -0,0 +1,179 @@
+//! `daemon`: long-running acquisition into the configured sinks
+
+use crate::DeviceArgs;
+use ads1263::{
+    Acquisition, ActivatedSocket, Ads1263Config, Ads1263Error, CaptureHeader, Sink, SinkConfig,
+    SystemdNotifier, UnixSocketSink, listen_fds,
+};
+use signal_hook::consts::{SIGHUP, SIGTERM};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the main loop checks for signals and the watchdog
+const POLL: Duration = Duration::from_millis(100);
+
+/// Acquire into the sinks of `--config` until SIGTERM, Ctrl+C, or an error
+///
+/// Reports readiness and pings the watchdog if run as a `Type=notify`
+/// systemd service, and serves Unix socket and WebSocket sinks on the
+/// sockets systemd passed if it was socket-activated. SIGHUP re-reads the
+/// configuration file and restarts the acquisition with it; an invalid
+/// file is logged and the running acquisition kept.
+pub fn run(
+    config: Ads1263Config,
+    device: &DeviceArgs,
+    running: &AtomicBool,
+) -> ads1263::Result<()> {
+    if device.config.is_none() {
+        return Err(Ads1263Error::InvalidConfig(
+            "the daemon needs a configuration file, pass --config".to_string(),
+        ));
+    }
+    let hangup = Arc::new(AtomicBool::new(false));
+    let terminate = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(SIGHUP, hangup.clone())?;
+    signal_hook::flag::register(SIGTERM, terminate.clone())?;
+    let notifier = SystemdNotifier::from_env();
+    let sockets = listen_fds();
+
+    let mut acq = start(&config, &sockets)?;
+    ready(notifier.as_ref(), &config);
+    let mut pinged = Instant::now();
+
+    while running.load(Ordering::SeqCst) && !terminate.load(Ordering::SeqCst) {
+        if hangup.swap(false, Ordering::SeqCst) {
+            notify(notifier.as_ref(), SystemdNotifier::reloading);
+            match device.config() {
+                Ok(config) => {
+                    log::info!("Reloading the configuration");
+                    acq.join()?;
+                    acq = start(&config, &sockets)?;
+                    ready(notifier.as_ref(), &config);
+                }
+                Err(e) => {
+                    log::error!("Keeping the running configuration: {}", e);
+                    notify(notifier.as_ref(), SystemdNotifier::ready);
+                    notify(notifier.as_ref(), |n| {
+                        n.status(&format!("Reload failed: {}", e))
+                    });
+                }
+            }
+        }
+
+        match acq.recv_timeout(POLL) {
+            Err(Ads1263Error::Timeout) => {}
+            // The sampler finished its scans
+            Err(Ads1263Error::WorkerTerminated) => break,
+            Err(e) => return Err(e),
+            Ok(_) => {}
+        }
+
+        // Only a running sampler keeps the watchdog quiet
+        if let Some(notifier) = &notifier
+            && let Some(interval) = notifier.watchdog_interval()
+            && acq.is_running()
+            && pinged.elapsed() >= interval / 2
+        {
+            notify(Some(notifier), SystemdNotifier::watchdog);
+            pinged = Instant::now();
+        }
+    }
+
+    notify(notifier.as_ref(), SystemdNotifier::stopping);
+    acq.join()?;
+    Ok(())
+}
+
+/// Open the device and start acquiring into the configured sinks
+fn start(
+    config: &Ads1263Config,
+    sockets: &[(String, ActivatedSocket)],
+) -> ads1263::Result<Acquisition> {
+    if config.sinks.is_empty() {
+        return Err(Ads1263Error::InvalidConfig(
+            "no [[sinks]] configured for the daemon".to_string(),
+        ));
+    }
+    let mut adc = config.open()?;
+    let header = CaptureHeader::from_device(&mut adc, config.map.clone())?;
+    let sinks = config
+        .sinks
+        .iter()
+        .map(|sink| build(sink, &header, sockets))
+        .collect::<ads1263::Result<Vec<_>>>()?;
+    adc.start_acquisition_sink(&config.channels, config.acquisition.clone(), sinks)
+}
+
+/// Sink of `sink`, on an activated socket with its address if there is one
+fn build(
+    sink: &SinkConfig,
+    header: &CaptureHeader,
+    sockets: &[(String, ActivatedSocket)],
+) -> ads1263::Result<Box<dyn Sink>> {
+    match sink {
+        SinkConfig::UnixSocket { path } => {
+            let activated = sockets.iter().find_map(|(_, socket)| match socket {
+                ActivatedSocket::Unix(listener) => {
+                    let addr = listener.local_addr().ok()?;
+                    (addr.as_pathname() == Some(path.as_path())).then_some(listener)
+                }
+                _ => None,
+            });
+            if let Some(listener) = activated {
+                log::info!("Serving {} on the activated socket", path.display());
+                let listener = listener.try_clone()?;
+                return Ok(Box::new(UnixSocketSink::from_listener(
+                    listener,
+                    header.map.clone(),
+                )?));
+            }
+        }
+        #[cfg(feature = "websocket")]
+        SinkConfig::Websocket { bind } => {
+            use std::net::ToSocketAddrs;
+            // systemd binds a bare port to [::], so compare ports only
+            let port = bind.to_socket_addrs()?.next().map(|addr| addr.port());
+            let activated = sockets.iter().find_map(|(_, socket)| match socket {
+                ActivatedSocket::Tcp(listener) => {
+                    let addr = listener.local_addr().ok()?;
+                    (Some(addr.port()) == port).then_some(listener)
+                }
+                _ => None,
+            });
+            if let Some(listener) = activated {
+                log::info!("Serving WebSocket {} on the activated socket", bind);
+                let listener = listener.try_clone()?;
+                return Ok(Box::new(ads1263::WebSocketSink::from_listener(
+                    listener,
+                    header.map.clone(),
+                )?));
+            }
+        }
+        _ => {}
+    }
+    sink.build(header)
+}
+
+fn ready(notifier: Option<&SystemdNotifier>, config: &Ads1263Config) {
+    let status = format!(
+        "Acquiring {} channels into {} sinks",
+        config.channels.len(),
+        config.sinks.len()
+    );
+    log::info!("{}", status);
+    notify(notifier, SystemdNotifier::ready);
+    notify(notifier, |n| n.status(&status));
+}
+
+/// Send a notification if run by systemd; failures are only logged
+fn notify(
+    notifier: Option<&SystemdNotifier>,
+    send: impl FnOnce(&SystemdNotifier) -> ads1263::Result<()>,
+) {
+    if let Some(notifier) = notifier
+        && let Err(e) = send(notifier)
+    {
+        log::warn!("Failed to notify systemd: {}", e);
+    }
+}
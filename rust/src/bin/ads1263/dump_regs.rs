@@ -0,0 +1,108 @@
+//! `dump-regs`: register contents with decoded fields
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263Config, Register};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Dump the registers as found, without resetting or configuring the chip
+    #[arg(long)]
+    raw: bool,
+    /// Re-read until Ctrl+C, highlighting registers that changed
+    #[arg(long)]
+    watch: bool,
+    /// Time between reads with `--watch`
+    #[arg(long, default_value_t = 500, value_name = "MS")]
+    interval: u64,
+}
+
+/// Print address, name, raw value, and decoded fields of every register
+///
+/// With `--json`, prints `{"registers": [{"address", "name", "value",
+/// "changed", "fields"}]}`, one line per read with `--watch`.
+pub fn run(
+    config: &Ads1263Config,
+    args: &Args,
+    running: &AtomicBool,
+    json: bool,
+) -> ads1263::Result<()> {
+    let mut adc = if args.raw {
+        crate::open_raw(config)?
+    } else {
+        config.open()?
+    };
+
+    let mut previous = adc.read_registers()?;
+    print_registers(&previous, &previous, json);
+    if !args.watch {
+        return Ok(());
+    }
+
+    let interval = Duration::from_millis(args.interval);
+    while running.load(Ordering::SeqCst) {
+        std::thread::sleep(interval);
+        let values = adc.read_registers()?;
+        if !json {
+            // Clear the screen and redraw from the top
+            print!("\x1B[2J\x1B[H");
+        }
+        print_registers(&values, &previous, json);
+        previous = values;
+    }
+    Ok(())
+}
+
+/// One line per register, in reverse video where it differs from `previous`
+fn print_registers(values: &[u8; 27], previous: &[u8; 27], json: bool) {
+    if json {
+        println!("{}", document(values, previous));
+        return;
+    }
+    println!("ADDR NAME      VALUE FIELDS");
+    for ((reg, &value), &old) in Register::ALL.iter().zip(values).zip(previous) {
+        let fields: Vec<String> = reg
+            .fields(value)
+            .into_iter()
+            .map(|(name, meaning)| format!("{}={}", name, meaning))
+            .collect();
+        let line = format!(
+            "0x{:02X} {:<9} 0x{:02X}  {}",
+            *reg as u8,
+            reg.name(),
+            value,
+            fields.join(" ")
+        );
+        if value == old {
+            println!("{}", line);
+        } else {
+            println!("\x1B[7m{}\x1B[0m", line);
+        }
+    }
+}
+
+fn document(values: &[u8; 27], previous: &[u8; 27]) -> String {
+    let registers = Register::ALL
+        .iter()
+        .zip(values)
+        .zip(previous)
+        .map(|((reg, &value), &old)| {
+            let fields = reg
+                .fields(value)
+                .into_iter()
+                .fold(Object::new(), |fields, (name, meaning)| {
+                    fields.str(name, &meaning)
+                });
+            Object::new()
+                .int("address", *reg as u8)
+                .str("name", reg.name())
+                .int("value", value)
+                .bool("changed", value != old)
+                .value("fields", &fields.finish())
+                .finish()
+        });
+    Object::new()
+        .value("registers", &json::array(registers))
+        .finish()
+}
@@ -0,0 +1,171 @@
+//! `gpio`: the ADS1263's own GPIO pins
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263Config, ChipGpio, GpioDirection};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    action: Option<Action>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Action {
+    /// Show direction and level of every pin (default)
+    Status,
+    /// Make a pin an input or an output
+    Dir {
+        /// GPIO pin (0-7)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..8))]
+        pin: u8,
+        direction: Direction,
+    },
+    /// Print a pin's level as 0 or 1
+    Read {
+        /// GPIO pin (0-7)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..8))]
+        pin: u8,
+    },
+    /// Drive a pin high (1) or low (0), making it an output
+    Write {
+        /// GPIO pin (0-7)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..8))]
+        pin: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(0..=1))]
+        level: u8,
+    },
+    /// Return a pin to analog input use
+    Release {
+        /// GPIO pin (0-7)
+        #[arg(value_parser = clap::value_parser!(u8).range(0..8))]
+        pin: u8,
+    },
+    /// Print level changes until Ctrl+C
+    Watch {
+        /// Time between reads
+        #[arg(long, default_value_t = 10, value_name = "MS")]
+        interval: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Direction {
+    In,
+    Out,
+}
+
+/// Run a GPIO action on the chip as found, so settings persist between calls
+///
+/// With `--json`, `status` prints `{"pins": [{"pin", "input", "mode",
+/// "level"}]}` with a `null` level for analog pins, `read` prints
+/// `{"pin", "level"}`, and `watch` prints a `{"time", "pin", "level"}` line
+/// per change.
+pub fn run(
+    config: &Ads1263Config,
+    args: &Args,
+    running: &AtomicBool,
+    json: bool,
+) -> ads1263::Result<()> {
+    let mut adc = crate::open_raw(config)?;
+    let mut gpio = adc.gpio();
+
+    match args.action.as_ref().unwrap_or(&Action::Status) {
+        Action::Status => {
+            let levels = gpio.read_all()?;
+            let mut pins = Vec::new();
+            if !json {
+                println!("PIN   INPUT  MODE    LEVEL");
+            }
+            for pin in 0..ChipGpio::PINS {
+                let (mode, level) = match gpio.direction(pin)? {
+                    None => ("analog", "-"),
+                    Some(GpioDirection::Input) => ("input", bit(levels, pin)),
+                    Some(GpioDirection::Output) => ("output", bit(levels, pin)),
+                };
+                if json {
+                    let level = (mode != "analog").then_some(level);
+                    let object = Object::new()
+                        .int("pin", pin)
+                        .str("input", ChipGpio::pin_name(pin))
+                        .str("mode", mode)
+                        .opt("level", level, str::to_string);
+                    pins.push(object.finish());
+                    continue;
+                }
+                println!(
+                    "GPIO{} {:<6} {:<7} {}",
+                    pin,
+                    ChipGpio::pin_name(pin),
+                    mode,
+                    level
+                );
+            }
+            if json {
+                println!(
+                    "{}",
+                    Object::new().value("pins", &json::array(pins)).finish()
+                );
+            }
+        }
+        Action::Dir { pin, direction } => {
+            let direction = match direction {
+                Direction::In => GpioDirection::Input,
+                Direction::Out => GpioDirection::Output,
+            };
+            gpio.set_direction(*pin, direction)?;
+        }
+        Action::Read { pin } => {
+            let level = u8::from(gpio.read(*pin)?);
+            if json {
+                let document = Object::new().int("pin", pin).int("level", level);
+                println!("{}", document.finish());
+            } else {
+                println!("{}", level);
+            }
+        }
+        Action::Write { pin, level } => {
+            // Level first, so the pin doesn't glitch when it becomes an output
+            gpio.write(*pin, *level == 1)?;
+            gpio.set_direction(*pin, GpioDirection::Output)?;
+        }
+        Action::Release { pin } => gpio.release(*pin)?,
+        Action::Watch { interval } => {
+            let start = Instant::now();
+            let mut previous = gpio.read_all()?;
+            if !json {
+                println!("{:08b}", previous);
+            }
+            while running.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(*interval));
+                let levels = gpio.read_all()?;
+                for pin in (0..ChipGpio::PINS).filter(|pin| (levels ^ previous) & (1 << pin) != 0) {
+                    if json {
+                        let change = Object::new()
+                            .num("time", start.elapsed().as_secs_f64())
+                            .int("pin", pin)
+                            .value("level", bit(levels, pin));
+                        println!("{}", change.finish());
+                        continue;
+                    }
+                    println!(
+                        "{:>10.3} s GPIO{} ({}) {} -> {}",
+                        start.elapsed().as_secs_f64(),
+                        pin,
+                        ChipGpio::pin_name(pin),
+                        bit(previous, pin),
+                        bit(levels, pin)
+                    );
+                }
+                previous = levels;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `0` or `1` for one pin of a GPIODAT value
+fn bit(levels: u8, pin: u8) -> &'static str {
+    if levels & (1 << pin) != 0 { "1" } else { "0" }
+}
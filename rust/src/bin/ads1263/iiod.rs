@@ -0,0 +1,33 @@
+//! `iiod`: ADC1 served to libiio clients
+
+use ads1263::{Ads1263Config, IIOD_PORT, IioServer};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "0.0.0.0")]
+    bind: String,
+    /// TCP port
+    #[arg(long, default_value_t = IIOD_PORT)]
+    port: u16,
+}
+
+/// Serve until Ctrl+C (`iio_info -u ip:<host>`)
+pub fn run(config: &Ads1263Config, args: &Args, running: &AtomicBool) -> ads1263::Result<()> {
+    let adc = config.open()?;
+    let server = IioServer::bind(
+        (args.bind.as_str(), args.port),
+        adc,
+        config.map.clone(),
+        &config.channels,
+    )?;
+    eprintln!("IIO server listening on {}", server.local_addr());
+
+    while running.load(Ordering::SeqCst) {
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    server.shutdown()?;
+    Ok(())
+}
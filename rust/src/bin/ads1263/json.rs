@@ -0,0 +1,96 @@
+//! JSON documents printed with `--json`
+//!
+//! Built by hand like the library's JSON Lines output. Keys are snake_case
+//! and come in a fixed order; numbers that are not finite become `null`.
+
+use std::fmt::{Display, Write};
+
+/// JSON object built one field at a time
+pub struct Object(String);
+
+impl Object {
+    pub fn new() -> Self {
+        Self("{".to_string())
+    }
+
+    /// Field with an already encoded JSON value, e.g. from [`array`]
+    pub fn value(mut self, key: &str, json: &str) -> Self {
+        if self.0.len() > 1 {
+            self.0.push(',');
+        }
+        self.0.push_str(&string(key));
+        self.0.push(':');
+        self.0.push_str(json);
+        self
+    }
+
+    pub fn str(self, key: &str, value: &str) -> Self {
+        self.value(key, &string(value))
+    }
+
+    pub fn num(self, key: &str, value: f64) -> Self {
+        self.value(key, &number(value))
+    }
+
+    /// Integer field
+    pub fn int(self, key: &str, value: impl Display) -> Self {
+        self.value(key, &value.to_string())
+    }
+
+    pub fn bool(self, key: &str, value: bool) -> Self {
+        self.value(key, if value { "true" } else { "false" })
+    }
+
+    /// Field that is `null` if `value` is `None`
+    pub fn opt<T>(self, key: &str, value: Option<T>, encode: impl FnOnce(T) -> String) -> Self {
+        match value {
+            Some(value) => {
+                let json = encode(value);
+                self.value(key, &json)
+            }
+            None => self.value(key, "null"),
+        }
+    }
+
+    /// The encoded object
+    pub fn finish(mut self) -> String {
+        self.0.push('}');
+        self.0
+    }
+}
+
+/// Array of encoded values
+pub fn array(items: impl IntoIterator<Item = String>) -> String {
+    let items: Vec<String> = items.into_iter().collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Quoted and escaped JSON string
+pub fn string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON number, or `null` if `value` is not finite
+pub fn number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
@@ -0,0 +1,376 @@
+//! `ads1263` command-line tool
+//!
+//! Exercises the High-Precision AD HAT without writing Rust: every
+//! subcommand starts from the board defaults or a configuration file
+//! (`--config`), with channels, data rate, gain, reference voltage, and
+//! pins overridable by flags. Channels can be given by number or by the
+//! names and aliases the configuration file assigns them.
+//!
+//! ```text
+//! ads1263 --channels 0,1,2 --rate 1200 --gain 4 monitor
+//! ads1263 --gain 8 --rate 20 read --channel 3 --samples 10 --average
+//! ads1263 --diff --channels 0,2 stream
+//! ads1263 read --pair ain0:ain3 --samples 10
+//! ads1263 --config /etc/ads1263.toml --channels inlet,level monitor --high level=90
+//! ads1263 --config /etc/ads1263.toml stream --format jsonl --output scan.jsonl
+//! ```
+
+mod adc2;
+mod benchmark;
+mod daemon;
+mod dump_regs;
+mod gpio;
+mod iiod;
+mod json;
+mod monitor;
+mod noise;
+mod read;
+mod record;
+mod replay;
+mod rtd;
+mod scan;
+mod selftest;
+mod stream;
+mod temp;
+
+use ads1263::{
+    Ads1263, Ads1263Config, ChannelMap, CleanupPolicy, DataRate, DigitalFilter, Gain, Hal,
+    InputMode, MuxInput,
+};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Channels scanned if neither the configuration nor `--channels` name any
+const DEFAULT_CHANNELS: [u8; 5] = [0, 1, 2, 3, 4];
+
+/// Command-line tool for the ADS1263 High-Precision AD HAT
+#[derive(Debug, Parser)]
+#[command(name = "ads1263", version, about)]
+struct Cli {
+    #[command(flatten)]
+    device: DeviceArgs,
+
+    /// Print results as JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Device settings shared by all subcommands
+#[derive(Debug, Args)]
+struct DeviceArgs {
+    /// TOML or YAML configuration file to start from
+    #[arg(short, long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Channels to scan by number or configured name, e.g. `0,1,inlet`
+    #[arg(long, global = true, value_delimiter = ',', value_name = "LIST")]
+    channels: Vec<String>,
+
+    /// Scan differential pairs instead of inputs against AINCOM: channel 0
+    /// is AIN0-AIN1, ..., channel 4 is AIN8-AIN9
+    #[arg(long, global = true)]
+    diff: bool,
+
+    /// ADC1 data rate in samples per second, e.g. `400` or `16.6`
+    #[arg(short, long, global = true, value_parser = parse_data_rate, value_name = "SPS")]
+    rate: Option<DataRate>,
+
+    /// ADC1 PGA gain factor (1, 2, 4, ..., 64)
+    #[arg(short, long, global = true, value_parser = parse_gain, value_name = "N")]
+    gain: Option<Gain>,
+
+    /// ADC1 digital filter (sinc1, sinc2, sinc3, sinc4, fir)
+    #[arg(long, global = true, value_parser = parse_filter)]
+    filter: Option<DigitalFilter>,
+
+    /// Reference voltage used to convert codes to volts
+    #[arg(long, global = true, value_name = "VOLTS")]
+    vref: Option<f64>,
+
+    /// RST pin (BCM numbering)
+    #[arg(long, global = true, value_name = "BCM")]
+    rst: Option<u8>,
+
+    /// CS pin (BCM numbering)
+    #[arg(long, global = true, value_name = "BCM")]
+    cs: Option<u8>,
+
+    /// DRDY pin (BCM numbering)
+    #[arg(long, global = true, value_name = "BCM")]
+    drdy: Option<u8>,
+
+    /// START pin (BCM numbering), if wired
+    #[arg(long, global = true, value_name = "BCM")]
+    start: Option<u8>,
+
+    /// SPI clock in Hz
+    #[arg(long, global = true, value_name = "HZ")]
+    spi_clock: Option<u32>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Live dashboard of the channels' values, statistics, and health
+    #[command(alias = "watch")]
+    Monitor(monitor::Args),
+    /// Acquire continuously into a file, stdout, or a socket
+    Stream(stream::Args),
+    /// Capture to a binary file with the full device metadata
+    Record(record::Args),
+    /// Stream a recorded capture to a file, stdout, or a socket
+    Replay(replay::Args),
+    /// Measure sample rate and read latency across SPI clocks and read methods
+    #[command(alias = "rate")]
+    Benchmark(benchmark::Args),
+    /// Print raw codes and volts of one channel
+    Read(read::Args),
+    /// Read every configured channel and internal monitor once
+    Scan,
+    /// Show all ten inputs converted by ADC2
+    Adc2,
+    /// Read an RTD's resistance and temperature
+    Rtd(rtd::Args),
+    /// Read the ADC's internal temperature sensor
+    Temp(temp::Args),
+    /// Acquire into the configuration file's sinks as a systemd service
+    Daemon,
+    /// Serve ADC1 to libiio clients such as `iio_info`
+    Iiod(iiod::Args),
+    /// Print every register with its decoded fields
+    DumpRegs(dump_regs::Args),
+    /// Set, read, and watch the ADC's own GPIO pins
+    Gpio(gpio::Args),
+    /// Check chip, registers, noise, supplies, temperature, and TDACs
+    Selftest(selftest::Args),
+    /// Tabulate noise and ENOB with shorted inputs per gain and data rate
+    Noise(noise::Args),
+}
+
+impl DeviceArgs {
+    /// Configuration file, or defaults, with the flags applied
+    fn config(&self) -> ads1263::Result<Ads1263Config> {
+        let mut config = match &self.config {
+            Some(path) => Ads1263Config::from_file(path)?,
+            None => Ads1263Config::default(),
+        };
+        if self.diff {
+            config.mode = InputMode::Differential;
+        }
+        if !self.channels.is_empty() {
+            config.channels = self
+                .channels
+                .iter()
+                .map(|key| resolve_channel(&config, key))
+                .collect::<ads1263::Result<_>>()?;
+        }
+        if config.channels.is_empty() {
+            config.channels = DEFAULT_CHANNELS.to_vec();
+        }
+        if let Some(rate) = self.rate {
+            config.adc1.data_rate = rate;
+        }
+        if let Some(gain) = self.gain {
+            config.adc1.gain = gain;
+        }
+        if let Some(filter) = self.filter {
+            config.adc1.filter = filter;
+        }
+        if let Some(vref) = self.vref {
+            let mut map = ChannelMap::new(vref);
+            for (channel, info) in config.map.iter() {
+                map.insert(channel, info.clone());
+            }
+            config.map = map;
+        }
+        if let Some(pin) = self.rst {
+            config.pins.rst = pin;
+        }
+        if let Some(pin) = self.cs {
+            config.pins.cs = pin;
+        }
+        if let Some(pin) = self.drdy {
+            config.pins.drdy = pin;
+        }
+        if let Some(pin) = self.start {
+            config.pins.start = Some(pin);
+        }
+        if let Some(hz) = self.spi_clock {
+            config.spi.clock_speed = hz;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    // Initialize logging
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> ads1263::Result<()> {
+    let config = cli.device.config()?;
+
+    // Setup Ctrl+C handler for graceful exit
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+    ctrlc::set_handler(move || {
+        r.store(false, Ordering::SeqCst);
+    })
+    .expect("Error setting Ctrl+C handler");
+
+    match cli.command {
+        Command::Monitor(args) => monitor::run(&config, &args, &running),
+        Command::Stream(args) => stream::run(&config, &args, &running),
+        Command::Record(args) => record::run(&config, &args, &running),
+        Command::Replay(args) => replay::run(&args, &running),
+        Command::Benchmark(args) => benchmark::run(&config, &args, cli.json),
+        Command::Read(args) => read::run(&config, &args, cli.json),
+        Command::Scan => scan::run(&config, cli.json),
+        Command::Adc2 => adc2::run(&config, &running, cli.json),
+        Command::Rtd(args) => rtd::run(&config, &args, &running, cli.json),
+        Command::Temp(args) => temp::run(&config, &args, &running, cli.json),
+        Command::Daemon => daemon::run(config, &cli.device, &running),
+        Command::Iiod(args) => iiod::run(&config, &args, &running),
+        Command::DumpRegs(args) => dump_regs::run(&config, &args, &running, cli.json),
+        Command::Gpio(args) => gpio::run(&config, &args, &running, cli.json),
+        Command::Selftest(args) => selftest::run(&config, &args, cli.json),
+        Command::Noise(args) => noise::run(&config, &args, &running, cli.json),
+    }
+}
+
+/// Device as found, without resetting or configuring it, left running on exit
+///
+/// Releases RST in case a previous run held the chip in reset.
+fn open_raw(config: &Ads1263Config) -> ads1263::Result<Ads1263> {
+    let mut pins = config.pins;
+    pins.cleanup = CleanupPolicy::LeaveRunning;
+    let mut hal = Hal::with_config(pins, config.spi)?;
+    hal.set_rst(true);
+    hal.delay_ms(10);
+    Ok(Ads1263::new(hal))
+}
+
+/// Channel named `key` in the channel map, or numbered `key`
+fn resolve_channel(config: &Ads1263Config, key: &str) -> ads1263::Result<u8> {
+    config.map.lookup(key).ok_or_else(|| {
+        let names: Vec<&str> = config
+            .map
+            .iter()
+            .map(|(_, info)| info.name.as_str())
+            .collect();
+        ads1263::Ads1263Error::InvalidConfig(if names.is_empty() {
+            format!("unknown channel '{}'", key)
+        } else {
+            format!(
+                "unknown channel '{}' (configured: {})",
+                key,
+                names.join(", ")
+            )
+        })
+    })
+}
+
+/// Gain from its factor
+fn parse_gain(value: &str) -> Result<Gain, String> {
+    Ok(match value {
+        "1" => Gain::Gain1,
+        "2" => Gain::Gain2,
+        "4" => Gain::Gain4,
+        "8" => Gain::Gain8,
+        "16" => Gain::Gain16,
+        "32" => Gain::Gain32,
+        "64" => Gain::Gain64,
+        _ => return Err("expected 1, 2, 4, 8, 16, 32, or 64".to_string()),
+    })
+}
+
+/// Data rate from samples per second
+fn parse_data_rate(value: &str) -> Result<DataRate, String> {
+    value
+        .parse()
+        .ok()
+        .and_then(DataRate::from_sps)
+        .ok_or_else(|| {
+            // Truncated, so 16.6 reads as in the datasheet
+            let rates: Vec<String> = DataRate::ALL
+                .iter()
+                .map(|r| ((r.sps() * 10.0).floor() / 10.0).to_string())
+                .collect();
+            format!("expected one of {}", rates.join(", "))
+        })
+}
+
+/// Digital filter from its name
+fn parse_filter(value: &str) -> Result<DigitalFilter, String> {
+    Ok(match value.to_ascii_lowercase().as_str() {
+        "sinc1" => DigitalFilter::Sinc1,
+        "sinc2" => DigitalFilter::Sinc2,
+        "sinc3" => DigitalFilter::Sinc3,
+        "sinc4" => DigitalFilter::Sinc4,
+        "fir" => DigitalFilter::Fir,
+        _ => return Err("expected sinc1, sinc2, sinc3, sinc4, or fir".to_string()),
+    })
+}
+
+/// Multiplexer input from its name, e.g. `ain3` or `aincom`
+fn parse_mux_input(value: &str) -> Result<MuxInput, String> {
+    let value = value.trim().to_ascii_lowercase();
+    Ok(match value.as_str() {
+        "aincom" | "com" => MuxInput::AinCom,
+        "temp" | "temperature" => MuxInput::Temperature,
+        "avdd" => MuxInput::AnalogSupply,
+        "dvdd" => MuxInput::DigitalSupply,
+        "tdac" => MuxInput::TdacTest,
+        "float" => MuxInput::Float,
+        _ => match value.strip_prefix("ain").and_then(|n| n.parse().ok()) {
+            Some(n) if n <= 9 => MuxInput::Ain(n),
+            _ => {
+                return Err(
+                    "expected ain0 to ain9, aincom, temp, avdd, dvdd, tdac, or float".to_string(),
+                );
+            }
+        },
+    })
+}
+
+/// Positive and negative multiplexer inputs, e.g. `ain0:ain3`
+fn parse_mux_pair(value: &str) -> Result<(MuxInput, MuxInput), String> {
+    let (positive, negative) = value
+        .split_once(':')
+        .ok_or("expected POSITIVE:NEGATIVE, e.g. `ain0:ain3`")?;
+    Ok((parse_mux_input(positive)?, parse_mux_input(negative)?))
+}
+
+/// Duration from a number with a `ms`, `s`, `m`, or `h` unit, e.g. `1s`
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let seconds = match unit.trim() {
+        "ms" => 0.001,
+        "s" => 1.0,
+        "m" | "min" => 60.0,
+        "h" => 3600.0,
+        _ => return Err("expected a unit of ms, s, m, or h, e.g. `1s`".to_string()),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .and_then(|n| Duration::try_from_secs_f64(n * seconds).ok())
+        .ok_or_else(|| format!("invalid number '{}'", number))
+}
@@ -0,0 +1,274 @@
+//! `monitor`: live dashboard of the scanned channels
+
+use ads1263::{Ads1263, Ads1263Config, Ads1263Error, Sample, StatsAccumulator};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Time between screen updates
+const REFRESH: Duration = Duration::from_millis(100);
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Values kept per channel for the sparkline and statistics
+    #[arg(long, default_value_t = 200)]
+    history: usize,
+    /// High alarm limit in the channel's unit, e.g. `temp=80` or `3=4.5`
+    #[arg(long, value_parser = parse_limit, value_name = "CHANNEL=VALUE")]
+    high: Vec<(String, f64)>,
+    /// Low alarm limit in the channel's unit, e.g. `temp=5` or `3=0.2`
+    #[arg(long, value_parser = parse_limit, value_name = "CHANNEL=VALUE")]
+    low: Vec<(String, f64)>,
+}
+
+/// Display state of one channel
+struct Channel {
+    channel: u8,
+    name: String,
+    unit: String,
+    high: Option<f64>,
+    low: Option<f64>,
+    history: VecDeque<f64>,
+}
+
+impl Channel {
+    /// Alarm label and color of the latest value
+    fn alarm(&self) -> (&'static str, Color) {
+        match self.history.back() {
+            Some(&v) if self.high.is_some_and(|high| v > high) => ("HIGH", Color::Red),
+            Some(&v) if self.low.is_some_and(|low| v < low) => ("LOW", Color::Red),
+            _ if self.high.is_none() && self.low.is_none() => ("-", Color::DarkGray),
+            _ => ("ok", Color::Green),
+        }
+    }
+}
+
+/// Driver health since the dashboard started
+#[derive(Default)]
+struct Health {
+    scans: u64,
+    crc_errors: u64,
+    errors: u64,
+    last_error: Option<String>,
+    dropped: u64,
+    overruns: u64,
+}
+
+/// Run the dashboard until `q`, Esc, or Ctrl+C
+///
+/// Read errors are counted and shown instead of ending the dashboard.
+pub fn run(config: &Ads1263Config, args: &Args, running: &AtomicBool) -> ads1263::Result<()> {
+    let map = &config.map;
+    for (key, _) in args.high.iter().chain(&args.low) {
+        let channel = crate::resolve_channel(config, key)?;
+        if !config.channels.contains(&channel) {
+            return Err(Ads1263Error::InvalidConfig(format!(
+                "alarm limit for {}, which is not scanned",
+                map.name(channel)
+            )));
+        }
+    }
+    let mut adc = config.open()?;
+    let mut channels: Vec<Channel> = config
+        .channels
+        .iter()
+        .map(|&channel| {
+            let name = map.name(channel).into_owned();
+            let limit = |limits: &[(String, f64)]| {
+                limits
+                    .iter()
+                    .find(|(key, _)| map.lookup(key) == Some(channel))
+                    .map(|&(_, value)| value)
+            };
+            Channel {
+                channel,
+                high: limit(&args.high),
+                low: limit(&args.low),
+                name,
+                unit: map.unit(channel).to_string(),
+                history: VecDeque::with_capacity(args.history),
+            }
+        })
+        .collect();
+
+    let mut terminal = ratatui::init();
+    let result = dashboard(
+        &mut terminal,
+        &mut adc,
+        config,
+        &mut channels,
+        args.history,
+        running,
+    );
+    ratatui::restore();
+    result
+}
+
+/// Scan, record, and redraw every [`REFRESH`] until a quit key or Ctrl+C
+fn dashboard(
+    terminal: &mut DefaultTerminal,
+    adc: &mut Ads1263,
+    config: &Ads1263Config,
+    channels: &mut [Channel],
+    history: usize,
+    running: &AtomicBool,
+) -> ads1263::Result<()> {
+    let map = &config.map;
+    let started = Instant::now();
+    let mut health = Health::default();
+    let mut samples: Vec<Sample> = Vec::with_capacity(channels.len());
+    let mut drawn = Instant::now() - REFRESH;
+
+    while running.load(Ordering::SeqCst) {
+        match adc.get_samples_into(&config.channels, &mut samples) {
+            Ok(()) => {
+                health.scans += 1;
+                for (channel, sample) in channels.iter_mut().zip(&samples) {
+                    if !sample.crc_ok {
+                        health.crc_errors += 1;
+                    }
+                    if channel.history.len() == history.max(1) {
+                        channel.history.pop_front();
+                    }
+                    channel.history.push_back(map.convert_sample(sample));
+                }
+            }
+            Err(e) => {
+                health.errors += 1;
+                health.last_error = Some(e.to_string());
+                std::thread::sleep(REFRESH);
+            }
+        }
+
+        if drawn.elapsed() >= REFRESH {
+            let overrun = adc.overrun_stats();
+            health.dropped = overrun.dropped;
+            health.overruns = overrun.overruns;
+            let uptime = started.elapsed();
+            terminal.draw(|frame| render(frame, config, channels, &health, uptime))?;
+            drawn = Instant::now();
+            if quit_requested()? {
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// true after `q`, Esc, or Ctrl+C; raw mode delivers Ctrl+C as a key
+fn quit_requested() -> ads1263::Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            let ctrl_c =
+                key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+            if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn render(
+    frame: &mut Frame,
+    config: &Ads1263Config,
+    channels: &[Channel],
+    health: &Health,
+    uptime: Duration,
+) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(4),
+    ])
+    .areas(frame.area());
+
+    let adc1 = config.adc1;
+    frame.render_widget(
+        Line::from(format!(
+            " ads1263 monitor  {} SPS  gain {}  {:?}  vref {} V  up {}s   q to quit",
+            adc1.data_rate.sps(),
+            1u32 << (adc1.gain as u32),
+            adc1.filter,
+            config.map.reference(),
+            uptime.as_secs()
+        ))
+        .bold(),
+        header,
+    );
+
+    let block = Block::bordered().title(" Channels ");
+    let inner = block.inner(body);
+    frame.render_widget(block, body);
+    let rows = Layout::vertical(channels.iter().map(|_| Constraint::Length(1))).split(inner);
+    for (channel, &row) in channels.iter().zip(rows.iter()) {
+        let [text, spark] =
+            Layout::horizontal([Constraint::Length(82), Constraint::Min(0)]).areas(row);
+
+        let mut stats = StatsAccumulator::new();
+        channel.history.iter().for_each(|&v| stats.push(v));
+        let stats = stats.stats();
+        let (alarm, color) = channel.alarm();
+        let line = match channel.history.back() {
+            Some(value) => Line::from(vec![
+                Span::raw(format!("{:>2} {:<10}", channel.channel, channel.name)),
+                Span::styled(format!("{:>14.6}", value), Style::new().bold()),
+                Span::raw(format!(
+                    " {:<4} min {:>11.6} max {:>11.6} σ {:>9.6} ",
+                    channel.unit, stats.min, stats.max, stats.std_dev
+                )),
+                Span::styled(format!("{:<4}", alarm), Style::new().fg(color)),
+            ]),
+            None => Line::from(format!(
+                "{:>2} {:<10} {:>13}",
+                channel.channel, channel.name, "-"
+            )),
+        };
+        frame.render_widget(line, text);
+
+        // Scaled between the window's extremes, the only range that fits one row
+        let range = (stats.max - stats.min).max(f64::EPSILON);
+        let data: Vec<u64> = channel
+            .history
+            .iter()
+            .rev()
+            .take(usize::from(spark.width))
+            .rev()
+            .map(|&v| ((v - stats.min) / range * 100.0) as u64)
+            .collect();
+        frame.render_widget(Sparkline::default().data(&data).max(100).cyan(), spark);
+    }
+
+    let rate = health.scans as f64 / uptime.as_secs_f64().max(1e-3);
+    let mut lines = vec![Line::from(format!(
+        " scans {}  ({:.1}/s)   crc errors {}   read errors {}   dropped {}   overruns {}",
+        health.scans, rate, health.crc_errors, health.errors, health.dropped, health.overruns
+    ))];
+    if let Some(error) = &health.last_error {
+        lines.push(Line::from(format!(" last error: {}", error)).red());
+    }
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Health ")),
+        footer,
+    );
+}
+
+/// `CHANNEL=VALUE` with a channel name or number
+fn parse_limit(value: &str) -> Result<(String, f64), String> {
+    let (channel, limit) = value
+        .split_once('=')
+        .ok_or("expected CHANNEL=VALUE, e.g. `temp=80`")?;
+    let limit = limit
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid limit '{}'", limit))?;
+    Ok((channel.trim().to_string(), limit))
+}
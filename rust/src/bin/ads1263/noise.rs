@@ -0,0 +1,170 @@
+//! `noise`: input-referred noise and ENOB with shorted inputs
+
+use crate::json::{self, Object};
+use ads1263::{Adc1Config, Ads1263, Ads1263Config, DataRate, Gain, MuxInput, StatsAccumulator};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Conversions per gain and data rate
+    #[arg(short = 'n', long, default_value_t = 256)]
+    samples: usize,
+    /// PGA gains to measure, e.g. `1,8,32`
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = crate::parse_gain,
+        default_value = "1,2,4,8,16,32,64",
+        value_name = "LIST"
+    )]
+    gains: Vec<Gain>,
+    /// Data rates to measure, e.g. `20,400`; the configured rate if omitted
+    #[arg(long, value_delimiter = ',', value_parser = crate::parse_data_rate, value_name = "LIST")]
+    rates: Vec<DataRate>,
+}
+
+/// Title, value, and decimals of each printed table
+type Figure = (&'static str, fn(&Cell) -> f64, usize);
+
+const FIGURES: [Figure; 4] = [
+    ("Noise in µV rms", |cell| cell.rms, 3),
+    ("Noise in µV p-p", |cell| cell.peak_to_peak, 3),
+    ("ENOB", Cell::enob, 1),
+    ("Noise-free bits", Cell::noise_free_bits, 1),
+];
+
+/// Noise of one gain and data rate, in µV at the input
+struct Cell {
+    rms: f64,
+    peak_to_peak: f64,
+    /// Full-scale range 2·VREF/gain in µV
+    full_scale: f64,
+}
+
+impl Cell {
+    /// Effective number of bits, from the rms noise
+    fn enob(&self) -> f64 {
+        (self.full_scale / self.rms).log2()
+    }
+
+    /// Noise-free bits, from the peak-to-peak noise
+    fn noise_free_bits(&self) -> f64 {
+        (self.full_scale / self.peak_to_peak).log2()
+    }
+}
+
+/// Measure every gain and data rate, then print one table per figure
+///
+/// Both inputs are routed to AINCOM, so the result is the ADC's own noise
+/// with the configured filter and reference. Ctrl+C stops early and
+/// prints what was measured.
+///
+/// With `--json`, prints `{"filter", "reference", "samples", "results":
+/// [{"data_rate", "gain", "rms_uv", "peak_to_peak_uv", "enob",
+/// "noise_free_bits"}]}` instead of the tables.
+pub fn run(
+    config: &Ads1263Config,
+    args: &Args,
+    running: &AtomicBool,
+    json: bool,
+) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+    let reference = config.map.reference();
+    let rates = if args.rates.is_empty() {
+        vec![config.adc1.data_rate]
+    } else {
+        args.rates.clone()
+    };
+
+    let mut table = Vec::with_capacity(rates.len());
+    'measure: for &data_rate in &rates {
+        let mut row = Vec::with_capacity(args.gains.len());
+        for &gain in &args.gains {
+            if !running.load(Ordering::SeqCst) {
+                table.push(row);
+                break 'measure;
+            }
+            eprintln!("gain {:>2} at {} SPS...", factor(gain), sps(data_rate));
+            adc.configure_adc1(Adc1Config {
+                gain,
+                data_rate,
+                ..config.adc1
+            })?;
+            row.push(measure(&mut adc, args.samples, gain, reference)?);
+        }
+        table.push(row);
+    }
+    adc.configure_adc1(config.adc1)?;
+
+    if json {
+        let results = rates.iter().zip(&table).flat_map(|(&data_rate, row)| {
+            args.gains.iter().zip(row).map(move |(&gain, cell)| {
+                Object::new()
+                    .num("data_rate", sps(data_rate))
+                    .int("gain", factor(gain))
+                    .num("rms_uv", cell.rms)
+                    .num("peak_to_peak_uv", cell.peak_to_peak)
+                    .num("enob", cell.enob())
+                    .num("noise_free_bits", cell.noise_free_bits())
+                    .finish()
+            })
+        });
+        let document = Object::new()
+            .str(
+                "filter",
+                &format!("{:?}", config.adc1.filter).to_lowercase(),
+            )
+            .num("reference", reference)
+            .int("samples", args.samples)
+            .value("results", &json::array(results));
+        println!("{}", document.finish());
+        return Ok(());
+    }
+
+    println!(
+        "ADC1, {:?} filter, {} V reference, {} conversions per cell, inputs shorted to AINCOM",
+        config.adc1.filter, reference, args.samples
+    );
+    for (title, value, precision) in FIGURES {
+        println!();
+        println!("{}", title);
+        print!("{:>9}", "SPS");
+        for &gain in &args.gains {
+            print!(" {:>9}", format!("G={}", factor(gain)));
+        }
+        println!();
+        for (data_rate, row) in rates.iter().zip(&table) {
+            print!("{:>9}", sps(*data_rate));
+            for cell in row {
+                print!(" {:>9.*}", precision, value(cell));
+            }
+            println!();
+        }
+    }
+    Ok(())
+}
+
+/// Capture shorted-input conversions at the current configuration
+fn measure(adc: &mut Ads1263, samples: usize, gain: Gain, reference: f64) -> ads1263::Result<Cell> {
+    let gain = f64::from(factor(gain));
+    let mut stats = StatsAccumulator::new();
+    for raw in adc.capture_inputs(MuxInput::AinCom, MuxInput::AinCom, samples.max(2))? {
+        stats.push(Ads1263::raw_to_voltage_adc1(raw as u32, reference) / gain * 1e6);
+    }
+    let stats = stats.stats();
+    Ok(Cell {
+        rms: stats.std_dev,
+        peak_to_peak: stats.peak_to_peak(),
+        full_scale: 2.0 * reference / gain * 1e6,
+    })
+}
+
+/// PGA gain factor
+fn factor(gain: Gain) -> u32 {
+    1 << gain as u32
+}
+
+/// Data rate truncated to one decimal, so 16.6 reads as in the datasheet
+fn sps(data_rate: DataRate) -> f64 {
+    (data_rate.sps() * 10.0).floor() / 10.0
+}
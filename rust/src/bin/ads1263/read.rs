@@ -0,0 +1,108 @@
+//! `read`: one-shot measurement of a channel
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263, Ads1263Config, Ads1263Error, InputMode, MuxInput};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Channel number or name to read; defaults to the first configured channel
+    #[arg(long)]
+    channel: Option<String>,
+    /// Read any two multiplexer inputs instead of a channel, e.g. `ain0:ain3`
+    #[arg(long, value_parser = crate::parse_mux_pair, value_name = "AINP:AINN", conflicts_with_all = ["channel", "vs"])]
+    pair: Option<(MuxInput, MuxInput)>,
+    /// Negative input of a single-ended channel instead of AINCOM, e.g. `ain9`
+    #[arg(long, value_parser = crate::parse_mux_input, value_name = "INPUT")]
+    vs: Option<MuxInput>,
+    /// Consecutive conversions to take
+    #[arg(short = 'n', long, default_value_t = 1)]
+    samples: usize,
+    /// Print only the mean of the conversions
+    #[arg(long)]
+    average: bool,
+}
+
+/// Print `raw volts` per conversion, or their mean with `--average`
+///
+/// Volts are at the input, i.e. divided by the PGA gain. With `--json`,
+/// prints `{"channel", "name", "samples": [{"raw", "volts"}]}`, or
+/// `{"channel", "name", "count", "raw", "volts"}` with `--average`;
+/// `--pair` and `--vs` reads have `"positive"` and `"negative"` inputs
+/// instead of a channel.
+pub fn run(config: &Ads1263Config, args: &Args, json: bool) -> ads1263::Result<()> {
+    let channel = match &args.channel {
+        Some(key) => crate::resolve_channel(config, key)?,
+        None => config.channels[0],
+    };
+    let inputs = match (args.pair, args.vs) {
+        (Some(pair), _) => Some(pair),
+        (None, Some(negative)) => {
+            if config.mode == InputMode::Differential {
+                return Err(Ads1263Error::InvalidConfig(
+                    "--vs applies to single-ended channels, drop --diff".to_string(),
+                ));
+            }
+            Some((MuxInput::Ain(channel), negative))
+        }
+        (None, None) => None,
+    };
+    let mut adc = config.open()?;
+
+    let n = args.samples.max(1);
+    let codes = match inputs {
+        Some((positive, negative)) => adc.capture_inputs(positive, negative, n)?,
+        None => adc.capture(channel, n)?.values().collect(),
+    };
+    let gain = f64::from(1u32 << (config.adc1.gain as u32));
+    let volts = |raw: i32| Ads1263::raw_to_voltage_adc1(raw as u32, config.map.reference()) / gain;
+    let describe = |object: Object| match inputs {
+        Some((positive, negative)) => object
+            .str("positive", &input_name(positive))
+            .str("negative", &input_name(negative)),
+        None => object
+            .int("channel", channel)
+            .str("name", &config.map.name(channel)),
+    };
+
+    if args.average {
+        let n = codes.len() as f64;
+        let raw = codes.iter().copied().map(f64::from).sum::<f64>() / n;
+        let volts = codes.iter().copied().map(volts).sum::<f64>() / n;
+        if json {
+            let document = describe(Object::new())
+                .int("count", codes.len())
+                .num("raw", raw)
+                .num("volts", volts);
+            println!("{}", document.finish());
+        } else {
+            println!("{:.1} {:.9}", raw, volts);
+        }
+    } else if json {
+        let samples = codes.iter().map(|&raw| {
+            Object::new()
+                .int("raw", raw)
+                .num("volts", volts(raw))
+                .finish()
+        });
+        let document = describe(Object::new()).value("samples", &json::array(samples));
+        println!("{}", document.finish());
+    } else {
+        for &raw in &codes {
+            println!("{} {:.9}", raw, volts(raw));
+        }
+    }
+    Ok(())
+}
+
+/// Name of a multiplexer input as accepted by `--pair` and `--vs`
+fn input_name(input: MuxInput) -> String {
+    match input {
+        MuxInput::Ain(n) => format!("ain{}", n),
+        MuxInput::AinCom => "aincom".to_string(),
+        MuxInput::Temperature => "temp".to_string(),
+        MuxInput::AnalogSupply => "avdd".to_string(),
+        MuxInput::DigitalSupply => "dvdd".to_string(),
+        MuxInput::TdacTest => "tdac".to_string(),
+        MuxInput::Float => "float".to_string(),
+    }
+}
@@ -0,0 +1,106 @@
+//! `record`: capture to a binary file for later `replay` or analysis
+
+use ads1263::{
+    AcquisitionConfig, Ads1263Config, Ads1263Error, CaptureHeader, CaptureWriter, Sample, Sink,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often the main thread checks for Ctrl+C and the duration
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Capture file to create
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+    /// Stop after this many scans
+    #[arg(long)]
+    scans: Option<u64>,
+    /// Stop after this long, e.g. `10s` or `1h`
+    #[arg(long, value_parser = crate::parse_duration)]
+    duration: Option<Duration>,
+    /// Compress every frame with zstd
+    #[cfg(feature = "zstd")]
+    #[arg(long)]
+    zstd: bool,
+}
+
+/// Sink counting the samples it passes on
+struct Counting<S> {
+    sink: S,
+    samples: Arc<AtomicU64>,
+}
+
+impl<S: Sink> Sink for Counting<S> {
+    fn write(&mut self, samples: &[Sample]) -> ads1263::Result<()> {
+        self.sink.write(samples)?;
+        self.samples
+            .fetch_add(samples.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> ads1263::Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// Record until Ctrl+C, `--scans`, `--duration`, or an error
+///
+/// The header stores the chip ID, driver version, input mode, ADC1
+/// configuration, channel map, and wall-clock start, so the file can be
+/// replayed and converted without the configuration it was recorded with.
+pub fn run(config: &Ads1263Config, args: &Args, running: &AtomicBool) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+    let header = CaptureHeader::from_device(&mut adc, config.map.clone())?;
+    let writer: Box<dyn Sink> = {
+        #[cfg(feature = "zstd")]
+        if args.zstd {
+            Box::new(CaptureWriter::create_zstd(&args.path, &header)?)
+        } else {
+            Box::new(CaptureWriter::create(&args.path, &header)?)
+        }
+        #[cfg(not(feature = "zstd"))]
+        Box::new(CaptureWriter::create(&args.path, &header)?)
+    };
+    let samples = Arc::new(AtomicU64::new(0));
+    let sink = Counting {
+        sink: writer,
+        samples: samples.clone(),
+    };
+    let acquisition = AcquisitionConfig {
+        max_scans: args.scans,
+        ..config.acquisition.clone()
+    };
+    let acq = adc.start_acquisition_sink(&config.channels, acquisition, sink)?;
+
+    let started = Instant::now();
+    while running.load(Ordering::SeqCst)
+        && args
+            .duration
+            .is_none_or(|duration| started.elapsed() < duration)
+    {
+        match acq.recv_timeout(POLL) {
+            Err(Ads1263Error::Timeout) => {}
+            // The sampler finished its scans
+            Err(Ads1263Error::WorkerTerminated) => break,
+            Err(e) => return Err(e),
+            Ok(_) => {}
+        }
+    }
+    let dropped = acq.dropped();
+    // Joining drops the writer, which writes the last frame
+    acq.join()?;
+
+    eprintln!(
+        "{} samples of {} channels in {:.1} s to {} ({} dropped)",
+        samples.load(Ordering::Relaxed),
+        config.channels.len(),
+        started.elapsed().as_secs_f64(),
+        args.path.display(),
+        dropped
+    );
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! `replay`: stream a recorded capture through an output
+
+use crate::stream::Output;
+use ads1263::ReplaySource;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Capture file written by `record` or `stream -f binary`
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+    #[command(flatten)]
+    output: Output,
+    /// Pace samples at the recorded rate instead of as fast as possible
+    #[arg(long)]
+    realtime: bool,
+    /// Pace samples at this multiple of the recorded rate, e.g. `10`
+    #[arg(long, conflicts_with = "realtime")]
+    speed: Option<f64>,
+}
+
+/// Write every recorded sample to the output until the end or Ctrl+C
+///
+/// Values are converted with the channel map stored in the capture, not
+/// the one configured, and a binary output keeps the recorded header.
+pub fn run(args: &Args, running: &AtomicBool) -> ads1263::Result<()> {
+    let mut replay = ReplaySource::open(&args.path)?;
+    if args.realtime {
+        replay = replay.realtime();
+    }
+    if let Some(speed) = args.speed {
+        replay = replay.speed(speed);
+    }
+    let mut sink = args.output.sink(replay.header())?;
+
+    for sample in replay {
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        sink.write(&[sample?])?;
+    }
+    sink.flush()
+}
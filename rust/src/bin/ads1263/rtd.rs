@@ -0,0 +1,137 @@
+//! `rtd`: resistance and temperature of an RTD
+
+use crate::json::Object;
+use ads1263::{Ads1263Config, DataRate, Delay, IdacCurrent, RtdAlpha, RtdElement, RtdWiring};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// RTD element
+    #[arg(long = "type", value_enum, default_value_t = RtdType::Pt100)]
+    rtd_type: RtdType,
+    /// Temperature coefficient: 385, 3911, or 3916 for α = 0.00385/K,
+    /// 0.003911/K, or 0.003916/K
+    #[arg(long, default_value = "385", value_parser = parse_alpha, value_name = "385|3911|3916")]
+    alpha: RtdAlpha,
+    /// Number of leads between the RTD and the HAT
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u8).range(2..=4))]
+    wires: u8,
+    /// Reference resistor in ohms
+    #[arg(long, default_value_t = 2000.0, value_name = "OHMS")]
+    rref: f64,
+    /// Excitation current, e.g. `250u` or `1m`
+    #[arg(long, default_value = "250u", value_parser = parse_idac, value_name = "AMPS")]
+    idac: IdacCurrent,
+    /// Keep reading at this interval, e.g. `1s`, until Ctrl+C
+    #[arg(long, value_parser = crate::parse_duration, value_name = "INTERVAL")]
+    continuous: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum RtdType {
+    Pt100,
+    Pt500,
+    Pt1000,
+}
+
+impl RtdType {
+    /// Resistance at 0 °C in ohms
+    fn r0(self) -> f64 {
+        match self {
+            RtdType::Pt100 => 100.0,
+            RtdType::Pt500 => 500.0,
+            RtdType::Pt1000 => 1000.0,
+        }
+    }
+}
+
+/// Print resistance and temperature once, or every `--continuous` interval
+///
+/// Conversions run at 20 SPS with an 8.8 ms delay for line-frequency
+/// rejection; `--gain` applies and is divided out of the resistance, so
+/// it only changes the input range. 3-wire readings average two conversions
+/// with the IDACs swapped to cancel the lead resistance. With `--json`,
+/// each reading is a
+/// `{"resistance", "temperature"}` line in ohms and °C.
+pub fn run(
+    config: &Ads1263Config,
+    args: &Args,
+    running: &AtomicBool,
+    json: bool,
+) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+    let element = RtdElement::new(args.rtd_type.r0(), args.alpha);
+    let wiring = match args.wires {
+        2 => RtdWiring::TwoWire,
+        3 => RtdWiring::ThreeWire,
+        _ => RtdWiring::FourWire,
+    };
+
+    loop {
+        let started = Instant::now();
+        let resistance = adc.read_rtd_resistance(
+            wiring,
+            args.idac,
+            args.rref,
+            Delay::Delay8_8ms,
+            config.adc1.gain,
+            DataRate::Sps20,
+        )?;
+        let temperature = element.celsius(resistance);
+        if json {
+            let document = Object::new()
+                .num("resistance", resistance)
+                .num("temperature", temperature);
+            println!("{}", document.finish());
+        } else {
+            println!("{:.3} Ω {:.2} °C", resistance, temperature);
+        }
+
+        let Some(interval) = args.continuous else {
+            return Ok(());
+        };
+        while running.load(Ordering::SeqCst) && started.elapsed() < interval {
+            std::thread::sleep(Duration::from_millis(10).min(interval));
+        }
+        if !running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+    }
+}
+
+/// Temperature coefficient from the digits of α, e.g. 385 for 0.00385/K
+fn parse_alpha(value: &str) -> Result<RtdAlpha, String> {
+    match value.trim() {
+        "385" => Ok(RtdAlpha::Alpha385),
+        "3911" => Ok(RtdAlpha::Alpha3911),
+        "3916" => Ok(RtdAlpha::Alpha3916),
+        _ => Err("expected one of 385, 3911, 3916".to_string()),
+    }
+}
+
+/// Excitation current from amps with a `u` (µ) or `m` suffix
+fn parse_idac(value: &str) -> Result<IdacCurrent, String> {
+    let text = value.trim().trim_end_matches(['A', 'a']);
+    let (number, scale) = if let Some(number) = text.strip_suffix(['u', 'µ']) {
+        (number, 1.0)
+    } else if let Some(number) = text.strip_suffix('m') {
+        (number, 1000.0)
+    } else {
+        (text, 1_000_000.0)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|amps| (amps * scale).round())
+        .filter(|ua| *ua >= 0.0 && *ua <= f64::from(u32::MAX))
+        .and_then(|ua| IdacCurrent::from_microamps(ua as u32))
+        .ok_or_else(|| {
+            let currents: Vec<String> = IdacCurrent::ALL[1..]
+                .iter()
+                .map(|current| format!("{}u", current.microamps()))
+                .collect();
+            format!("expected one of {}", currents.join(", "))
+        })
+}
@@ -0,0 +1,92 @@
+//! `scan`: every configured channel and internal monitor, once
+
+use crate::json::{self, Object};
+use ads1263::{Ads1263Config, Monitor};
+
+/// One converted input
+struct Row {
+    name: String,
+    /// Channel number, `None` for a monitor
+    channel: Option<u8>,
+    /// ADC1 code, `None` for a monitor
+    raw: Option<i32>,
+    value: f64,
+    unit: String,
+}
+
+/// Read the channels in the channel map's units, then the monitors
+///
+/// With `--json`, prints `{"channels": [...], "monitors": [...]}`.
+pub fn run(config: &Ads1263Config, json: bool) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+    let mut rows = Vec::with_capacity(config.channels.len() + Monitor::ALL.len());
+    for &channel in &config.channels {
+        let sample = adc.get_sample(channel)?;
+        rows.push(Row {
+            name: config.map.name(channel).into_owned(),
+            channel: Some(channel),
+            raw: Some(sample.raw),
+            value: config.map.convert_sample(&sample),
+            unit: config.map.unit(channel).to_string(),
+        });
+    }
+    for monitor in Monitor::ALL {
+        rows.push(Row {
+            name: monitor_name(monitor).to_string(),
+            channel: None,
+            raw: None,
+            value: adc.read_monitor(monitor)?,
+            unit: monitor.unit().to_string(),
+        });
+    }
+
+    if json {
+        println!("{}", document(&rows));
+    } else {
+        let width = rows.iter().map(|row| row.name.len()).max().unwrap_or(0);
+        println!(
+            "{:<width$} {:>4} {:>11} {:>14} UNIT",
+            "NAME", "CH", "RAW", "VALUE"
+        );
+        for row in &rows {
+            let dash = || "-".to_string();
+            println!(
+                "{:<width$} {:>4} {:>11} {:>14.6} {}",
+                row.name,
+                row.channel.map_or_else(dash, |channel| channel.to_string()),
+                row.raw.map_or_else(dash, |raw| raw.to_string()),
+                row.value,
+                row.unit
+            );
+        }
+    }
+    Ok(())
+}
+
+fn monitor_name(monitor: Monitor) -> &'static str {
+    match monitor {
+        Monitor::Temperature => "temperature",
+        Monitor::AnalogSupply => "avdd",
+        Monitor::DigitalSupply => "dvdd",
+    }
+}
+
+/// `{"channels": [...], "monitors": [...]}`
+fn document(rows: &[Row]) -> String {
+    let encode = |row: &&Row| {
+        let mut object = Object::new().str("name", &row.name);
+        if let (Some(channel), Some(raw)) = (row.channel, row.raw) {
+            object = object.int("channel", channel).int("raw", raw);
+        }
+        object
+            .num("value", row.value)
+            .str("unit", &row.unit)
+            .finish()
+    };
+    let (channels, monitors): (Vec<&Row>, Vec<&Row>) =
+        rows.iter().partition(|row| row.channel.is_some());
+    Object::new()
+        .value("channels", &json::array(channels.iter().map(encode)))
+        .value("monitors", &json::array(monitors.iter().map(encode)))
+        .finish()
+}
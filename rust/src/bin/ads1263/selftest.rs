@@ -0,0 +1,205 @@
+//! `selftest`: pass/fail checks after assembling a board
+
+use crate::json::{self, Object};
+use ads1263::{
+    Adc1Calibration, Adc1Config, Ads1263, Ads1263Config, DacVoltage, Hal, Monitor, MuxInput,
+    StatsAccumulator,
+};
+
+/// Allowed supply deviation from nominal
+const SUPPLY_TOLERANCE: f64 = 0.05;
+/// Nominal AVDD - AVSS of the HAT
+const AVDD: f64 = 5.0;
+/// Nominal DVDD of the HAT, from the Pi's 3.3 V rail
+const DVDD: f64 = 3.3;
+/// Plausible die temperature range in °C
+const TEMPERATURE_RANGE: (f64, f64) = (-20.0, 85.0);
+/// Largest acceptable noise with shorted inputs, in µV rms
+const NOISE_LIMIT_UV: f64 = 20.0;
+/// TDAC loopback: AIN6 at 90 % and AIN7 at 10 % of the supply
+const TDAC_RATIO: f64 = 0.8;
+/// Allowed relative deviation of the loopback voltage
+const TDAC_TOLERANCE: f64 = 0.03;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Conversions taken for the noise measurement
+    #[arg(long, default_value_t = 64)]
+    noise_samples: usize,
+}
+
+/// Outcome of one check
+struct Check {
+    name: &'static str,
+    passed: bool,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, passed: bool, detail: String) -> Self {
+        Self {
+            name,
+            passed,
+            detail,
+        }
+    }
+}
+
+/// Run every check, print the report, and exit with status 1 on a failure
+///
+/// The TDAC loopback drives AIN6 and AIN7: disconnect anything wired to
+/// them first. With `--json`, prints `{"checks": [{"name", "passed",
+/// "detail"}], "passed", "failed"}`.
+pub fn run(config: &Ads1263Config, args: &Args, json: bool) -> ads1263::Result<()> {
+    let mut hal = Hal::with_config(config.pins, config.spi)?;
+    hal.set_wait_strategy(config.wait)?;
+    let mut adc = Ads1263::new(hal);
+
+    let mut checks = vec![check("chip id", chip_id(&mut adc))];
+    // Later checks need a working chip; a reset brings it to a known state
+    if checks[0].passed {
+        adc.init_adc1_with(Adc1Config::default())?;
+        checks.push(check("register readback", readback(&mut adc)));
+        checks.push(check(
+            "noise",
+            noise(&mut adc, args.noise_samples, config.map.reference()),
+        ));
+        checks.push(check(
+            "analog supply",
+            supply(&mut adc, Monitor::AnalogSupply, AVDD),
+        ));
+        checks.push(check(
+            "digital supply",
+            supply(&mut adc, Monitor::DigitalSupply, DVDD),
+        ));
+        checks.push(check("temperature", temperature(&mut adc)));
+        checks.push(check(
+            "tdac loopback",
+            tdac_loopback(&mut adc, config.map.reference()),
+        ));
+    }
+
+    let failed = checks.iter().filter(|check| !check.passed).count();
+    if json {
+        let report = checks.iter().map(|check| {
+            Object::new()
+                .str("name", check.name)
+                .bool("passed", check.passed)
+                .str("detail", &check.detail)
+                .finish()
+        });
+        let document = Object::new()
+            .value("checks", &json::array(report))
+            .int("passed", checks.len() - failed)
+            .int("failed", failed);
+        println!("{}", document.finish());
+    } else {
+        for check in &checks {
+            let verdict = if check.passed { "PASS" } else { "FAIL" };
+            println!("{:<18} {}  {}", check.name, verdict, check.detail);
+        }
+        println!("{} passed, {} failed", checks.len() - failed, failed);
+    }
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Errors of a check's communication fail the check rather than the run
+fn check(name: &'static str, result: ads1263::Result<(bool, String)>) -> Check {
+    match result {
+        Ok((passed, detail)) => Check::new(name, passed, detail),
+        Err(e) => Check::new(name, false, e.to_string()),
+    }
+}
+
+fn chip_id(adc: &mut Ads1263) -> ads1263::Result<(bool, String)> {
+    let id = adc.read_chip_id()?;
+    Ok((id == 1, format!("ID {} (expected 1)", id)))
+}
+
+/// Write patterns to the calibration registers and read them back
+fn readback(adc: &mut Ads1263) -> ads1263::Result<(bool, String)> {
+    let patterns = [0x55u8, 0xAA, 0x00, 0xFF];
+    let mut mismatches = Vec::new();
+    for pattern in patterns {
+        let bytes = u32::from_le_bytes([pattern, pattern, pattern, 0]);
+        let written = Adc1Calibration {
+            // Sign-extend the 24-bit offset
+            offset: ((bytes << 8) as i32) >> 8,
+            full_scale: bytes,
+        };
+        adc.set_adc1_calibration(written)?;
+        if adc.adc1_calibration()? != written {
+            mismatches.push(format!("0x{:02X}", pattern));
+        }
+    }
+    adc.set_adc1_calibration(Adc1Calibration::default())?;
+
+    if mismatches.is_empty() {
+        Ok((true, format!("{} patterns", patterns.len())))
+    } else {
+        Ok((false, format!("mismatch for {}", mismatches.join(", "))))
+    }
+}
+
+/// Standard deviation with both inputs on AINCOM
+fn noise(adc: &mut Ads1263, samples: usize, reference: f64) -> ads1263::Result<(bool, String)> {
+    let mut stats = StatsAccumulator::new();
+    for _ in 0..samples.max(2) {
+        let raw = adc.read_inputs(MuxInput::AinCom, MuxInput::AinCom)?;
+        stats.push(Ads1263::raw_to_voltage_adc1(raw as u32, reference) * 1e6);
+    }
+    let stats = stats.stats();
+    Ok((
+        stats.std_dev < NOISE_LIMIT_UV,
+        format!(
+            "{:.2} µV rms, {:.2} µV p-p ({} conversions, limit {} µV)",
+            stats.std_dev,
+            stats.peak_to_peak(),
+            stats.count,
+            NOISE_LIMIT_UV
+        ),
+    ))
+}
+
+fn supply(adc: &mut Ads1263, monitor: Monitor, nominal: f64) -> ads1263::Result<(bool, String)> {
+    let volts = adc.read_monitor(monitor)?;
+    Ok((
+        (volts - nominal).abs() <= nominal * SUPPLY_TOLERANCE,
+        format!(
+            "{:.3} V (expected {:.2} V ±{}%)",
+            volts,
+            nominal,
+            SUPPLY_TOLERANCE * 100.0
+        ),
+    ))
+}
+
+fn temperature(adc: &mut Ads1263) -> ads1263::Result<(bool, String)> {
+    let celsius = adc.read_monitor(Monitor::Temperature)?;
+    let (low, high) = TEMPERATURE_RANGE;
+    Ok((
+        (low..=high).contains(&celsius),
+        format!("{:.1} °C (expected {} to {} °C)", celsius, low, high),
+    ))
+}
+
+/// Drive AIN6 and AIN7 from the TDACs and measure the difference
+fn tdac_loopback(adc: &mut Ads1263, reference: f64) -> ads1263::Result<(bool, String)> {
+    adc.set_dac(DacVoltage::Volt4_5, true, true)?;
+    adc.set_dac(DacVoltage::Volt0_5, false, true)?;
+    adc.hal().delay_ms(10);
+    let result = adc.read_inputs(MuxInput::Ain(6), MuxInput::Ain(7));
+    adc.set_dac(DacVoltage::Volt4_5, true, false)?;
+    adc.set_dac(DacVoltage::Volt0_5, false, false)?;
+
+    // Ratiometric against AVDD, so the supply's tolerance cancels
+    let volts = Ads1263::raw_to_voltage_adc1(result? as u32, reference);
+    let expected = TDAC_RATIO * reference;
+    Ok((
+        (volts - expected).abs() <= expected * TDAC_TOLERANCE,
+        format!("{:.3} V (expected {:.3} V)", volts, expected),
+    ))
+}
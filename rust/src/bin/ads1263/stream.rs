@@ -0,0 +1,295 @@
+//! `stream`: continuous acquisition into a file, stdout, or a socket
+
+use ads1263::{
+    AcquisitionConfig, Ads1263Config, Ads1263Error, CaptureHeader, CaptureWriter, CsvSink, Edge,
+    JsonLinesSink, Sample, Sink, Trigger, TriggerCondition, TriggerConfig, UdpSink, UnixSocketSink,
+};
+use clap::ValueEnum;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often the main thread checks for Ctrl+C
+const POLL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(flatten)]
+    output: Output,
+    /// Stop after this many scans
+    #[arg(long, conflicts_with = "trigger")]
+    scans: Option<u64>,
+    /// Capture events into numbered files instead of streaming, e.g.
+    /// `'ch0 > 2.5V'`, `'inlet < 100mV'`, `'ch1 in 1V..2V'`, or `'gpio17 rising'`
+    #[arg(long, value_name = "CONDITION")]
+    trigger: Option<String>,
+    /// Scans kept from before each trigger
+    #[arg(long, default_value_t = 0, requires = "trigger", value_name = "SCANS")]
+    pre: usize,
+    /// Scans captured from each trigger on, including the triggering scan
+    #[arg(
+        long,
+        default_value_t = 1000,
+        requires = "trigger",
+        value_name = "SCANS"
+    )]
+    post: usize,
+    /// Events to capture before stopping
+    #[arg(long, default_value_t = 1, requires = "trigger")]
+    count: usize,
+}
+
+/// Where and in which format samples are written, shared with `replay`
+#[derive(Debug, clap::Args)]
+pub struct Output {
+    /// Output format of files and stdout [default: csv]
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+    /// `-` for stdout, a file path, `unix:<path>` for a Unix socket server,
+    /// or `udp:<host:port>` for UDP packets
+    #[arg(short, long, default_value = "-", value_name = "TARGET")]
+    output: String,
+}
+
+/// Output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// Delimited text with channel names and converted values
+    Csv,
+    /// One JSON object per sample
+    Jsonl,
+    /// Self-describing binary capture, readable by `CaptureReader`
+    Binary,
+}
+
+/// Acquire until Ctrl+C, `--scans`, or an error
+pub fn run(config: &Ads1263Config, args: &Args, running: &AtomicBool) -> ads1263::Result<()> {
+    if let Some(trigger) = &args.trigger {
+        return triggered(config, args, trigger, running);
+    }
+    let sink = args.output.sink(&config.header())?;
+    let adc = config.open()?;
+    let acquisition = AcquisitionConfig {
+        max_scans: args.scans,
+        ..config.acquisition.clone()
+    };
+    let acq = adc.start_acquisition_sink(&config.channels, acquisition, sink)?;
+
+    while running.load(Ordering::SeqCst) {
+        match acq.recv_timeout(POLL) {
+            Err(Ads1263Error::Timeout) => {}
+            // The sampler finished its scans
+            Err(Ads1263Error::WorkerTerminated) => break,
+            Err(e) => return Err(e),
+            Ok(_) => {}
+        }
+    }
+    acq.join()?;
+    Ok(())
+}
+
+/// Write `--count` trigger events to numbered files, then stop
+///
+/// Each event holds up to `--pre` scans from before the trigger and
+/// `--post` scans from it on, with their original timestamps; the trigger
+/// re-arms after every event.
+fn triggered(
+    config: &Ads1263Config,
+    args: &Args,
+    trigger: &str,
+    running: &AtomicBool,
+) -> ads1263::Result<()> {
+    let output = &args.output.output;
+    if output == "-" || output.starts_with("unix:") || output.starts_with("udp:") {
+        return Err(Ads1263Error::InvalidConfig(
+            "--trigger writes one file per event, pass --output FILE".to_string(),
+        ));
+    }
+    let condition = condition(config, trigger)?;
+    let mut trigger_state = Trigger::new(
+        TriggerConfig {
+            condition,
+            pre_trigger: args.pre,
+            post_trigger: args.post,
+            timeout: None,
+        },
+        config.channels.len(),
+    )?;
+    let header = config.header();
+    let adc = config.open()?;
+    let acq = adc.start_acquisition(&config.channels, config.acquisition.clone())?;
+
+    // The scans behind the trigger's raw codes, to write with timestamps
+    let keep = args.pre + args.post.max(1);
+    let mut recent: VecDeque<Vec<Sample>> = VecDeque::with_capacity(keep);
+    let mut values = Vec::with_capacity(config.channels.len());
+    let mut events = 0;
+    eprintln!("Waiting for {}", trigger);
+    while running.load(Ordering::SeqCst) && events < args.count {
+        let scan = match acq.recv_timeout(POLL) {
+            Err(Ads1263Error::Timeout) => continue,
+            Err(Ads1263Error::WorkerTerminated) => break,
+            Err(e) => return Err(e),
+            Ok(scan) => scan,
+        };
+        values.clear();
+        values.extend(scan.iter().map(|sample| sample.raw as u32));
+        if recent.len() == keep {
+            recent.pop_front();
+        }
+        recent.push_back(scan);
+
+        let Some(capture) = trigger_state.feed(&values) else {
+            continue;
+        };
+        events += 1;
+        let path = numbered(output, events);
+        let mut sink = args.output.sink_at(&header, &path)?;
+        for scan in recent.range(recent.len() - capture.len()..) {
+            sink.write(scan)?;
+        }
+        sink.flush()?;
+        eprintln!(
+            "Event {} of {}: {} scans, {} before the trigger, in {}",
+            events,
+            args.count,
+            capture.len(),
+            capture.trigger_index,
+            path
+        );
+    }
+    acq.join()?;
+    Ok(())
+}
+
+/// Trigger condition parsed from `--trigger`
+///
+/// Levels are volts at the ADC, before the channel map's scaling, with an
+/// optional `V`, `mV`, or `uV` suffix.
+fn condition(config: &Ads1263Config, text: &str) -> ads1263::Result<TriggerCondition> {
+    let invalid = || {
+        Ads1263Error::InvalidConfig(format!(
+            "invalid trigger '{}' (e.g. 'ch0 > 2.5V', 'ch1 in 1V..2V', or 'gpio17 rising')",
+            text
+        ))
+    };
+    let level = |text: &str| -> ads1263::Result<i32> {
+        let text = text.trim();
+        let (number, factor) = if let Some(number) = text.strip_suffix("mV") {
+            (number, 1e-3)
+        } else if let Some(number) = text.strip_suffix("uV").or_else(|| text.strip_suffix("µV")) {
+            (number, 1e-6)
+        } else {
+            (text.strip_suffix('V').unwrap_or(text), 1.0)
+        };
+        let volts: f64 = number.trim().parse().map_err(|_| invalid())?;
+        let code = volts * factor / config.map.reference() * 2147483648.0;
+        Ok(code.round().clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32)
+    };
+    // Position of the channel within a scan, which the trigger watches
+    let index = |key: &str| -> ads1263::Result<usize> {
+        let channel = crate::resolve_channel(config, key.trim())?;
+        config
+            .channels
+            .iter()
+            .position(|&scanned| scanned == channel)
+            .ok_or_else(|| {
+                Ads1263Error::InvalidConfig(format!(
+                    "trigger channel {} is not scanned",
+                    config.map.name(channel)
+                ))
+            })
+    };
+
+    for (operator, edge) in [(">", Edge::Rising), ("<", Edge::Falling)] {
+        if let Some((key, value)) = text.split_once(operator) {
+            return Ok(TriggerCondition::Level {
+                channel: index(key)?,
+                level: level(value)?,
+                edge,
+            });
+        }
+    }
+    match text.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [key, word @ ("in" | "out"), range] => {
+            let (low, high) = range.split_once("..").ok_or_else(invalid)?;
+            let (low, high) = (level(low)?, level(high)?);
+            if low > high {
+                return Err(invalid());
+            }
+            Ok(TriggerCondition::Window {
+                channel: index(key)?,
+                low,
+                high,
+                enter: *word == "in",
+            })
+        }
+        [pin, edge] if pin.starts_with("gpio") => Ok(TriggerCondition::External {
+            pin: pin["gpio".len()..].parse().map_err(|_| invalid())?,
+            edge: match *edge {
+                "rising" => Edge::Rising,
+                "falling" => Edge::Falling,
+                "both" => Edge::Both,
+                _ => return Err(invalid()),
+            },
+        }),
+        _ => Err(invalid()),
+    }
+}
+
+/// `path` with `-<n>` before its extension, e.g. `event-2.csv`
+fn numbered(path: &str, n: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, n, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+impl Output {
+    /// Sink writing `--format` to `--output`
+    ///
+    /// Values are converted with the header's channel map, and binary
+    /// captures start with the header.
+    pub fn sink(&self, header: &CaptureHeader) -> ads1263::Result<Box<dyn Sink>> {
+        self.sink_at(header, &self.output)
+    }
+
+    /// Sink writing `--format` to `target` instead of `--output`
+    fn sink_at(&self, header: &CaptureHeader, target: &str) -> ads1263::Result<Box<dyn Sink>> {
+        let map = header.map.clone();
+        if let Some(path) = target.strip_prefix("unix:") {
+            check_binary(self.format)?;
+            return Ok(Box::new(UnixSocketSink::bind(path, map)?));
+        }
+        if let Some(target) = target.strip_prefix("udp:") {
+            check_binary(self.format)?;
+            return Ok(Box::new(UdpSink::new(target, map)?));
+        }
+        let writer: Box<dyn Write + Send> = match target {
+            "-" => Box::new(std::io::stdout()),
+            path => Box::new(File::create(path)?),
+        };
+        let writer = BufWriter::new(writer);
+        Ok(match self.format.unwrap_or(Format::Csv) {
+            Format::Csv => Box::new(CsvSink::new(writer, map)),
+            Format::Jsonl => Box::new(JsonLinesSink::new(writer, map)),
+            Format::Binary => Box::new(CaptureWriter::new(writer, header)?),
+        })
+    }
+}
+
+/// Sockets carry their own binary frames
+fn check_binary(format: Option<Format>) -> ads1263::Result<()> {
+    match format {
+        None | Some(Format::Binary) => Ok(()),
+        _ => Err(Ads1263Error::InvalidConfig(
+            "socket outputs send binary frames, use --format binary".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,70 @@
+//! `temp`: the ADC's internal temperature sensor
+
+use crate::json::Object;
+use ads1263::{Ads1263Config, Monitor};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Temperature unit
+    #[arg(long, value_enum, default_value_t = Unit::C)]
+    unit: Unit,
+    /// Keep reading at this interval, e.g. `1s`, until Ctrl+C
+    #[arg(long, value_parser = crate::parse_duration, value_name = "INTERVAL")]
+    continuous: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum Unit {
+    /// Degrees Celsius
+    C,
+    /// Degrees Fahrenheit
+    F,
+    /// Kelvin
+    K,
+}
+
+impl Unit {
+    /// `celsius` in this unit, with its symbol
+    fn convert(self, celsius: f64) -> (f64, &'static str) {
+        match self {
+            Unit::C => (celsius, "°C"),
+            Unit::F => (celsius * 9.0 / 5.0 + 32.0, "°F"),
+            Unit::K => (celsius + 273.15, "K"),
+        }
+    }
+}
+
+/// Print the die temperature once, or every `--continuous` interval
+///
+/// With `--json`, each reading is a `{"temperature", "unit"}` line.
+pub fn run(
+    config: &Ads1263Config,
+    args: &Args,
+    running: &AtomicBool,
+    json: bool,
+) -> ads1263::Result<()> {
+    let mut adc = config.open()?;
+
+    loop {
+        let started = Instant::now();
+        let (value, symbol) = args.unit.convert(adc.read_monitor(Monitor::Temperature)?);
+        if json {
+            let document = Object::new().num("temperature", value).str("unit", symbol);
+            println!("{}", document.finish());
+        } else {
+            println!("{:.2} {}", value, symbol);
+        }
+
+        let Some(interval) = args.continuous else {
+            return Ok(());
+        };
+        while running.load(Ordering::SeqCst) && started.elapsed() < interval {
+            std::thread::sleep(Duration::from_millis(10).min(interval));
+        }
+        if !running.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,375 @@
+//! Wheatstone bridges
+//!
+//! A bridge's output is proportional to its excitation, so it is measured
+//! ratiometrically: the reference is the excitation itself (AVDD when the
+//! bridge is fed from the HAT's 5 V, or an external input pair sensing the
+//! excitation at the bridge), and the result is the output in mV per volt
+//! of excitation, independent of supply drift; see
+//! [`Ads1263::read_ratio`].
+//!
+//! [`LoadCell`] scales that to a force or weight, with tare and a
+//! calibration against a known weight. [`StrainGauge`] converts it to
+//! strain in µε for quarter, half, and full bridges of bonded gauges,
+//! see [`BridgeType`].
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{Gain, MuxInput, ReferenceSource};
+use crate::sensors::{Sensor, SensorSetup};
+
+/// Load cell on a differential input pair
+///
+/// Reports `(mV/V - tare) × scale` in its unit; without a scale, the unit
+/// is mV/V. Rated output from the data sheet gives a first scale, and
+/// [`calibrate`](Self::calibrate) with a known weight a precise one.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{LoadCell, MuxInput, Sensor};
+///
+/// // 5 kg cell, 2 mV/V rated output, on AIN0/AIN1
+/// let mut scale = LoadCell::new(MuxInput::Ain(0), MuxInput::Ain(1)).rated(5.0, 2.0, "kg");
+/// scale.tare(&mut adc, 16)?;
+///
+/// // Put a 1 kg weight on it
+/// scale.calibrate(&mut adc, 1.0, 16)?;
+/// println!("{:.4} {}", scale.read(&mut adc)?, scale.unit());
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadCell {
+    setup: SensorSetup,
+    tare: f64,
+    scale: f64,
+    unit: String,
+}
+
+impl LoadCell {
+    /// Bridge output between `positive` and `negative` at gain 64 against
+    /// the AVDD/AVSS reference (±15.6 mV/V full scale), reported in mV/V
+    pub fn new(positive: MuxInput, negative: MuxInput) -> Self {
+        let setup = SensorSetup::new(positive, negative).gain(Gain::Gain64);
+        Self::with_setup(setup)
+    }
+
+    /// Load cell measured with a custom setup
+    ///
+    /// The setup's reference must be the bridge excitation for the reading
+    /// to be ratiometric.
+    pub fn with_setup(setup: SensorSetup) -> Self {
+        Self {
+            setup,
+            tare: 0.0,
+            scale: 1.0,
+            unit: "mV/V".to_string(),
+        }
+    }
+
+    /// Take the reference from the bridge excitation sensed on an external
+    /// input pair, at `volts`
+    pub fn excitation(mut self, reference: ReferenceSource, volts: f64) -> Self {
+        self.setup = self.setup.reference(reference, volts);
+        self
+    }
+
+    /// Scale from the data sheet: `capacity` in `unit` at a rated output
+    /// of `mv_per_v`
+    pub fn rated(self, capacity: f64, mv_per_v: f64, unit: &str) -> Self {
+        self.scale(capacity / mv_per_v, unit)
+    }
+
+    /// Set the scale in `unit` per mV/V
+    pub fn scale(mut self, per_mv_per_v: f64, unit: &str) -> Self {
+        self.scale = per_mv_per_v;
+        self.unit = unit.to_string();
+        self
+    }
+
+    /// Set the zero-load output in mV/V
+    pub fn tare_offset(mut self, mv_per_v: f64) -> Self {
+        self.tare = mv_per_v;
+        self
+    }
+
+    /// Zero-load output in mV/V
+    pub fn tare_value(&self) -> f64 {
+        self.tare
+    }
+
+    /// Scale in the unit per mV/V
+    pub fn scale_factor(&self) -> f64 {
+        self.scale
+    }
+
+    /// Bridge output in mV/V of a conversion result
+    pub fn mv_per_v(&self, code: i32) -> f64 {
+        mv_per_v(&self.setup, code)
+    }
+
+    /// Zero the cell: average `samples` conversions of the unloaded cell
+    /// and use them as the tare
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the conversions
+    ///
+    /// # Returns
+    ///
+    /// The new tare in mV/V
+    pub fn tare(&mut self, adc: &mut Ads1263, samples: usize) -> Result<f64> {
+        self.tare = self.average(adc, samples)?;
+        log::debug!("Load cell tare: {:.6} mV/V", self.tare);
+        Ok(self.tare)
+    }
+
+    /// Calibrate the scale with `known` (in the unit) on the tared cell,
+    /// averaging `samples` conversions
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::SensorFault` if the output did not change
+    /// from the tare, or the error of the conversions
+    ///
+    /// # Returns
+    ///
+    /// The new scale in the unit per mV/V
+    pub fn calibrate(&mut self, adc: &mut Ads1263, known: f64, samples: usize) -> Result<f64> {
+        let span = self.average(adc, samples)? - self.tare;
+        if span.abs() < 1e-9 {
+            return Err(Ads1263Error::SensorFault(format!(
+                "load cell output {:.6} mV/V did not change under {} {}",
+                span, known, self.unit
+            )));
+        }
+        self.scale = known / span;
+        log::debug!(
+            "Load cell calibration: {} {} at {:.6} mV/V -> {:.6} {}/(mV/V)",
+            known,
+            self.unit,
+            span,
+            self.scale,
+            self.unit
+        );
+        Ok(self.scale)
+    }
+
+    /// Mean output in mV/V of `samples` conversions
+    fn average(&self, adc: &mut Ads1263, samples: usize) -> Result<f64> {
+        average(adc, &self.setup, samples)
+    }
+}
+
+impl Sensor for LoadCell {
+    fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        (self.mv_per_v(code) - self.tare) * self.scale
+    }
+}
+
+/// Arrangement of the active gauges in a strain bridge
+///
+/// Arms without a gauge are completed with fixed resistors of the gauge
+/// resistance. Poisson arrangements place gauges across the strain axis,
+/// which see `-ν` times the axial strain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BridgeType {
+    /// One active gauge, three completion resistors
+    Quarter,
+    /// Two active gauges in adjacent arms, one in tension and one in
+    /// compression (bending)
+    HalfBending,
+    /// Two active gauges in adjacent arms, one axial and one transverse,
+    /// with Poisson's ratio ν
+    HalfPoisson(f64),
+    /// Four active gauges, two in tension and two in compression (bending)
+    FullBending,
+    /// Four active gauges, two axial and two transverse, with Poisson's
+    /// ratio ν
+    FullPoisson(f64),
+}
+
+impl BridgeType {
+    /// Strain of a bridge output `vr` (strained minus unstrained output,
+    /// in V/V) with gauge factor `gf`
+    ///
+    /// Includes the nonlinearity of quarter and Poisson bridges, whose
+    /// gauges change opposite arms by different amounts.
+    pub fn strain(self, vr: f64, gf: f64) -> f64 {
+        match self {
+            BridgeType::Quarter => 4.0 * vr / (gf * (1.0 - 2.0 * vr)),
+            BridgeType::HalfBending => 2.0 * vr / gf,
+            BridgeType::HalfPoisson(nu) => 4.0 * vr / (gf * ((1.0 + nu) + 2.0 * vr * (nu - 1.0))),
+            BridgeType::FullBending => vr / gf,
+            BridgeType::FullPoisson(nu) => 2.0 * vr / (gf * ((1.0 + nu) + vr * (nu - 1.0))),
+        }
+    }
+
+    /// Bridge output in V/V at `strain` with gauge factor `gf`, the
+    /// inverse of [`strain`](Self::strain)
+    pub fn output(self, strain: f64, gf: f64) -> f64 {
+        let x = gf * strain;
+        match self {
+            BridgeType::Quarter => x / (4.0 + 2.0 * x),
+            BridgeType::HalfBending => x / 2.0,
+            BridgeType::HalfPoisson(nu) => x * (1.0 + nu) / (4.0 + 2.0 * x * (1.0 - nu)),
+            BridgeType::FullBending => x,
+            BridgeType::FullPoisson(nu) => x * (1.0 + nu) / (2.0 + x * (1.0 - nu)),
+        }
+    }
+}
+
+/// Strain gauge bridge on a differential input pair, reported in µε
+///
+/// Measured ratiometrically like a [`LoadCell`]; the unstrained output is
+/// removed with [`zero`](Self::zero). Lead resistance in series with a
+/// quarter-bridge gauge desensitizes it, which
+/// [`lead_resistance`](Self::lead_resistance) corrects.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{BridgeType, MuxInput, Sensor, StrainGauge};
+///
+/// let mut gauge = StrainGauge::new(BridgeType::Quarter, 2.1, MuxInput::Ain(2), MuxInput::Ain(3))
+///     .lead_resistance(0.5, 350.0);
+/// gauge.zero(&mut adc, 16)?;
+/// println!("{:.1} µε", gauge.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrainGauge {
+    setup: SensorSetup,
+    bridge: BridgeType,
+    gauge_factor: f64,
+    zero: f64,
+    lead_ratio: f64,
+}
+
+impl StrainGauge {
+    /// `bridge` of gauges with `gauge_factor` between `positive` and
+    /// `negative`, at gain 64 against the AVDD/AVSS reference
+    pub fn new(
+        bridge: BridgeType,
+        gauge_factor: f64,
+        positive: MuxInput,
+        negative: MuxInput,
+    ) -> Self {
+        let setup = SensorSetup::new(positive, negative).gain(Gain::Gain64);
+        Self::with_setup(bridge, gauge_factor, setup)
+    }
+
+    /// Strain gauge bridge measured with a custom setup
+    ///
+    /// The setup's reference must be the bridge excitation for the reading
+    /// to be ratiometric.
+    pub fn with_setup(bridge: BridgeType, gauge_factor: f64, setup: SensorSetup) -> Self {
+        Self {
+            setup,
+            bridge,
+            gauge_factor,
+            zero: 0.0,
+            lead_ratio: 0.0,
+        }
+    }
+
+    /// Take the reference from the bridge excitation sensed on an external
+    /// input pair, at `volts`
+    pub fn excitation(mut self, reference: ReferenceSource, volts: f64) -> Self {
+        self.setup = self.setup.reference(reference, volts);
+        self
+    }
+
+    /// Set the unstrained output in mV/V
+    pub fn zero_offset(mut self, mv_per_v: f64) -> Self {
+        self.zero = mv_per_v;
+        self
+    }
+
+    /// Correct for `lead_ohms` in series with a quarter-bridge gauge of
+    /// `gauge_ohms`; ignored for the other bridges
+    pub fn lead_resistance(mut self, lead_ohms: f64, gauge_ohms: f64) -> Self {
+        self.lead_ratio = lead_ohms / gauge_ohms;
+        self
+    }
+
+    /// Bridge arrangement
+    pub fn bridge(&self) -> BridgeType {
+        self.bridge
+    }
+
+    /// Unstrained output in mV/V
+    pub fn zero_value(&self) -> f64 {
+        self.zero
+    }
+
+    /// Bridge output in mV/V of a conversion result
+    pub fn mv_per_v(&self, code: i32) -> f64 {
+        mv_per_v(&self.setup, code)
+    }
+
+    /// Strain in µε of a bridge output in mV/V
+    pub fn microstrain(&self, mv_per_v: f64) -> f64 {
+        let vr = (mv_per_v - self.zero) / 1000.0;
+        let strain = self.bridge.strain(vr, self.gauge_factor);
+        let lead = match self.bridge {
+            BridgeType::Quarter => 1.0 + self.lead_ratio,
+            _ => 1.0,
+        };
+        strain * lead * 1e6
+    }
+
+    /// Zero the bridge: average `samples` conversions of the unstrained
+    /// bridge and use them as the zero
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the conversions
+    ///
+    /// # Returns
+    ///
+    /// The new zero in mV/V
+    pub fn zero(&mut self, adc: &mut Ads1263, samples: usize) -> Result<f64> {
+        self.zero = average(adc, &self.setup, samples)?;
+        log::debug!("Strain gauge zero: {:.6} mV/V", self.zero);
+        Ok(self.zero)
+    }
+}
+
+impl Sensor for StrainGauge {
+    fn unit(&self) -> &str {
+        "µε"
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.microstrain(self.mv_per_v(code))
+    }
+}
+
+/// Bridge output in mV/V of a conversion result
+fn mv_per_v(setup: &SensorSetup, code: i32) -> f64 {
+    setup.ratio(code) * 1000.0
+}
+
+/// Mean bridge output in mV/V of `samples` conversions
+fn average(adc: &mut Ads1263, setup: &SensorSetup, samples: usize) -> Result<f64> {
+    let n = samples.max(1);
+    let mut sum = 0.0;
+    for _ in 0..n {
+        sum += adc.read_ratio(setup)? * 1000.0;
+    }
+    Ok(sum / n as f64)
+}
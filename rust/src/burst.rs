@@ -0,0 +1,79 @@
+//! Burst capture of consecutive conversions
+//!
+//! [`Ads1263::capture`](crate::Ads1263::capture) routes one channel, leaves
+//! the multiplexer alone, and collects N back-to-back conversions at the
+//! configured data rate. The result carries [`BurstTiming`] so noise and
+//! ENOB analyses can check that no conversion was missed.
+
+use crate::sample::Sample;
+use std::time::Duration;
+
+/// Timing summary of a burst
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BurstTiming {
+    /// Time from the first to the last sample
+    pub elapsed: Duration,
+    /// Shortest interval between consecutive samples
+    pub min_interval: Duration,
+    /// Longest interval between consecutive samples
+    pub max_interval: Duration,
+    /// Mean interval between consecutive samples
+    pub mean_interval: Duration,
+    /// Intervals longer than twice the data rate period (likely missed conversions)
+    pub late: usize,
+}
+
+impl BurstTiming {
+    /// Summarize the sample timestamps against the expected conversion period
+    pub fn from_samples(samples: &[Sample], period: Duration) -> Self {
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return Self::default();
+        };
+        let intervals = samples
+            .windows(2)
+            .map(|w| w[1].timestamp.saturating_duration_since(w[0].timestamp));
+        let mut timing = Self {
+            elapsed: last.timestamp.saturating_duration_since(first.timestamp),
+            min_interval: Duration::MAX,
+            ..Self::default()
+        };
+        for interval in intervals {
+            timing.min_interval = timing.min_interval.min(interval);
+            timing.max_interval = timing.max_interval.max(interval);
+            if interval > period * 2 {
+                timing.late += 1;
+            }
+        }
+        if samples.len() < 2 {
+            timing.min_interval = Duration::ZERO;
+        } else {
+            timing.mean_interval = timing.elapsed / (samples.len() - 1) as u32;
+        }
+        timing
+    }
+
+    /// Achieved sample rate in samples per second (0 for fewer than 2 samples)
+    pub fn sps(&self) -> f64 {
+        if self.mean_interval.is_zero() {
+            0.0
+        } else {
+            1.0 / self.mean_interval.as_secs_f64()
+        }
+    }
+}
+
+/// Conversions collected by a burst capture
+#[derive(Debug, Clone, PartialEq)]
+pub struct Burst {
+    /// Conversions in the order they were taken
+    pub samples: Vec<Sample>,
+    /// Timing of the capture
+    pub timing: BurstTiming,
+}
+
+impl Burst {
+    /// Signed raw values of all samples
+    pub fn values(&self) -> impl Iterator<Item = i32> + '_ {
+        self.samples.iter().map(|s| s.raw)
+    }
+}
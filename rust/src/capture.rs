@@ -0,0 +1,791 @@
+//! Binary capture files
+//!
+//! A compact, lossless format for full-rate captures. The file describes
+//! itself: after the magic and version comes a header with the chip ID,
+//! driver version, input mode, ADC1 configuration, wall-clock start time,
+//! and the [`ChannelMap`] (names, units, scaling, curves), followed by
+//! frames of samples. [`CaptureWriter`] is a [`Sink`];
+//! [`CaptureReader`] reads the header and iterates over the samples.
+//!
+//! All integers are little-endian:
+//!
+//! ```text
+//! file    = magic "ADS1263C" | version u16 | header length u32 | header | frame*
+//! header  = chip ID u8 | driver version str | input mode u8
+//!         | gain u8 | data rate u8 | filter u8 | delay u8 | reference source u8
+//!         | wall clock at start, ns since the Unix epoch u64
+//!         | reference voltage f64 | channel count u16 | channel*
+//! channel = number u8 | name str | unit str | scale f64 | offset f64 | curve str
+//! str     = length u16 | UTF-8 bytes
+//! frame   = sample count u32 | CRC-32 of the records u32 | record*
+//! record  = time since start, ns i64 | sequence u64 | raw i32 | dropped u32
+//!         | generation u32 | channel u8 | status u8 | CRC ok u8 | reserved u8
+//! ```
+//!
+//! Curves are stored in their text form (see [`Curve`]), empty if none.
+//! A frame cut short by a killed process ends the capture; everything
+//! before it is intact.
+//! Headers longer than [`MAX_HEADER_LEN`] and frames of more than
+//! [`MAX_FRAME_SAMPLES`] are rejected before anything is allocated, so a
+//! corrupt length cannot exhaust memory.
+//!
+//! With the `zstd` feature, [`CaptureWriter::create_zstd`] compresses
+//! each frame as its own zstd frame, and [`CaptureReader::open`] reads
+//! compressed and uncompressed files alike.
+
+use crate::ads1263::Ads1263;
+use crate::channel_map::{ChannelInfo, ChannelMap};
+#[cfg(feature = "zstd")]
+use crate::compress::ZstdWriter;
+use crate::error::{Ads1263Error, Result};
+use crate::linearize::Curve;
+use crate::registers::{
+    Adc1Config, DataRate, Delay, DigitalFilter, Gain, InputMode, ReferenceSource, StatusFlags,
+};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// File magic of binary captures
+pub const MAGIC: [u8; 8] = *b"ADS1263C";
+/// Current format version
+pub const VERSION: u16 = 1;
+/// Size of one sample record in bytes
+pub const RECORD_LEN: usize = 32;
+/// Largest header in bytes a capture may have
+pub const MAX_HEADER_LEN: usize = 1 << 20;
+/// Largest number of samples in one frame
+pub const MAX_FRAME_SAMPLES: usize = 1 << 20;
+
+/// Samples per frame unless configured otherwise
+const DEFAULT_FRAME_SAMPLES: usize = 4096;
+
+/// Magic number at the start of a zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Description of a capture stored at the start of the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureHeader {
+    /// Chip ID read from the device
+    pub chip_id: u8,
+    /// Version of the driver that wrote the capture
+    pub driver_version: String,
+    /// Input mode the channels were measured in
+    pub input_mode: InputMode,
+    /// ADC1 configuration at the start of the capture
+    pub adc1: Adc1Config,
+    /// Channel names, units, and scaling
+    pub map: ChannelMap,
+}
+
+impl CaptureHeader {
+    /// Header for a single-ended capture with the given configuration
+    pub fn new(adc1: Adc1Config, map: ChannelMap) -> Self {
+        Self {
+            chip_id: 1,
+            driver_version: env!("CARGO_PKG_VERSION").to_string(),
+            input_mode: InputMode::SingleEnded,
+            adc1,
+            map,
+        }
+    }
+
+    /// Header describing the device's current chip ID, mode, and configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chip ID cannot be read
+    pub fn from_device(adc: &mut Ads1263, map: ChannelMap) -> Result<Self> {
+        Ok(Self {
+            chip_id: adc.read_chip_id()?,
+            input_mode: adc.get_mode(),
+            ..Self::new(adc.adc1_config(), map)
+        })
+    }
+
+    fn encode(&self, wall_clock: SystemTime, out: &mut Vec<u8>) -> Result<()> {
+        out.push(self.chip_id);
+        put_str(out, &self.driver_version)?;
+        out.push(match self.input_mode {
+            InputMode::SingleEnded => 0,
+            InputMode::Differential => 1,
+        });
+        out.extend_from_slice(&[
+            self.adc1.gain as u8,
+            self.adc1.data_rate as u8,
+            self.adc1.filter as u8,
+            self.adc1.delay as u8,
+            self.adc1.reference as u8,
+        ]);
+        let nanos = wall_clock
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        out.extend_from_slice(&nanos.to_le_bytes());
+
+        out.extend_from_slice(&self.map.reference().to_le_bytes());
+        let channels: Vec<_> = self.map.iter().collect();
+        out.extend_from_slice(&(channels.len() as u16).to_le_bytes());
+        for (channel, info) in channels {
+            out.push(channel);
+            put_str(out, &info.name)?;
+            put_str(out, &info.unit)?;
+            out.extend_from_slice(&info.scale.to_le_bytes());
+            out.extend_from_slice(&info.offset.to_le_bytes());
+            let curve = info
+                .curve
+                .as_ref()
+                .map(Curve::to_string)
+                .unwrap_or_default();
+            put_str(out, &curve)?;
+        }
+        Ok(())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<(Self, SystemTime)> {
+        let mut d = Decoder(bytes);
+        let chip_id = d.u8()?;
+        let driver_version = d.str()?;
+        let input_mode = match d.u8()? {
+            0 => InputMode::SingleEnded,
+            1 => InputMode::Differential,
+            other => return Err(invalid(format!("unknown input mode {}", other))),
+        };
+        let adc1 = Adc1Config {
+            gain: lookup(&GAINS, d.u8()?, |v| v as u8, "gain")?,
+            data_rate: lookup(&DATA_RATES, d.u8()?, |v| v as u8, "data rate")?,
+            filter: lookup(&FILTERS, d.u8()?, |v| v as u8, "filter")?,
+            delay: lookup(&DELAYS, d.u8()?, |v| v as u8, "delay")?,
+            reference: lookup(&REFERENCES, d.u8()?, |v| v as u8, "reference source")?,
+        };
+        let wall_clock = UNIX_EPOCH + Duration::from_nanos(d.u64()?);
+
+        let mut map = ChannelMap::new(d.f64()?);
+        for _ in 0..d.u16()? {
+            let channel = d.u8()?;
+            let mut info = ChannelInfo::new(d.str()?)
+                .unit(d.str()?)
+                .linear(d.f64()?, d.f64()?);
+            let curve = d.str()?;
+            if !curve.is_empty() {
+                info = info.curve(curve.parse()?);
+            }
+            map.insert(channel, info);
+        }
+        let header = Self {
+            chip_id,
+            driver_version,
+            input_mode,
+            adc1,
+            map,
+        };
+        Ok((header, wall_clock))
+    }
+}
+
+/// Sink writing a binary capture
+///
+/// Samples are collected into frames of `frame_samples` samples, each
+/// written with its CRC in one piece. [`flush`](Sink::flush) writes the
+/// current partial frame; dropping the writer does the same.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, CaptureWriter, ChannelMap, DataRate, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps38400)?;
+///
+/// let header = CaptureHeader::from_device(&mut adc, ChannelMap::new(5.0))?;
+/// let writer = CaptureWriter::create("capture.ads", &header)?;
+/// let acq = adc.start_acquisition_sink(&[0], AcquisitionConfig::default(), writer)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct CaptureWriter<W: Write> {
+    writer: Option<W>,
+    /// Instant record times are measured from
+    start: Instant,
+    /// Records of the frame being collected
+    frame: Vec<u8>,
+    frame_samples: usize,
+}
+
+impl CaptureWriter<BufWriter<File>> {
+    /// Create (or truncate) a capture file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created or written
+    pub fn create(path: impl AsRef<Path>, header: &CaptureHeader) -> Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), header)
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl CaptureWriter<ZstdWriter<File>> {
+    /// Create (or truncate) a zstd-compressed capture file at `path`
+    ///
+    /// Every frame is compressed as one zstd frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created or written
+    pub fn create_zstd(path: impl AsRef<Path>, header: &CaptureHeader) -> Result<Self> {
+        Self::new(ZstdWriter::create(path)?, header)
+    }
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Write the file header to `writer`
+    ///
+    /// The current time is recorded as the capture's wall-clock start.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails and
+    /// `Ads1263Error::InvalidCapture` if a name or curve is too long or
+    /// the header exceeds [`MAX_HEADER_LEN`]
+    pub fn new(mut writer: W, header: &CaptureHeader) -> Result<Self> {
+        let start = Instant::now();
+        let mut body = Vec::new();
+        header.encode(SystemTime::now(), &mut body)?;
+        if body.len() > MAX_HEADER_LEN {
+            return Err(invalid(format!(
+                "header of {} bytes exceeds {} bytes",
+                body.len(),
+                MAX_HEADER_LEN
+            )));
+        }
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(body.len() as u32).to_le_bytes())?;
+        writer.write_all(&body)?;
+        Ok(Self {
+            writer: Some(writer),
+            start,
+            frame: Vec::with_capacity(DEFAULT_FRAME_SAMPLES * RECORD_LEN),
+            frame_samples: DEFAULT_FRAME_SAMPLES,
+        })
+    }
+
+    /// Set the number of samples per frame (default 4096, at most
+    /// [`MAX_FRAME_SAMPLES`])
+    ///
+    /// Smaller frames lose less data if the process is killed; larger
+    /// ones have less overhead.
+    pub fn frame_samples(mut self, samples: usize) -> Self {
+        self.frame_samples = samples.clamp(1, MAX_FRAME_SAMPLES);
+        self
+    }
+
+    /// Write the last frame, flush, and return the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails
+    pub fn finish(mut self) -> Result<W> {
+        self.write_frame()?;
+        let mut writer = self
+            .writer
+            .take()
+            .ok_or_else(|| invalid("capture already finished".into()))?;
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    fn write_frame(&mut self) -> Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        let count = (self.frame.len() / RECORD_LEN) as u32;
+        writer.write_all(&count.to_le_bytes())?;
+        writer.write_all(&crc32(&self.frame).to_le_bytes())?;
+        writer.write_all(&self.frame)?;
+        // Completes the frame on disk, and its zstd frame when compressed
+        writer.flush()?;
+        self.frame.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> Sink for CaptureWriter<W> {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        for sample in samples {
+            let offset = match sample.timestamp.checked_duration_since(self.start) {
+                Some(d) => d.as_nanos() as i64,
+                None => -(self.start.duration_since(sample.timestamp).as_nanos() as i64),
+            };
+            self.frame.extend_from_slice(&offset.to_le_bytes());
+            self.frame.extend_from_slice(&sample.sequence.to_le_bytes());
+            self.frame.extend_from_slice(&sample.raw.to_le_bytes());
+            self.frame
+                .extend_from_slice(&sample.dropped_since_last.to_le_bytes());
+            self.frame
+                .extend_from_slice(&sample.generation.to_le_bytes());
+            self.frame.extend_from_slice(&[
+                sample.channel,
+                sample.status.bits(),
+                u8::from(sample.crc_ok),
+                0,
+            ]);
+            if self.frame.len() >= self.frame_samples * RECORD_LEN {
+                self.write_frame()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the current partial frame and flush the writer
+    fn flush(&mut self) -> Result<()> {
+        self.write_frame()?;
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for CaptureWriter<W> {
+    fn drop(&mut self) {
+        let result = self
+            .write_frame()
+            .and_then(|()| match self.writer.as_mut() {
+                Some(writer) => writer.flush().map_err(Ads1263Error::from),
+                None => Ok(()),
+            });
+        if let Err(e) = result {
+            log::warn!("Failed to write last capture frame: {}", e);
+        }
+    }
+}
+
+/// Reader of binary captures
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::CaptureReader;
+/// use std::time::Instant;
+///
+/// let capture = CaptureReader::open("capture.ads")?;
+/// let header = capture.header().clone();
+/// println!("{:?} at {} SPS", header.adc1.gain, header.adc1.data_rate.sps());
+/// for sample in capture.samples(Instant::now()) {
+///     let sample = sample?;
+///     println!("{} = {}", header.map.name(sample.channel), header.map.convert_sample(&sample));
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct CaptureReader<R: Read> {
+    reader: R,
+    header: CaptureHeader,
+    wall_clock: SystemTime,
+    frame: Vec<u8>,
+}
+
+/// Capture file opened by [`CaptureReader::open`]
+///
+/// Decompresses zstd-compressed captures (`zstd` feature).
+pub struct CaptureFile(CaptureInput);
+
+enum CaptureInput {
+    Plain(BufReader<File>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Decoder<'static, BufReader<File>>),
+}
+
+impl Read for CaptureFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.0 {
+            CaptureInput::Plain(reader) => reader.read(buf),
+            #[cfg(feature = "zstd")]
+            CaptureInput::Zstd(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl CaptureReader<CaptureFile> {
+    /// Open a capture file, compressed or not
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be read and
+    /// `Ads1263Error::InvalidCapture` if the header is not recognized
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let mut file = BufReader::new(File::open(path)?);
+        let input = if file.fill_buf()?.starts_with(&ZSTD_MAGIC) {
+            #[cfg(feature = "zstd")]
+            {
+                CaptureInput::Zstd(zstd::Decoder::with_buffer(file)?)
+            }
+            #[cfg(not(feature = "zstd"))]
+            return Err(invalid(
+                "zstd-compressed capture (enable the zstd feature)".into(),
+            ));
+        } else {
+            CaptureInput::Plain(file)
+        };
+        Self::new(CaptureFile(input))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Read the file header from `reader`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidCapture` if the header is not
+    /// recognized, malformed, or longer than [`MAX_HEADER_LEN`]
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut preamble = [0u8; 14];
+        reader.read_exact(&mut preamble)?;
+        if preamble[..8] != MAGIC {
+            return Err(invalid("not a binary capture".into()));
+        }
+        let version = u16::from_le_bytes([preamble[8], preamble[9]]);
+        if version != VERSION {
+            return Err(invalid(format!("unsupported version {}", version)));
+        }
+        let len = u32::from_le_bytes([preamble[10], preamble[11], preamble[12], preamble[13]]);
+        let len = usize::try_from(len)
+            .ok()
+            .filter(|&len| len <= MAX_HEADER_LEN)
+            .ok_or_else(|| {
+                invalid(format!(
+                    "header length {} exceeds {} bytes",
+                    len, MAX_HEADER_LEN
+                ))
+            })?;
+        let mut body = vec![0u8; len];
+        reader.read_exact(&mut body)?;
+        let (header, wall_clock) = CaptureHeader::decode(&body)?;
+        Ok(Self {
+            reader,
+            header,
+            wall_clock,
+            frame: Vec::new(),
+        })
+    }
+
+    /// Header of the capture
+    pub fn header(&self) -> &CaptureHeader {
+        &self.header
+    }
+
+    /// Wall-clock time the capture started
+    pub fn wall_clock(&self) -> SystemTime {
+        self.wall_clock
+    }
+
+    /// Read the next frame into `samples`, replacing its contents
+    ///
+    /// Timestamps are `start` plus the recorded offset.
+    ///
+    /// # Returns
+    ///
+    /// false at the end of the capture, including a final frame cut short
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidCapture` if a frame's CRC does not match
+    /// or it claims more than [`MAX_FRAME_SAMPLES`] samples
+    pub fn read_frame(&mut self, start: Instant, samples: &mut Vec<Sample>) -> Result<bool> {
+        samples.clear();
+        let mut prefix = [0u8; 8];
+        if !read_full(&mut self.reader, &mut prefix)? {
+            return Ok(false);
+        }
+        let count = u32::from_le_bytes([prefix[0], prefix[1], prefix[2], prefix[3]]);
+        let crc = u32::from_le_bytes([prefix[4], prefix[5], prefix[6], prefix[7]]);
+        let (count, len) = usize::try_from(count)
+            .ok()
+            .filter(|&count| count <= MAX_FRAME_SAMPLES)
+            .and_then(|count| Some((count, count.checked_mul(RECORD_LEN)?)))
+            .ok_or_else(|| {
+                invalid(format!(
+                    "frame of {} samples exceeds {}",
+                    count, MAX_FRAME_SAMPLES
+                ))
+            })?;
+        self.frame.resize(len, 0);
+        if !read_full(&mut self.reader, &mut self.frame)? {
+            log::warn!("Capture ends in a truncated frame of {} samples", count);
+            return Ok(false);
+        }
+        if crc32(&self.frame) != crc {
+            return Err(invalid("frame CRC mismatch".into()));
+        }
+
+        samples.reserve(count);
+        for record in self.frame.chunks_exact(RECORD_LEN) {
+            let word = |at: usize| [record[at], record[at + 1], record[at + 2], record[at + 3]];
+            let long =
+                |at: usize| u64::from_le_bytes(record[at..at + 8].try_into().unwrap_or_default());
+            let offset = long(0) as i64;
+            let magnitude = Duration::from_nanos(offset.unsigned_abs());
+            let timestamp = if offset >= 0 {
+                start + magnitude
+            } else {
+                start.checked_sub(magnitude).unwrap_or(start)
+            };
+            samples.push(Sample {
+                raw: i32::from_le_bytes(word(16)),
+                channel: record[28],
+                timestamp,
+                status: StatusFlags::from_bits(record[29]),
+                crc_ok: record[30] != 0,
+                dropped_since_last: u32::from_le_bytes(word(20)),
+                sequence: long(8),
+                generation: u32::from_le_bytes(word(24)),
+            });
+        }
+        Ok(true)
+    }
+
+    /// Iterate over all remaining samples, with timestamps relative to `start`
+    pub fn samples(self, start: Instant) -> CaptureSamples<R> {
+        CaptureSamples {
+            reader: self,
+            start,
+            frame: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the samples of a [`CaptureReader`]
+///
+/// Ends after the last frame or the first error.
+pub struct CaptureSamples<R: Read> {
+    reader: CaptureReader<R>,
+    start: Instant,
+    frame: std::vec::IntoIter<Sample>,
+    done: bool,
+}
+
+impl<R: Read> CaptureSamples<R> {
+    /// Header of the capture
+    pub fn header(&self) -> &CaptureHeader {
+        self.reader.header()
+    }
+}
+
+impl<R: Read> Iterator for CaptureSamples<R> {
+    type Item = Result<Sample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(sample) = self.frame.next() {
+                return Some(Ok(sample));
+            }
+            if self.done {
+                return None;
+            }
+            let mut samples = Vec::new();
+            match self.reader.read_frame(self.start, &mut samples) {
+                Ok(true) => self.frame = samples.into_iter(),
+                Ok(false) => self.done = true,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> std::iter::FusedIterator for CaptureSamples<R> {}
+
+const GAINS: [Gain; 7] = [
+    Gain::Gain1,
+    Gain::Gain2,
+    Gain::Gain4,
+    Gain::Gain8,
+    Gain::Gain16,
+    Gain::Gain32,
+    Gain::Gain64,
+];
+
+const DATA_RATES: [DataRate; 16] = [
+    DataRate::Sps2_5,
+    DataRate::Sps5,
+    DataRate::Sps10,
+    DataRate::Sps16_6,
+    DataRate::Sps20,
+    DataRate::Sps50,
+    DataRate::Sps60,
+    DataRate::Sps100,
+    DataRate::Sps400,
+    DataRate::Sps1200,
+    DataRate::Sps2400,
+    DataRate::Sps4800,
+    DataRate::Sps7200,
+    DataRate::Sps14400,
+    DataRate::Sps19200,
+    DataRate::Sps38400,
+];
+
+const FILTERS: [DigitalFilter; 5] = [
+    DigitalFilter::Sinc1,
+    DigitalFilter::Sinc2,
+    DigitalFilter::Sinc3,
+    DigitalFilter::Sinc4,
+    DigitalFilter::Fir,
+];
+
+const DELAYS: [Delay; 12] = [
+    Delay::Delay0,
+    Delay::Delay8_7us,
+    Delay::Delay17us,
+    Delay::Delay35us,
+    Delay::Delay169us,
+    Delay::Delay139us,
+    Delay::Delay278us,
+    Delay::Delay555us,
+    Delay::Delay1_1ms,
+    Delay::Delay2_2ms,
+    Delay::Delay4_4ms,
+    Delay::Delay8_8ms,
+];
+
+const REFERENCES: [ReferenceSource; 5] = [
+    ReferenceSource::Internal2_5V,
+    ReferenceSource::ExternalAin01,
+    ReferenceSource::ExternalAin23,
+    ReferenceSource::ExternalAin45,
+    ReferenceSource::AvddAvss,
+];
+
+/// Find the variant whose register value is `bits`
+fn lookup<T: Copy>(variants: &[T], bits: u8, code: impl Fn(T) -> u8, what: &str) -> Result<T> {
+    variants
+        .iter()
+        .copied()
+        .find(|&v| code(v) == bits)
+        .ok_or_else(|| invalid(format!("unknown {} 0x{:02X}", what, bits)))
+}
+
+fn invalid(msg: String) -> Ads1263Error {
+    Ads1263Error::InvalidCapture(msg)
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) -> Result<()> {
+    let len = u16::try_from(s.len()).map_err(|_| invalid(format!("string too long: '{}'", s)))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// Cursor over the header bytes
+struct Decoder<'a>(&'a [u8]);
+
+impl Decoder<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.0.len() < N {
+            return Err(invalid("header truncated".into()));
+        }
+        let (head, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(head.try_into().unwrap_or([0; N]))
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take()?))
+    }
+
+    fn str(&mut self) -> Result<String> {
+        let len = usize::from(self.u16()?);
+        if self.0.len() < len {
+            return Err(invalid("header truncated".into()));
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        String::from_utf8(head.to_vec()).map_err(|_| invalid("string is not UTF-8".into()))
+    }
+}
+
+/// Fill `buf` completely; false if the input ended first
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// CRC-32 (IEEE 802.3, as used by zip and PNG)
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_capture() -> Vec<u8> {
+        let header = CaptureHeader::new(Adc1Config::default(), ChannelMap::new(5.0));
+        CaptureWriter::new(Vec::new(), &header)
+            .unwrap()
+            .finish()
+            .unwrap()
+    }
+
+    #[test]
+    fn rejects_oversized_header_length() {
+        let mut bytes = empty_capture();
+        bytes[10..14].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            CaptureReader::new(&bytes[..]),
+            Err(Ads1263Error::InvalidCapture(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_oversized_frame() {
+        let mut bytes = empty_capture();
+        bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        let mut reader = CaptureReader::new(&bytes[..]).unwrap();
+        let mut samples = Vec::new();
+        assert!(matches!(
+            reader.read_frame(Instant::now(), &mut samples),
+            Err(Ads1263Error::InvalidCapture(_))
+        ));
+    }
+
+    #[test]
+    fn empty_capture_round_trips() {
+        let bytes = empty_capture();
+        let mut reader = CaptureReader::new(&bytes[..]).unwrap();
+        assert_eq!(reader.header().map, ChannelMap::new(5.0));
+        let mut samples = Vec::new();
+        assert!(!reader.read_frame(Instant::now(), &mut samples).unwrap());
+    }
+}
@@ -0,0 +1,227 @@
+//! Channel names, units, and scaling
+//!
+//! A [`ChannelMap`] gives each channel a name, an engineering-unit label,
+//! and a linear scale/offset from volts to that unit, e.g. a 0–5 V
+//! pressure transmitter to 0–100 kPa, optionally followed by a [`Curve`]
+//! for nonlinear sensors. Used as a [`Pipeline`](crate::Pipeline)
+//! stage it turns raw codes into physical values; sinks use it to label
+//! their output.
+
+use crate::linearize::Curve;
+use crate::pipeline::{Reading, Stage};
+use crate::sample::Sample;
+use std::borrow::Cow;
+
+/// Name, unit, and scaling of one channel
+///
+/// The physical value is `volts * scale + offset`, passed through
+/// `curve` if one is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelInfo {
+    /// Channel name, e.g. "inlet_pressure"
+    pub name: String,
+    /// Engineering unit of the scaled value, e.g. "kPa"
+    pub unit: String,
+    /// Unit per volt
+    pub scale: f64,
+    /// Value at 0 V
+    pub offset: f64,
+    /// Linearization applied after scale and offset
+    pub curve: Option<Curve>,
+    /// Other names the channel can be looked up by, e.g. "coolant"
+    pub aliases: Vec<String>,
+}
+
+impl ChannelInfo {
+    /// Channel reporting volts unchanged
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            unit: "V".to_string(),
+            scale: 1.0,
+            offset: 0.0,
+            curve: None,
+            aliases: Vec::new(),
+        }
+    }
+
+    /// Add a name the channel can also be looked up by
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Set the unit label
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Set scale and offset
+    pub fn linear(mut self, scale: f64, offset: f64) -> Self {
+        self.scale = scale;
+        self.offset = offset;
+        self
+    }
+
+    /// Set a linearization curve, applied after scale and offset
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+
+    /// Map the input range `volts` linearly onto `values`
+    ///
+    /// `ChannelInfo::new("p").unit("kPa").range((0.0, 5.0), (0.0, 100.0))`
+    /// gives 20 kPa/V. An empty input range leaves the scaling unchanged.
+    pub fn range(self, volts: (f64, f64), values: (f64, f64)) -> Self {
+        let span = volts.1 - volts.0;
+        if span == 0.0 {
+            return self;
+        }
+        let scale = (values.1 - values.0) / span;
+        self.linear(scale, values.0 - volts.0 * scale)
+    }
+
+    /// Convert volts to the channel's unit
+    pub fn apply(&self, volts: f64) -> f64 {
+        let value = volts * self.scale + self.offset;
+        match &self.curve {
+            Some(curve) => curve.apply(value),
+            None => value,
+        }
+    }
+}
+
+/// Per-channel names, units, and scaling
+///
+/// Channels without an entry are named `ch<n>` and reported in volts.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{ChannelInfo, ChannelMap, Pipeline, SamplesConfig};
+///
+/// let map = ChannelMap::new(5.0)
+///     .channel(0, ChannelInfo::new("inlet").unit("kPa").range((0.0, 5.0), (0.0, 100.0)));
+///
+/// let pipeline = Pipeline::new().average(16).stage(map.clone());
+/// for reading in pipeline.apply(adc.samples(0, SamplesConfig::default())).take(10) {
+///     let reading = reading?;
+///     println!("{}: {:.2} {}", map.name(0), reading.value, map.unit(0));
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelMap {
+    reference: f64,
+    channels: Vec<Option<ChannelInfo>>,
+}
+
+impl ChannelMap {
+    /// Create an empty map for ADC1 codes taken with `reference` volts
+    pub fn new(reference: f64) -> Self {
+        Self {
+            reference,
+            channels: Vec::new(),
+        }
+    }
+
+    /// Add or replace the entry of a channel
+    pub fn channel(mut self, channel: u8, info: ChannelInfo) -> Self {
+        self.insert(channel, info);
+        self
+    }
+
+    /// Add or replace the entry of a channel
+    pub fn insert(&mut self, channel: u8, info: ChannelInfo) {
+        let index = usize::from(channel);
+        if self.channels.len() <= index {
+            self.channels.resize_with(index + 1, || None);
+        }
+        self.channels[index] = Some(info);
+    }
+
+    /// Reference voltage used to convert codes to volts
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+
+    /// Entry of a channel, if configured
+    pub fn get(&self, channel: u8) -> Option<&ChannelInfo> {
+        self.channels.get(usize::from(channel))?.as_ref()
+    }
+
+    /// Configured channels in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &ChannelInfo)> {
+        self.channels
+            .iter()
+            .enumerate()
+            .filter_map(|(i, info)| Some((i as u8, info.as_ref()?)))
+    }
+
+    /// Name of a channel, `ch<n>` if not configured
+    pub fn name(&self, channel: u8) -> Cow<'_, str> {
+        match self.get(channel) {
+            Some(info) => Cow::Borrowed(&info.name),
+            None => Cow::Owned(format!("ch{}", channel)),
+        }
+    }
+
+    /// Channel called `key`
+    ///
+    /// `key` is a configured name or alias, `ch<n>`, or a bare channel
+    /// number, so users can refer to channels by name wherever a number
+    /// was accepted.
+    pub fn lookup(&self, key: &str) -> Option<u8> {
+        let key = key.trim();
+        let named = self
+            .iter()
+            .find(|(_, info)| info.name == key || info.aliases.iter().any(|alias| alias == key));
+        if let Some((channel, _)) = named {
+            return Some(channel);
+        }
+        key.strip_prefix("ch").unwrap_or(key).parse().ok()
+    }
+
+    /// Unit of a channel, `V` if not configured
+    pub fn unit(&self, channel: u8) -> &str {
+        self.get(channel).map_or("V", |info| &info.unit)
+    }
+
+    /// Convert a (possibly averaged) ADC1 code of a channel to its unit
+    pub fn convert(&self, channel: u8, code: f64) -> f64 {
+        let volts = codes_to_volts(code, self.reference);
+        match self.get(channel) {
+            Some(info) => info.apply(volts),
+            None => volts,
+        }
+    }
+
+    /// Convert a sample to its channel's unit
+    pub fn convert_sample(&self, sample: &Sample) -> f64 {
+        self.convert(sample.channel, f64::from(sample.raw))
+    }
+}
+
+impl Stage for ChannelMap {
+    /// Convert the reading from ADC codes to the channel's unit
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        out.push(Reading {
+            value: self.convert(input.channel, input.value),
+            ..input
+        });
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// Signed ADC1 code to volts, matching [`Ads1263::raw_to_voltage_adc1`](crate::Ads1263::raw_to_voltage_adc1)
+fn codes_to_volts(code: f64, reference: f64) -> f64 {
+    if code < 0.0 {
+        code / 2147483648.0 * reference
+    } else {
+        code / 2147483647.0 * reference
+    }
+}
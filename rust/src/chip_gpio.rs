@@ -0,0 +1,158 @@
+//! General-purpose I/O pins of the ADS1263 itself
+//!
+//! Eight analog inputs double as digital pins: GPIO0 to GPIO6 are AIN3 to
+//! AIN9 and GPIO7 is AINCOM. A pin is an analog input until it is given a
+//! [`GpioDirection`]; [`ChipGpio::release`] returns it to analog use. Levels
+//! are referred to the analog supply (AVDD/AVSS), not to the Pi's 3.3 V.
+//!
+//! The pins are reached through the driver's SPI connection with
+//! [`Ads1263::gpio`]. Their registers survive conversions but not a chip
+//! reset, so re-initializing ADC1 releases all pins.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::Register;
+
+/// GpioDirection of an on-chip GPIO pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpioDirection {
+    /// Level is read from the pin
+    Input,
+    /// Level is driven onto the pin
+    Output,
+}
+
+/// Handle on the ADS1263 GPIO pins, borrowed from the driver
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, GpioDirection, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// let mut gpio = adc.gpio();
+/// gpio.set_direction(0, GpioDirection::Output)?; // AIN3
+/// gpio.write(0, true)?;
+/// gpio.set_direction(1, GpioDirection::Input)?; // AIN4
+/// println!("GPIO1 is {}", if gpio.read(1)? { "high" } else { "low" });
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct ChipGpio<'a> {
+    adc: &'a mut Ads1263,
+}
+
+impl<'a> ChipGpio<'a> {
+    /// Number of pins
+    pub const PINS: u8 = 8;
+
+    pub(crate) fn new(adc: &'a mut Ads1263) -> Self {
+        Self { adc }
+    }
+
+    /// Analog input a GPIO pin shares, e.g. `AIN3` for pin 0
+    pub fn pin_name(pin: u8) -> &'static str {
+        match pin {
+            0 => "AIN3",
+            1 => "AIN4",
+            2 => "AIN5",
+            3 => "AIN6",
+            4 => "AIN7",
+            5 => "AIN8",
+            6 => "AIN9",
+            7 => "AINCOM",
+            _ => "?",
+        }
+    }
+
+    /// Connect `pin` to the GPIO logic as an input or output
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidGpio` for a pin above 7
+    pub fn set_direction(&mut self, pin: u8, direction: GpioDirection) -> Result<()> {
+        let mask = Self::mask(pin)?;
+        // GpioDirection first, so an input never drives the pin
+        let dir = self.adc.read_reg(Register::GpioDir)?;
+        let dir = match direction {
+            GpioDirection::Input => dir | mask,
+            GpioDirection::Output => dir & !mask,
+        };
+        self.adc.write_reg(Register::GpioDir, dir)?;
+        let con = self.adc.read_reg(Register::GpioCon)?;
+        self.adc.write_reg(Register::GpioCon, con | mask)?;
+        log::debug!(
+            "GPIO{} ({}) set to {:?}",
+            pin,
+            Self::pin_name(pin),
+            direction
+        );
+        Ok(())
+    }
+
+    /// Disconnect `pin` from the GPIO logic, leaving it an analog input
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidGpio` for a pin above 7
+    pub fn release(&mut self, pin: u8) -> Result<()> {
+        let mask = Self::mask(pin)?;
+        let con = self.adc.read_reg(Register::GpioCon)?;
+        self.adc.write_reg(Register::GpioCon, con & !mask)?;
+        log::debug!("GPIO{} ({}) released", pin, Self::pin_name(pin));
+        Ok(())
+    }
+
+    /// GpioDirection of `pin`, or `None` while it is an analog input
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidGpio` for a pin above 7
+    pub fn direction(&mut self, pin: u8) -> Result<Option<GpioDirection>> {
+        let mask = Self::mask(pin)?;
+        if self.adc.read_reg(Register::GpioCon)? & mask == 0 {
+            return Ok(None);
+        }
+        Ok(Some(if self.adc.read_reg(Register::GpioDir)? & mask != 0 {
+            GpioDirection::Input
+        } else {
+            GpioDirection::Output
+        }))
+    }
+
+    /// Level of `pin`: the input level, or the level driven by an output
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidGpio` for a pin above 7
+    pub fn read(&mut self, pin: u8) -> Result<bool> {
+        let mask = Self::mask(pin)?;
+        Ok(self.read_all()? & mask != 0)
+    }
+
+    /// Levels of all pins, GPIO0 in bit 0
+    pub fn read_all(&mut self) -> Result<u8> {
+        self.adc.read_reg(Register::GpioDat)
+    }
+
+    /// Drive `pin` high or low
+    ///
+    /// Only pins set to [`GpioDirection::Output`] follow the level.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidGpio` for a pin above 7
+    pub fn write(&mut self, pin: u8, high: bool) -> Result<()> {
+        let mask = Self::mask(pin)?;
+        let dat = self.adc.read_reg(Register::GpioDat)?;
+        let dat = if high { dat | mask } else { dat & !mask };
+        self.adc.write_reg(Register::GpioDat, dat)
+    }
+
+    fn mask(pin: u8) -> Result<u8> {
+        if pin < Self::PINS {
+            Ok(1 << pin)
+        } else {
+            Err(Ads1263Error::InvalidGpio(pin))
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Wall-clock alignment of sample timestamps
+//!
+//! Sample timestamps are monotonic `Instant`s: they never jump, but they
+//! have no relation to UTC and drift against it. A [`WallClock`] samples
+//! (`Instant`, `SystemTime`) pairs periodically, fits the rate between the
+//! two clocks over the recent pairs, and maps monotonic timestamps onto
+//! UTC with that drift removed, so captures can be merged with data from
+//! other instruments.
+//!
+//! The system clock is only as good as its synchronization. If PTP or NTP
+//! reports an offset between the system clock and the reference (e.g.
+//! `ptp4l`/`pmc` or `chronyc tracking`), pass it to
+//! [`WallClock::set_offset`] and it is removed from every mapped timestamp.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Settings of a [`WallClock`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClockConfig {
+    /// Time between clock correlations
+    pub interval: Duration,
+    /// Number of recent correlations the drift is fitted over
+    pub window: usize,
+    /// A correlation further than this from the prediction is treated as a
+    /// step of the system clock and restarts the fit
+    pub step_threshold: Duration,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            window: 30,
+            step_threshold: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Maps monotonic timestamps onto drift-corrected UTC
+///
+/// Call [`update`](Self::update) regularly (sinks do it on every write);
+/// it correlates the clocks once per configured interval.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{SamplesConfig, WallClock};
+///
+/// let mut clock = WallClock::new();
+/// clock.set_offset(-0.000_042); // system clock 42 µs behind PTP
+/// for sample in adc.samples(0, SamplesConfig::default()).take(100) {
+///     let sample = sample?;
+///     clock.update();
+///     println!("{:?} {}", clock.to_utc(sample.timestamp), sample.raw);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct WallClock {
+    config: ClockConfig,
+    /// Recent correlations, oldest first
+    points: VecDeque<(Instant, SystemTime)>,
+    /// System clock seconds per monotonic second
+    rate: f64,
+    /// System clock minus reference clock in seconds
+    offset: f64,
+}
+
+impl WallClock {
+    /// Create a clock with default settings, correlated once
+    pub fn new() -> Self {
+        Self::with_config(ClockConfig::default())
+    }
+
+    /// Create a clock with custom settings, correlated once
+    pub fn with_config(config: ClockConfig) -> Self {
+        let mut clock = Self {
+            config,
+            points: VecDeque::with_capacity(config.window.max(2)),
+            rate: 1.0,
+            offset: 0.0,
+        };
+        clock.correlate();
+        clock
+    }
+
+    /// Set the offset of the system clock from the reference clock
+    ///
+    /// Positive if the system clock is ahead, in seconds, as reported by
+    /// the PTP or NTP daemon. Subtracted from every mapped timestamp.
+    pub fn set_offset(&mut self, seconds: f64) {
+        self.offset = seconds;
+    }
+
+    /// Offset of the system clock from the reference clock in seconds
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Correlate the clocks if the configured interval has passed
+    pub fn update(&mut self) {
+        let due = self
+            .points
+            .back()
+            .is_none_or(|(at, _)| at.elapsed() >= self.config.interval);
+        if due {
+            self.correlate();
+        }
+    }
+
+    /// Correlate the clocks now
+    pub fn correlate(&mut self) {
+        // Bracket the system clock read and take the midpoint
+        let before = Instant::now();
+        let wall = SystemTime::now();
+        let after = Instant::now();
+        let mono = before + after.duration_since(before) / 2;
+
+        if !self.points.is_empty() {
+            let error = seconds_between(self.predict(mono), wall).abs();
+            if error > self.config.step_threshold.as_secs_f64() {
+                log::warn!(
+                    "System clock stepped by {:.3} s; restarting drift fit",
+                    error
+                );
+                self.points.clear();
+                self.rate = 1.0;
+            }
+        }
+        if self.points.len() >= self.config.window.max(2) {
+            self.points.pop_front();
+        }
+        self.points.push_back((mono, wall));
+        self.fit();
+    }
+
+    /// Drift of the system clock against the monotonic clock in ppm
+    pub fn drift_ppm(&self) -> f64 {
+        (self.rate - 1.0) * 1e6
+    }
+
+    /// Map a monotonic timestamp onto UTC
+    pub fn to_utc(&self, instant: Instant) -> SystemTime {
+        let local = self.predict(instant);
+        add_seconds(local, -self.offset)
+    }
+
+    /// Map a monotonic timestamp onto the system clock, without the offset
+    fn predict(&self, instant: Instant) -> SystemTime {
+        let Some(&(mono, wall)) = self.points.back() else {
+            return add_seconds(SystemTime::now(), -signed_elapsed(instant, Instant::now()));
+        };
+        add_seconds(wall, signed_elapsed(mono, instant) * self.rate)
+    }
+
+    /// Least-squares rate of the system clock over the stored correlations
+    fn fit(&mut self) {
+        let Some(&(mono0, wall0)) = self.points.front() else {
+            return;
+        };
+        let n = self.points.len() as f64;
+        if n < 2.0 {
+            return;
+        }
+        let xy: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|&(mono, wall)| {
+                (
+                    mono.duration_since(mono0).as_secs_f64(),
+                    seconds_between(wall0, wall),
+                )
+            })
+            .collect();
+        let mean_x = xy.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = xy.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let cov: f64 = xy.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let var: f64 = xy.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        if var > 0.0 {
+            self.rate = cov / var;
+        }
+    }
+}
+
+impl Default for WallClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Seconds from `from` to `to`, negative if `to` is earlier
+fn signed_elapsed(from: Instant, to: Instant) -> f64 {
+    match to.checked_duration_since(from) {
+        Some(d) => d.as_secs_f64(),
+        None => -from.duration_since(to).as_secs_f64(),
+    }
+}
+
+/// Seconds from `from` to `to`, negative if `to` is earlier
+fn seconds_between(from: SystemTime, to: SystemTime) -> f64 {
+    match to.duration_since(from) {
+        Ok(d) => d.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+/// Shift a system time by a signed number of seconds
+fn add_seconds(time: SystemTime, seconds: f64) -> SystemTime {
+    let shift = Duration::try_from_secs_f64(seconds.abs()).unwrap_or_default();
+    let shifted = if seconds >= 0.0 {
+        time.checked_add(shift)
+    } else {
+        time.checked_sub(shift)
+    };
+    shifted.unwrap_or(UNIX_EPOCH)
+}
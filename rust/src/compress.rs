@@ -0,0 +1,131 @@
+//! Zstandard compression for file sinks
+//!
+//! [`ZstdWriter`] compresses everything written through it as a sequence
+//! of independent zstd frames, one per `frame_bytes` of input and one per
+//! `flush`. The concatenated frames are an ordinary `.zst` stream that
+//! `zstd -d`, `zstdcat`, and [`CaptureReader::open`](crate::CaptureReader::open)
+//! read, and a process killed mid-recording loses at most the frame being
+//! collected.
+//!
+//! Wrap any [`Write`] target, or use the `create_zstd` constructors of
+//! [`CaptureWriter`](crate::CaptureWriter::create_zstd) and
+//! [`CsvSink`](crate::CsvSink::create_zstd).
+
+use crate::error::Result;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Default uncompressed size of one frame
+const DEFAULT_FRAME_BYTES: usize = 1 << 20;
+
+/// Default zstd compression level
+const DEFAULT_LEVEL: i32 = 3;
+
+/// Writer compressing into a stream of independent zstd frames
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{ChannelMap, CsvSink, ZstdWriter};
+///
+/// let zst = ZstdWriter::create("capture.csv.zst")?.level(9);
+/// let sink = CsvSink::new(zst, ChannelMap::new(5.0));
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct ZstdWriter<W: Write> {
+    writer: Option<W>,
+    /// Uncompressed data of the frame being collected
+    buffer: Vec<u8>,
+    frame_bytes: usize,
+    level: i32,
+}
+
+impl ZstdWriter<File> {
+    /// Create (or truncate) a compressed file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> ZstdWriter<W> {
+    /// Compress into `writer` at level 3 in frames of 1 MiB
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Some(writer),
+            buffer: Vec::new(),
+            frame_bytes: DEFAULT_FRAME_BYTES,
+            level: DEFAULT_LEVEL,
+        }
+    }
+
+    /// Set the compression level (1-22, default 3)
+    pub fn level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Set the uncompressed size of one frame (default 1 MiB)
+    ///
+    /// Smaller frames lose less data if the process is killed; larger
+    /// ones compress better.
+    pub fn frame_bytes(mut self, bytes: usize) -> Self {
+        self.frame_bytes = bytes.max(1);
+        self
+    }
+
+    /// Write the last frame, flush, and return the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails
+    pub fn finish(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.writer.take().expect("writer present until finished"))
+    }
+
+    /// Compress the collected data as one frame
+    fn write_frame(&mut self) -> io::Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let frame = zstd::bulk::compress(&self.buffer, self.level)?;
+        writer.write_all(&frame)?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for ZstdWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= self.frame_bytes {
+            self.write_frame()?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Write the collected data as a frame and flush the writer
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_frame()?;
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write> Drop for ZstdWriter<W> {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!("Failed to write last zstd frame: {}", e);
+        }
+    }
+}
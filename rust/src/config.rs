@@ -0,0 +1,2178 @@
+//! Configuration files
+//!
+//! [`Ads1263Config`] describes a complete deployment in one TOML or YAML
+//! file: HAT pins and SPI settings, the ADC1 and ADC2 setup, the channel
+//! map, sensors, sinks, and the acquisition itself. Loading validates the
+//! whole file before any hardware is touched, so a typo fails at startup
+//! with the offending key instead of producing wrong readings.
+//!
+//! ```toml
+//! [pins]
+//! drdy = 17
+//! start = 27
+//!
+//! [spi]
+//! clock_speed = 2_000_000
+//! wait = "interrupt"
+//!
+//! [adc1]
+//! mode = "single_ended"
+//! gain = 1
+//! data_rate = 400
+//! filter = "sinc4"
+//! vref = 5.08
+//!
+//! [[channels]]
+//! input = 0
+//! name = "inlet"
+//! unit = "kPa"
+//! range = { volts = [0.5, 4.5], values = [0.0, 100.0] }
+//!
+//! [[channels]]
+//! input = 3
+//! name = "level"
+//! aliases = ["tank"]
+//! unit = "%"
+//! curve = "table: 0=0, 1=40, 2.5=100"
+//!
+//! [[sensors]]
+//! type = "rtd"
+//! name = "oven"
+//! r_ref = 2000.0
+//!
+//! [[sensors]]
+//! type = "thermocouple"
+//! name = "kiln"
+//! thermocouple = "k"
+//! positive = 0
+//! negative = 1
+//!
+//! [[sinks]]
+//! type = "csv"
+//! path = "/var/log/ads1263/scan.csv"
+//! rotate = { max_duration = "1h", keep_files = 48 }
+//!
+//! [[sinks]]
+//! type = "udp"
+//! target = "239.0.0.1:5005"
+//!
+//! [acquisition]
+//! capacity = 256
+//! watchdog = { max_errors = 5 }
+//! ```
+//!
+//! The same keys are used in YAML. Durations are strings with a unit
+//! (`ns`, `us`, `ms`, `s`, `m`, `h`), e.g. `"250ms"`. Channels are scanned
+//! in the order they are listed. Channel names and `aliases` are accepted
+//! wherever a channel number is, see [`ChannelMap::lookup`].
+//!
+//! Requires the `config` feature.
+
+use crate::acquisition::{Acquisition, AcquisitionConfig, WatchdogConfig};
+use crate::ads1263::{Ads1263, ConversionControl, RtdWiring};
+use crate::bridge::{BridgeType, LoadCell, StrainGauge};
+use crate::capture::{CaptureHeader, CaptureWriter};
+use crate::channel_map::{ChannelInfo, ChannelMap};
+use crate::csv::CsvSink;
+use crate::current::{LoopCurrent, ShuntCurrent};
+use crate::error::{Ads1263Error, Result};
+use crate::hal::{CleanupPolicy, Hal, PinConfig, SpiConfig, WaitStrategy};
+use crate::jsonl::JsonLinesSink;
+use crate::linearize::Curve;
+use crate::registers::{
+    Adc1Config, Adc2DataRate, Adc2Gain, DataRate, Delay, DigitalFilter, Gain, IdacCurrent,
+    InputMode, MuxInput, ReferenceSource,
+};
+use crate::rotate::RotatingSink;
+use crate::rtd::{RtdAlpha, RtdConfig, RtdElement, RtdPins, RtdSensor};
+use crate::sensors::{RatiometricSensor, Sensor, SensorSetup};
+use crate::sink::Sink;
+#[cfg(feature = "sqlite")]
+use crate::sqlite::SqliteSink;
+use crate::thermistor::{Thermistor, ThermistorDivider, ThermistorModel};
+use crate::thermocouple::{Thermocouple, ThermocoupleType};
+use crate::udp::UdpSink;
+use crate::unix_socket::UnixSocketSink;
+#[cfg(feature = "websocket")]
+use crate::websocket::WebSocketSink;
+use rppal::spi::{Bus, SlaveSelect};
+use serde::{Deserialize, Deserializer};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// ADC2 setup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Adc2Config {
+    /// PGA gain
+    pub gain: Adc2Gain,
+    /// Output data rate
+    pub data_rate: Adc2DataRate,
+}
+
+/// Sensor read on demand, outside the scanned channels
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SensorConfig {
+    /// Load cell measured ratiometrically, see [`LoadCell`]
+    LoadCell {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input
+        negative: u8,
+        /// Rated capacity in `unit`, with `sensitivity`
+        capacity: Option<f64>,
+        /// Rated output in mV/V, with `capacity`
+        sensitivity: Option<f64>,
+        /// Scale in `unit` per mV/V, e.g. from a calibration, instead of
+        /// `capacity` and `sensitivity`
+        scale: Option<f64>,
+        /// Zero-load output in mV/V
+        #[serde(default)]
+        tare: f64,
+        /// Unit of the reading; `"kg"` when scaled, else `"mV/V"`
+        unit: Option<String>,
+        /// Reference sensing the excitation: `"avdd"`, `"ain0_ain1"`,
+        /// `"ain2_ain3"`, or `"ain4_ain5"`
+        #[serde(default, deserialize_with = "excitation_reference")]
+        excitation: ReferenceSource,
+        /// PGA gain
+        #[serde(default = "default_load_cell_gain", deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// 4–20 mA transmitter through a sense resistor, see [`LoopCurrent`]
+    LoopCurrent {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input, AINCOM if omitted
+        negative: Option<u8>,
+        /// Sense resistor in ohms
+        sense: f64,
+        /// Value at 4 mA
+        #[serde(default = "default_loop_zero")]
+        zero: f64,
+        /// Change in value from 4 to 20 mA
+        #[serde(default = "default_loop_span")]
+        span: f64,
+        /// Unit of the reading
+        #[serde(default = "default_loop_unit")]
+        unit: String,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// Input as a fraction of its excitation, see [`RatiometricSensor`]
+    Ratiometric {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input, AINCOM if omitted
+        negative: Option<u8>,
+        /// Reference sensing the excitation: `"avdd"`, `"ain0_ain1"`,
+        /// `"ain2_ain3"`, or `"ain4_ain5"`
+        #[serde(default, deserialize_with = "excitation_reference")]
+        excitation: ReferenceSource,
+        /// Unit per ratio
+        #[serde(default = "default_ratio_scale")]
+        scale: f64,
+        /// Value at a ratio of 0
+        #[serde(default)]
+        offset: f64,
+        /// Unit of the reading
+        #[serde(default = "default_ratio_unit")]
+        unit: String,
+        /// PGA gain
+        #[serde(default, deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// RTD on the HAT's RTD circuit unless given other inputs, see
+    /// [`RtdSensor`] and [`RtdConfig`]
+    Rtd {
+        /// Sensor name
+        name: String,
+        /// Resistance at 0 °C in ohms, e.g. 1000 for a PT1000
+        #[serde(default = "default_r0")]
+        r0: f64,
+        /// Temperature coefficient: 385, 3911, or 3916 for α = 0.00385/K,
+        /// 0.003911/K, or 0.003916/K
+        #[serde(default, deserialize_with = "rtd_alpha")]
+        alpha: RtdAlpha,
+        /// Number of leads: 2, 3, or 4
+        #[serde(default, deserialize_with = "rtd_wiring")]
+        wires: RtdWiring,
+        /// AIN input of IDAC1, the force lead in 4-wire connection; AIN3 if
+        /// omitted
+        excitation: Option<u8>,
+        /// AIN input of IDAC2 for 3-wire connection; AINCOM if omitted
+        compensation: Option<u8>,
+        /// Positive sense input; AIN7 if omitted
+        positive: Option<u8>,
+        /// Negative sense input; AIN6 if omitted
+        negative: Option<u8>,
+        /// Reference input pair across the reference resistor:
+        /// `"ain0_ain1"`, `"ain2_ain3"`, or `"ain4_ain5"`
+        #[serde(default = "default_rtd_reference", deserialize_with = "rtd_reference")]
+        reference: ReferenceSource,
+        /// Excitation current in µA
+        #[serde(default = "default_rtd_idac", deserialize_with = "idac_current")]
+        idac: IdacCurrent,
+        /// Reference resistor in ohms
+        #[serde(default = "default_r_ref")]
+        r_ref: f64,
+        /// PGA gain
+        #[serde(default, deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+        /// Conversion start delay
+        #[serde(default = "default_rtd_delay", deserialize_with = "delay")]
+        delay: Delay,
+    },
+    /// Current through a shunt resistor in amps, see [`ShuntCurrent`]
+    ShuntCurrent {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input
+        negative: u8,
+        /// Shunt resistance in ohms
+        shunt: f64,
+        /// Largest expected current in amps
+        rated: Option<f64>,
+        /// Largest burden voltage the measured circuit tolerates
+        max_burden: Option<f64>,
+        /// PGA gain
+        #[serde(default, deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// Strain gauge bridge in µε, see [`StrainGauge`]
+    StrainGauge {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input
+        negative: u8,
+        /// Arrangement: `"quarter"`, `"half_bending"`, `"half_poisson"`,
+        /// `"full_bending"`, or `"full_poisson"`
+        #[serde(deserialize_with = "bridge_type")]
+        bridge: BridgeType,
+        /// Poisson's ratio of the Poisson arrangements
+        poisson: Option<f64>,
+        /// Gauge factor
+        #[serde(default = "default_gauge_factor")]
+        gauge_factor: f64,
+        /// Unstrained output in mV/V
+        #[serde(default)]
+        zero: f64,
+        /// Lead resistance in series with a quarter-bridge gauge in ohms
+        lead_resistance: Option<f64>,
+        /// Gauge resistance in ohms, for `lead_resistance`
+        #[serde(default = "default_gauge_resistance")]
+        gauge_resistance: f64,
+        /// Reference sensing the excitation: `"avdd"`, `"ain0_ain1"`,
+        /// `"ain2_ain3"`, or `"ain4_ain5"`
+        #[serde(default, deserialize_with = "excitation_reference")]
+        excitation: ReferenceSource,
+        /// PGA gain
+        #[serde(default = "default_load_cell_gain", deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// NTC thermistor in a voltage divider, see [`Thermistor`]
+    Thermistor {
+        /// Sensor name
+        name: String,
+        /// Positive AIN input, the divider midpoint
+        positive: u8,
+        /// Negative AIN input, AINCOM if omitted
+        negative: Option<u8>,
+        /// β in kelvins, with `r0`
+        beta: Option<f64>,
+        /// Resistance at `t0` in ohms, for `beta`
+        r0: Option<f64>,
+        /// Temperature of `r0` in °C
+        #[serde(default = "default_thermistor_t0")]
+        t0: f64,
+        /// Steinhart–Hart coefficients `[a, b, c]` instead of `beta`
+        steinhart_hart: Option<[f64; 3]>,
+        /// Fixed divider resistor in ohms
+        r_fixed: f64,
+        /// Thermistor position in the divider: `"low"` (to ground) or
+        /// `"high"` (to the excitation)
+        #[serde(default, rename = "side", deserialize_with = "thermistor_side")]
+        high_side: bool,
+        /// Divider supply in volts, the reference if omitted
+        excitation: Option<f64>,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+    },
+    /// Thermocouple with cold-junction compensation, see [`Thermocouple`]
+    Thermocouple {
+        /// Sensor name
+        name: String,
+        /// Type letter, e.g. `"k"`
+        #[serde(deserialize_with = "thermocouple_type")]
+        thermocouple: ThermocoupleType,
+        /// Positive AIN input
+        positive: u8,
+        /// Negative AIN input, AINCOM if omitted
+        negative: Option<u8>,
+        /// PGA gain
+        #[serde(default = "default_thermocouple_gain", deserialize_with = "gain")]
+        gain: Gain,
+        /// Output data rate
+        #[serde(default = "default_sensor_rate", deserialize_with = "data_rate")]
+        data_rate: DataRate,
+        /// Bias AINCOM to mid-supply
+        #[serde(default)]
+        vbias: bool,
+        /// Fixed cold-junction temperature in °C instead of the internal
+        /// temperature sensor
+        cold_junction: Option<f64>,
+    },
+}
+
+fn default_load_cell_gain() -> Gain {
+    Gain::Gain64
+}
+
+fn default_gauge_factor() -> f64 {
+    2.0
+}
+
+fn default_gauge_resistance() -> f64 {
+    350.0
+}
+
+/// Poisson's ratio of steel, for Poisson bridges without `poisson`
+const DEFAULT_POISSON: f64 = 0.3;
+
+fn default_loop_zero() -> f64 {
+    4.0
+}
+
+fn default_loop_span() -> f64 {
+    16.0
+}
+
+fn default_loop_unit() -> String {
+    "mA".to_string()
+}
+
+fn default_ratio_scale() -> f64 {
+    1.0
+}
+
+fn default_ratio_unit() -> String {
+    "V/V".to_string()
+}
+
+fn default_r0() -> f64 {
+    100.0
+}
+
+fn default_r_ref() -> f64 {
+    2000.0
+}
+
+fn default_rtd_reference() -> ReferenceSource {
+    ReferenceSource::ExternalAin45
+}
+
+fn default_rtd_idac() -> IdacCurrent {
+    IdacCurrent::Ua250
+}
+
+fn default_sensor_rate() -> DataRate {
+    DataRate::Sps20
+}
+
+fn default_rtd_delay() -> Delay {
+    Delay::Delay8_8ms
+}
+
+fn default_thermistor_t0() -> f64 {
+    25.0
+}
+
+fn default_thermocouple_gain() -> Gain {
+    Gain::Gain32
+}
+
+impl SensorConfig {
+    /// Name of the sensor
+    pub fn name(&self) -> &str {
+        match self {
+            SensorConfig::LoadCell { name, .. }
+            | SensorConfig::LoopCurrent { name, .. }
+            | SensorConfig::Ratiometric { name, .. }
+            | SensorConfig::Rtd { name, .. }
+            | SensorConfig::ShuntCurrent { name, .. }
+            | SensorConfig::StrainGauge { name, .. }
+            | SensorConfig::Thermistor { name, .. }
+            | SensorConfig::Thermocouple { name, .. } => name,
+        }
+    }
+
+    /// Unit of [`read`](Self::read)
+    pub fn unit(&self) -> &str {
+        match self {
+            SensorConfig::LoadCell {
+                capacity,
+                scale,
+                unit,
+                ..
+            } => match unit {
+                Some(unit) => unit,
+                None if capacity.is_some() || scale.is_some() => "kg",
+                None => "mV/V",
+            },
+            SensorConfig::LoopCurrent { unit, .. } | SensorConfig::Ratiometric { unit, .. } => unit,
+            SensorConfig::ShuntCurrent { .. } => "A",
+            SensorConfig::StrainGauge { .. } => "µε",
+            SensorConfig::Rtd { .. }
+            | SensorConfig::Thermistor { .. }
+            | SensorConfig::Thermocouple { .. } => "°C",
+        }
+    }
+
+    /// Take one reading in the sensor's unit
+    ///
+    /// Reconfigures ADC1; re-apply the channel setup with
+    /// [`Ads1263::configure_adc1`] before scanning again.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the underlying read
+    pub fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        match *self {
+            SensorConfig::LoadCell { .. } => self.load_cell()?.read(adc),
+            SensorConfig::LoopCurrent { .. } => self.loop_current()?.read(adc),
+            SensorConfig::Ratiometric {
+                ref unit,
+                positive,
+                negative,
+                excitation,
+                scale,
+                offset,
+                gain,
+                data_rate,
+                ..
+            } => {
+                let negative = negative.map_or(MuxInput::AinCom, MuxInput::Ain);
+                let setup = SensorSetup::new(MuxInput::Ain(positive), negative)
+                    .gain(gain)
+                    .data_rate(data_rate)
+                    .ratiometric(excitation);
+                RatiometricSensor::with_setup(setup)
+                    .linear(scale, offset, unit)
+                    .read(adc)
+            }
+            SensorConfig::Rtd {
+                r0,
+                alpha,
+                gain,
+                data_rate,
+                delay,
+                ..
+            } => RtdSensor::new(RtdElement::new(r0, alpha))
+                .config(self.rtd_config().unwrap_or_default())
+                .gain(gain)
+                .data_rate(data_rate)
+                .delay(delay)
+                .read(adc),
+            SensorConfig::ShuntCurrent { .. } => self.shunt_current()?.read(adc),
+            SensorConfig::StrainGauge {
+                positive,
+                negative,
+                bridge,
+                poisson,
+                gauge_factor,
+                zero,
+                lead_resistance,
+                gauge_resistance,
+                excitation,
+                gain,
+                data_rate,
+                ..
+            } => {
+                let bridge = match (bridge, poisson) {
+                    (BridgeType::HalfPoisson(_), Some(nu)) => BridgeType::HalfPoisson(nu),
+                    (BridgeType::FullPoisson(_), Some(nu)) => BridgeType::FullPoisson(nu),
+                    (bridge, _) => bridge,
+                };
+                let setup = SensorSetup::new(MuxInput::Ain(positive), MuxInput::Ain(negative))
+                    .gain(gain)
+                    .data_rate(data_rate)
+                    .reference(excitation, DEFAULT_VREF);
+                let mut sensor =
+                    StrainGauge::with_setup(bridge, gauge_factor, setup).zero_offset(zero);
+                if let Some(ohms) = lead_resistance {
+                    sensor = sensor.lead_resistance(ohms, gauge_resistance);
+                }
+                sensor.read(adc)
+            }
+            SensorConfig::Thermistor {
+                positive,
+                negative,
+                r_fixed,
+                high_side,
+                excitation,
+                data_rate,
+                ..
+            } => {
+                let negative = negative.map_or(MuxInput::AinCom, MuxInput::Ain);
+                let setup =
+                    SensorSetup::new(MuxInput::Ain(positive), negative).data_rate(data_rate);
+                let divider = if high_side {
+                    ThermistorDivider::HighSide { r_fixed }
+                } else {
+                    ThermistorDivider::LowSide { r_fixed }
+                };
+                let mut sensor = Thermistor::with_setup(self.thermistor_model()?, divider, setup);
+                if let Some(volts) = excitation {
+                    sensor = sensor.excitation(volts);
+                }
+                sensor.read(adc)
+            }
+            SensorConfig::Thermocouple {
+                thermocouple,
+                positive,
+                negative,
+                gain,
+                data_rate,
+                vbias,
+                cold_junction,
+                ..
+            } => {
+                let negative = negative.map_or(MuxInput::AinCom, MuxInput::Ain);
+                let setup = SensorSetup::new(MuxInput::Ain(positive), negative)
+                    .gain(gain)
+                    .data_rate(data_rate)
+                    .reference(ReferenceSource::Internal2_5V, 2.5)
+                    .vbias(vbias);
+                let mut sensor = Thermocouple::with_setup(thermocouple, setup);
+                if let Some(celsius) = cold_junction {
+                    sensor = sensor.cold_junction(celsius);
+                }
+                sensor.read(adc)
+            }
+        }
+    }
+
+    /// RTD circuit of an RTD sensor
+    fn rtd_config(&self) -> Option<RtdConfig> {
+        let SensorConfig::Rtd {
+            wires,
+            excitation,
+            compensation,
+            positive,
+            negative,
+            reference,
+            idac,
+            r_ref,
+            ..
+        } = *self
+        else {
+            return None;
+        };
+        let hat = RtdPins::HAT;
+        let pin = |input: Option<u8>, default| input.map_or(default, MuxInput::Ain);
+        let pins = RtdPins {
+            excitation: pin(excitation, hat.excitation),
+            compensation: pin(compensation, hat.compensation),
+            positive: pin(positive, hat.positive),
+            negative: pin(negative, hat.negative),
+        };
+        Some(RtdConfig {
+            wiring: wires,
+            pins,
+            reference,
+            idac,
+            r_ref,
+        })
+    }
+
+    /// Load cell of a load cell sensor, scaled by `scale`, or by
+    /// `capacity` and `sensitivity`
+    fn load_cell(&self) -> Result<LoadCell> {
+        let SensorConfig::LoadCell {
+            name,
+            positive,
+            negative,
+            capacity,
+            sensitivity,
+            scale,
+            tare,
+            excitation,
+            gain,
+            data_rate,
+            ..
+        } = self
+        else {
+            return Err(invalid(format!(
+                "sensor '{}' is not a load cell",
+                self.name()
+            )));
+        };
+        let scale = match (scale, capacity, sensitivity) {
+            (Some(scale), None, None) => Some(*scale),
+            (None, Some(capacity), Some(sensitivity)) => Some(capacity / sensitivity),
+            (None, None, None) => None,
+            _ => {
+                return Err(invalid(format!(
+                    "sensor '{}': give either scale, or capacity and sensitivity",
+                    name
+                )));
+            }
+        };
+        let setup = SensorSetup::new(MuxInput::Ain(*positive), MuxInput::Ain(*negative))
+            .gain(*gain)
+            .data_rate(*data_rate)
+            .reference(*excitation, DEFAULT_VREF);
+        let sensor = LoadCell::with_setup(setup).tare_offset(*tare);
+        Ok(match scale {
+            Some(scale) => sensor.scale(scale, self.unit()),
+            None => sensor,
+        })
+    }
+
+    /// Loop of a 4–20 mA sensor
+    fn loop_current(&self) -> Result<LoopCurrent> {
+        let SensorConfig::LoopCurrent {
+            positive,
+            negative,
+            sense,
+            zero,
+            span,
+            ref unit,
+            data_rate,
+            ..
+        } = *self
+        else {
+            return Err(invalid(format!(
+                "sensor '{}' is not a current loop",
+                self.name()
+            )));
+        };
+        let negative = negative.map_or(MuxInput::AinCom, MuxInput::Ain);
+        Ok(LoopCurrent::new(MuxInput::Ain(positive), negative, sense)
+            .scale(zero, span, unit)
+            .data_rate(data_rate))
+    }
+
+    /// Shunt of a shunt current sensor
+    fn shunt_current(&self) -> Result<ShuntCurrent> {
+        let SensorConfig::ShuntCurrent {
+            positive,
+            negative,
+            shunt,
+            rated,
+            max_burden,
+            gain,
+            data_rate,
+            ..
+        } = *self
+        else {
+            return Err(invalid(format!("sensor '{}' is not a shunt", self.name())));
+        };
+        let mut sensor = ShuntCurrent::new(MuxInput::Ain(positive), MuxInput::Ain(negative), shunt)
+            .gain(gain)
+            .data_rate(data_rate);
+        if let Some(amps) = rated {
+            sensor = sensor.rated(amps);
+        }
+        if let Some(volts) = max_burden {
+            sensor = sensor.max_burden(volts);
+        }
+        Ok(sensor)
+    }
+
+    /// Model of a thermistor sensor from `beta` and `r0`, or
+    /// `steinhart_hart`
+    fn thermistor_model(&self) -> Result<ThermistorModel> {
+        let SensorConfig::Thermistor {
+            name,
+            beta,
+            r0,
+            t0,
+            steinhart_hart,
+            ..
+        } = self
+        else {
+            return Err(invalid(format!(
+                "sensor '{}' is not a thermistor",
+                self.name()
+            )));
+        };
+        match (beta, r0, steinhart_hart) {
+            (Some(beta), Some(r0), None) => Ok(ThermistorModel::Beta {
+                r0: *r0,
+                t0: *t0,
+                beta: *beta,
+            }),
+            (None, None, Some([a, b, c])) => Ok(ThermistorModel::steinhart_hart(*a, *b, *c)),
+            _ => Err(invalid(format!(
+                "sensor '{}': give either beta and r0, or steinhart_hart",
+                name
+            ))),
+        }
+    }
+
+    /// Check the inputs and resistances of the sensor
+    fn validate(&self) -> Result<()> {
+        match self {
+            SensorConfig::LoadCell {
+                name,
+                positive,
+                negative,
+                sensitivity,
+                ..
+            } => {
+                for input in [positive, negative] {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                if positive == negative {
+                    return Err(invalid(format!(
+                        "sensor '{}': both inputs are {}",
+                        name, positive
+                    )));
+                }
+                if let Some(mv_per_v) = sensitivity
+                    && (!mv_per_v.is_finite() || *mv_per_v <= 0.0)
+                {
+                    return Err(invalid(format!(
+                        "sensor '{}': sensitivity must be positive, got {}",
+                        name, mv_per_v
+                    )));
+                }
+                self.load_cell()?;
+            }
+            SensorConfig::Rtd {
+                name,
+                r0,
+                r_ref,
+                excitation,
+                compensation,
+                positive,
+                negative,
+                ..
+            } => {
+                for (key, ohms) in [("r0", r0), ("r_ref", r_ref)] {
+                    if !ohms.is_finite() || *ohms <= 0.0 {
+                        return Err(invalid(format!(
+                            "sensor '{}': {} must be positive, got {}",
+                            name, key, ohms
+                        )));
+                    }
+                }
+                let inputs = [excitation, compensation, positive, negative];
+                for input in inputs.into_iter().flatten() {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                if let Some(config) = self.rtd_config() {
+                    config.validate().map_err(|error| in_sensor(name, error))?;
+                }
+            }
+            SensorConfig::LoopCurrent {
+                name,
+                positive,
+                negative,
+                span,
+                ..
+            } => {
+                for input in std::iter::once(positive).chain(negative) {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                if *span == 0.0 {
+                    return Err(invalid(format!("sensor '{}': span must not be 0", name)));
+                }
+                self.loop_current()?
+                    .validate()
+                    .map_err(|error| in_sensor(name, error))?;
+            }
+            SensorConfig::Ratiometric {
+                name,
+                positive,
+                negative,
+                ..
+            } => {
+                for input in std::iter::once(positive).chain(negative) {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+            }
+            SensorConfig::ShuntCurrent {
+                name,
+                positive,
+                negative,
+                ..
+            } => {
+                for input in [positive, negative] {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                if positive == negative {
+                    return Err(invalid(format!(
+                        "sensor '{}': both inputs are {}",
+                        name, positive
+                    )));
+                }
+                self.shunt_current()?
+                    .validate()
+                    .map_err(|error| in_sensor(name, error))?;
+            }
+            SensorConfig::StrainGauge {
+                name,
+                positive,
+                negative,
+                bridge,
+                poisson,
+                gauge_factor,
+                lead_resistance,
+                gauge_resistance,
+                ..
+            } => {
+                for input in [positive, negative] {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                if positive == negative {
+                    return Err(invalid(format!(
+                        "sensor '{}': both inputs are {}",
+                        name, positive
+                    )));
+                }
+                if let Some(nu) = poisson {
+                    if !matches!(
+                        bridge,
+                        BridgeType::HalfPoisson(_) | BridgeType::FullPoisson(_)
+                    ) {
+                        return Err(invalid(format!(
+                            "sensor '{}': poisson applies to half_poisson and full_poisson",
+                            name
+                        )));
+                    }
+                    if !(0.0..0.5).contains(nu) {
+                        return Err(invalid(format!(
+                            "sensor '{}': poisson must be in 0 to 0.5, got {}",
+                            name, nu
+                        )));
+                    }
+                }
+                let positive = [
+                    ("gauge_factor", Some(*gauge_factor)),
+                    ("gauge_resistance", Some(*gauge_resistance)),
+                    ("lead_resistance", *lead_resistance),
+                ];
+                for (key, value) in positive {
+                    if let Some(value) = value
+                        && (!value.is_finite() || value <= 0.0)
+                    {
+                        return Err(invalid(format!(
+                            "sensor '{}': {} must be positive, got {}",
+                            name, key, value
+                        )));
+                    }
+                }
+            }
+            SensorConfig::Thermistor {
+                name,
+                positive,
+                negative,
+                beta,
+                r0,
+                r_fixed,
+                excitation,
+                ..
+            } => {
+                for input in std::iter::once(positive).chain(negative) {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+                self.thermistor_model()?;
+                let positive = [
+                    ("beta", *beta),
+                    ("r0", *r0),
+                    ("r_fixed", Some(*r_fixed)),
+                    ("excitation", *excitation),
+                ];
+                for (key, value) in positive {
+                    if let Some(value) = value
+                        && (!value.is_finite() || value <= 0.0)
+                    {
+                        return Err(invalid(format!(
+                            "sensor '{}': {} must be positive, got {}",
+                            name, key, value
+                        )));
+                    }
+                }
+            }
+            SensorConfig::Thermocouple {
+                name,
+                positive,
+                negative,
+                ..
+            } => {
+                for input in std::iter::once(positive).chain(negative) {
+                    if *input > 9 {
+                        return Err(invalid(format!(
+                            "sensor '{}': input {} out of range (max 9)",
+                            name, input
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prefix a validation error with the sensor it belongs to
+fn in_sensor(name: &str, error: Ads1263Error) -> Ads1263Error {
+    match error {
+        Ads1263Error::InvalidConfig(message) => invalid(format!("sensor '{}': {}", name, message)),
+        error => error,
+    }
+}
+
+/// File rotation of a file sink, see [`RotatingSink`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rotation {
+    /// Start a new file once the current one reaches this size
+    pub max_bytes: Option<u64>,
+    /// Start a new file once the current one has been written this long
+    #[serde(deserialize_with = "optional_duration")]
+    pub max_duration: Option<Duration>,
+    /// Delete the oldest files beyond this many
+    pub keep_files: Option<usize>,
+}
+
+/// Destination of the acquired scans
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SinkConfig {
+    /// Delimited text, see [`CsvSink`]
+    Csv {
+        /// Output file
+        path: PathBuf,
+        /// Field delimiter
+        #[serde(default = "default_delimiter")]
+        delimiter: char,
+        /// File rotation
+        rotate: Option<Rotation>,
+    },
+    /// JSON Lines, see [`JsonLinesSink`]
+    Jsonl {
+        /// Output file
+        path: PathBuf,
+        /// One object per scan instead of one per sample
+        #[serde(default)]
+        per_scan: bool,
+        /// File rotation
+        rotate: Option<Rotation>,
+    },
+    /// Binary capture file, see [`CaptureWriter`]
+    Capture {
+        /// Output file
+        path: PathBuf,
+        /// File rotation
+        rotate: Option<Rotation>,
+    },
+    /// Sample packets to a UDP address, see [`UdpSink`]
+    Udp {
+        /// Unicast or multicast address, e.g. `239.0.0.1:5005`
+        target: String,
+    },
+    /// Sample frames on a Unix domain socket, see [`UnixSocketSink`]
+    UnixSocket {
+        /// Socket path
+        path: PathBuf,
+    },
+    /// WebSocket server, see [`WebSocketSink`]
+    #[cfg(feature = "websocket")]
+    Websocket {
+        /// Listen address, e.g. `0.0.0.0:8080`
+        bind: String,
+    },
+    /// SQLite database, see [`SqliteSink`]
+    #[cfg(feature = "sqlite")]
+    Sqlite {
+        /// Database file
+        path: PathBuf,
+    },
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+impl SinkConfig {
+    /// Create the sink for the device described by `header`
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the sink's constructor, e.g. `Ads1263Error::Io`
+    /// if a file cannot be created
+    pub fn build(&self, header: &CaptureHeader) -> Result<Box<dyn Sink>> {
+        let map = header.map.clone();
+        Ok(match self {
+            SinkConfig::Csv {
+                path,
+                delimiter,
+                rotate,
+            } => {
+                let delimiter = *delimiter;
+                let make = move |path: &Path| {
+                    CsvSink::create(path, map.clone()).map(|sink| sink.delimiter(delimiter))
+                };
+                rotated(path, rotate.as_ref(), make)?
+            }
+            SinkConfig::Jsonl {
+                path,
+                per_scan,
+                rotate,
+            } => {
+                let per_scan = *per_scan;
+                let make = move |path: &Path| {
+                    JsonLinesSink::create(path, map.clone()).map(|sink| sink.per_scan(per_scan))
+                };
+                rotated(path, rotate.as_ref(), make)?
+            }
+            SinkConfig::Capture { path, rotate } => {
+                let header = header.clone();
+                let make = move |path: &Path| CaptureWriter::create(path, &header);
+                rotated(path, rotate.as_ref(), make)?
+            }
+            SinkConfig::Udp { target } => Box::new(UdpSink::new(target.as_str(), map)?),
+            SinkConfig::UnixSocket { path } => Box::new(UnixSocketSink::bind(path, map)?),
+            #[cfg(feature = "websocket")]
+            SinkConfig::Websocket { bind } => Box::new(WebSocketSink::bind(bind.as_str(), map)?),
+            #[cfg(feature = "sqlite")]
+            SinkConfig::Sqlite { path } => Box::new(SqliteSink::open(path, header)?),
+        })
+    }
+}
+
+/// File sink created by `make`, rotated if `rotate` is set
+fn rotated<S, F>(path: &Path, rotate: Option<&Rotation>, mut make: F) -> Result<Box<dyn Sink>>
+where
+    S: Sink + 'static,
+    F: FnMut(&Path) -> Result<S> + Send + 'static,
+{
+    let Some(rotate) = rotate else {
+        return Ok(Box::new(make(path)?));
+    };
+    let mut sink = RotatingSink::new(path, make)?;
+    if let Some(bytes) = rotate.max_bytes {
+        sink = sink.max_bytes(bytes);
+    }
+    if let Some(duration) = rotate.max_duration {
+        sink = sink.max_duration(duration);
+    }
+    if let Some(files) = rotate.keep_files {
+        sink = sink.keep_files(files);
+    }
+    Ok(Box::new(sink))
+}
+
+/// Complete setup of a board, its channels, and where the data goes
+///
+/// Load one with [`from_file`](Self::from_file), adjust fields if needed,
+/// and [`start`](Self::start) the acquisition; or use
+/// [`open`](Self::open) and the other building blocks individually.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::Ads1263Config;
+///
+/// let config = Ads1263Config::from_file("/etc/ads1263.toml")?;
+/// let acq = config.start()?;
+///
+/// // Runs until a sink or the device fails
+/// if let Some(Err(e)) = acq.recv() {
+///     eprintln!("Acquisition stopped: {}", e);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ads1263Config {
+    /// HAT pin assignment
+    pub pins: PinConfig,
+    /// SPI bus settings
+    pub spi: SpiConfig,
+    /// How DRDY is awaited
+    pub wait: WaitStrategy,
+    /// How ADC1 conversions are started and stopped
+    pub control: ConversionControl,
+    /// Input mode of the scanned channels
+    pub mode: InputMode,
+    /// ADC1 setup
+    pub adc1: Adc1Config,
+    /// ADC2 setup; `None` leaves ADC2 off
+    pub adc2: Option<Adc2Config>,
+    /// Names, units, and scaling of the channels, with the reference voltage
+    pub map: ChannelMap,
+    /// Channels to scan, in order
+    pub channels: Vec<u8>,
+    /// Sensors read on demand
+    pub sensors: Vec<SensorConfig>,
+    /// Destinations of the acquired scans
+    pub sinks: Vec<SinkConfig>,
+    /// Background acquisition settings
+    pub acquisition: AcquisitionConfig,
+}
+
+impl Default for Ads1263Config {
+    /// Waveshare HAT defaults, 5 V reference, no channels or sinks
+    fn default() -> Self {
+        Self {
+            pins: PinConfig::default(),
+            spi: SpiConfig::default(),
+            wait: WaitStrategy::default(),
+            control: ConversionControl::default(),
+            mode: InputMode::default(),
+            adc1: Adc1Config::default(),
+            adc2: None,
+            map: ChannelMap::new(DEFAULT_VREF),
+            channels: Vec::new(),
+            sensors: Vec::new(),
+            sinks: Vec::new(),
+            acquisition: AcquisitionConfig::default(),
+        }
+    }
+}
+
+/// Reference voltage if the file sets none
+const DEFAULT_VREF: f64 = 5.0;
+
+impl Ads1263Config {
+    /// Load and validate a configuration file
+    ///
+    /// The format follows the extension: `.toml`, or `.yaml`/`.yml`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be read and
+    /// `Ads1263Error::InvalidConfig` if it does not parse or validate
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        match extension.to_ascii_lowercase().as_str() {
+            "toml" => Self::from_toml(&text),
+            "yaml" | "yml" => Self::from_yaml(&text),
+            _ => Err(invalid(format!(
+                "{}: unknown format, expected .toml, .yaml, or .yml",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parse and validate a TOML configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the text does not parse or
+    /// validate
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        file.into_config()
+    }
+
+    /// Parse and validate a YAML configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the text does not parse or
+    /// validate
+    pub fn from_yaml(text: &str) -> Result<Self> {
+        let file: ConfigFile = serde_yaml::from_str(text).map_err(|e| invalid(e.to_string()))?;
+        file.into_config()
+    }
+
+    /// Check the configuration for conflicts
+    ///
+    /// Called by the loaders; call it again after changing fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` naming the first problem, or
+    /// the error of [`SpiConfig::validate`]
+    pub fn validate(&self) -> Result<()> {
+        self.spi.validate()?;
+
+        let pins = [
+            ("rst", Some(self.pins.rst)),
+            ("cs", Some(self.pins.cs)),
+            ("drdy", Some(self.pins.drdy)),
+            ("start", self.pins.start),
+        ];
+        let mut used: Vec<(&str, u8)> = Vec::new();
+        for (name, pin) in pins {
+            let Some(pin) = pin else { continue };
+            if let Some((other, _)) = used.iter().find(|&&(_, p)| p == pin) {
+                return Err(invalid(format!(
+                    "pins.{} and pins.{} are both GPIO {}",
+                    other, name, pin
+                )));
+            }
+            used.push((name, pin));
+        }
+        if self.control == ConversionControl::Pin && self.pins.start.is_none() {
+            return Err(invalid("adc1.control = \"pin\" needs pins.start"));
+        }
+
+        let reference = self.map.reference();
+        if !reference.is_finite() || reference <= 0.0 {
+            return Err(invalid(format!(
+                "adc1.vref must be positive, got {}",
+                reference
+            )));
+        }
+
+        let max = match self.mode {
+            InputMode::SingleEnded => 10,
+            InputMode::Differential => 4,
+        };
+        for (i, &channel) in self.channels.iter().enumerate() {
+            if channel > max {
+                return Err(invalid(format!(
+                    "channels[{}]: input {} out of range for {:?} mode (max {})",
+                    i, channel, self.mode, max
+                )));
+            }
+            if self.channels[..i].contains(&channel) {
+                return Err(invalid(format!(
+                    "channels[{}]: input {} listed twice",
+                    i, channel
+                )));
+            }
+        }
+        for sensor in &self.sensors {
+            sensor.validate()?;
+        }
+        let names = self.map.iter().flat_map(|(_, info)| {
+            std::iter::once(info.name.as_str()).chain(info.aliases.iter().map(String::as_str))
+        });
+        let names = names.chain(self.sensors.iter().map(SensorConfig::name));
+        let mut seen: Vec<&str> = Vec::new();
+        for name in names {
+            if seen.contains(&name) {
+                return Err(invalid(format!("name '{}' used twice", name)));
+            }
+            // A number would hide the channel of that number from lookups
+            if name.parse::<u8>().is_ok() {
+                return Err(invalid(format!("name '{}' is a channel number", name)));
+            }
+            seen.push(name);
+        }
+
+        if self.acquisition.capacity == 0 {
+            return Err(invalid("acquisition.capacity must be at least 1"));
+        }
+        Ok(())
+    }
+
+    /// Open the HAT and initialize it with this configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Hal::with_config`] and
+    /// [`Ads1263::init_adc1_with`]
+    pub fn open(&self) -> Result<Ads1263> {
+        let mut hal = Hal::with_config(self.pins, self.spi)?;
+        hal.set_wait_strategy(self.wait)?;
+        let mut adc = Ads1263::new(hal);
+        adc.set_conversion_control(self.control)?;
+        adc.set_mode(self.mode);
+        adc.init_adc1_with(self.adc1)?;
+        if let Some(adc2) = self.adc2 {
+            adc.set_adc2_rate(adc2.gain, adc2.data_rate)?;
+        }
+        Ok(adc)
+    }
+
+    /// Capture header of the configured device
+    pub fn header(&self) -> CaptureHeader {
+        CaptureHeader::new(self.adc1, self.map.clone())
+    }
+
+    /// Create all configured sinks, written in the order they are listed
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first sink that cannot be created
+    pub fn sink(&self) -> Result<Vec<Box<dyn Sink>>> {
+        let header = self.header();
+        self.sinks.iter().map(|sink| sink.build(&header)).collect()
+    }
+
+    /// Sensor named `name`
+    pub fn sensor(&self, name: &str) -> Option<&SensorConfig> {
+        self.sensors.iter().find(|sensor| sensor.name() == name)
+    }
+
+    /// Open the HAT and start the acquisition into the configured sinks
+    ///
+    /// The sampler thread writes every scan to the sinks; the returned
+    /// [`Acquisition`] only carries the error that stops it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if no channels are configured,
+    /// or the errors of [`open`](Self::open), [`sink`](Self::sink), and
+    /// [`Ads1263::start_acquisition_sink`]
+    pub fn start(&self) -> Result<Acquisition> {
+        if self.channels.is_empty() {
+            return Err(invalid("no channels to acquire"));
+        }
+        let sink = self.sink()?;
+        let adc = self.open()?;
+        adc.start_acquisition_sink(&self.channels, self.acquisition.clone(), sink)
+    }
+}
+
+/// `Ads1263Error::InvalidConfig` with `message`
+fn invalid(message: impl Into<String>) -> Ads1263Error {
+    Ads1263Error::InvalidConfig(message.into())
+}
+
+/// Option named `value` out of `options`, or an error listing them
+fn choose<K, T>(key: &str, value: K, options: &[(K, T)]) -> Result<T>
+where
+    K: PartialEq + Display,
+    T: Copy,
+{
+    if let Some(&(_, option)) = options.iter().find(|(name, _)| *name == value) {
+        return Ok(option);
+    }
+    let expected: Vec<String> = options.iter().map(|(name, _)| name.to_string()).collect();
+    Err(invalid(format!(
+        "{}: invalid value {} (expected one of {})",
+        key,
+        value,
+        expected.join(", ")
+    )))
+}
+
+/// Parse a duration such as `250ms`, `1.5s`, or `2h`
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let seconds = match unit.trim() {
+        "ns" => number / 1e9,
+        "us" | "µs" => number / 1e6,
+        "ms" => number / 1e3,
+        "s" => number,
+        "m" | "min" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// Gain from its factor
+fn gain_of(key: &str, factor: u32) -> Result<Gain> {
+    choose(
+        key,
+        factor,
+        &[
+            (1, Gain::Gain1),
+            (2, Gain::Gain2),
+            (4, Gain::Gain4),
+            (8, Gain::Gain8),
+            (16, Gain::Gain16),
+            (32, Gain::Gain32),
+            (64, Gain::Gain64),
+        ],
+    )
+}
+
+/// Data rate from samples per second
+fn data_rate_of(key: &str, sps: f64) -> Result<DataRate> {
+    DataRate::from_sps(sps).ok_or_else(|| invalid(format!("{}: invalid data rate {}", key, sps)))
+}
+
+/// Delay matching a duration such as `35us`
+fn delay_of(key: &str, text: &str) -> Result<Delay> {
+    const DELAYS: [Delay; 12] = [
+        Delay::Delay0,
+        Delay::Delay8_7us,
+        Delay::Delay17us,
+        Delay::Delay35us,
+        Delay::Delay169us,
+        Delay::Delay139us,
+        Delay::Delay278us,
+        Delay::Delay555us,
+        Delay::Delay1_1ms,
+        Delay::Delay2_2ms,
+        Delay::Delay4_4ms,
+        Delay::Delay8_8ms,
+    ];
+    let duration = match text.trim() {
+        "0" => Duration::ZERO,
+        text => duration_of(key, text)?,
+    };
+    DELAYS
+        .into_iter()
+        .find(|delay| delay.duration().abs_diff(duration) < Duration::from_nanos(500))
+        .ok_or_else(|| invalid(format!("{}: no conversion delay of {}", key, text)))
+}
+
+/// Duration from its text form
+fn duration_of(key: &str, text: &str) -> Result<Duration> {
+    parse_duration(text).ok_or_else(|| {
+        invalid(format!(
+            "{}: invalid duration '{}' (e.g. \"250ms\" or \"1s\")",
+            key, text
+        ))
+    })
+}
+
+/// Serde error carrying the message of a validation error
+fn de_error<E: serde::de::Error>(error: Ads1263Error) -> E {
+    match error {
+        Ads1263Error::InvalidConfig(message) => E::custom(message),
+        error => E::custom(error),
+    }
+}
+
+fn gain<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Gain, D::Error> {
+    let factor = u32::deserialize(deserializer)?;
+    gain_of("gain", factor).map_err(de_error)
+}
+
+fn data_rate<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<DataRate, D::Error> {
+    let sps = f64::deserialize(deserializer)?;
+    data_rate_of("data_rate", sps).map_err(de_error)
+}
+
+fn delay<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Delay, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    delay_of("delay", &text).map_err(de_error)
+}
+
+fn rtd_alpha<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<RtdAlpha, D::Error> {
+    let alpha = u32::deserialize(deserializer)?;
+    choose(
+        "alpha",
+        alpha,
+        &[
+            (385, RtdAlpha::Alpha385),
+            (3911, RtdAlpha::Alpha3911),
+            (3916, RtdAlpha::Alpha3916),
+        ],
+    )
+    .map_err(de_error)
+}
+
+fn rtd_wiring<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<RtdWiring, D::Error> {
+    let wires = u8::deserialize(deserializer)?;
+    choose(
+        "wires",
+        wires,
+        &[
+            (2, RtdWiring::TwoWire),
+            (3, RtdWiring::ThreeWire),
+            (4, RtdWiring::FourWire),
+        ],
+    )
+    .map_err(de_error)
+}
+
+fn rtd_reference<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<ReferenceSource, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    choose(
+        "reference",
+        text.as_str(),
+        &[
+            ("ain0_ain1", ReferenceSource::ExternalAin01),
+            ("ain2_ain3", ReferenceSource::ExternalAin23),
+            ("ain4_ain5", ReferenceSource::ExternalAin45),
+        ],
+    )
+    .map_err(de_error)
+}
+
+fn idac_current<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<IdacCurrent, D::Error> {
+    let microamps = u32::deserialize(deserializer)?;
+    IdacCurrent::from_microamps(microamps)
+        .filter(|current| *current != IdacCurrent::Off)
+        .ok_or_else(|| {
+            let currents: Vec<String> = IdacCurrent::ALL[1..]
+                .iter()
+                .map(|current| current.microamps().to_string())
+                .collect();
+            de_error(invalid(format!(
+                "idac: no excitation current of {} µA (expected one of {})",
+                microamps,
+                currents.join(", ")
+            )))
+        })
+}
+
+fn bridge_type<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<BridgeType, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    choose(
+        "bridge",
+        text.as_str(),
+        &[
+            ("quarter", BridgeType::Quarter),
+            ("half_bending", BridgeType::HalfBending),
+            ("half_poisson", BridgeType::HalfPoisson(DEFAULT_POISSON)),
+            ("full_bending", BridgeType::FullBending),
+            ("full_poisson", BridgeType::FullPoisson(DEFAULT_POISSON)),
+        ],
+    )
+    .map_err(de_error)
+}
+
+fn excitation_reference<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<ReferenceSource, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    choose(
+        "excitation",
+        text.as_str(),
+        &[
+            ("avdd", ReferenceSource::AvddAvss),
+            ("ain0_ain1", ReferenceSource::ExternalAin01),
+            ("ain2_ain3", ReferenceSource::ExternalAin23),
+            ("ain4_ain5", ReferenceSource::ExternalAin45),
+        ],
+    )
+    .map_err(de_error)
+}
+
+fn thermistor_side<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<bool, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    choose("side", text.as_str(), &[("low", false), ("high", true)]).map_err(de_error)
+}
+
+fn thermocouple_type<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<ThermocoupleType, D::Error> {
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(de_error)
+}
+
+fn optional_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Duration>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(text) => duration_of("duration", &text).map(Some).map_err(de_error),
+        None => Ok(None),
+    }
+}
+
+/// Configuration file as parsed, before validation
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    pins: PinsFile,
+    spi: SpiFile,
+    adc1: Adc1File,
+    adc2: Option<Adc2File>,
+    channels: Vec<ChannelFile>,
+    sensors: Vec<SensorConfig>,
+    sinks: Vec<SinkConfig>,
+    acquisition: AcquisitionFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PinsFile {
+    rst: Option<u8>,
+    cs: Option<u8>,
+    drdy: Option<u8>,
+    start: Option<u8>,
+    cleanup: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SpiFile {
+    bus: Option<u8>,
+    slave_select: Option<u8>,
+    clock_speed: Option<u32>,
+    wait: Option<String>,
+    /// Sleep between pin reads of `wait = "sleep"`
+    poll_interval: Option<String>,
+    /// Busy phase of `wait = "spin_then_sleep"`
+    spin: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Adc1File {
+    mode: Option<String>,
+    gain: Option<u32>,
+    data_rate: Option<f64>,
+    filter: Option<String>,
+    delay: Option<String>,
+    reference: Option<String>,
+    vref: Option<f64>,
+    control: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Adc2File {
+    gain: Option<u32>,
+    data_rate: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChannelFile {
+    input: u8,
+    name: Option<String>,
+    #[serde(default)]
+    aliases: Vec<String>,
+    unit: Option<String>,
+    scale: Option<f64>,
+    offset: Option<f64>,
+    range: Option<RangeFile>,
+    curve: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RangeFile {
+    volts: (f64, f64),
+    values: (f64, f64),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AcquisitionFile {
+    capacity: Option<usize>,
+    max_scans: Option<u64>,
+    thread_name: Option<String>,
+    watchdog: Option<WatchdogFile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WatchdogFile {
+    max_errors: Option<u32>,
+    max_attempts: Option<u32>,
+    retry_delay: Option<String>,
+}
+
+impl ConfigFile {
+    /// Convert to typed settings and validate them
+    fn into_config(self) -> Result<Ads1263Config> {
+        let mut config = Ads1263Config::default();
+
+        let pins = self.pins;
+        let defaults = PinConfig::default();
+        config.pins = PinConfig {
+            rst: pins.rst.unwrap_or(defaults.rst),
+            cs: pins.cs.unwrap_or(defaults.cs),
+            drdy: pins.drdy.unwrap_or(defaults.drdy),
+            start: pins.start,
+            cleanup: match pins.cleanup.as_deref() {
+                Some(cleanup) => choose(
+                    "pins.cleanup",
+                    cleanup,
+                    &[
+                        ("reset_chip", CleanupPolicy::ResetChip),
+                        ("tristate_pins", CleanupPolicy::TristatePins),
+                        ("leave_running", CleanupPolicy::LeaveRunning),
+                    ],
+                )?,
+                None => defaults.cleanup,
+            },
+        };
+
+        let spi = self.spi;
+        if let Some(bus) = spi.bus {
+            config.spi.bus = choose(
+                "spi.bus",
+                bus,
+                &[
+                    (0, Bus::Spi0),
+                    (1, Bus::Spi1),
+                    (2, Bus::Spi2),
+                    (3, Bus::Spi3),
+                    (4, Bus::Spi4),
+                    (5, Bus::Spi5),
+                    (6, Bus::Spi6),
+                ],
+            )?;
+        }
+        if let Some(slave_select) = spi.slave_select {
+            config.spi.slave_select = choose(
+                "spi.slave_select",
+                slave_select,
+                &[
+                    (0, SlaveSelect::Ss0),
+                    (1, SlaveSelect::Ss1),
+                    (2, SlaveSelect::Ss2),
+                    (3, SlaveSelect::Ss3),
+                ],
+            )?;
+        }
+        if let Some(hz) = spi.clock_speed {
+            config.spi.clock_speed = hz;
+        }
+        let poll_interval = match &spi.poll_interval {
+            Some(text) => duration_of("spi.poll_interval", text)?,
+            None => Duration::from_micros(100),
+        };
+        let spin = match &spi.spin {
+            Some(text) => duration_of("spi.spin", text)?,
+            None => Duration::from_micros(200),
+        };
+        if let Some(wait) = spi.wait.as_deref() {
+            config.wait = match wait {
+                "interrupt" => WaitStrategy::Interrupt,
+                "busy_spin" => WaitStrategy::BusySpin,
+                "sleep" => WaitStrategy::Sleep { poll_interval },
+                "spin_then_sleep" => WaitStrategy::SpinThenSleep {
+                    spin_us: u32::try_from(spin.as_micros()).unwrap_or(u32::MAX),
+                },
+                wait => {
+                    return Err(invalid(format!(
+                        "spi.wait: invalid value {} (expected one of interrupt, busy_spin, sleep, spin_then_sleep)",
+                        wait
+                    )));
+                }
+            };
+        }
+
+        let adc1 = self.adc1;
+        if let Some(mode) = adc1.mode.as_deref() {
+            config.mode = choose(
+                "adc1.mode",
+                mode,
+                &[
+                    ("single_ended", InputMode::SingleEnded),
+                    ("differential", InputMode::Differential),
+                ],
+            )?;
+        }
+        if let Some(gain) = adc1.gain {
+            config.adc1.gain = gain_of("adc1.gain", gain)?;
+        }
+        if let Some(sps) = adc1.data_rate {
+            config.adc1.data_rate = data_rate_of("adc1.data_rate", sps)?;
+        }
+        if let Some(filter) = adc1.filter.as_deref() {
+            config.adc1.filter = choose(
+                "adc1.filter",
+                filter,
+                &[
+                    ("sinc1", DigitalFilter::Sinc1),
+                    ("sinc2", DigitalFilter::Sinc2),
+                    ("sinc3", DigitalFilter::Sinc3),
+                    ("sinc4", DigitalFilter::Sinc4),
+                    ("fir", DigitalFilter::Fir),
+                ],
+            )?;
+        }
+        if let Some(delay) = adc1.delay.as_deref() {
+            config.adc1.delay = delay_of("adc1.delay", delay)?;
+        }
+        if let Some(reference) = adc1.reference.as_deref() {
+            config.adc1.reference = choose(
+                "adc1.reference",
+                reference,
+                &[
+                    ("avdd", ReferenceSource::AvddAvss),
+                    ("internal", ReferenceSource::Internal2_5V),
+                    ("ain0_ain1", ReferenceSource::ExternalAin01),
+                    ("ain2_ain3", ReferenceSource::ExternalAin23),
+                    ("ain4_ain5", ReferenceSource::ExternalAin45),
+                ],
+            )?;
+        }
+        if let Some(control) = adc1.control.as_deref() {
+            config.control = choose(
+                "adc1.control",
+                control,
+                &[
+                    ("command", ConversionControl::Command),
+                    ("pin", ConversionControl::Pin),
+                ],
+            )?;
+        }
+        let vref = adc1.vref.unwrap_or(match config.adc1.reference {
+            ReferenceSource::Internal2_5V => 2.5,
+            _ => DEFAULT_VREF,
+        });
+
+        if let Some(adc2) = self.adc2 {
+            let mut settings = Adc2Config::default();
+            if let Some(gain) = adc2.gain {
+                settings.gain = choose(
+                    "adc2.gain",
+                    gain,
+                    &[
+                        (1, Adc2Gain::Gain1),
+                        (2, Adc2Gain::Gain2),
+                        (4, Adc2Gain::Gain4),
+                        (8, Adc2Gain::Gain8),
+                        (16, Adc2Gain::Gain16),
+                        (32, Adc2Gain::Gain32),
+                        (64, Adc2Gain::Gain64),
+                        (128, Adc2Gain::Gain128),
+                    ],
+                )?;
+            }
+            if let Some(sps) = adc2.data_rate {
+                settings.data_rate = choose(
+                    "adc2.data_rate",
+                    sps,
+                    &[
+                        (10.0, Adc2DataRate::Sps10),
+                        (100.0, Adc2DataRate::Sps100),
+                        (400.0, Adc2DataRate::Sps400),
+                        (800.0, Adc2DataRate::Sps800),
+                    ],
+                )?;
+            }
+            config.adc2 = Some(settings);
+        }
+
+        config.map = ChannelMap::new(vref);
+        for (i, channel) in self.channels.into_iter().enumerate() {
+            let key = format!("channels[{}]", i);
+            let name = channel
+                .name
+                .unwrap_or_else(|| format!("ch{}", channel.input));
+            let mut info = ChannelInfo::new(name);
+            for alias in channel.aliases {
+                info = info.alias(alias);
+            }
+            if let Some(unit) = channel.unit {
+                info = info.unit(unit);
+            }
+            match (
+                channel.range,
+                channel.scale.is_some() || channel.offset.is_some(),
+            ) {
+                (Some(_), true) => {
+                    return Err(invalid(format!(
+                        "{}: set either range or scale/offset",
+                        key
+                    )));
+                }
+                (Some(range), false) => {
+                    if range.volts.0 == range.volts.1 {
+                        return Err(invalid(format!("{}.range: empty volts range", key)));
+                    }
+                    info = info.range(range.volts, range.values);
+                }
+                (None, _) => {
+                    info = info.linear(channel.scale.unwrap_or(1.0), channel.offset.unwrap_or(0.0));
+                }
+            }
+            if let Some(curve) = channel.curve {
+                let curve: Curve = curve
+                    .parse()
+                    .map_err(|e| invalid(format!("{}.curve: {}", key, e)))?;
+                info = info.curve(curve);
+            }
+            config.map.insert(channel.input, info);
+            config.channels.push(channel.input);
+        }
+
+        config.sensors = self.sensors;
+        config.sinks = self.sinks;
+
+        let acquisition = self.acquisition;
+        if let Some(capacity) = acquisition.capacity {
+            config.acquisition.capacity = capacity;
+        }
+        config.acquisition.max_scans = acquisition.max_scans;
+        if let Some(name) = acquisition.thread_name {
+            config.acquisition.thread_name = name;
+        }
+        if let Some(watchdog) = acquisition.watchdog {
+            let mut settings = WatchdogConfig::default();
+            if let Some(errors) = watchdog.max_errors {
+                settings.max_errors = errors;
+            }
+            if let Some(attempts) = watchdog.max_attempts {
+                settings.max_attempts = attempts;
+            }
+            if let Some(delay) = &watchdog.retry_delay {
+                settings.retry_delay = duration_of("acquisition.watchdog.retry_delay", delay)?;
+            }
+            config.acquisition.watchdog = Some(settings);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example from the module documentation
+    const EXAMPLE: &str = r#"
+[pins]
+drdy = 17
+start = 27
+
+[spi]
+clock_speed = 2_000_000
+wait = "interrupt"
+
+[adc1]
+mode = "single_ended"
+gain = 1
+data_rate = 400
+filter = "sinc4"
+vref = 5.08
+
+[[channels]]
+input = 0
+name = "inlet"
+unit = "kPa"
+range = { volts = [0.5, 4.5], values = [0.0, 100.0] }
+
+[[channels]]
+input = 3
+name = "level"
+aliases = ["tank"]
+unit = "%"
+curve = "table: 0=0, 1=40, 2.5=100"
+
+[[sensors]]
+type = "rtd"
+name = "oven"
+r_ref = 2000.0
+
+[[sensors]]
+type = "thermocouple"
+name = "kiln"
+thermocouple = "k"
+positive = 0
+negative = 1
+
+[[sinks]]
+type = "csv"
+path = "/var/log/ads1263/scan.csv"
+rotate = { max_duration = "1h", keep_files = 48 }
+
+[[sinks]]
+type = "udp"
+target = "239.0.0.1:5005"
+
+[acquisition]
+capacity = 256
+watchdog = { max_errors = 5 }
+"#;
+
+    /// `EXAMPLE` in YAML
+    const EXAMPLE_YAML: &str = r#"
+pins:
+  drdy: 17
+  start: 27
+spi:
+  clock_speed: 2000000
+  wait: interrupt
+adc1:
+  mode: single_ended
+  gain: 1
+  data_rate: 400
+  filter: sinc4
+  vref: 5.08
+channels:
+  - input: 0
+    name: inlet
+    unit: kPa
+    range: { volts: [0.5, 4.5], values: [0.0, 100.0] }
+  - input: 3
+    name: level
+    aliases: [tank]
+    unit: "%"
+    curve: "table: 0=0, 1=40, 2.5=100"
+sensors:
+  - type: rtd
+    name: oven
+    r_ref: 2000.0
+  - type: thermocouple
+    name: kiln
+    thermocouple: k
+    positive: 0
+    negative: 1
+sinks:
+  - type: csv
+    path: /var/log/ads1263/scan.csv
+    rotate: { max_duration: 1h, keep_files: 48 }
+  - type: udp
+    target: "239.0.0.1:5005"
+acquisition:
+  capacity: 256
+  watchdog: { max_errors: 5 }
+"#;
+
+    #[test]
+    fn empty_file_is_default() {
+        let config = Ads1263Config::from_toml("").unwrap();
+        let default = Ads1263Config::default();
+        assert_eq!(format!("{:?}", config), format!("{:?}", default));
+        assert_eq!(config.adc1, Adc1Config::default());
+        assert_eq!(config.map.reference(), DEFAULT_VREF);
+        assert!(config.channels.is_empty());
+        assert!(config.adc2.is_none());
+    }
+
+    #[test]
+    fn module_example_parses() {
+        let config = Ads1263Config::from_toml(EXAMPLE).unwrap();
+        assert_eq!(config.pins.drdy, 17);
+        assert_eq!(config.pins.start, Some(27));
+        assert_eq!(config.spi.clock_speed, 2_000_000);
+        assert_eq!(config.adc1.filter, DigitalFilter::Sinc4);
+        assert_eq!(config.adc1.data_rate, DataRate::Sps400);
+        assert_eq!(config.map.reference(), 5.08);
+        assert_eq!(config.channels, vec![0, 3]);
+        assert_eq!(config.map.get(3).unwrap().aliases, vec!["tank".to_string()]);
+        assert!(config.sensor("kiln").is_some());
+        assert_eq!(config.sinks.len(), 2);
+        assert_eq!(config.acquisition.capacity, 256);
+        assert_eq!(config.acquisition.watchdog.unwrap().max_errors, 5);
+    }
+
+    #[test]
+    fn toml_and_yaml_agree() {
+        let toml = Ads1263Config::from_toml(EXAMPLE).unwrap();
+        let yaml = Ads1263Config::from_yaml(EXAMPLE_YAML).unwrap();
+        assert_eq!(format!("{:?}", toml), format!("{:?}", yaml));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let cases = [
+            ("[adc1]\ngain = 3", "adc1.gain"),
+            ("[adc1]\ndata_rate = 123", "adc1.data_rate"),
+            ("[adc1]\nfilter = \"sinc5\"", "adc1.filter"),
+            ("[adc1]\ndelay = \"36us\"", "adc1.delay"),
+            ("[adc1]\nvref = -1.0", "adc1.vref"),
+            ("[adc1]\ncontrol = \"pin\"", "pins.start"),
+            ("[adc2]\ndata_rate = 50", "adc2.data_rate"),
+            ("[pins]\ncs = 17", "GPIO 17"),
+            ("[spi]\nwait = \"poll\"", "spi.wait"),
+            ("[spi]\npoll_interval = \"fast\"", "spi.poll_interval"),
+            ("[[channels]]\ninput = 11", "out of range"),
+            (
+                "[[channels]]\ninput = 1\n[[channels]]\ninput = 1",
+                "listed twice",
+            ),
+            ("[[channels]]\ninput = 1\nname = \"2\"", "channel number"),
+            ("[acquisition]\ncapacity = 0", "acquisition.capacity"),
+            ("[pins]\ngpio = 4", "unknown field"),
+        ];
+        for (text, expected) in cases {
+            match Ads1263Config::from_toml(text) {
+                Err(Ads1263Error::InvalidConfig(message)) => assert!(
+                    message.contains(expected),
+                    "{:?}: '{}' does not mention {}",
+                    text,
+                    message,
+                    expected
+                ),
+                other => panic!("{:?} gave {:?}", text, other.map(|_| ())),
+            }
+        }
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("1.5s"), Some(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2h"), Some(Duration::from_secs(7200)));
+        assert_eq!(parse_duration(" 35us "), Some(Duration::from_micros(35)));
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("ms"), None);
+        assert_eq!(parse_duration("1d"), None);
+    }
+
+    #[test]
+    fn matches_conversion_delays() {
+        assert_eq!(delay_of("delay", "0").unwrap(), Delay::Delay0);
+        assert_eq!(delay_of("delay", "35us").unwrap(), Delay::Delay35us);
+        assert_eq!(delay_of("delay", "8.7us").unwrap(), Delay::Delay8_7us);
+        assert_eq!(delay_of("delay", "1.1ms").unwrap(), Delay::Delay1_1ms);
+        assert!(delay_of("delay", "1ms").is_err());
+    }
+}
@@ -0,0 +1,255 @@
+//! CSV export
+//!
+//! [`CsvSink`] writes one line per sample with its timestamp, channel
+//! name, raw code, and value converted by a [`ChannelMap`]:
+//!
+//! ```text
+//! timestamp,channel,raw,value
+//! 0.000000,inlet,1073741824,50.000000
+//! 0.000833,ch1,-2147484,-0.005000
+//! ```
+//!
+//! Timestamps are seconds since the first sample, or Unix time if a
+//! [`WallClock`] is attached. Fields containing the delimiter, quotes, or
+//! line breaks are quoted per RFC 4180. The sink can be handed to an acquisition
+//! ([`Ads1263::start_acquisition_sink`](crate::Ads1263::start_acquisition_sink))
+//! or fed pipeline output with [`CsvSink::write_readings`].
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+#[cfg(feature = "zstd")]
+use crate::compress::ZstdWriter;
+use crate::error::Result;
+use crate::pipeline::Reading;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Sink writing samples as delimited text
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelInfo, ChannelMap, CsvSink, DataRate, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let sink = CsvSink::create("capture.csv", map)?.delimiter(';').precision(3);
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct CsvSink<W: Write> {
+    writer: W,
+    map: ChannelMap,
+    delimiter: char,
+    precision: usize,
+    header: bool,
+    clock: Option<WallClock>,
+    /// Timestamp of the first line; relative times are measured from it
+    start: Option<Instant>,
+}
+
+impl CsvSink<BufWriter<File>> {
+    /// Create (or truncate) a CSV file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created
+    pub fn create(path: impl AsRef<Path>, map: ChannelMap) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), map))
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl CsvSink<ZstdWriter<File>> {
+    /// Create (or truncate) a zstd-compressed CSV file at `path`
+    ///
+    /// Text is compressed in frames of 1 MiB; each [`flush`](Sink::flush)
+    /// completes a frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created
+    pub fn create_zstd(path: impl AsRef<Path>, map: ChannelMap) -> Result<Self> {
+        Ok(Self::new(ZstdWriter::create(path)?, map))
+    }
+}
+
+impl<W: Write> CsvSink<W> {
+    /// Write to `writer`, naming and converting channels with `map`
+    ///
+    /// Defaults to comma-delimited lines with a header and six decimals.
+    pub fn new(writer: W, map: ChannelMap) -> Self {
+        Self {
+            writer,
+            map,
+            delimiter: ',',
+            precision: 6,
+            header: true,
+            clock: None,
+            start: None,
+        }
+    }
+
+    /// Set the field delimiter
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the number of decimals of converted values
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Write a header line before the first sample (default true)
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Write Unix timestamps from a [`WallClock`] instead of relative times
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Channel map used for names and conversion
+    pub fn map(&self) -> &ChannelMap {
+        &self.map
+    }
+
+    /// Flush and return the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if flushing fails
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Write pipeline output
+    ///
+    /// Reading values are taken as (possibly filtered) ADC codes, i.e. from
+    /// a pipeline without a [`ChannelMap`] stage; the raw column holds the
+    /// code and the value column its conversion.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails
+    pub fn write_readings(&mut self, readings: &[Reading]) -> Result<()> {
+        for reading in readings {
+            let value = self.map.convert(reading.channel, reading.value);
+            self.write_line(reading.timestamp, reading.channel, reading.value, value)?;
+        }
+        Ok(())
+    }
+
+    fn write_line(
+        &mut self,
+        at: Instant,
+        channel: u8,
+        raw: impl std::fmt::Display,
+        value: f64,
+    ) -> Result<()> {
+        let d = self.delimiter;
+        if self.header {
+            self.header = false;
+            let [time, channel, raw, value] =
+                ["timestamp", "channel", "raw", "value"].map(|name| quote(name, d));
+            writeln!(self.writer, "{time}{d}{channel}{d}{raw}{d}{value}")?;
+        }
+        let time = self.timestamp(at);
+        writeln!(
+            self.writer,
+            "{}.{:06}{d}{}{d}{}{d}{:.*}",
+            time.as_secs(),
+            time.subsec_micros(),
+            quote(&self.map.name(channel), d),
+            raw,
+            self.precision,
+            value
+        )?;
+        Ok(())
+    }
+
+    /// Seconds since the first line, or since the Unix epoch with a clock
+    fn timestamp(&mut self, at: Instant) -> Duration {
+        match &mut self.clock {
+            Some(clock) => {
+                clock.update();
+                clock
+                    .to_utc(at)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+            }
+            None => at.saturating_duration_since(*self.start.get_or_insert(at)),
+        }
+    }
+}
+
+/// Quote a field per RFC 4180 if it contains the delimiter, a quote, or a
+/// line break, doubling embedded quotes
+fn quote(field: &str, delimiter: char) -> Cow<'_, str> {
+    if field.contains([delimiter, '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+impl<W: Write + Send> Sink for CsvSink<W> {
+    /// Write one line per sample
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        for sample in samples {
+            let value = self.map.convert_sample(sample);
+            self.write_line(sample.timestamp, sample.channel, sample.raw, value)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel_map::ChannelInfo;
+
+    #[test]
+    fn quote_leaves_plain_fields() {
+        assert!(matches!(quote("inlet", ','), Cow::Borrowed("inlet")));
+        assert_eq!(quote("a;b", ','), "a;b");
+    }
+
+    #[test]
+    fn quote_escapes_special_fields() {
+        assert_eq!(quote("a,b", ','), "\"a,b\"");
+        assert_eq!(quote("a;b", ';'), "\"a;b\"");
+        assert_eq!(quote("6\" pipe", ','), "\"6\"\" pipe\"");
+        assert_eq!(quote("a\nb", ','), "\"a\nb\"");
+    }
+
+    #[test]
+    fn channel_names_are_quoted() {
+        let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("flow, \"main\""));
+        let mut sink = CsvSink::new(Vec::new(), map).precision(1);
+        sink.write_line(Instant::now(), 0, 0, 0.0).unwrap();
+        let text = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            text,
+            "timestamp,channel,raw,value\n0.000000,\"flow, \"\"main\"\"\",0,0.0\n"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Current measurements
+//!
+//! [`ShuntCurrent`] measures the voltage across a shunt resistor on a
+//! differential input pair against the internal 2.5 V reference and
+//! reports the current through it, checking the burden voltage the shunt
+//! drops in the measured circuit. [`LoopCurrent`] reads a 4–20 mA
+//! transmitter through its sense resistor and maps the loop current to
+//! the transmitter's range, with NAMUR NE 43 fault limits.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{DataRate, Gain, MuxInput, ReferenceSource};
+use crate::sensors::{Sensor, SensorSetup, gain_factor};
+
+/// Fraction of full scale above which a conversion counts as clipped
+const CLIP: f64 = 0.99;
+
+/// Current through a shunt resistor in amps
+///
+/// The shunt sits in the measured circuit and drops its burden voltage,
+/// `I × R`, which the circuit has to tolerate and the input range has to
+/// cover. [`validate`](Self::validate) checks both for the rated current
+/// before any conversion, and [`read`](Sensor::read) reports a clipped
+/// input or a burden above the limit as a fault.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{Gain, MuxInput, Sensor, ShuntCurrent};
+///
+/// // 10 mΩ shunt across AIN4/AIN5, up to 5 A, at most 100 mV burden
+/// let supply = ShuntCurrent::new(MuxInput::Ain(4), MuxInput::Ain(5), 0.01)
+///     .gain(Gain::Gain16)
+///     .rated(5.0)
+///     .max_burden(0.1);
+/// supply.validate()?;
+/// println!("{:.4} A", supply.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShuntCurrent {
+    setup: SensorSetup,
+    shunt: f64,
+    rated: Option<f64>,
+    max_burden: Option<f64>,
+}
+
+impl ShuntCurrent {
+    /// Shunt of `ohms` between `positive` and `negative`, at unity gain
+    /// against the internal 2.5 V reference
+    pub fn new(positive: MuxInput, negative: MuxInput, ohms: f64) -> Self {
+        let setup =
+            SensorSetup::new(positive, negative).reference(ReferenceSource::Internal2_5V, 2.5);
+        Self::with_setup(setup, ohms)
+    }
+
+    /// Shunt of `ohms` measured with a custom setup
+    pub fn with_setup(setup: SensorSetup, ohms: f64) -> Self {
+        Self {
+            setup,
+            shunt: ohms,
+            rated: None,
+            max_burden: None,
+        }
+    }
+
+    /// Set the PGA gain, for shunts with a small burden voltage
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.setup = self.setup.gain(gain);
+        self
+    }
+
+    /// Set the data rate
+    pub fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.setup = self.setup.data_rate(data_rate);
+        self
+    }
+
+    /// Set the largest expected current in amps, checked by
+    /// [`validate`](Self::validate)
+    pub fn rated(mut self, amps: f64) -> Self {
+        self.rated = Some(amps);
+        self
+    }
+
+    /// Set the largest burden voltage the measured circuit tolerates
+    pub fn max_burden(mut self, volts: f64) -> Self {
+        self.max_burden = Some(volts);
+        self
+    }
+
+    /// Shunt resistance in ohms
+    pub fn shunt(&self) -> f64 {
+        self.shunt
+    }
+
+    /// Largest shunt voltage the input range covers at the set gain
+    pub fn full_scale(&self) -> f64 {
+        self.setup.vref / gain_factor(self.setup.gain)
+    }
+
+    /// Current in amps of a conversion result
+    pub fn amps(&self, code: i32) -> f64 {
+        self.setup.volts(code) / self.shunt
+    }
+
+    /// Check the shunt against the input range and the burden limit
+    ///
+    /// At the rated current, the burden voltage must stay below the full
+    /// scale of the input range and below the burden limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the shunt is not positive
+    /// or the rated current violates either limit
+    pub fn validate(&self) -> Result<()> {
+        let invalid =
+            |message: String| Err(Ads1263Error::InvalidConfig(format!("shunt: {}", message)));
+
+        if !self.shunt.is_finite() || self.shunt <= 0.0 {
+            return invalid(format!("resistance {} Ω must be positive", self.shunt));
+        }
+        let Some(amps) = self.rated else {
+            return Ok(());
+        };
+        let burden = amps.abs() * self.shunt;
+        if burden > self.full_scale() {
+            return invalid(format!(
+                "{} A drops {:.4} V, beyond the {:.4} V input range at gain {}",
+                amps,
+                burden,
+                self.full_scale(),
+                gain_factor(self.setup.gain)
+            ));
+        }
+        if let Some(limit) = self.max_burden
+            && burden > limit
+        {
+            return invalid(format!(
+                "{} A drops {:.4} V, beyond the {:.4} V burden limit; use a smaller shunt",
+                amps, burden, limit
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Sensor for ShuntCurrent {
+    fn unit(&self) -> &str {
+        "A"
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.amps(code)
+    }
+
+    /// Take one conversion and check the burden voltage
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::SensorFault` if the shunt voltage clips the
+    /// input range or exceeds the burden limit, or the error of the
+    /// conversion
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        let code = adc.read_sensor(&self.setup)?;
+        let volts = self.setup.volts(code);
+        if volts.abs() >= self.full_scale() * CLIP {
+            return Err(Ads1263Error::SensorFault(format!(
+                "shunt voltage {:.4} V at the {:.4} V input range (over-range)",
+                volts,
+                self.full_scale()
+            )));
+        }
+        if let Some(limit) = self.max_burden
+            && volts.abs() > limit
+        {
+            return Err(Ads1263Error::SensorFault(format!(
+                "shunt burden {:.4} V above the {:.4} V limit",
+                volts, limit
+            )));
+        }
+        let amps = volts / self.shunt;
+        log::debug!("Shunt: {:.6} V -> {:.6} A", volts, amps);
+        Ok(amps)
+    }
+}
+
+/// 4–20 mA current loop transmitter, reported in its engineering unit
+///
+/// The loop current through the sense resistor maps linearly from 4 mA
+/// (`zero`) to 20 mA (`zero + span`); without a mapping, the sensor
+/// reports mA. Currents below 3.6 mA (broken loop, transmitter fault) or
+/// above 21 mA (over-range, short circuit) are faults, as in NAMUR NE 43.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{LoopCurrent, MuxInput, Sensor};
+///
+/// // Pressure transmitter, 0 to 10 bar, 100 Ω sense resistor from AIN0 to AINCOM
+/// let pressure =
+///     LoopCurrent::new(MuxInput::Ain(0), MuxInput::AinCom, 100.0).scale(0.0, 10.0, "bar");
+/// pressure.validate()?;
+/// println!("{:.3} bar", pressure.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopCurrent {
+    shunt: ShuntCurrent,
+    zero: f64,
+    span: f64,
+    unit: String,
+}
+
+impl LoopCurrent {
+    /// Lowest healthy loop current in mA
+    pub const FAULT_LOW: f64 = 3.6;
+    /// Highest healthy loop current in mA
+    pub const FAULT_HIGH: f64 = 21.0;
+
+    /// Sense resistor of `ohms` between `positive` and `negative`, at
+    /// unity gain against the internal 2.5 V reference, reported in mA
+    pub fn new(positive: MuxInput, negative: MuxInput, ohms: f64) -> Self {
+        Self::with_shunt(ShuntCurrent::new(positive, negative, ohms))
+    }
+
+    /// Loop measured through a configured shunt
+    pub fn with_shunt(shunt: ShuntCurrent) -> Self {
+        Self {
+            shunt: shunt.rated(Self::FAULT_HIGH / 1000.0),
+            zero: 4.0,
+            span: 16.0,
+            unit: "mA".to_string(),
+        }
+    }
+
+    /// Map 4 mA to `zero` and 20 mA to `zero + span`, in `unit`
+    pub fn scale(mut self, zero: f64, span: f64, unit: &str) -> Self {
+        self.zero = zero;
+        self.span = span;
+        self.unit = unit.to_string();
+        self
+    }
+
+    /// Set the data rate
+    pub fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.shunt = self.shunt.data_rate(data_rate);
+        self
+    }
+
+    /// Loop current in mA of a conversion result
+    pub fn milliamps(&self, code: i32) -> f64 {
+        self.shunt.amps(code) * 1000.0
+    }
+
+    /// Value in the unit at `milliamps`
+    pub fn value(&self, milliamps: f64) -> f64 {
+        self.zero + (milliamps - 4.0) / 16.0 * self.span
+    }
+
+    /// Check that the sense resistor is positive and that 21 mA stays
+    /// inside the input range, see [`ShuntCurrent::validate`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` describing the problem
+    pub fn validate(&self) -> Result<()> {
+        self.shunt.validate()
+    }
+}
+
+impl Sensor for LoopCurrent {
+    fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.shunt.setup()
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.value(self.milliamps(code))
+    }
+
+    /// Take one conversion and check the loop current
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::SensorFault` if the loop current is below
+    /// 3.6 mA or above 21 mA, or the error of [`ShuntCurrent`]'s read
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        let milliamps = self.shunt.read(adc)? * 1000.0;
+        if milliamps < Self::FAULT_LOW {
+            return Err(Ads1263Error::SensorFault(format!(
+                "loop current {:.3} mA below {} mA (broken loop or transmitter fault)",
+                milliamps,
+                Self::FAULT_LOW
+            )));
+        }
+        if milliamps > Self::FAULT_HIGH {
+            return Err(Ads1263Error::SensorFault(format!(
+                "loop current {:.3} mA above {} mA (over-range or short circuit)",
+                milliamps,
+                Self::FAULT_HIGH
+            )));
+        }
+        let value = self.value(milliamps);
+        log::debug!("Loop: {:.4} mA -> {:.4} {}", milliamps, value, self.unit);
+        Ok(value)
+    }
+}
@@ -0,0 +1,362 @@
+//! D-Bus service
+//!
+//! [`DbusService`] publishes the board on the system or session bus, so
+//! other services on the Pi can read values and react to alarms with any
+//! D-Bus binding instead of linking against this crate. The object
+//! `/io/github/jimimatt/Ads1263` implements the interface
+//! `io.github.jimimatt.Ads1263`:
+//!
+//! | Member | Kind | Signature | Meaning |
+//! |--------|------|-----------|---------|
+//! | `Latest` | Method | `() → a(ysdsx)` | Latest sample of every channel: number, name, value, unit, Unix time in µs |
+//! | `Value` | Method | `(y) → d` | Latest converted value of a channel |
+//! | `Gain` | Property | `u` | PGA gain factor |
+//! | `DataRate` | Property | `d` | Samples per second |
+//! | `Filter` | Property | `s` | Digital filter, e.g. `Sinc4` |
+//! | `Reference` | Property | `d` | Reference voltage |
+//! | `Channels` | Property | `a(yss)` | Configured channels: number, name, unit |
+//! | `AlarmRaised` | Signal | `(yssdi)` | Channel, name, `high` or `low`, value, raw code |
+//! | `AlarmCleared` | Signal | `(yssdi)` | As `AlarmRaised` |
+//!
+//! ```text
+//! busctl call io.github.jimimatt.Ads1263 /io/github/jimimatt/Ads1263 io.github.jimimatt.Ads1263 Latest
+//! ```
+//!
+//! Requires the `dbus` feature.
+
+use crate::alarm::{AlarmEvent, AlarmKind, AlarmMonitor};
+use crate::capture::CaptureHeader;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::registers::Adc1Config;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zbus::blocking::Connection;
+use zbus::blocking::connection::Builder;
+use zbus::object_server::SignalEmitter;
+
+/// Default well-known bus name
+pub const BUS_NAME: &str = "io.github.jimimatt.Ads1263";
+
+/// Path of the published object
+pub const OBJECT_PATH: &str = "/io/github/jimimatt/Ads1263";
+
+/// Bus to publish the service on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bus {
+    /// System bus, for services; owning a name needs a bus policy
+    System,
+    /// Session bus of the current user
+    Session,
+}
+
+/// State shared by the service, its sink, and the bus thread
+struct State {
+    header: CaptureHeader,
+    /// Latest sample and its UTC time, indexed by channel
+    latest: Vec<Option<(Sample, SystemTime)>>,
+}
+
+/// Lock the state, ignoring poisoning
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The published object
+struct Adc {
+    state: Arc<Mutex<State>>,
+}
+
+#[zbus::interface(name = "io.github.jimimatt.Ads1263")]
+impl Adc {
+    /// Latest sample of every channel: number, name, value, unit, Unix µs
+    fn latest(&self) -> Vec<(u8, String, f64, String, i64)> {
+        let state = lock(&self.state);
+        let map = &state.header.map;
+        state
+            .latest
+            .iter()
+            .flatten()
+            .map(|(sample, time)| {
+                (
+                    sample.channel,
+                    map.name(sample.channel).into_owned(),
+                    map.convert_sample(sample),
+                    map.unit(sample.channel).to_string(),
+                    unix_micros(*time),
+                )
+            })
+            .collect()
+    }
+
+    /// Latest converted value of `channel`
+    fn value(&self, channel: u8) -> zbus::fdo::Result<f64> {
+        let state = lock(&self.state);
+        state
+            .latest
+            .get(usize::from(channel))
+            .and_then(Option::as_ref)
+            .map(|(sample, _)| state.header.map.convert_sample(sample))
+            .ok_or_else(|| {
+                zbus::fdo::Error::InvalidArgs(format!("no sample of channel {}", channel))
+            })
+    }
+
+    #[zbus(property)]
+    fn gain(&self) -> u32 {
+        1 << (lock(&self.state).header.adc1.gain as u32)
+    }
+
+    #[zbus(property)]
+    fn data_rate(&self) -> f64 {
+        lock(&self.state).header.adc1.data_rate.sps()
+    }
+
+    #[zbus(property)]
+    fn filter(&self) -> String {
+        format!("{:?}", lock(&self.state).header.adc1.filter)
+    }
+
+    #[zbus(property)]
+    fn reference(&self) -> f64 {
+        lock(&self.state).header.map.reference()
+    }
+
+    #[zbus(property)]
+    fn channels(&self) -> Vec<(u8, String, String)> {
+        lock(&self.state)
+            .header
+            .map
+            .iter()
+            .map(|(channel, info)| (channel, info.name.clone(), info.unit.clone()))
+            .collect()
+    }
+
+    #[zbus(signal)]
+    async fn alarm_raised(
+        emitter: &SignalEmitter<'_>,
+        channel: u8,
+        name: &str,
+        limit: &str,
+        value: f64,
+        raw: i32,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn alarm_cleared(
+        emitter: &SignalEmitter<'_>,
+        channel: u8,
+        name: &str,
+        limit: &str,
+        value: f64,
+        raw: i32,
+    ) -> zbus::Result<()>;
+}
+
+/// Board published on D-Bus
+///
+/// The bus connection serves requests on its own thread. Feed it with
+/// [`sink`](Self::sink); the name is released once the service and its
+/// sinks are dropped. Failing alarm signals are logged and do not stop
+/// the acquisition.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, Bus, CaptureHeader, ChannelMap, DataRate, DbusService, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps100)?;
+///
+/// let header = CaptureHeader::from_device(&mut adc, ChannelMap::new(5.0))?;
+/// let dbus = DbusService::connect(Bus::System, &header)?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), dbus.sink())?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct DbusService {
+    connection: Connection,
+    state: Arc<Mutex<State>>,
+}
+
+impl DbusService {
+    /// Publish the board described by `header` as [`BUS_NAME`] on `bus`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Dbus` if the bus is unreachable or the name
+    /// cannot be owned
+    pub fn connect(bus: Bus, header: &CaptureHeader) -> Result<Self> {
+        Self::connect_as(bus, BUS_NAME, header)
+    }
+
+    /// Publish under another bus name, e.g. one per board
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Dbus` if the bus is unreachable or the name
+    /// cannot be owned
+    pub fn connect_as(bus: Bus, name: &str, header: &CaptureHeader) -> Result<Self> {
+        let state = Arc::new(Mutex::new(State {
+            header: header.clone(),
+            latest: Vec::new(),
+        }));
+        let builder = match bus {
+            Bus::System => Builder::system()?,
+            Bus::Session => Builder::session()?,
+        };
+        let connection = builder
+            .name(name)?
+            .serve_at(
+                OBJECT_PATH,
+                Adc {
+                    state: state.clone(),
+                },
+            )?
+            .build()?;
+        log::info!("D-Bus service {} published on the {:?} bus", name, bus);
+        Ok(Self { connection, state })
+    }
+
+    /// Sink recording the latest samples for `Latest` and `Value`
+    ///
+    /// Combine it with a file sink as a tuple `(file, dbus.sink())`.
+    pub fn sink(&self) -> DbusSink {
+        DbusSink {
+            connection: self.connection.clone(),
+            state: self.state.clone(),
+            clock: WallClock::new(),
+            alarms: None,
+            scan: Vec::new(),
+        }
+    }
+
+    /// Publish a changed ADC1 configuration, e.g. after a reconfiguration
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Dbus` if the change notification fails
+    pub fn set_adc1_config(&self, adc1: Adc1Config) -> Result<()> {
+        lock(&self.state).header.adc1 = adc1;
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, Adc>(OBJECT_PATH)?;
+        let adc = iface.get();
+        let emitter = iface.signal_emitter();
+        zbus::block_on(async {
+            adc.gain_changed(emitter).await?;
+            adc.data_rate_changed(emitter).await?;
+            adc.filter_changed(emitter).await
+        })?;
+        Ok(())
+    }
+
+    /// The bus connection, e.g. to publish further objects
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+}
+
+/// Sink feeding a [`DbusService`]
+pub struct DbusSink {
+    connection: Connection,
+    state: Arc<Mutex<State>>,
+    clock: WallClock,
+    alarms: Option<AlarmMonitor>,
+    /// Raw codes of the current scan for the alarm monitor
+    scan: Vec<u32>,
+}
+
+impl DbusSink {
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Check every scan with `alarms` and emit `AlarmRaised` and
+    /// `AlarmCleared` for its events
+    ///
+    /// The monitor's channels are indices within a scan, as usual; the
+    /// signals carry the channel numbers.
+    pub fn alarms(mut self, alarms: AlarmMonitor) -> Self {
+        self.alarms = Some(alarms);
+        self
+    }
+
+    /// Emit the signal of an alarm event of `samples`
+    fn emit(&self, samples: &[Sample], event: &AlarmEvent) -> Result<()> {
+        let (index, kind, raised) = match *event {
+            AlarmEvent::Raised { channel, kind, .. } => (channel, kind, true),
+            AlarmEvent::Cleared { channel, kind, .. } => (channel, kind, false),
+        };
+        let Some(sample) = samples.get(index) else {
+            return Ok(());
+        };
+        let (name, value) = {
+            let state = lock(&self.state);
+            let map = &state.header.map;
+            (
+                map.name(sample.channel).into_owned(),
+                map.convert_sample(sample),
+            )
+        };
+        let limit = match kind {
+            AlarmKind::High => "high",
+            AlarmKind::Low => "low",
+        };
+        let iface = self
+            .connection
+            .object_server()
+            .interface::<_, Adc>(OBJECT_PATH)?;
+        let emitter = iface.signal_emitter();
+        zbus::block_on(async {
+            if raised {
+                Adc::alarm_raised(emitter, sample.channel, &name, limit, value, sample.raw).await
+            } else {
+                Adc::alarm_cleared(emitter, sample.channel, &name, limit, value, sample.raw).await
+            }
+        })?;
+        Ok(())
+    }
+}
+
+impl Sink for DbusSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.clock.update();
+        {
+            let mut state = lock(&self.state);
+            for sample in samples {
+                let index = usize::from(sample.channel);
+                if state.latest.len() <= index {
+                    state.latest.resize(index + 1, None);
+                }
+                state.latest[index] = Some((*sample, self.clock.to_utc(sample.timestamp)));
+            }
+        }
+        let Some(mut alarms) = self.alarms.take() else {
+            return Ok(());
+        };
+        self.scan.clear();
+        self.scan.extend(samples.iter().map(|s| s.raw as u32));
+        let at = samples
+            .first()
+            .map_or_else(std::time::Instant::now, |s| s.timestamp);
+        for event in alarms.check_at(&self.scan, at) {
+            if let Err(e) = self.emit(samples, event) {
+                log::warn!("Failed to emit D-Bus alarm signal: {}", e);
+            }
+        }
+        self.alarms = Some(alarms);
+        Ok(())
+    }
+}
+
+/// Microseconds since the Unix epoch
+fn unix_micros(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros() as i64
+}
@@ -0,0 +1,312 @@
+//! Oversampling and decimation
+//!
+//! Running the ADS1263 fast and averaging in software trades output rate
+//! for resolution: averaging N conversions with uncorrelated noise lowers
+//! the noise by √N, i.e. ½·log2(N) extra effective bits. [`Decimator`]
+//! does this as a [`Pipeline`](crate::Pipeline) stage, either as a plain
+//! block average (sinc1) or as a higher-order sinc filter that suppresses
+//! aliasing of out-of-band noise better.
+//!
+//! [`Downsampler`] instead limits the output by time, e.g. to feed a 1 Hz
+//! telemetry topic from a 1200 SPS acquisition.
+
+use crate::filter::MovingAverage;
+use crate::pipeline::{PerChannel, Reading, Stage};
+use std::time::{Duration, Instant};
+
+/// Oversample-and-decimate stage
+///
+/// Emits one reading per `factor` input readings of each channel. With
+/// order 1 the output is the mean of each block of `factor` inputs. With
+/// order k > 1 the inputs first pass through k - 1 moving averages of
+/// length `factor` (together a sinc^k response); the first k - 1 outputs,
+/// taken while those averages are still filling, are suppressed.
+///
+/// The output timestamp is that of the last input in the block.
+#[derive(Debug, Clone)]
+pub struct Decimator {
+    factor: usize,
+    order: u32,
+    channels: PerChannel<DecimatorState>,
+    /// Output buffer of the cascade stages
+    scratch: Vec<Reading>,
+}
+
+#[derive(Debug, Clone)]
+struct DecimatorState {
+    /// Cascade of moving averages in front of the block average
+    cascade: Vec<MovingAverage>,
+    sum: f64,
+    count: usize,
+    /// Blocks completed, saturating at the warm-up length
+    blocks: u32,
+}
+
+impl Decimator {
+    /// Block average of `factor` samples (sinc1)
+    ///
+    /// A factor of 0 is treated as 1.
+    pub fn new(factor: usize) -> Self {
+        Self::sinc(factor, 1)
+    }
+
+    /// Sinc^`order` decimation by `factor`
+    ///
+    /// An order of 0 is treated as 1.
+    pub fn sinc(factor: usize, order: u32) -> Self {
+        Self {
+            factor: factor.max(1),
+            order: order.max(1),
+            channels: PerChannel::default(),
+            scratch: Vec::with_capacity(1),
+        }
+    }
+
+    /// Decimation factor
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    /// Filter order
+    pub fn order(&self) -> u32 {
+        self.order
+    }
+
+    /// Ideal resolution gain in bits for white noise, ½·log2(factor)
+    pub fn extra_bits(&self) -> f64 {
+        (self.factor as f64).log2() / 2.0
+    }
+}
+
+impl Stage for Decimator {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        if !input.value.is_finite() {
+            return;
+        }
+        let (factor, order) = (self.factor, self.order);
+        let state = self
+            .channels
+            .get_or_insert_with(input.channel, || DecimatorState {
+                cascade: (1..order).map(|_| MovingAverage::new(factor)).collect(),
+                sum: 0.0,
+                count: 0,
+                blocks: 0,
+            });
+
+        // Each average emits exactly one reading per finite input
+        let mut reading = input;
+        for average in &mut state.cascade {
+            average.process(reading, &mut self.scratch);
+            reading = self.scratch.pop().unwrap_or(reading);
+        }
+
+        state.sum += reading.value;
+        state.count += 1;
+        if state.count < factor {
+            return;
+        }
+        let value = state.sum / factor as f64;
+        state.sum = 0.0;
+        state.count = 0;
+
+        if state.blocks + 1 < order {
+            state.blocks += 1;
+            return;
+        }
+        out.push(Reading { value, ..reading });
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+/// What a [`Downsampler`] forwards per interval
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DownsampleMode {
+    /// The last reading of the interval
+    Latest,
+    /// The mean of the interval (default)
+    #[default]
+    Mean,
+    /// The smallest and largest reading, in the order they occurred
+    MinMax,
+}
+
+/// Rate limiter forwarding at most one value (or min/max pair) per interval
+///
+/// Each channel's interval starts at its first reading and closes with the
+/// first reading at least `interval` later, which is included. The output
+/// carries the timestamp of the reading it came from (latest, min, max) or
+/// of the closing reading (mean).
+#[derive(Debug, Clone)]
+pub struct Downsampler {
+    interval: Duration,
+    mode: DownsampleMode,
+    channels: PerChannel<DownsampleState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DownsampleState {
+    started: Instant,
+    count: u64,
+    sum: f64,
+    min: Reading,
+    max: Reading,
+}
+
+impl DownsampleState {
+    /// Empty interval starting at `input`
+    fn start(input: Reading) -> Self {
+        Self {
+            started: input.timestamp,
+            count: 0,
+            sum: 0.0,
+            min: input,
+            max: input,
+        }
+    }
+}
+
+impl Downsampler {
+    /// Forward at most one reading per `interval` and channel
+    pub fn new(interval: Duration, mode: DownsampleMode) -> Self {
+        Self {
+            interval,
+            mode,
+            channels: PerChannel::default(),
+        }
+    }
+
+    /// Length of an interval
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Aggregation mode
+    pub fn mode(&self) -> DownsampleMode {
+        self.mode
+    }
+}
+
+impl Stage for Downsampler {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        if !input.value.is_finite() {
+            return;
+        }
+        let state = self
+            .channels
+            .get_or_insert_with(input.channel, || DownsampleState::start(input));
+        if state.count == 0 {
+            *state = DownsampleState::start(input);
+        }
+        state.count += 1;
+        state.sum += input.value;
+        if input.value < state.min.value {
+            state.min = input;
+        }
+        if input.value > state.max.value {
+            state.max = input;
+        }
+
+        if input.timestamp.saturating_duration_since(state.started) < self.interval {
+            return;
+        }
+        match self.mode {
+            DownsampleMode::Latest => out.push(input),
+            DownsampleMode::Mean => out.push(Reading {
+                value: state.sum / state.count as f64,
+                ..input
+            }),
+            DownsampleMode::MinMax => {
+                let (first, second) = if state.max.timestamp < state.min.timestamp {
+                    (state.max, state.min)
+                } else {
+                    (state.min, state.max)
+                };
+                out.push(first);
+                out.push(second);
+            }
+        }
+        state.count = 0;
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `values` of channel 0, one millisecond apart, through `stage`
+    fn run(stage: &mut impl Stage, values: &[f64]) -> Vec<Reading> {
+        let start = Instant::now();
+        let mut out = Vec::new();
+        for (ms, &value) in values.iter().enumerate() {
+            let reading = Reading {
+                channel: 0,
+                timestamp: start + Duration::from_millis(ms as u64),
+                value,
+            };
+            stage.process(reading, &mut out);
+        }
+        out
+    }
+
+    #[test]
+    fn decimator_emits_one_block_mean_per_factor() {
+        let values: Vec<f64> = (0..20).map(f64::from).collect();
+        let out = run(&mut Decimator::new(4), &values);
+        let means: Vec<f64> = out.iter().map(|r| r.value).collect();
+        assert_eq!(means, [1.5, 5.5, 9.5, 13.5, 17.5]);
+        // Timestamped with the last input of the block
+        let spacing = out[1].timestamp - out[0].timestamp;
+        assert_eq!(spacing, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn sinc_decimator_has_unity_dc_gain() {
+        let out = run(&mut Decimator::sinc(4, 3), &[7.0; 40]);
+        // The first order - 1 blocks are suppressed while the cascade fills
+        assert_eq!(out.len(), 40 / 4 - 2);
+        for reading in out {
+            assert!((reading.value - 7.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn downsampler_closes_intervals_at_the_first_late_reading() {
+        let values: Vec<f64> = (0..25).map(f64::from).collect();
+        let interval = Duration::from_millis(10);
+
+        // 0..=10, then 11..=21; 22..25 is still open
+        let latest = run(
+            &mut Downsampler::new(interval, DownsampleMode::Latest),
+            &values,
+        );
+        let latest: Vec<f64> = latest.iter().map(|r| r.value).collect();
+        assert_eq!(latest, [10.0, 21.0]);
+
+        let mean = run(
+            &mut Downsampler::new(interval, DownsampleMode::Mean),
+            &values,
+        );
+        let mean: Vec<f64> = mean.iter().map(|r| r.value).collect();
+        assert_eq!(mean, [5.0, 16.0]);
+    }
+
+    #[test]
+    fn downsampler_min_max_keeps_order_of_occurrence() {
+        let values = [5.0, 9.0, 1.0, 4.0, 3.0];
+        let interval = Duration::from_millis(4);
+        let out = run(
+            &mut Downsampler::new(interval, DownsampleMode::MinMax),
+            &values,
+        );
+        let extremes: Vec<f64> = out.iter().map(|r| r.value).collect();
+        assert_eq!(extremes, [9.0, 1.0]);
+        assert!(out[0].timestamp < out[1].timestamp);
+    }
+}
@@ -0,0 +1,95 @@
+//! Pluggable delay providers
+//!
+//! The HAL waits through a [`DelayProvider`] for reset pulses, register
+//! settling, and START pulses. [`StdDelay`] (the default) sleeps the thread,
+//! which is cheap but can overshoot by tens of microseconds; [`SpinDelay`]
+//! busy-waits for precise short delays; [`HybridDelay`] sleeps for most of
+//! a delay and spins the remainder.
+//!
+//! Install a provider with [`Hal::set_delay_provider`](crate::Hal::set_delay_provider).
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Source of blocking delays used by the HAL
+pub trait DelayProvider: Send {
+    /// Block for at least `us` microseconds
+    fn delay_us(&self, us: u64);
+
+    /// Block for at least `ms` milliseconds
+    fn delay_ms(&self, ms: u64) {
+        self.delay_us(ms.saturating_mul(1000));
+    }
+}
+
+/// Delays with `thread::sleep` (default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdDelay;
+
+impl DelayProvider for StdDelay {
+    fn delay_us(&self, us: u64) {
+        thread::sleep(Duration::from_micros(us));
+    }
+
+    fn delay_ms(&self, ms: u64) {
+        thread::sleep(Duration::from_millis(ms));
+    }
+}
+
+/// Delays by busy-waiting on the monotonic clock
+///
+/// Precise to about a microsecond, but occupies the core for the whole delay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpinDelay;
+
+impl DelayProvider for SpinDelay {
+    fn delay_us(&self, us: u64) {
+        spin_for(Duration::from_micros(us));
+    }
+}
+
+/// Sleeps for most of a delay and spins the final stretch
+///
+/// Delays up to `spin_threshold` are spun entirely.
+#[derive(Debug, Clone, Copy)]
+pub struct HybridDelay {
+    /// Portion of each delay that is busy-waited
+    pub spin_threshold: Duration,
+}
+
+impl Default for HybridDelay {
+    fn default() -> Self {
+        Self {
+            // Covers typical scheduler wake-up latency on a Pi
+            spin_threshold: Duration::from_micros(200),
+        }
+    }
+}
+
+impl DelayProvider for HybridDelay {
+    fn delay_us(&self, us: u64) {
+        let total = Duration::from_micros(us);
+        let deadline = Instant::now() + total;
+        if let Some(sleep) = total.checked_sub(self.spin_threshold) {
+            thread::sleep(sleep);
+        }
+        spin_for(deadline.saturating_duration_since(Instant::now()));
+    }
+}
+
+impl<F> DelayProvider for F
+where
+    F: Fn(Duration) + Send,
+{
+    fn delay_us(&self, us: u64) {
+        self(Duration::from_micros(us));
+    }
+}
+
+/// Busy-wait for `duration`
+fn spin_for(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
@@ -0,0 +1,202 @@
+//! Synchronized dual-ADC scanning
+//!
+//! The ADS1263 has two independent converters. [`DualScan`] keeps ADC1 on
+//! one fast channel, converting back-to-back at its data rate, while ADC2
+//! cycles through the remaining inputs at its own (slower) rate, e.g.
+//! vibration at 4800 SPS plus eight temperatures at 10 SPS. Both streams
+//! are merged into one timestamp-ordered sequence of [`DualSample`]s.
+//!
+//! ADC2 has no DRDY output: its status is polled after every ADC1
+//! conversion, so ADC2 timestamps have the resolution of the ADC1 data
+//! period.
+
+use crate::ads1263::Ads1263;
+use crate::error::Result;
+use crate::protocol;
+use crate::registers::{Adc2DataRate, Adc2Gain};
+use crate::sample::Sample;
+
+/// Converter a [`DualSample`] was taken on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Converter {
+    /// 32-bit main ADC
+    Adc1,
+    /// 24-bit auxiliary ADC
+    Adc2,
+}
+
+/// Sample of a dual-ADC scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DualSample {
+    /// Converter that took the sample
+    pub converter: Converter,
+    /// The conversion; ADC2 codes are sign-extended from 24 bits
+    pub sample: Sample,
+}
+
+impl DualSample {
+    /// Convert the raw value to volts with the converter's scaling
+    pub fn voltage(&self, reference: f64) -> f64 {
+        match self.converter {
+            Converter::Adc1 => self.sample.voltage(reference),
+            Converter::Adc2 => {
+                Ads1263::raw_to_voltage_adc2(self.sample.raw as u32 & 0x00FF_FFFF, reference)
+            }
+        }
+    }
+}
+
+/// Settings for [`Ads1263::dual_scan`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualScanConfig {
+    /// Channel converted continuously by ADC1
+    pub fast_channel: u8,
+    /// Channels ADC2 cycles through, in order
+    pub slow_channels: Vec<u8>,
+    /// ADC2 data rate
+    pub adc2_rate: Adc2DataRate,
+    /// ADC2 gain
+    pub adc2_gain: Adc2Gain,
+}
+
+impl DualScanConfig {
+    /// Scan `fast_channel` on ADC1 and `slow_channels` on ADC2 at 10 SPS, unity gain
+    pub fn new(fast_channel: u8, slow_channels: impl Into<Vec<u8>>) -> Self {
+        Self {
+            fast_channel,
+            slow_channels: slow_channels.into(),
+            adc2_rate: Adc2DataRate::Sps10,
+            adc2_gain: Adc2Gain::Gain1,
+        }
+    }
+
+    /// Set the ADC2 data rate
+    pub fn adc2_rate(mut self, rate: Adc2DataRate) -> Self {
+        self.adc2_rate = rate;
+        self
+    }
+
+    /// Set the ADC2 gain
+    pub fn adc2_gain(mut self, gain: Adc2Gain) -> Self {
+        self.adc2_gain = gain;
+        self
+    }
+}
+
+/// Blocking iterator over a dual-ADC scan
+///
+/// Created by [`Ads1263::dual_scan`]. Each call to `next` yields the next
+/// sample of either converter in timestamp order; the iterator ends after
+/// the first error. ADC2 is stopped when it is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Adc2DataRate, Ads1263, Converter, DataRate, DualScanConfig, Hal};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps4800)?;
+///
+/// let config = DualScanConfig::new(0, [2, 3, 4, 5, 6, 7, 8, 9]).adc2_rate(Adc2DataRate::Sps10);
+/// for sample in adc.dual_scan(config)?.take(48_000) {
+///     let sample = sample?;
+///     match sample.converter {
+///         Converter::Adc1 => { /* vibration */ }
+///         Converter::Adc2 => println!("ch{}: {:.4} V", sample.sample.channel, sample.voltage(5.0)),
+///     }
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct DualScan<'a> {
+    adc: &'a mut Ads1263,
+    config: DualScanConfig,
+    /// Index of the slow channel ADC2 is converting
+    slow: usize,
+    /// ADC2 sample found after the last ADC1 sample, delivered next
+    pending: Option<Sample>,
+    done: bool,
+}
+
+impl<'a> DualScan<'a> {
+    pub(crate) fn new(adc: &'a mut Ads1263, config: DualScanConfig) -> Result<Self> {
+        let mode = adc.get_mode();
+        protocol::channel_mux(mode, config.fast_channel)?;
+        for &channel in &config.slow_channels {
+            protocol::channel_mux(mode, channel)?;
+        }
+
+        adc.select_channel(config.fast_channel)?;
+        if let Some(&first) = config.slow_channels.first() {
+            adc.stop_adc2()?;
+            adc.set_adc2_rate(config.adc2_gain, config.adc2_rate)?;
+            adc.select_channel_adc2(first)?;
+            adc.start_adc2()?;
+        }
+        log::info!(
+            "Dual scan: ch{} on ADC1, {:?} on ADC2",
+            config.fast_channel,
+            config.slow_channels
+        );
+        Ok(Self {
+            adc,
+            config,
+            slow: 0,
+            pending: None,
+            done: false,
+        })
+    }
+
+    /// Read the next ADC1 conversion and check ADC2 afterwards
+    fn read(&mut self) -> Result<Sample> {
+        let sample = self.adc.read_next_sample(self.config.fast_channel)?;
+        if let Some(&channel) = self.config.slow_channels.get(self.slow)
+            && let Some(slow) = self.adc.poll_adc2_sample(channel)?
+        {
+            self.pending = Some(slow);
+            self.slow = (self.slow + 1) % self.config.slow_channels.len();
+            // Restart so the next conversion is fully settled on the new input
+            self.adc
+                .select_channel_adc2(self.config.slow_channels[self.slow])?;
+            self.adc.start_adc2()?;
+        }
+        Ok(sample)
+    }
+}
+
+impl Iterator for DualScan<'_> {
+    type Item = Result<DualSample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.pending.take() {
+            return Some(Ok(DualSample {
+                converter: Converter::Adc2,
+                sample,
+            }));
+        }
+        if self.done {
+            return None;
+        }
+        match self.read() {
+            Ok(sample) => Some(Ok(DualSample {
+                converter: Converter::Adc1,
+                sample,
+            })),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for DualScan<'_> {}
+
+impl Drop for DualScan<'_> {
+    fn drop(&mut self) {
+        if !self.config.slow_channels.is_empty()
+            && let Err(e) = self.adc.stop_adc2()
+        {
+            log::warn!("Failed to stop ADC2: {}", e);
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Generic async driver on top of `embedded-hal-async`
+//!
+//! [`Ads1263EhAsync`] talks to the ADS1263 through the `embedded-hal` 1.0
+//! traits instead of rppal, so it can run on any executor (e.g. Embassy)
+//! that provides an async [`SpiDevice`], an async DRDY input implementing
+//! [`Wait`], an RST output pin, and an async [`DelayNs`].
+//!
+//! The SPI device is expected to manage chip select. DRDY waits have no
+//! built-in timeout; wrap calls in your executor's timeout combinator
+//! (e.g. `embassy_time::with_timeout`) if the signal may never arrive.
+//! ADC2 reads poll the status byte and give up after 500 ms.
+//!
+//! Register values, the reset sequence, and the chip ID check are the same
+//! as for [`Ads1263`](crate::Ads1263). The driver does not need std; build
+//! the crate with `default-features = false, features = ["embedded-hal-async"]`
+//! for bare-metal targets.
+//!
+//! Requires the `embedded-hal-async` feature.
+
+use crate::error::{Ads1263Error, Result};
+use crate::protocol::{self, ADC2_POLL_INTERVAL_US, ADC2_TIMEOUT_MS, RESET_HOLD_MS};
+use crate::registers::*;
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_async::spi::{Operation, SpiDevice};
+
+/// ADS1263 driver generic over `embedded-hal-async` peripherals
+///
+/// # Example
+///
+/// ```ignore
+/// let mut adc = Ads1263EhAsync::new(spi_device, drdy_pin, rst_pin, delay);
+/// adc.init_adc1(DataRate::Sps400).await?;
+/// let raw = adc.read_channel(0).await?;
+/// ```
+pub struct Ads1263EhAsync<SPI, DRDY, RST, D> {
+    spi: SPI,
+    drdy: DRDY,
+    rst: RST,
+    delay: D,
+    scan_mode: InputMode,
+}
+
+impl<SPI, DRDY, RST, D> Ads1263EhAsync<SPI, DRDY, RST, D>
+where
+    SPI: SpiDevice,
+    DRDY: Wait,
+    RST: OutputPin,
+    D: DelayNs,
+{
+    /// Create a new driver instance
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - SPI device (Mode1, chip select handled by the device)
+    /// * `drdy` - Data ready input pin
+    /// * `rst` - Reset output pin
+    /// * `delay` - Async delay provider
+    pub fn new(spi: SPI, drdy: DRDY, rst: RST, delay: D) -> Self {
+        Self {
+            spi,
+            drdy,
+            rst,
+            delay,
+            scan_mode: InputMode::SingleEnded,
+        }
+    }
+
+    /// Release the underlying peripherals
+    pub fn release(self) -> (SPI, DRDY, RST, D) {
+        (self.spi, self.drdy, self.rst, self.delay)
+    }
+
+    // ========================================================================
+    // Low-level operations
+    // ========================================================================
+
+    /// Hardware reset the device
+    async fn reset(&mut self) -> Result<()> {
+        log::debug!("Performing hardware reset");
+        self.rst.set_high().map_err(pin_error)?;
+        self.delay.delay_ms(RESET_HOLD_MS).await;
+        self.rst.set_low().map_err(pin_error)?;
+        self.delay.delay_ms(RESET_HOLD_MS).await;
+        self.rst.set_high().map_err(pin_error)?;
+        self.delay.delay_ms(RESET_HOLD_MS).await;
+        Ok(())
+    }
+
+    /// Send a command to the ADC
+    async fn write_cmd(&mut self, cmd: Command) -> Result<()> {
+        self.spi.write(&[cmd as u8]).await.map_err(spi_error)
+    }
+
+    /// Write to a register
+    async fn write_reg(&mut self, reg: Register, data: u8) -> Result<()> {
+        self.spi
+            .write(&[Command::WReg as u8 | reg as u8, 0x00, data])
+            .await
+            .map_err(spi_error)
+    }
+
+    /// Read from a register
+    async fn read_reg(&mut self, reg: Register) -> Result<u8> {
+        let mut data = [0u8];
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[Command::RReg as u8 | reg as u8, 0x00]),
+                Operation::Read(&mut data),
+            ])
+            .await
+            .map_err(spi_error)?;
+        Ok(data[0])
+    }
+
+    /// Write to a register and log a mismatch on readback
+    async fn write_reg_verify(
+        &mut self,
+        reg: Register,
+        data: u8,
+        name: &'static str,
+    ) -> Result<()> {
+        self.write_reg(reg, data).await?;
+        self.delay.delay_ms(1).await;
+
+        let read_back = self.read_reg(reg).await?;
+        if read_back == data {
+            log::info!("{} configured successfully (0x{:02X})", name, data);
+        } else {
+            log::warn!(
+                "{} configuration mismatch: wrote 0x{:02X}, read 0x{:02X}",
+                name,
+                data,
+                read_back
+            );
+        }
+        Ok(())
+    }
+
+    /// Reset the device and verify the chip ID
+    async fn reset_and_identify(&mut self) -> Result<()> {
+        self.reset().await?;
+        protocol::verify_chip_id(self.read_chip_id().await?)
+    }
+
+    // ========================================================================
+    // Public configuration methods
+    // ========================================================================
+
+    /// Read the chip ID
+    ///
+    /// # Returns
+    ///
+    /// The chip ID (should be 1 for ADS1263)
+    pub async fn read_chip_id(&mut self) -> Result<u8> {
+        Ok(protocol::chip_id(self.read_reg(Register::Id).await?))
+    }
+
+    /// Set the input mode (single-ended or differential)
+    pub fn set_mode(&mut self, mode: InputMode) {
+        self.scan_mode = mode;
+        log::info!("Input mode set to {:?}", mode);
+    }
+
+    /// Get the current input mode
+    pub fn get_mode(&self) -> InputMode {
+        self.scan_mode
+    }
+
+    /// Initialize ADC1 with specified data rate
+    ///
+    /// Uses the same register configuration as
+    /// [`Ads1263::init_adc1`](crate::Ads1263::init_adc1).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChipId` if chip ID is not 1
+    pub async fn init_adc1(&mut self, rate: DataRate) -> Result<()> {
+        self.init_adc1_with(Adc1Config {
+            data_rate: rate,
+            ..Adc1Config::default()
+        })
+        .await
+    }
+
+    /// Initialize ADC1 with a full configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChipId` if chip ID is not 1
+    pub async fn init_adc1_with(&mut self, config: Adc1Config) -> Result<()> {
+        self.reset_and_identify().await?;
+        self.write_cmd(Command::Stop1).await?;
+        for (reg, data, name) in protocol::adc1_writes(&config) {
+            self.write_reg_verify(reg, data, name).await?;
+        }
+        self.write_cmd(Command::Start1).await?;
+        log::info!("ADC1 initialized with data rate {:?}", config.data_rate);
+        Ok(())
+    }
+
+    /// Initialize ADC2 with specified data rate
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChipId` if chip ID is not 1
+    pub async fn init_adc2(&mut self, rate: Adc2DataRate) -> Result<()> {
+        self.reset_and_identify().await?;
+        self.write_cmd(Command::Stop2).await?;
+
+        for (reg, data, name) in protocol::adc2_writes(Adc2Gain::Gain1, rate, Delay::Delay35us) {
+            self.write_reg_verify(reg, data, name).await?;
+        }
+
+        log::info!("ADC2 initialized with data rate {:?}", rate);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Data acquisition
+    // ========================================================================
+
+    /// Read an ADC1 channel value (raw 32-bit)
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub async fn read_channel(&mut self, channel: u8) -> Result<u32> {
+        let inpmux = protocol::channel_mux(self.scan_mode, channel)?;
+        self.write_reg(Register::InpMux, inpmux).await?;
+        self.drdy.wait_for_low().await.map_err(pin_error)?;
+
+        // Status byte + 4 data bytes + CRC, fetched once after DRDY
+        let mut frame = [0u8; 6];
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[Command::RData1 as u8]),
+                Operation::Read(&mut frame),
+            ])
+            .await
+            .map_err(spi_error)?;
+        if !StatusFlags::from_bits(frame[0]).adc1_new_data() {
+            log::warn!("ADC1 status 0x{:02X} reports no new data", frame[0]);
+            return Err(Ads1263Error::NoNewData);
+        }
+
+        let data = u32::from_be_bytes([frame[1], frame[2], frame[3], frame[4]]);
+        if !protocol::checksum(data, frame[5]) {
+            log::warn!(
+                "ADC1 checksum error: data=0x{:08X}, crc=0x{:02X}",
+                data,
+                frame[5]
+            );
+        }
+        Ok(data)
+    }
+
+    /// Read an ADC2 channel value (raw 24-bit)
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if no conversion is ready within
+    /// 500 ms
+    pub async fn read_channel_adc2(&mut self, channel: u8) -> Result<u32> {
+        let adc2mux = protocol::channel_mux(self.scan_mode, channel)?;
+        self.write_reg(Register::Adc2Mux, adc2mux).await?;
+        self.write_cmd(Command::Start2).await?;
+
+        // Status byte + 3 data bytes + padding + CRC
+        // ADC2 has no DRDY output, so the status byte is the only ready signal
+        let mut frame = [0u8; 6];
+        let mut polls = ADC2_TIMEOUT_MS * 1000 / ADC2_POLL_INTERVAL_US;
+        loop {
+            self.spi
+                .transaction(&mut [
+                    Operation::Write(&[Command::RData2 as u8]),
+                    Operation::Read(&mut frame),
+                ])
+                .await
+                .map_err(spi_error)?;
+            if StatusFlags::from_bits(frame[0]).adc2_new_data() {
+                break;
+            }
+            if polls == 0 {
+                log::warn!("No ADC2 conversion within {} ms", ADC2_TIMEOUT_MS);
+                return Err(Ads1263Error::Timeout);
+            }
+            polls -= 1;
+            self.delay.delay_us(ADC2_POLL_INTERVAL_US).await;
+        }
+
+        let data = u32::from_be_bytes([0, frame[1], frame[2], frame[3]]);
+        if !protocol::checksum(data, frame[5]) {
+            log::warn!(
+                "ADC2 checksum error: data=0x{:06X}, crc=0x{:02X}",
+                data,
+                frame[5]
+            );
+        }
+        Ok(data)
+    }
+
+    /// Read multiple ADC1 channels into `values`
+    ///
+    /// Reads `min(channels.len(), values.len())` channels without allocating.
+    pub async fn read_all(&mut self, channels: &[u8], values: &mut [u32]) -> Result<()> {
+        for (value, &ch) in values.iter_mut().zip(channels) {
+            *value = self.read_channel(ch).await?;
+        }
+        Ok(())
+    }
+
+    /// Stop ADC1 conversions
+    pub async fn stop_adc1(&mut self) -> Result<()> {
+        self.write_cmd(Command::Stop1).await
+    }
+
+    /// Start ADC1 conversions
+    pub async fn start_adc1(&mut self) -> Result<()> {
+        self.write_cmd(Command::Start1).await
+    }
+}
+
+fn spi_error<E: embedded_hal::spi::Error>(e: E) -> Ads1263Error {
+    Ads1263Error::SpiBus(e.kind())
+}
+
+fn pin_error<E: embedded_hal::digital::Error>(e: E) -> Ads1263Error {
+    Ads1263Error::Pin(e.kind())
+}
@@ -1,18 +1,50 @@
 //! Error types for the ADS1263 driver
 
+use core::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur when interacting with the ADS1263
 #[derive(Error, Debug)]
 pub enum Ads1263Error {
     /// SPI communication error
+    #[cfg(feature = "rpi")]
     #[error("SPI error: {0}")]
     Spi(#[from] rppal::spi::Error),
 
     /// GPIO error
+    #[cfg(feature = "rpi")]
     #[error("GPIO error: {0}")]
     Gpio(#[from] rppal::gpio::Error),
 
+    /// I/O or operating system error
+    #[cfg(feature = "rpi")]
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Board identification error
+    #[cfg(feature = "rpi")]
+    #[error("System error: {0}")]
+    System(#[from] rppal::system::Error),
+
+    /// SPI error reported by an `embedded-hal` bus implementation
+    #[cfg(feature = "embedded-hal-async")]
+    #[error("SPI bus error: {0:?}")]
+    SpiBus(embedded_hal::spi::ErrorKind),
+
+    /// Pin error reported by an `embedded-hal` GPIO implementation
+    #[cfg(feature = "embedded-hal-async")]
+    #[error("Pin error: {0:?}")]
+    Pin(embedded_hal::digital::ErrorKind),
+
+    /// SPI clock speed outside the range supported by the ADS1263
+    #[error("Invalid SPI clock: {hz} Hz (max: {max} Hz)")]
+    InvalidSpiClock { hz: u32, max: u32 },
+
+    /// SPI mode other than Mode1 (CPOL=0, CPHA=1)
+    #[cfg(feature = "rpi")]
+    #[error("Invalid SPI mode: {0:?} (ADS1263 requires Mode1)")]
+    InvalidSpiMode(rppal::spi::Mode),
+
     /// Device initialization failed
     #[error("Device initialization failed")]
     InitFailed,
@@ -25,10 +57,46 @@ pub enum Ads1263Error {
     #[error("Invalid channel: {0} (max: {1})")]
     InvalidChannel(u8, u8),
 
+    /// No sensor bound to the channel of a [`SensorRegistry`](crate::SensorRegistry)
+    #[error("No sensor on channel {0}")]
+    NoSensor(u8),
+
+    /// Sensor reading outside what the sensor can produce, e.g. an open circuit
+    #[cfg(feature = "rpi")]
+    #[error("Sensor fault: {0}")]
+    SensorFault(String),
+
+    /// On-chip GPIO pin outside 0-7
+    #[error("Invalid GPIO: {0} (max: 7)")]
+    InvalidGpio(u8),
+
+    /// Pin-controlled conversions requested without a START pin
+    #[error("No START pin configured")]
+    NoStartPin,
+
+    /// Invalid device index in a multi-device bank
+    #[error("Invalid device: {0} (bank has {1})")]
+    InvalidDevice(usize, usize),
+
     /// Timeout waiting for DRDY signal
     #[error("Timeout waiting for DRDY")]
     Timeout,
 
+    /// DRDY did not assert within the time expected from the ADC configuration
+    #[error(
+        "Timeout waiting for DRDY: expected a conversion within {expected:?}, waited {waited:?}"
+    )]
+    DrdyTimeout {
+        /// Expected conversion period for the configured rate, filter, and delay
+        expected: Duration,
+        /// Time actually waited before giving up
+        waited: Duration,
+    },
+
+    /// Conversion frame read after DRDY did not carry new data
+    #[error("No new conversion data in status byte")]
+    NoNewData,
+
     /// CRC checksum verification failed
     #[error("CRC checksum error")]
     ChecksumError,
@@ -36,7 +104,101 @@ pub enum Ads1263Error {
     /// Register write verification failed
     #[error("Register write verification failed for {register}")]
     RegisterVerifyFailed { register: &'static str },
+
+    /// Number of channels does not match the number of devices
+    #[error("Channel count mismatch: expected {expected}, got {got}")]
+    ChannelCountMismatch { expected: usize, got: usize },
+
+    /// Output buffer is shorter than the requested number of values
+    #[error("Buffer too small: need {needed}, got {got}")]
+    BufferTooSmall { needed: usize, got: usize },
+
+    /// A background worker owning the driver stopped unexpectedly
+    #[error("Driver worker terminated")]
+    WorkerTerminated,
+
+    /// Capture file has no room for more samples
+    #[error("Capture full: capacity {capacity} samples")]
+    CaptureFull { capacity: u64 },
+
+    /// Capture file is malformed or of an unsupported format
+    #[cfg(feature = "rpi")]
+    #[error("Invalid capture: {0}")]
+    InvalidCapture(String),
+
+    /// HDF5 library error
+    #[cfg(feature = "hdf5")]
+    #[error("HDF5 error: {0}")]
+    Hdf5(#[from] hdf5_metno::Error),
+
+    /// Arrow record batch error
+    #[cfg(feature = "parquet")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    /// Parquet writer error
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    /// SQLite database error
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// PostgreSQL connection parameters are invalid
+    #[cfg(feature = "postgres")]
+    #[error("PostgreSQL error: {0}")]
+    Postgres(#[from] postgres::Error),
+
+    /// D-Bus connection or object server error
+    #[cfg(feature = "dbus")]
+    #[error("D-Bus error: {0}")]
+    Dbus(#[from] zbus::Error),
+
+    /// liblsl call failed
+    #[cfg(feature = "lsl")]
+    #[error("LSL error: {0}")]
+    Lsl(String),
+
+    /// gRPC server transport error
+    #[cfg(feature = "grpc")]
+    #[error("gRPC error: {0}")]
+    Grpc(#[from] tonic::transport::Error),
+
+    /// Polynomial or lookup table is malformed
+    #[cfg(feature = "rpi")]
+    #[error("Invalid curve: {0}")]
+    InvalidCurve(String),
+
+    /// Configuration file does not parse or is inconsistent
+    #[cfg(feature = "rpi")]
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
+}
+
+impl Ads1263Error {
+    /// true for faults a device re-initialization may clear
+    ///
+    /// Covers bus, GPIO, DRDY, and data-integrity errors; configuration and
+    /// usage errors are not transient.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Timeout
+            | Self::DrdyTimeout { .. }
+            | Self::NoNewData
+            | Self::ChecksumError
+            | Self::RegisterVerifyFailed { .. }
+            | Self::InvalidChipId(_)
+            | Self::InitFailed => true,
+            #[cfg(feature = "rpi")]
+            Self::Spi(_) | Self::Gpio(_) | Self::Io(_) => true,
+            #[cfg(feature = "embedded-hal-async")]
+            Self::SpiBus(_) | Self::Pin(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Result type alias for ADS1263 operations
-pub type Result<T> = std::result::Result<T, Ads1263Error>;
+pub type Result<T> = core::result::Result<T, Ads1263Error>;
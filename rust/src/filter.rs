@@ -0,0 +1,289 @@
+//! Filter stages for the processing [`Pipeline`](crate::Pipeline)
+//!
+//! All filters keep independent history per channel and skip non-finite
+//! inputs, so their output never contains NaN.
+
+use crate::pipeline::{PerChannel, Reading, Stage};
+use std::collections::VecDeque;
+
+/// Boxcar (moving-average) filter
+///
+/// Outputs the mean of the last `window` finite values of each channel.
+/// Until the window has filled, the mean of the values seen so far is
+/// output, unless [`full_window_only`](Self::full_window_only) is set, in
+/// which case nothing is output during startup.
+#[derive(Debug, Clone)]
+pub struct MovingAverage {
+    window: usize,
+    full_window_only: bool,
+    channels: PerChannel<AverageState>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AverageState {
+    values: VecDeque<f64>,
+    sum: f64,
+    /// Updates since the sum was last recomputed from scratch
+    updates: usize,
+}
+
+impl MovingAverage {
+    /// Average over `window` samples (a window of 0 is treated as 1)
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            full_window_only: false,
+            channels: PerChannel::default(),
+        }
+    }
+
+    /// Suppress output until `window` samples have been seen
+    pub fn full_window_only(mut self, enable: bool) -> Self {
+        self.full_window_only = enable;
+        self
+    }
+
+    /// Window length in samples
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+impl Stage for MovingAverage {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        if !input.value.is_finite() {
+            return;
+        }
+        let window = self.window;
+        let state = self
+            .channels
+            .get_or_insert_with(input.channel, || AverageState {
+                values: VecDeque::with_capacity(window),
+                ..AverageState::default()
+            });
+
+        if state.values.len() == window
+            && let Some(oldest) = state.values.pop_front()
+        {
+            state.sum -= oldest;
+        }
+        state.values.push_back(input.value);
+        state.sum += input.value;
+
+        // Recompute periodically so rounding errors of the running sum don't accumulate
+        state.updates += 1;
+        if state.updates >= window {
+            state.sum = state.values.iter().sum();
+            state.updates = 0;
+        }
+
+        if self.full_window_only && state.values.len() < window {
+            return;
+        }
+        out.push(Reading {
+            value: state.sum / state.values.len() as f64,
+            ..input
+        });
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+/// Rolling-median filter
+///
+/// Outputs the median of the last `window` finite values of each channel,
+/// which removes isolated spikes without smearing steps the way an average
+/// does. During startup the median of the values seen so far is output.
+#[derive(Debug, Clone)]
+pub struct MedianFilter {
+    window: usize,
+    channels: PerChannel<VecDeque<f64>>,
+    /// Sort buffer reused between samples
+    scratch: Vec<f64>,
+}
+
+impl MedianFilter {
+    /// Median over `window` samples (a window of 0 is treated as 1)
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            channels: PerChannel::default(),
+            scratch: Vec::with_capacity(window),
+        }
+    }
+
+    /// Window length in samples
+    pub fn window(&self) -> usize {
+        self.window
+    }
+}
+
+impl Stage for MedianFilter {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        if !input.value.is_finite() {
+            return;
+        }
+        let values = push_window(&mut self.channels, self.window, input);
+        out.push(Reading {
+            value: median(values, &mut self.scratch),
+            ..input
+        });
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+/// Hampel despike filter
+///
+/// Compares each value with the median of the last `window` values of its
+/// channel. If it deviates by more than `threshold` robust standard
+/// deviations (1.4826 × the median absolute deviation), it is replaced by
+/// the median; otherwise it passes through unchanged. Unlike
+/// [`MedianFilter`], clean data is not altered.
+///
+/// The window trails the current value, so there is no added latency;
+/// spikes up to `window / 2` samples long are removed.
+#[derive(Debug, Clone)]
+pub struct Despike {
+    window: usize,
+    threshold: f64,
+    channels: PerChannel<VecDeque<f64>>,
+    scratch: Vec<f64>,
+}
+
+/// Scale from median absolute deviation to standard deviation for Gaussian noise
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+impl Despike {
+    /// Despike over `window` samples at `threshold` robust standard deviations
+    ///
+    /// A window of 0 is treated as 1; 3.0 is a common threshold.
+    pub fn new(window: usize, threshold: f64) -> Self {
+        Self {
+            window: window.max(1),
+            threshold,
+            channels: PerChannel::default(),
+            scratch: Vec::with_capacity(window),
+        }
+    }
+}
+
+impl Stage for Despike {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        if !input.value.is_finite() {
+            return;
+        }
+        let values = push_window(&mut self.channels, self.window, input);
+        let med = median(values, &mut self.scratch);
+
+        self.scratch.clear();
+        self.scratch.extend(values.iter().map(|v| (v - med).abs()));
+        let sigma = MAD_TO_SIGMA * median_of(&mut self.scratch);
+
+        let value = if (input.value - med).abs() > self.threshold * sigma {
+            log::trace!(
+                "Despiked channel {}: {} -> {}",
+                input.channel,
+                input.value,
+                med
+            );
+            med
+        } else {
+            input.value
+        };
+        out.push(Reading { value, ..input });
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+/// Append a value to its channel's window and return the window
+fn push_window(
+    channels: &mut PerChannel<VecDeque<f64>>,
+    window: usize,
+    input: Reading,
+) -> &VecDeque<f64> {
+    let values = channels.get_or_insert_with(input.channel, || VecDeque::with_capacity(window));
+    if values.len() == window {
+        values.pop_front();
+    }
+    values.push_back(input.value);
+    values
+}
+
+/// Median of `values`, using `scratch` as sort buffer
+fn median(values: &VecDeque<f64>, scratch: &mut Vec<f64>) -> f64 {
+    scratch.clear();
+    scratch.extend(values.iter().copied());
+    median_of(scratch)
+}
+
+/// Median of a non-empty slice, reordering it
+fn median_of(values: &mut [f64]) -> f64 {
+    let odd = values.len() % 2 == 1;
+    let (lower, upper, _) = values.select_nth_unstable_by(values.len() / 2, f64::total_cmp);
+    if odd {
+        return *upper;
+    }
+    let below = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (below + *upper) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Run `values` of channel 0 through `stage`
+    fn run(stage: &mut impl Stage, values: &[f64]) -> Vec<f64> {
+        let timestamp = Instant::now();
+        let mut out = Vec::new();
+        for &value in values {
+            let reading = Reading {
+                channel: 0,
+                timestamp,
+                value,
+            };
+            stage.process(reading, &mut out);
+        }
+        out.iter().map(|r| r.value).collect()
+    }
+
+    #[test]
+    fn median_over_odd_and_even_windows() {
+        let mut odd = MedianFilter::new(3);
+        assert_eq!(run(&mut odd, &[1.0, 5.0, 2.0, 8.0]), [1.0, 3.0, 2.0, 5.0]);
+
+        let mut even = MedianFilter::new(4);
+        assert_eq!(
+            run(&mut even, &[4.0, 1.0, 3.0, 2.0, 9.0]),
+            [4.0, 2.5, 3.0, 2.5, 2.5]
+        );
+    }
+
+    #[test]
+    fn despike_replaces_a_single_spike() {
+        let mut despike = Despike::new(5, 3.0);
+        let out = run(&mut despike, &[10.0, 10.1, 9.9, 10.0, 10.2, 50.0, 10.1]);
+        // Window 10.1, 9.9, 10.0, 10.2, 50.0 has median 10.1
+        assert_eq!(out[5], 10.1);
+        assert_eq!(out[6], 10.1);
+        assert_eq!(out[..5], [10.0, 10.1, 9.9, 10.0, 10.2]);
+    }
+
+    #[test]
+    fn despike_passes_a_step_change() {
+        let mut despike = Despike::new(5, 3.0);
+        let input = [10.0, 10.0, 10.0, 10.0, 10.0, 20.0, 20.0, 20.0, 20.0, 20.0];
+        let out = run(&mut despike, &input);
+        // The first window / 2 samples of the step look like a spike
+        assert_eq!(out[5..7], [10.0, 10.0]);
+        assert_eq!(out[7..], input[7..]);
+    }
+}
@@ -0,0 +1,459 @@
+//! gRPC acquisition service
+//!
+//! [`GrpcServer`] exposes one board through the `ads1263.v1.Acquisition`
+//! service defined in `proto/ads1263.proto`: configuration, start and stop,
+//! a server stream of scans, and ADC1 calibration. The code generated from
+//! the same file, including the client, is in [`proto`], so Rust test
+//! systems need no protobuf tooling of their own; other languages generate
+//! their client from the `.proto` file.
+//!
+//! While an acquisition runs the device belongs to its sampler thread:
+//! configuration changes are applied between two scans, and calibration is
+//! rejected with `FAILED_PRECONDITION` until the acquisition is stopped.
+//!
+//! Requires the `grpc` feature (implies `stream`). The build runs `protoc`,
+//! a vendored binary or the one named by the `PROTOC` environment variable.
+
+use crate::acquisition::{Acquisition, AcquisitionConfig, Reconfiguration};
+use crate::ads1263::{Adc1Calibration, Ads1263, Calibration};
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{Adc1Config, DataRate, DigitalFilter, Gain};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use futures_core::Stream;
+use proto::acquisition_server::AcquisitionServer;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll};
+use std::time::UNIX_EPOCH;
+use tokio::sync::{broadcast, mpsc};
+use tonic::{Request, Response, Status};
+
+/// Code generated from `proto/ads1263.proto`
+#[allow(missing_docs, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("ads1263.v1");
+}
+
+/// Scans buffered per streaming client before it misses some
+const STREAM_SCANS: usize = 64;
+
+type RpcResult<T> = std::result::Result<Response<T>, Status>;
+
+/// The board and its acquisition, if one is running
+struct Device {
+    /// The driver while no acquisition holds it
+    adc: Option<Ads1263>,
+    acquisition: Option<Acquisition>,
+    adc1: Adc1Config,
+    channels: Vec<u8>,
+}
+
+impl Device {
+    /// The driver, if no acquisition is running
+    fn adc(&mut self) -> std::result::Result<&mut Ads1263, Status> {
+        self.reclaim()?;
+        self.adc
+            .as_mut()
+            .ok_or_else(|| Status::failed_precondition("acquisition running"))
+    }
+
+    /// Take the driver back from an acquisition that has stopped
+    fn reclaim(&mut self) -> std::result::Result<(), Status> {
+        if self.acquisition.as_ref().is_some_and(|a| !a.is_running()) {
+            self.stop()?;
+        }
+        Ok(())
+    }
+
+    /// Stop the acquisition and take the driver back
+    fn stop(&mut self) -> std::result::Result<(), Status> {
+        if let Some(acquisition) = self.acquisition.take() {
+            self.adc = Some(acquisition.join()?);
+            log::info!("gRPC acquisition stopped");
+        }
+        Ok(())
+    }
+
+    fn status(&self) -> proto::Status {
+        let acquisition = self.acquisition.as_ref();
+        proto::Status {
+            running: acquisition.is_some_and(Acquisition::is_running),
+            channels: self.channels.iter().map(|&c| c.into()).collect(),
+            generation: acquisition.map_or(0, Acquisition::generation),
+            dropped: acquisition.map_or(0, Acquisition::dropped),
+        }
+    }
+}
+
+/// Sink broadcasting scans to the streaming clients
+struct BatchSink {
+    map: ChannelMap,
+    clock: WallClock,
+    sender: broadcast::Sender<Arc<proto::SampleBatch>>,
+}
+
+impl Sink for BatchSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if self.sender.receiver_count() == 0 {
+            return Ok(());
+        }
+        self.clock.update();
+        let samples = samples
+            .iter()
+            .map(|s| proto::Sample {
+                channel: s.channel.into(),
+                timestamp_us: self
+                    .clock
+                    .to_utc(s.timestamp)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros() as i64,
+                raw: s.raw,
+                value: self.map.convert_sample(s),
+                unit: self.map.unit(s.channel).to_string(),
+                sequence: s.sequence,
+                generation: s.generation,
+                status: s.status.bits().into(),
+                crc_ok: s.crc_ok,
+            })
+            .collect();
+        // No receivers left is not an error
+        let _ = self.sender.send(Arc::new(proto::SampleBatch { samples }));
+        Ok(())
+    }
+}
+
+/// Response stream of `StreamSamples`
+pub struct BatchStream {
+    receiver: mpsc::Receiver<std::result::Result<proto::SampleBatch, Status>>,
+}
+
+impl Stream for BatchStream {
+    type Item = std::result::Result<proto::SampleBatch, Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// gRPC server controlling one board
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, ChannelMap, DataRate, GrpcServer, Hal};
+///
+/// async fn run() -> ads1263::Result<()> {
+///     let mut adc = Ads1263::new(Hal::new()?);
+///     adc.init_adc1(DataRate::Sps400)?;
+///
+///     GrpcServer::new(adc, ChannelMap::new(5.0))
+///         .serve("0.0.0.0:50051".parse().unwrap())
+///         .await
+/// }
+/// ```
+///
+/// A client using the generated code:
+///
+/// ```no_run
+/// use ads1263::grpc::proto::acquisition_client::AcquisitionClient;
+/// use ads1263::grpc::proto::{StartRequest, StreamSamplesRequest};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut client = AcquisitionClient::connect("http://raspberrypi.local:50051").await?;
+/// client.start(StartRequest { channels: vec![0, 1] }).await?;
+/// let mut scans = client
+///     .stream_samples(StreamSamplesRequest::default())
+///     .await?
+///     .into_inner();
+/// while let Some(scan) = scans.message().await? {
+///     println!("{:?}", scan.samples);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct GrpcServer {
+    device: Arc<Mutex<Device>>,
+    map: ChannelMap,
+    scans: broadcast::Sender<Arc<proto::SampleBatch>>,
+}
+
+impl GrpcServer {
+    /// Serve `adc`, converting samples with `map`
+    pub fn new(adc: Ads1263, map: ChannelMap) -> Self {
+        let (scans, _) = broadcast::channel(STREAM_SCANS);
+        Self {
+            device: Arc::new(Mutex::new(Device {
+                adc1: adc.adc1_config(),
+                adc: Some(adc),
+                acquisition: None,
+                channels: Vec::new(),
+            })),
+            map,
+            scans,
+        }
+    }
+
+    /// The tonic service, e.g. to add to a server with other services
+    pub fn into_service(self) -> AcquisitionServer<Self> {
+        AcquisitionServer::new(self)
+    }
+
+    /// Serve on `addr` until the task is cancelled
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Grpc` if the address cannot be bound or the
+    /// server fails
+    pub async fn serve(self, addr: SocketAddr) -> Result<()> {
+        log::info!("gRPC server listening on {}", addr);
+        tonic::transport::Server::builder()
+            .add_service(self.into_service())
+            .serve(addr)
+            .await?;
+        Ok(())
+    }
+
+    /// Run `f` on the device from a blocking thread
+    async fn with_device<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Device) -> std::result::Result<T, Status> + Send + 'static,
+    ) -> std::result::Result<T, Status> {
+        let device = self.device.clone();
+        tokio::task::spawn_blocking(move || f(&mut lock(&device)))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+    }
+}
+
+#[tonic::async_trait]
+impl proto::acquisition_server::Acquisition for GrpcServer {
+    type StreamSamplesStream = BatchStream;
+
+    async fn get_config(&self, _: Request<proto::GetConfigRequest>) -> RpcResult<proto::Config> {
+        Ok(Response::new(config(&lock(&self.device).adc1)))
+    }
+
+    async fn configure(&self, request: Request<proto::Config>) -> RpcResult<proto::Config> {
+        let request = request.into_inner();
+        self.with_device(move |device| {
+            let adc1 = merge(device.adc1, &request)?;
+            device.reclaim()?;
+            match &device.acquisition {
+                Some(acquisition) => acquisition.reconfigure(Reconfiguration::Adc1(adc1))?,
+                None => device.adc()?.configure_adc1(adc1)?,
+            }
+            device.adc1 = adc1;
+            Ok(Response::new(config(&adc1)))
+        })
+        .await
+    }
+
+    async fn start(&self, request: Request<proto::StartRequest>) -> RpcResult<proto::Status> {
+        let channels = request
+            .into_inner()
+            .channels
+            .into_iter()
+            .map(|c| u8::try_from(c).map_err(|_| Status::invalid_argument("invalid channel")))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if channels.is_empty() {
+            return Err(Status::invalid_argument("no channels"));
+        }
+        let sink = BatchSink {
+            map: self.map.clone(),
+            clock: WallClock::new(),
+            sender: self.scans.clone(),
+        };
+        self.with_device(move |device| {
+            device.adc()?;
+            let adc = device.adc.take().expect("driver present when stopped");
+            let acquisition =
+                Acquisition::try_start_sink(adc, &channels, AcquisitionConfig::default(), sink)
+                    .map_err(|(e, adc)| {
+                        device.adc = Some(adc);
+                        e
+                    })?;
+            log::info!("gRPC acquisition started on channels {:?}", channels);
+            device.acquisition = Some(acquisition);
+            device.channels = channels;
+            Ok(Response::new(device.status()))
+        })
+        .await
+    }
+
+    async fn stop(&self, _: Request<proto::StopRequest>) -> RpcResult<proto::Status> {
+        self.with_device(|device| {
+            device.stop()?;
+            Ok(Response::new(device.status()))
+        })
+        .await
+    }
+
+    async fn get_status(&self, _: Request<proto::GetStatusRequest>) -> RpcResult<proto::Status> {
+        Ok(Response::new(lock(&self.device).status()))
+    }
+
+    async fn stream_samples(
+        &self,
+        request: Request<proto::StreamSamplesRequest>,
+    ) -> RpcResult<Self::StreamSamplesStream> {
+        let channels = request.into_inner().channels;
+        let mut scans = self.scans.subscribe();
+        let (sender, receiver) = mpsc::channel(STREAM_SCANS);
+        tokio::spawn(async move {
+            loop {
+                let batch = match scans.recv().await {
+                    Ok(batch) => batch,
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        log::debug!("gRPC stream lagging, {} scans missed", missed);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                let samples: Vec<_> = batch
+                    .samples
+                    .iter()
+                    .filter(|s| channels.is_empty() || channels.contains(&s.channel))
+                    .cloned()
+                    .collect();
+                if samples.is_empty() {
+                    continue;
+                }
+                if sender
+                    .send(Ok(proto::SampleBatch { samples }))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(BatchStream { receiver }))
+    }
+
+    async fn calibrate(
+        &self,
+        request: Request<proto::CalibrateRequest>,
+    ) -> RpcResult<proto::CalibrationRegisters> {
+        let request = request.into_inner();
+        let calibration = match request.kind() {
+            proto::CalibrationKind::SelfOffset => Calibration::SelfOffset,
+            proto::CalibrationKind::SystemOffset => Calibration::SystemOffset,
+            proto::CalibrationKind::SystemGain => Calibration::SystemGain,
+            proto::CalibrationKind::Unspecified => {
+                return Err(Status::invalid_argument("calibration kind unspecified"));
+            }
+        };
+        let channel = u8::try_from(request.channel)
+            .map_err(|_| Status::invalid_argument("invalid channel"))?;
+        self.with_device(move |device| {
+            let adc = device.adc()?;
+            if calibration != Calibration::SelfOffset {
+                adc.select_channel(channel)?;
+            }
+            Ok(Response::new(registers(adc.calibrate_adc1(calibration)?)))
+        })
+        .await
+    }
+
+    async fn get_calibration(
+        &self,
+        _: Request<proto::GetCalibrationRequest>,
+    ) -> RpcResult<proto::CalibrationRegisters> {
+        self.with_device(|device| Ok(Response::new(registers(device.adc()?.adc1_calibration()?))))
+            .await
+    }
+
+    async fn set_calibration(
+        &self,
+        request: Request<proto::CalibrationRegisters>,
+    ) -> RpcResult<proto::CalibrationRegisters> {
+        let request = request.into_inner();
+        let calibration = Adc1Calibration {
+            offset: request.offset,
+            full_scale: request.full_scale,
+        };
+        self.with_device(move |device| {
+            let adc = device.adc()?;
+            adc.set_adc1_calibration(calibration)?;
+            Ok(Response::new(registers(adc.adc1_calibration()?)))
+        })
+        .await
+    }
+}
+
+impl From<Ads1263Error> for Status {
+    fn from(e: Ads1263Error) -> Self {
+        match &e {
+            Ads1263Error::InvalidChannel(..) => Status::invalid_argument(e.to_string()),
+            Ads1263Error::WorkerTerminated => Status::failed_precondition(e.to_string()),
+            Ads1263Error::Timeout | Ads1263Error::DrdyTimeout { .. } => {
+                Status::deadline_exceeded(e.to_string())
+            }
+            _ => Status::internal(e.to_string()),
+        }
+    }
+}
+
+/// Lock the device, ignoring poisoning
+fn lock(device: &Mutex<Device>) -> MutexGuard<'_, Device> {
+    device.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// `Config` message of an ADC1 configuration
+fn config(adc1: &Adc1Config) -> proto::Config {
+    let filter = match adc1.filter {
+        DigitalFilter::Sinc1 => proto::Filter::Sinc1,
+        DigitalFilter::Sinc2 => proto::Filter::Sinc2,
+        DigitalFilter::Sinc3 => proto::Filter::Sinc3,
+        DigitalFilter::Sinc4 => proto::Filter::Sinc4,
+        DigitalFilter::Fir => proto::Filter::Fir,
+    };
+    proto::Config {
+        gain: 1 << (adc1.gain as u32),
+        data_rate: adc1.data_rate.sps(),
+        filter: filter.into(),
+    }
+}
+
+/// `adc1` with the fields set in `request` replaced
+fn merge(mut adc1: Adc1Config, request: &proto::Config) -> std::result::Result<Adc1Config, Status> {
+    if request.gain != 0 {
+        adc1.gain = match request.gain {
+            1 => Gain::Gain1,
+            2 => Gain::Gain2,
+            4 => Gain::Gain4,
+            8 => Gain::Gain8,
+            16 => Gain::Gain16,
+            32 => Gain::Gain32,
+            64 => Gain::Gain64,
+            gain => return Err(Status::invalid_argument(format!("invalid gain {}", gain))),
+        };
+    }
+    if request.data_rate != 0.0 {
+        adc1.data_rate = DataRate::from_sps(request.data_rate).ok_or_else(|| {
+            Status::invalid_argument(format!("invalid data rate {}", request.data_rate))
+        })?;
+    }
+    match request.filter() {
+        proto::Filter::Unspecified => {}
+        proto::Filter::Sinc1 => adc1.filter = DigitalFilter::Sinc1,
+        proto::Filter::Sinc2 => adc1.filter = DigitalFilter::Sinc2,
+        proto::Filter::Sinc3 => adc1.filter = DigitalFilter::Sinc3,
+        proto::Filter::Sinc4 => adc1.filter = DigitalFilter::Sinc4,
+        proto::Filter::Fir => adc1.filter = DigitalFilter::Fir,
+    }
+    Ok(adc1)
+}
+
+/// `CalibrationRegisters` message of a calibration
+fn registers(calibration: Adc1Calibration) -> proto::CalibrationRegisters {
+    proto::CalibrationRegisters {
+        offset: calibration.offset,
+        full_scale: calibration.full_scale,
+    }
+}
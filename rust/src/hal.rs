@@ -3,11 +3,64 @@
 //! This module provides low-level hardware access for GPIO and SPI
 //! communication with the ADS1263 ADC on Raspberry Pi.
 
+use crate::delay::{DelayProvider, StdDelay};
 use crate::error::{Ads1263Error, Result};
-use rppal::gpio::{Gpio, InputPin, OutputPin};
+use rppal::gpio::{Event, Gpio, InputPin, OutputPin, Trigger};
 use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
+use rppal::system::{DeviceInfo, SoC};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// What happens to the control pins when the HAL is dropped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CleanupPolicy {
+    /// Keep RST high and CS deselected so conversions continue after the
+    /// process exits; the pins keep driving their levels
+    LeaveRunning,
+    /// Deselect the chip and return the pins to the mode they had before
+    /// the HAL claimed them (normally inputs), without resetting the chip
+    TristatePins,
+    /// Drive RST and CS low, holding the chip in reset (default)
+    #[default]
+    ResetChip,
+}
+
+/// GPIO controller providing the header pins
+///
+/// Raspberry Pi 1-4 expose the header through the BCM283x/BCM2711 GPIO
+/// block; on the Pi 5 the header is wired to the RP1 I/O controller, which
+/// shows up as a different gpiochip. rppal opens the right chip by label on
+/// all models, so the chip is detected for reporting only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpioChip {
+    /// SoC GPIO block of Raspberry Pi 1-4, Zero, and CM1-4
+    Bcm283x,
+    /// RP1 I/O controller of the Raspberry Pi 5
+    Rp1,
+}
+
+impl GpioChip {
+    /// Detect the GPIO controller of the running board
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::System` if the board cannot be identified
+    pub fn detect() -> Result<Self> {
+        let info = DeviceInfo::new()?;
+        let chip = match info.soc() {
+            SoC::Bcm2712 => GpioChip::Rp1,
+            _ => GpioChip::Bcm283x,
+        };
+        log::debug!(
+            "Detected {} ({}), GPIO chip {:?}",
+            info.model(),
+            info.soc(),
+            chip
+        );
+        Ok(chip)
+    }
+}
 
 /// Pin configuration for the ADS1263 HAT
 ///
@@ -20,6 +73,12 @@ pub struct PinConfig {
     pub cs: u8,
     /// Data ready pin (BCM numbering)
     pub drdy: u8,
+    /// Optional START pin (BCM numbering) for hardware conversion control
+    ///
+    /// Not connected on the Waveshare HAT, where START is tied low.
+    pub start: Option<u8>,
+    /// Pin handling on drop
+    pub cleanup: CleanupPolicy,
 }
 
 impl Default for PinConfig {
@@ -28,6 +87,8 @@ impl Default for PinConfig {
             rst: 18,  // BCM pin 18
             cs: 22,   // BCM pin 22
             drdy: 17, // BCM pin 17
+            start: None,
+            cleanup: CleanupPolicy::default(),
         }
     }
 }
@@ -45,6 +106,32 @@ pub struct SpiConfig {
     pub mode: Mode,
 }
 
+impl SpiConfig {
+    /// Highest SCLK frequency allowed by the datasheet (125 ns minimum period)
+    pub const MAX_CLOCK_SPEED: u32 = 8_000_000;
+
+    /// Check the configuration against the ADS1263 interface limits
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidSpiMode` unless the mode is Mode1, and
+    /// `Ads1263Error::InvalidSpiClock` if the clock speed is zero or above
+    /// [`MAX_CLOCK_SPEED`](Self::MAX_CLOCK_SPEED)
+    pub fn validate(&self) -> Result<()> {
+        // The ADS1263 only supports CPOL=0, CPHA=1
+        if self.mode != Mode::Mode1 {
+            return Err(Ads1263Error::InvalidSpiMode(self.mode));
+        }
+        if self.clock_speed == 0 || self.clock_speed > Self::MAX_CLOCK_SPEED {
+            return Err(Ads1263Error::InvalidSpiClock {
+                hz: self.clock_speed,
+                max: Self::MAX_CLOCK_SPEED,
+            });
+        }
+        Ok(())
+    }
+}
+
 impl Default for SpiConfig {
     fn default() -> Self {
         Self {
@@ -56,14 +143,64 @@ impl Default for SpiConfig {
     }
 }
 
+/// Default time to wait for DRDY
+const DRDY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Sleep between pin reads once the spin phase of `SpinThenSleep` is over
+const SPIN_THEN_SLEEP_INTERVAL: Duration = Duration::from_micros(10);
+
+/// How the HAL waits for DRDY to go low
+///
+/// Trades CPU usage for wake-up latency. At low data rates `Interrupt` or
+/// `Sleep` avoid burning a core; at the highest rates `BusySpin` or a short
+/// `SpinThenSleep` keep up with conversions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WaitStrategy {
+    /// Poll the pin continuously (lowest latency, occupies one core)
+    BusySpin,
+    /// Poll continuously for `spin_us` microseconds, then poll with short sleeps
+    SpinThenSleep {
+        /// Duration of the busy phase in microseconds
+        spin_us: u32,
+    },
+    /// Poll the pin with a sleep between reads
+    Sleep {
+        /// Time slept between pin reads
+        poll_interval: Duration,
+    },
+    /// Sleep in the kernel until the DRDY falling edge interrupt (default)
+    #[default]
+    Interrupt,
+}
+
+/// SPI device owned exclusively or shared with other drivers
+enum SpiBus {
+    Owned(Spi),
+    Shared(Arc<Mutex<Spi>>),
+}
+
 /// Hardware Abstraction Layer
 ///
 /// Provides low-level access to GPIO and SPI for the ADS1263
 pub struct Hal {
-    spi: Spi,
+    spi: SpiBus,
+    /// Clock and mode re-applied before each frame on a shared bus
+    spi_config: SpiConfig,
     rst_pin: OutputPin,
     cs_pin: OutputPin,
     drdy_pin: InputPin,
+    /// START pin, if wired
+    start_pin: Option<OutputPin>,
+    /// Current DRDY wait strategy
+    wait_strategy: WaitStrategy,
+    /// Transmit copy used by `spi_transfer`
+    scratch: Vec<u8>,
+    /// Pin handling on drop
+    cleanup: CleanupPolicy,
+    /// GPIO controller in use, if the board was identified
+    gpio_chip: Option<GpioChip>,
+    /// Source of blocking delays
+    delay: Box<dyn DelayProvider>,
 }
 
 impl Hal {
@@ -89,22 +226,95 @@ impl Hal {
     ///
     /// # Errors
     ///
-    /// Returns an error if GPIO or SPI initialization fails
+    /// Returns an error if the SPI configuration is invalid (see
+    /// [`SpiConfig::validate`]) or GPIO or SPI initialization fails
     pub fn with_config(pin_config: PinConfig, spi_config: SpiConfig) -> Result<Self> {
-        let gpio = Gpio::new()?;
+        spi_config.validate()?;
+        let spi = Spi::new(
+            spi_config.bus,
+            spi_config.slave_select,
+            spi_config.clock_speed,
+            spi_config.mode,
+        )?;
+        Self::build(pin_config, spi_config, SpiBus::Owned(spi))
+    }
 
-        // Configure GPIO pins
-        let rst_pin = gpio.get(pin_config.rst)?.into_output();
-        let cs_pin = gpio.get(pin_config.cs)?.into_output_high(); // CS starts high (inactive)
-        let drdy_pin = gpio.get(pin_config.drdy)?.into_input();
+    /// Create a new HAL instance on an SPI device shared with other drivers
+    ///
+    /// The bus lock is held for each complete chip-select frame, and the
+    /// clock speed and mode from `spi_config` are re-applied before every
+    /// frame in case another driver changed them. The `bus` and
+    /// `slave_select` fields of `spi_config` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin_config` - GPIO pins of this ADS1263
+    /// * `spi_config` - Clock speed and mode used for this device
+    /// * `spi` - Shared SPI device, e.g. from [`open_shared_spi`](Self::open_shared_spi)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SPI configuration is invalid or GPIO
+    /// initialization fails
+    pub fn with_shared_spi(
+        pin_config: PinConfig,
+        spi_config: SpiConfig,
+        spi: Arc<Mutex<Spi>>,
+    ) -> Result<Self> {
+        spi_config.validate()?;
+        Self::build(pin_config, spi_config, SpiBus::Shared(spi))
+    }
 
-        // Configure SPI
+    /// Open an SPI device for sharing between several drivers
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the SPI configuration is invalid or the SPI
+    /// device cannot be opened
+    pub fn open_shared_spi(spi_config: SpiConfig) -> Result<Arc<Mutex<Spi>>> {
+        spi_config.validate()?;
         let spi = Spi::new(
             spi_config.bus,
             spi_config.slave_select,
             spi_config.clock_speed,
             spi_config.mode,
         )?;
+        Ok(Arc::new(Mutex::new(spi)))
+    }
+
+    /// Configure the GPIO pins around an already opened SPI device
+    fn build(pin_config: PinConfig, spi_config: SpiConfig, spi: SpiBus) -> Result<Self> {
+        let gpio_chip = match GpioChip::detect() {
+            Ok(chip) => Some(chip),
+            Err(e) => {
+                log::warn!("Board detection failed ({})", e);
+                None
+            }
+        };
+        let gpio = Gpio::new()?;
+
+        // Configure GPIO pins
+        let rst_pin = gpio.get(pin_config.rst)?.into_output();
+        let cs_pin = gpio.get(pin_config.cs)?.into_output_high(); // CS starts high (inactive)
+        let mut drdy_pin = gpio.get(pin_config.drdy)?.into_input();
+        // START low leaves conversion control to the START1/STOP1 commands
+        let start_pin = match pin_config.start {
+            Some(pin) => Some(gpio.get(pin)?.into_output_low()),
+            None => None,
+        };
+
+        // Let the kernel wake us on the DRDY falling edge; fall back to polling
+        // if the interrupt cannot be configured
+        let wait_strategy = match drdy_pin.set_interrupt(Trigger::FallingEdge, None) {
+            Ok(()) => WaitStrategy::Interrupt,
+            Err(e) => {
+                log::warn!(
+                    "DRDY interrupt unavailable ({}), falling back to polling",
+                    e
+                );
+                WaitStrategy::BusySpin
+            }
+        };
 
         log::info!(
             "HAL initialized - RST: BCM{}, CS: BCM{}, DRDY: BCM{}",
@@ -112,21 +322,153 @@ impl Hal {
             pin_config.cs,
             pin_config.drdy
         );
-        log::info!(
-            "SPI configured - Bus: {:?}, Speed: {} Hz, Mode: {:?}",
-            spi_config.bus,
-            spi_config.clock_speed,
-            spi_config.mode
-        );
+        if let Some(pin) = pin_config.start {
+            log::info!("START pin: BCM{}", pin);
+        }
+        log::info!("GPIO chip: {:?}", gpio_chip);
+        log::info!("DRDY wait strategy: {:?}", wait_strategy);
+        log::debug!("Cleanup policy: {:?}", pin_config.cleanup);
+        match spi {
+            SpiBus::Owned(_) => log::info!(
+                "SPI configured - Bus: {:?}, Speed: {} Hz, Mode: {:?}",
+                spi_config.bus,
+                spi_config.clock_speed,
+                spi_config.mode
+            ),
+            SpiBus::Shared(_) => log::info!(
+                "Shared SPI configured - Speed: {} Hz, Mode: {:?}",
+                spi_config.clock_speed,
+                spi_config.mode
+            ),
+        }
 
         Ok(Self {
             spi,
+            spi_config,
             rst_pin,
             cs_pin,
             drdy_pin,
+            start_pin,
+            wait_strategy,
+            scratch: Vec::with_capacity(16),
+            cleanup: pin_config.cleanup,
+            gpio_chip,
+            delay: Box::new(StdDelay),
         })
     }
 
+    /// Get the GPIO controller the pins were claimed on
+    ///
+    /// `None` if board detection failed.
+    pub fn gpio_chip(&self) -> Option<GpioChip> {
+        self.gpio_chip
+    }
+
+    /// Get the current DRDY wait strategy
+    pub fn wait_strategy(&self) -> WaitStrategy {
+        self.wait_strategy
+    }
+
+    /// Select how DRDY waits are performed
+    ///
+    /// Switching to or from `Interrupt` arms or clears the falling-edge
+    /// interrupt on the DRDY pin.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Wait strategy to use for subsequent waits
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interrupt cannot be configured or cleared;
+    /// the previous strategy stays in effect
+    pub fn set_wait_strategy(&mut self, strategy: WaitStrategy) -> Result<()> {
+        match (self.wait_strategy, strategy) {
+            (WaitStrategy::Interrupt, WaitStrategy::Interrupt) => {}
+            (_, WaitStrategy::Interrupt) => {
+                self.drdy_pin.set_interrupt(Trigger::FallingEdge, None)?;
+            }
+            (WaitStrategy::Interrupt, _) => self.drdy_pin.clear_interrupt()?,
+            _ => {}
+        }
+        self.wait_strategy = strategy;
+        log::debug!("DRDY wait strategy set to {:?}", strategy);
+        Ok(())
+    }
+
+    /// Run a callback on every DRDY falling edge
+    ///
+    /// The callback executes on a separate thread managed by rppal. Installing
+    /// a callback replaces the synchronous edge interrupt, so an `Interrupt`
+    /// wait strategy is switched to `Sleep` polling.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - Closure invoked with the interrupt event
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interrupt cannot be configured
+    pub fn set_drdy_callback<C>(&mut self, callback: C) -> Result<()>
+    where
+        C: FnMut(Event) + Send + 'static,
+    {
+        self.drdy_pin
+            .set_async_interrupt(Trigger::FallingEdge, None, callback)?;
+        if self.wait_strategy == WaitStrategy::Interrupt {
+            self.wait_strategy = WaitStrategy::Sleep {
+                poll_interval: SPIN_THEN_SLEEP_INTERVAL,
+            };
+        }
+        log::debug!("DRDY callback installed");
+        Ok(())
+    }
+
+    /// Remove a callback installed with [`set_drdy_callback`](Self::set_drdy_callback)
+    ///
+    /// Blocking waits keep polling until [`set_wait_strategy`](Self::set_wait_strategy)
+    /// re-arms the synchronous interrupt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the interrupt cannot be cleared
+    pub fn clear_drdy_callback(&mut self) -> Result<()> {
+        self.drdy_pin.clear_async_interrupt()?;
+        log::debug!("DRDY callback removed");
+        Ok(())
+    }
+
+    /// Get the SPI clock speed in Hz
+    pub fn spi_clock(&self) -> u32 {
+        self.spi_config.clock_speed
+    }
+
+    /// Change the SPI clock speed at runtime
+    ///
+    /// On a shared bus the new speed takes effect with the next frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `hz` - Clock speed in Hz, at most [`SpiConfig::MAX_CLOCK_SPEED`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidSpiClock` if the speed is out of range, or
+    /// an SPI error if the driver rejects it; the previous speed stays in effect
+    pub fn set_spi_clock(&mut self, hz: u32) -> Result<()> {
+        let config = SpiConfig {
+            clock_speed: hz,
+            ..self.spi_config
+        };
+        config.validate()?;
+        if let SpiBus::Owned(spi) = &self.spi {
+            spi.set_clock_speed(hz)?;
+        }
+        self.spi_config = config;
+        log::debug!("SPI clock set to {} Hz", hz);
+        Ok(())
+    }
+
     /// Set the reset pin state
     ///
     /// # Arguments
@@ -155,6 +497,34 @@ impl Hal {
         }
     }
 
+    /// true if a START pin is configured
+    pub fn has_start_pin(&self) -> bool {
+        self.start_pin.is_some()
+    }
+
+    /// Set the START pin state
+    ///
+    /// While START is high, ADC1 converts continuously; a low-to-high
+    /// transition restarts the conversion.
+    ///
+    /// # Arguments
+    ///
+    /// * `high` - true to start conversions, false to stop them
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoStartPin` if no START pin is configured
+    #[inline]
+    pub fn set_start(&mut self, high: bool) -> Result<()> {
+        let pin = self.start_pin.as_mut().ok_or(Ads1263Error::NoStartPin)?;
+        if high {
+            pin.set_high();
+        } else {
+            pin.set_low();
+        }
+        Ok(())
+    }
+
     /// Read the data ready pin state
     ///
     /// # Returns
@@ -182,7 +552,7 @@ impl Hal {
     pub fn spi_transfer_byte(&mut self, tx: u8) -> Result<u8> {
         let write_buffer = [tx];
         let mut read_buffer = [0u8];
-        self.spi.transfer(&mut read_buffer, &write_buffer)?;
+        self.spi_transfer_into(&write_buffer, &mut read_buffer)?;
         Ok(read_buffer[0])
     }
 
@@ -225,11 +595,74 @@ impl Hal {
     ///
     /// Returns an error if SPI transfer fails
     pub fn spi_transfer(&mut self, buffer: &mut [u8]) -> Result<()> {
-        let write_buffer = buffer.to_vec();
-        self.spi.transfer(buffer, &write_buffer)?;
+        // Reuse the scratch buffer; it only reallocates when a longer transfer is seen
+        self.scratch.clear();
+        self.scratch.extend_from_slice(buffer);
+        with_spi(&self.spi, &self.spi_config, |spi| {
+            spi.transfer(buffer, &self.scratch)
+        })?;
+        Ok(())
+    }
+
+    /// Transfer bytes over SPI using separate transmit and receive buffers
+    ///
+    /// Does not allocate. If the buffers differ in length, the shorter one
+    /// determines how many bytes are clocked.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Bytes to transmit
+    /// * `rx` - Buffer receiving the bytes clocked in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI transfer fails
+    #[inline]
+    pub fn spi_transfer_into(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<()> {
+        let len = tx.len().min(rx.len());
+        with_spi(&self.spi, &self.spi_config, |spi| {
+            spi.transfer(&mut rx[..len], &tx[..len])
+        })?;
+        Ok(())
+    }
+
+    /// Run one complete chip-select framed SPI transfer
+    ///
+    /// Drives CS low, clocks out `tx` while filling `rx`, and releases CS.
+    /// On a shared bus the bus lock is held for the whole frame so other
+    /// drivers cannot clock data while this device is selected. Prefer this
+    /// over manual [`set_cs`](Self::set_cs) and byte transfers.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Bytes to transmit
+    /// * `rx` - Buffer receiving the bytes clocked in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SPI transfer fails
+    pub fn spi_frame(&mut self, tx: &[u8], rx: &mut [u8]) -> Result<()> {
+        let len = tx.len().min(rx.len());
+        let cs_pin = &mut self.cs_pin;
+        with_spi(&self.spi, &self.spi_config, |spi| {
+            cs_pin.set_low();
+            let result = spi.transfer(&mut rx[..len], &tx[..len]);
+            cs_pin.set_high();
+            result
+        })?;
         Ok(())
     }
 
+    /// Replace the provider used by [`delay_ms`](Self::delay_ms) and
+    /// [`delay_us`](Self::delay_us)
+    ///
+    /// # Arguments
+    ///
+    /// * `provider` - E.g. [`SpinDelay`](crate::SpinDelay) for precise short delays
+    pub fn set_delay_provider(&mut self, provider: impl DelayProvider + 'static) {
+        self.delay = Box::new(provider);
+    }
+
     /// Delay for a specified number of milliseconds
     ///
     /// # Arguments
@@ -237,7 +670,7 @@ impl Hal {
     /// * `ms` - Number of milliseconds to delay
     #[inline]
     pub fn delay_ms(&self, ms: u64) {
-        thread::sleep(Duration::from_millis(ms));
+        self.delay.delay_ms(ms);
     }
 
     /// Delay for a specified number of microseconds
@@ -247,27 +680,19 @@ impl Hal {
     /// * `us` - Number of microseconds to delay
     #[inline]
     pub fn delay_us(&self, us: u64) {
-        thread::sleep(Duration::from_micros(us));
+        self.delay.delay_us(us);
     }
 
     /// Wait for DRDY to go low (data ready) with timeout
     ///
     /// The ADS1263 pulls DRDY low when new conversion data is available.
+    /// The wait is performed according to the [`WaitStrategy`].
     ///
     /// # Errors
     ///
     /// Returns `Ads1263Error::Timeout` if DRDY doesn't go low within the timeout period
-    pub fn wait_drdy(&self) -> Result<()> {
-        const TIMEOUT_ITERATIONS: u32 = 4_000_000;
-
-        for _ in 0..TIMEOUT_ITERATIONS {
-            if !self.read_drdy() {
-                return Ok(());
-            }
-        }
-
-        log::error!("Timeout waiting for DRDY");
-        Err(Ads1263Error::Timeout)
+    pub fn wait_drdy(&mut self) -> Result<()> {
+        self.wait_drdy_within(DRDY_TIMEOUT)
     }
 
     /// Wait for DRDY with a specified timeout in milliseconds
@@ -279,27 +704,108 @@ impl Hal {
     /// # Errors
     ///
     /// Returns `Ads1263Error::Timeout` if DRDY doesn't go low within the timeout period
-    pub fn wait_drdy_timeout(&self, timeout_ms: u64) -> Result<()> {
-        let start = std::time::Instant::now();
-        let timeout = Duration::from_millis(timeout_ms);
+    pub fn wait_drdy_timeout(&mut self, timeout_ms: u64) -> Result<()> {
+        self.wait_drdy_within(Duration::from_millis(timeout_ms))
+    }
 
-        while start.elapsed() < timeout {
+    /// Wait for DRDY with a specified timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Timeout` if DRDY doesn't go low within the timeout period
+    pub fn wait_drdy_within(&mut self, timeout: Duration) -> Result<()> {
+        // Busy phase length and sleep between reads once it is over
+        let (spin, sleep) = match self.wait_strategy {
+            WaitStrategy::Interrupt => return self.wait_drdy_interrupt(timeout),
+            WaitStrategy::BusySpin => (timeout, Duration::ZERO),
+            WaitStrategy::SpinThenSleep { spin_us } => (
+                Duration::from_micros(spin_us.into()),
+                SPIN_THEN_SLEEP_INTERVAL,
+            ),
+            WaitStrategy::Sleep { poll_interval } => (Duration::ZERO, poll_interval),
+        };
+
+        let start = Instant::now();
+        loop {
             if !self.read_drdy() {
                 return Ok(());
             }
-            // Small sleep to avoid busy-waiting
-            thread::sleep(Duration::from_micros(10));
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                break;
+            }
+            if elapsed < spin {
+                std::hint::spin_loop();
+            } else {
+                thread::sleep(sleep);
+            }
         }
 
-        log::error!("Timeout ({} ms) waiting for DRDY", timeout_ms);
+        log::error!("Timeout ({} ms) waiting for DRDY", timeout.as_millis());
         Err(Ads1263Error::Timeout)
     }
 
-    /// Perform cleanup - set control pins low
+    /// Sleep until the DRDY falling edge interrupt fires
+    fn wait_drdy_interrupt(&mut self, timeout: Duration) -> Result<()> {
+        // Discard edges from conversions that were already consumed, then
+        // re-check the level so a conversion completed before arming isn't missed
+        self.drdy_pin.poll_interrupt(true, Some(Duration::ZERO))?;
+        if !self.read_drdy() {
+            return Ok(());
+        }
+
+        match self.drdy_pin.poll_interrupt(false, Some(timeout))? {
+            Some(_) => Ok(()),
+            None => {
+                log::error!(
+                    "Timeout ({} ms) waiting for DRDY interrupt",
+                    timeout.as_millis()
+                );
+                Err(Ads1263Error::Timeout)
+            }
+        }
+    }
+
+    /// Get the cleanup policy applied on drop
+    pub fn cleanup_policy(&self) -> CleanupPolicy {
+        self.cleanup
+    }
+
+    /// Change the cleanup policy applied on drop
+    pub fn set_cleanup_policy(&mut self, policy: CleanupPolicy) {
+        self.cleanup = policy;
+    }
+
+    /// Perform cleanup according to the [`CleanupPolicy`]
     pub fn cleanup(&mut self) {
-        self.rst_pin.set_low();
-        self.cs_pin.set_low();
-        log::debug!("HAL cleanup completed");
+        match self.cleanup {
+            CleanupPolicy::LeaveRunning => {
+                self.cs_pin.set_high();
+                self.rst_pin.set_high();
+                self.cs_pin.set_reset_on_drop(false);
+                self.rst_pin.set_reset_on_drop(false);
+                if let Some(pin) = &mut self.start_pin {
+                    pin.set_reset_on_drop(false);
+                }
+            }
+            CleanupPolicy::TristatePins => {
+                self.cs_pin.set_high();
+                self.cs_pin.set_reset_on_drop(true);
+                self.rst_pin.set_reset_on_drop(true);
+            }
+            CleanupPolicy::ResetChip => {
+                self.rst_pin.set_low();
+                self.cs_pin.set_low();
+                if let Some(pin) = &mut self.start_pin {
+                    pin.set_low();
+                }
+            }
+        }
+        log::debug!("HAL cleanup completed ({:?})", self.cleanup);
     }
 }
 
@@ -308,3 +814,20 @@ impl Drop for Hal {
         self.cleanup();
     }
 }
+
+/// Run an SPI operation, locking and re-configuring a shared bus first
+fn with_spi<T>(
+    bus: &SpiBus,
+    config: &SpiConfig,
+    f: impl FnOnce(&Spi) -> rppal::spi::Result<T>,
+) -> Result<T> {
+    match bus {
+        SpiBus::Owned(spi) => Ok(f(spi)?),
+        SpiBus::Shared(spi) => {
+            let spi = spi.lock().unwrap_or_else(PoisonError::into_inner);
+            spi.set_mode(config.mode)?;
+            spi.set_clock_speed(config.clock_speed)?;
+            Ok(f(&spi)?)
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Thread-safe shared access to one ADS1263
+//!
+//! [`Ads1263Handle`] wraps the driver in an `Arc<Mutex<_>>` so several
+//! threads (e.g. a web server and a logger) can use the same ADC. Each
+//! method locks the device for the duration of one operation; use
+//! [`Ads1263Handle::with`] to run a sequence of operations atomically.
+
+use crate::ads1263::{Ads1263, ConversionControl};
+use crate::error::Result;
+use crate::registers::*;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Cloneable, `Send + Sync` handle to a shared ADS1263 driver
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, Ads1263Handle, DataRate, Hal};
+/// use std::thread;
+///
+/// let adc = Ads1263Handle::new(Ads1263::new(Hal::new()?));
+/// adc.init_adc1(DataRate::Sps400)?;
+///
+/// let logger = adc.clone();
+/// let worker = thread::spawn(move || logger.get_channel_value(0));
+///
+/// let local = adc.get_channel_value(1)?;
+/// let remote = worker.join().unwrap()?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Clone)]
+pub struct Ads1263Handle {
+    adc: Arc<Mutex<Ads1263>>,
+}
+
+impl Ads1263Handle {
+    /// Wrap a driver for shared use
+    pub fn new(adc: Ads1263) -> Self {
+        Self {
+            adc: Arc::new(Mutex::new(adc)),
+        }
+    }
+
+    /// Lock the device for exclusive use
+    ///
+    /// A panic in another thread while holding the lock does not poison the
+    /// handle; the driver holds no invariants a partial operation can break.
+    pub fn lock(&self) -> MutexGuard<'_, Ads1263> {
+        self.adc.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Run several operations while holding the lock
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let adc = ads1263::Ads1263Handle::new(ads1263::Ads1263::new(ads1263::Hal::new()?));
+    /// use ads1263::InputMode;
+    ///
+    /// // No other thread can change the mode between these calls
+    /// let value = adc.with(|adc| {
+    ///     adc.set_mode(InputMode::Differential);
+    ///     adc.get_channel_value(2)
+    /// })?;
+    /// # Ok::<(), ads1263::Ads1263Error>(())
+    /// ```
+    pub fn with<T>(&self, f: impl FnOnce(&mut Ads1263) -> T) -> T {
+        f(&mut self.lock())
+    }
+
+    /// Recover the driver if this is the last handle
+    ///
+    /// Returns `self` unchanged if other clones still exist.
+    pub fn try_into_inner(self) -> std::result::Result<Ads1263, Self> {
+        match Arc::try_unwrap(self.adc) {
+            Ok(mutex) => Ok(mutex.into_inner().unwrap_or_else(PoisonError::into_inner)),
+            Err(adc) => Err(Self { adc }),
+        }
+    }
+
+    /// Read the chip ID; see [`Ads1263::read_chip_id`]
+    pub fn read_chip_id(&self) -> Result<u8> {
+        self.lock().read_chip_id()
+    }
+
+    /// Set the input mode; see [`Ads1263::set_mode`]
+    pub fn set_mode(&self, mode: InputMode) {
+        self.lock().set_mode(mode);
+    }
+
+    /// Get the current input mode
+    pub fn get_mode(&self) -> InputMode {
+        self.lock().get_mode()
+    }
+
+    /// Initialize ADC1; see [`Ads1263::init_adc1`]
+    pub fn init_adc1(&self, rate: DataRate) -> Result<()> {
+        self.lock().init_adc1(rate)
+    }
+
+    /// Initialize ADC2; see [`Ads1263::init_adc2`]
+    pub fn init_adc2(&self, rate: Adc2DataRate) -> Result<()> {
+        self.lock().init_adc2(rate)
+    }
+
+    /// Tune the SPI clock; see [`Ads1263::probe_spi_clock`]
+    pub fn probe_spi_clock(&self, max_hz: u32) -> Result<u32> {
+        self.lock().probe_spi_clock(max_hz)
+    }
+
+    /// Read an ADC1 channel; see [`Ads1263::get_channel_value`]
+    pub fn get_channel_value(&self, channel: u8) -> Result<u32> {
+        self.lock().get_channel_value(channel)
+    }
+
+    /// Read an ADC2 channel; see [`Ads1263::get_channel_value_adc2`]
+    pub fn get_channel_value_adc2(&self, channel: u8) -> Result<u32> {
+        self.lock().get_channel_value_adc2(channel)
+    }
+
+    /// Read multiple ADC1 channels as one locked scan; see [`Ads1263::get_all`]
+    pub fn get_all(&self, channels: &[u8]) -> Result<Vec<u32>> {
+        self.lock().get_all(channels)
+    }
+
+    /// Read multiple ADC1 channels into a buffer; see [`Ads1263::get_all_into`]
+    pub fn get_all_into(&self, channels: &[u8], values: &mut [u32]) -> Result<()> {
+        self.lock().get_all_into(channels, values)
+    }
+
+    /// Read all 10 ADC2 channels; see [`Ads1263::get_all_adc2`]
+    pub fn get_all_adc2(&self) -> Result<[u32; 10]> {
+        self.lock().get_all_adc2()
+    }
+
+    /// Poll ADC1 once without blocking; see [`Ads1263::try_read_adc1`]
+    pub fn try_read_adc1(&self) -> Result<Option<(i32, StatusFlags)>> {
+        self.lock().try_read_adc1()
+    }
+
+    /// Perform an RTD measurement; see [`Ads1263::read_rtd`]
+    pub fn read_rtd(&self, delay: Delay, gain: Gain, drate: DataRate) -> Result<u32> {
+        self.lock().read_rtd(delay, gain, drate)
+    }
+
+    /// Configure a DAC output; see [`Ads1263::set_dac`]
+    pub fn set_dac(&self, voltage: DacVoltage, positive: bool, enable: bool) -> Result<()> {
+        self.lock().set_dac(voltage, positive, enable)
+    }
+
+    /// Select ADC1 conversion control; see [`Ads1263::set_conversion_control`]
+    pub fn set_conversion_control(&self, control: ConversionControl) -> Result<()> {
+        self.lock().set_conversion_control(control)
+    }
+
+    /// Start ADC1 conversions
+    pub fn start_adc1(&self) -> Result<()> {
+        self.lock().start_adc1()
+    }
+
+    /// Stop ADC1 conversions
+    pub fn stop_adc1(&self) -> Result<()> {
+        self.lock().stop_adc1()
+    }
+
+    /// Start ADC2 conversions
+    pub fn start_adc2(&self) -> Result<()> {
+        self.lock().start_adc2()
+    }
+
+    /// Stop ADC2 conversions
+    pub fn stop_adc2(&self) -> Result<()> {
+        self.lock().stop_adc2()
+    }
+}
+
+impl From<Ads1263> for Ads1263Handle {
+    fn from(adc: Ads1263) -> Self {
+        Self::new(adc)
+    }
+}
+
+// The handle is only useful if it can cross threads
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Ads1263Handle>();
+};
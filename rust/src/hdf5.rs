@@ -0,0 +1,354 @@
+//! HDF5 export
+//!
+//! [`Hdf5Writer`] stores samples in the layout Python analysis tools (h5py,
+//! pandas, xarray) expect: one group per channel, named after the
+//! [`ChannelMap`] entry, holding four equally long datasets:
+//!
+//! ```text
+//! /                      chip_id, driver_version, input_mode, gain, data_rate, ...
+//! /inlet                 channel, unit, scale, offset, curve, reference
+//! /inlet/time            f64, seconds since the first sample
+//! /inlet/raw             i32, ADC1 code
+//! /inlet/value           f64, converted to the channel's unit
+//! /inlet/sequence        u64, acquisition sequence number
+//! ```
+//!
+//! Configuration is stored as attributes of the root group, calibration
+//! and units as attributes of each channel group. Datasets are chunked and
+//! grow as samples arrive, so the writer can be handed to an acquisition;
+//! [`export_hdf5`] converts a binary capture.
+
+use crate::capture::{CaptureHeader, CaptureReader};
+use crate::channel_map::ChannelMap;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use hdf5_metno::types::VarLenUnicode;
+use hdf5_metno::{Dataset, Extent, File, Group, H5Type, Location};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Default number of samples per chunk and per write
+const CHUNK_SAMPLES: usize = 4096;
+
+/// Sink writing samples to an HDF5 file
+///
+/// Samples are buffered per channel and appended a chunk at a time;
+/// [`flush`](Sink::flush), [`finish`](Self::finish), and drop write what
+/// is buffered.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, ChannelInfo, ChannelMap, DataRate, Hal, Hdf5Writer};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let header = CaptureHeader::from_device(&mut adc, map)?;
+/// let sink = Hdf5Writer::create("capture.h5", &header)?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct Hdf5Writer {
+    file: File,
+    map: ChannelMap,
+    channels: BTreeMap<u8, ChannelData>,
+    chunk: usize,
+    /// Monotonic and wall-clock time of the same instant
+    origin: (Instant, SystemTime),
+    /// Timestamp of the first sample; times are measured from it
+    start: Option<Instant>,
+    samples: u64,
+}
+
+/// Datasets and pending samples of one channel
+struct ChannelData {
+    time: Dataset,
+    raw: Dataset,
+    value: Dataset,
+    sequence: Dataset,
+    pending: Vec<Sample>,
+}
+
+impl Hdf5Writer {
+    /// Create (or truncate) an HDF5 file at `path` described by `header`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Hdf5` if the file cannot be created
+    pub fn create(path: impl AsRef<Path>, header: &CaptureHeader) -> Result<Self> {
+        let file = File::create(path)?;
+        write_header(&file, header)?;
+        Ok(Self {
+            file,
+            map: header.map.clone(),
+            channels: BTreeMap::new(),
+            chunk: CHUNK_SAMPLES,
+            origin: (Instant::now(), SystemTime::now()),
+            start: None,
+            samples: 0,
+        })
+    }
+
+    /// Set the number of samples per chunk (default 4096)
+    pub fn chunk_samples(mut self, samples: usize) -> Self {
+        self.chunk = samples.max(1);
+        self
+    }
+
+    /// Number of samples written or buffered
+    pub fn len(&self) -> u64 {
+        self.samples
+    }
+
+    /// true if no samples have been written
+    pub fn is_empty(&self) -> bool {
+        self.samples == 0
+    }
+
+    /// Write buffered samples and close the file
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Hdf5` if writing fails
+    pub fn finish(mut self) -> Result<()> {
+        self.flush()
+    }
+
+    /// Group and datasets of `channel`, created on its first sample
+    fn channel(&mut self, channel: u8) -> Result<&mut ChannelData> {
+        if !self.channels.contains_key(&channel) {
+            let data = create_channel(&self.file, &self.map, channel, self.chunk)?;
+            self.channels.insert(channel, data);
+        }
+        Ok(self
+            .channels
+            .get_mut(&channel)
+            .expect("channel created above"))
+    }
+
+    /// Append the pending samples of every channel
+    fn write_pending(&mut self) -> Result<()> {
+        let start = self.start.unwrap_or(self.origin.0);
+        for data in self.channels.values_mut() {
+            data.append(&self.map, start)?;
+        }
+        Ok(())
+    }
+
+    /// Record the wall-clock time of the first sample
+    fn set_start(&mut self, first: Instant) -> Result<()> {
+        let (at, wall) = self.origin;
+        let wall = if first >= at {
+            wall + (first - at)
+        } else {
+            wall - (at - first)
+        };
+        let unix = wall.duration_since(UNIX_EPOCH).unwrap_or_default();
+        attr(&self.file, "start_time", &unix.as_secs_f64())?;
+        self.start = Some(first);
+        Ok(())
+    }
+}
+
+impl Sink for Hdf5Writer {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if let Some(first) = samples.first()
+            && self.start.is_none()
+        {
+            self.set_start(first.timestamp)?;
+        }
+        let chunk = self.chunk;
+        let mut full = false;
+        for sample in samples {
+            let data = self.channel(sample.channel)?;
+            data.pending.push(*sample);
+            full |= data.pending.len() >= chunk;
+        }
+        self.samples += samples.len() as u64;
+        if full {
+            self.write_pending()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_pending()?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for Hdf5Writer {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            log::warn!("Failed to write final HDF5 samples: {}", e);
+        }
+    }
+}
+
+impl ChannelData {
+    /// Append the pending samples to the datasets
+    fn append(&mut self, map: &ChannelMap, start: Instant) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let time: Vec<f64> = self
+            .pending
+            .iter()
+            .map(|s| s.timestamp.saturating_duration_since(start).as_secs_f64())
+            .collect();
+        let raw: Vec<i32> = self.pending.iter().map(|s| s.raw).collect();
+        let value: Vec<f64> = self.pending.iter().map(|s| map.convert_sample(s)).collect();
+        let sequence: Vec<u64> = self.pending.iter().map(|s| s.sequence).collect();
+
+        extend(&self.time, &time)?;
+        extend(&self.raw, &raw)?;
+        extend(&self.value, &value)?;
+        extend(&self.sequence, &sequence)?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+/// Result of [`export_hdf5`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hdf5Export {
+    /// Samples written
+    pub samples: u64,
+    /// Channel groups written
+    pub channels: usize,
+}
+
+/// Export a binary capture as an HDF5 file
+///
+/// The file carries the capture's configuration, channel map, and start
+/// time; see the [module documentation](self) for the layout.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{CaptureReader, export_hdf5};
+///
+/// let h5 = export_hdf5(CaptureReader::open("capture.ads")?, "capture.h5")?;
+/// println!("{} samples in {} channels", h5.samples, h5.channels);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the capture cannot be read or the file written
+pub fn export_hdf5<R: Read>(
+    capture: CaptureReader<R>,
+    path: impl AsRef<Path>,
+) -> Result<Hdf5Export> {
+    let mut writer = Hdf5Writer::create(path, capture.header())?;
+    let start = Instant::now();
+    writer.origin = (start, capture.wall_clock());
+
+    let mut frame = Vec::new();
+    for sample in capture.samples(start) {
+        frame.push(sample?);
+        if frame.len() == writer.chunk {
+            writer.write(&frame)?;
+            frame.clear();
+        }
+    }
+    writer.write(&frame)?;
+
+    let export = Hdf5Export {
+        samples: writer.len(),
+        channels: writer.channels.len(),
+    };
+    writer.finish()?;
+    log::info!(
+        "Exported {} samples in {} channels to HDF5",
+        export.samples,
+        export.channels
+    );
+    Ok(export)
+}
+
+/// Configuration attributes of the root group
+fn write_header(file: &File, header: &CaptureHeader) -> Result<()> {
+    let adc1 = &header.adc1;
+    attr(file, "chip_id", &header.chip_id)?;
+    text(file, "driver_version", &header.driver_version)?;
+    text(file, "input_mode", &format!("{:?}", header.input_mode))?;
+    text(file, "gain", &format!("{:?}", adc1.gain))?;
+    text(file, "data_rate", &format!("{:?}", adc1.data_rate))?;
+    attr(file, "data_rate_sps", &adc1.data_rate.sps())?;
+    text(file, "filter", &format!("{:?}", adc1.filter))?;
+    text(file, "delay", &format!("{:?}", adc1.delay))?;
+    text(file, "reference_source", &format!("{:?}", adc1.reference))?;
+    attr(file, "reference", &header.map.reference())?;
+    Ok(())
+}
+
+/// Group of `channel` with its calibration attributes and empty datasets
+fn create_channel(file: &File, map: &ChannelMap, channel: u8, chunk: usize) -> Result<ChannelData> {
+    // '/' would nest groups
+    let group = file.create_group(&map.name(channel).replace('/', "_"))?;
+    attr(&group, "channel", &channel)?;
+    text(&group, "unit", map.unit(channel))?;
+    attr(&group, "reference", &map.reference())?;
+    if let Some(info) = map.get(channel) {
+        attr(&group, "scale", &info.scale)?;
+        attr(&group, "offset", &info.offset)?;
+        if let Some(curve) = &info.curve {
+            text(&group, "curve", &curve.to_string())?;
+        }
+    } else {
+        attr(&group, "scale", &1.0)?;
+        attr(&group, "offset", &0.0)?;
+    }
+
+    let time = dataset::<f64>(&group, "time", chunk)?;
+    text(&time, "unit", "s")?;
+    let raw = dataset::<i32>(&group, "raw", chunk)?;
+    let value = dataset::<f64>(&group, "value", chunk)?;
+    text(&value, "unit", map.unit(channel))?;
+    let sequence = dataset::<u64>(&group, "sequence", chunk)?;
+    Ok(ChannelData {
+        time,
+        raw,
+        value,
+        sequence,
+        pending: Vec::new(),
+    })
+}
+
+/// Empty one-dimensional dataset that grows in chunks of `chunk` elements
+fn dataset<T: H5Type>(group: &Group, name: &str, chunk: usize) -> Result<Dataset> {
+    Ok(group
+        .new_dataset::<T>()
+        .chunk((chunk,))
+        .shape(Extent::resizable(0))
+        .create(name)?)
+}
+
+/// Append `values` to a one-dimensional dataset
+fn extend<T: H5Type>(dataset: &Dataset, values: &[T]) -> Result<()> {
+    let len = dataset.size();
+    dataset.resize((len + values.len(),))?;
+    dataset.write_slice(values, len..len + values.len())?;
+    Ok(())
+}
+
+/// Scalar attribute
+fn attr<T: H5Type>(location: &Location, name: &str, value: &T) -> Result<()> {
+    location.new_attr::<T>().create(name)?.write_scalar(value)?;
+    Ok(())
+}
+
+/// UTF-8 string attribute
+fn text(location: &Location, name: &str, value: &str) -> Result<()> {
+    let value: VarLenUnicode = value
+        .parse()
+        .map_err(|e: hdf5_metno::types::StringError| hdf5_metno::Error::from(e.to_string()))?;
+    attr(location, name, &value)
+}
@@ -0,0 +1,170 @@
+//! Code histograms
+//!
+//! [`Histogram`] counts raw ADC codes in bins of configurable width. With a
+//! bin width of 1 every code has its own bin, so missing codes show up as
+//! gaps and bimodal noise or ground bounce as separate peaks; wider bins
+//! summarize long captures cheaply. Percentile queries give robust noise
+//! figures without keeping the samples.
+
+use std::collections::BTreeMap;
+
+/// Histogram of raw ADC codes
+///
+/// Bins are stored sparsely, so memory grows with the number of occupied
+/// bins, not with the code range.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::Histogram;
+///
+/// let burst = adc.capture(0, 100_000)?;
+/// let mut histogram = Histogram::new(1);
+/// histogram.extend(burst.values());
+///
+/// let p1 = histogram.percentile(1.0).unwrap_or_default();
+/// let p99 = histogram.percentile(99.0).unwrap_or_default();
+/// println!("98% of codes within {} LSB", p99 - p1);
+/// println!("{} missing codes", histogram.missing_bins().count());
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    bin_width: u32,
+    /// Count per bin index (code divided by bin width, rounded down)
+    bins: BTreeMap<i64, u64>,
+    count: u64,
+}
+
+impl Histogram {
+    /// Create an empty histogram with bins of `bin_width` codes
+    ///
+    /// A bin width of 0 is treated as 1.
+    pub fn new(bin_width: u32) -> Self {
+        Self {
+            bin_width: bin_width.max(1),
+            bins: BTreeMap::new(),
+            count: 0,
+        }
+    }
+
+    /// Width of a bin in codes
+    pub fn bin_width(&self) -> u32 {
+        self.bin_width
+    }
+
+    /// Count a code
+    pub fn push(&mut self, code: i32) {
+        let index = i64::from(code).div_euclid(i64::from(self.bin_width));
+        *self.bins.entry(index).or_insert(0) += 1;
+        self.count += 1;
+    }
+
+    /// Number of codes counted
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// true if no codes have been counted
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Number of codes in the bin containing `code`
+    pub fn count_at(&self, code: i32) -> u64 {
+        let index = i64::from(code).div_euclid(i64::from(self.bin_width));
+        self.bins.get(&index).copied().unwrap_or(0)
+    }
+
+    /// Occupied bins in ascending order as (lower edge, count)
+    pub fn bins(&self) -> impl Iterator<Item = (i64, u64)> + '_ {
+        self.bins
+            .iter()
+            .map(|(&index, &count)| (self.edge(index), count))
+    }
+
+    /// Lower edge of the lowest occupied bin
+    pub fn min(&self) -> Option<i64> {
+        self.bins.keys().next().map(|&index| self.edge(index))
+    }
+
+    /// Lower edge of the highest occupied bin
+    pub fn max(&self) -> Option<i64> {
+        self.bins.keys().next_back().map(|&index| self.edge(index))
+    }
+
+    /// Most frequent bin as (lower edge, count); the lowest one on ties
+    pub fn mode(&self) -> Option<(i64, u64)> {
+        self.bins()
+            .reduce(|best, bin| if bin.1 > best.1 { bin } else { best })
+    }
+
+    /// Lower edge of the bin holding the `p`-th percentile (0 to 100)
+    ///
+    /// Uses the nearest-rank method; `p` is clamped to 0..=100.
+    pub fn percentile(&self, p: f64) -> Option<i64> {
+        if self.count == 0 || p.is_nan() {
+            return None;
+        }
+        let rank = ((p.clamp(0.0, 100.0) / 100.0 * self.count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (edge, count) in self.bins() {
+            seen += count;
+            if seen >= rank {
+                return Some(edge);
+            }
+        }
+        self.max()
+    }
+
+    /// Lower edge of the bin holding the median
+    pub fn median(&self) -> Option<i64> {
+        self.percentile(50.0)
+    }
+
+    /// Lower edges of empty bins between the lowest and highest occupied bin
+    ///
+    /// With a bin width of 1 these are the missing codes.
+    pub fn missing_bins(&self) -> impl Iterator<Item = i64> + '_ {
+        self.bins
+            .keys()
+            .zip(self.bins.keys().skip(1))
+            .flat_map(|(&low, &high)| low + 1..high)
+            .map(|index| self.edge(index))
+    }
+
+    /// Forget all counts
+    pub fn reset(&mut self) {
+        self.bins.clear();
+        self.count = 0;
+    }
+
+    fn edge(&self, index: i64) -> i64 {
+        index * i64::from(self.bin_width)
+    }
+}
+
+impl Default for Histogram {
+    /// Histogram with one bin per code
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl Extend<i32> for Histogram {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        for code in iter {
+            self.push(code);
+        }
+    }
+}
+
+impl FromIterator<i32> for Histogram {
+    /// Histogram with one bin per code
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut histogram = Self::default();
+        histogram.extend(iter);
+        histogram
+    }
+}
@@ -0,0 +1,216 @@
+//! Home Assistant MQTT discovery
+//!
+//! [`HaDiscovery`] builds the MQTT messages that make each configured
+//! channel of a [`ChannelMap`] appear in Home Assistant as a sensor entity
+//! of one device, with its name and unit. The driver has no MQTT client of
+//! its own; publish the messages with any client (rumqttc, paho, ...):
+//!
+//! | Message | Topic | Retained |
+//! |---------|-------|----------|
+//! | [`config`](HaDiscovery::config) | `homeassistant/sensor/<node>/ch<n>/config` | yes |
+//! | [`online`](HaDiscovery::online) / [`offline`](HaDiscovery::offline) | `ads1263/<node>/availability` | yes |
+//! | [`state`](HaDiscovery::state) | `ads1263/<node>/<name>/state` | no |
+//!
+//! `<name>` is the channel name, lower-cased with characters other than
+//! letters, digits, and `_` replaced by `_`. Entities are identified by
+//! node and channel number, so renaming a channel keeps its history.
+//! Set the offline message as the client's last will so the sensors become
+//! unavailable when the process dies.
+
+use crate::channel_map::ChannelMap;
+use crate::jsonl::{number, string};
+use crate::sample::Sample;
+use std::fmt::Write as _;
+
+/// One message to publish
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MqttMessage {
+    /// Topic to publish on
+    pub topic: String,
+    /// UTF-8 payload
+    pub payload: String,
+    /// true if the broker should keep the message for new subscribers
+    pub retain: bool,
+}
+
+impl MqttMessage {
+    fn new(topic: String, payload: String, retain: bool) -> Self {
+        Self {
+            topic,
+            payload,
+            retain,
+        }
+    }
+}
+
+/// Home Assistant discovery messages for the channels of one board
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{ChannelInfo, ChannelMap, HaDiscovery};
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("Inlet pressure").unit("kPa").linear(20.0, 0.0));
+/// let ha = HaDiscovery::new("adhat_lab1").device_name("Lab AD HAT");
+///
+/// // Publish on connect, with `ha.offline()` as last will
+/// for message in ha.config(&map).into_iter().chain([ha.online()]) {
+///     println!("{} {}", message.topic, message.payload);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HaDiscovery {
+    node_id: String,
+    device_name: String,
+    base_topic: String,
+    discovery_prefix: String,
+}
+
+impl HaDiscovery {
+    /// Discovery for the board `node_id`, unique among the broker's devices
+    ///
+    /// Defaults to the device name "High-Precision AD HAT", state topics
+    /// under `ads1263/<node_id>`, and the discovery prefix `homeassistant`.
+    pub fn new(node_id: impl AsRef<str>) -> Self {
+        let node_id = object_id(node_id.as_ref());
+        Self {
+            base_topic: format!("ads1263/{}", node_id),
+            node_id,
+            device_name: "High-Precision AD HAT".to_string(),
+            discovery_prefix: "homeassistant".to_string(),
+        }
+    }
+
+    /// Set the device name shown in Home Assistant
+    pub fn device_name(mut self, name: impl Into<String>) -> Self {
+        self.device_name = name.into();
+        self
+    }
+
+    /// Set the topic prefix of state and availability messages
+    pub fn base_topic(mut self, topic: impl Into<String>) -> Self {
+        self.base_topic = topic.into();
+        self
+    }
+
+    /// Set the discovery prefix configured in Home Assistant (default `homeassistant`)
+    pub fn discovery_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.discovery_prefix = prefix.into();
+        self
+    }
+
+    /// Topic of the availability messages
+    pub fn availability_topic(&self) -> String {
+        format!("{}/availability", self.base_topic)
+    }
+
+    /// Topic of a channel's state messages
+    pub fn state_topic(&self, map: &ChannelMap, channel: u8) -> String {
+        format!(
+            "{}/{}/state",
+            self.base_topic,
+            object_id(&map.name(channel))
+        )
+    }
+
+    /// Retained discovery message of every configured channel
+    pub fn config(&self, map: &ChannelMap) -> Vec<MqttMessage> {
+        map.iter()
+            .map(|(channel, info)| {
+                let mut payload = String::from("{\"name\":");
+                string(&mut payload, &info.name);
+                let _ = write!(
+                    payload,
+                    ",\"unique_id\":\"{}_ch{}\",\"state_topic\":",
+                    self.node_id, channel
+                );
+                string(&mut payload, &self.state_topic(map, channel));
+                payload.push_str(",\"unit_of_measurement\":");
+                string(&mut payload, &info.unit);
+                if let Some(class) = device_class(&info.unit) {
+                    let _ = write!(payload, ",\"device_class\":\"{}\"", class);
+                }
+                payload.push_str(",\"state_class\":\"measurement\",\"availability_topic\":");
+                string(&mut payload, &self.availability_topic());
+                payload.push_str(",\"device\":");
+                self.device(&mut payload);
+                payload.push('}');
+                MqttMessage::new(self.config_topic(channel), payload, true)
+            })
+            .collect()
+    }
+
+    /// Retained empty messages removing the entities of `map` from Home Assistant
+    pub fn remove(&self, map: &ChannelMap) -> Vec<MqttMessage> {
+        map.iter()
+            .map(|(channel, _)| MqttMessage::new(self.config_topic(channel), String::new(), true))
+            .collect()
+    }
+
+    /// Retained message marking the sensors available
+    pub fn online(&self) -> MqttMessage {
+        MqttMessage::new(self.availability_topic(), "online".to_string(), true)
+    }
+
+    /// Retained message marking the sensors unavailable, e.g. as last will
+    pub fn offline(&self) -> MqttMessage {
+        MqttMessage::new(self.availability_topic(), "offline".to_string(), true)
+    }
+
+    /// State message of a sample, converted by `map`
+    ///
+    /// Values that are not finite are sent as `None`, which Home Assistant
+    /// shows as unknown.
+    pub fn state(&self, map: &ChannelMap, sample: &Sample) -> MqttMessage {
+        let value = map.convert_sample(sample);
+        let mut payload = String::new();
+        if value.is_finite() {
+            number(&mut payload, value);
+        } else {
+            payload.push_str("None");
+        }
+        MqttMessage::new(self.state_topic(map, sample.channel), payload, false)
+    }
+
+    /// Discovery topic of a channel
+    fn config_topic(&self, channel: u8) -> String {
+        format!(
+            "{}/sensor/{}/ch{}/config",
+            self.discovery_prefix, self.node_id, channel
+        )
+    }
+
+    /// Append the device object shared by all entities
+    fn device(&self, payload: &mut String) {
+        payload.push_str("{\"identifiers\":[");
+        string(payload, &self.node_id);
+        payload.push_str("],\"name\":");
+        string(payload, &self.device_name);
+        let _ = write!(
+            payload,
+            ",\"manufacturer\":\"Waveshare\",\"model\":\"High-Precision AD HAT (ADS1263)\",\"sw_version\":\"{}\"}}",
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+}
+
+/// `name` lower-cased, with characters other than `[a-z0-9_]` replaced by `_`
+fn object_id(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.to_ascii_lowercase() {
+            c @ ('a'..='z' | '0'..='9' | '_') => c,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Home Assistant device class of a unit, if it has one
+fn device_class(unit: &str) -> Option<&'static str> {
+    match unit {
+        "V" | "mV" | "µV" | "kV" => Some("voltage"),
+        "A" | "mA" => Some("current"),
+        "°C" | "°F" | "K" => Some("temperature"),
+        "Pa" | "hPa" | "kPa" | "bar" | "mbar" | "psi" => Some("pressure"),
+        _ => None,
+    }
+}
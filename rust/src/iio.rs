@@ -0,0 +1,725 @@
+//! Industrial I/O network bridge
+//!
+//! [`IioServer`] speaks the text protocol of `iiod`, the libiio network
+//! daemon, so standard IIO tooling reads the HAT like any IIO converter:
+//!
+//! ```text
+//! iio_info -u ip:raspberrypi.local
+//! iio_attr -u ip:raspberrypi.local -c ads1263 voltage0 raw
+//! iio_readdev -u ip:raspberrypi.local -s 1000 ads1263 voltage0 voltage1 timestamp > scans.bin
+//! ```
+//!
+//! The context has one device, `iio:device0` named `ads1263`, with an
+//! input channel `voltage<N>` per served ADC1 input `AIN<N>` and a
+//! `timestamp` channel:
+//!
+//! | Attribute | Meaning |
+//! |-----------|---------|
+//! | `sampling_frequency` | ADC1 data rate in samples per second, writable |
+//! | `sampling_frequency_available` | The supported data rates |
+//! | `in_voltage<N>_raw` | One conversion of the channel, unless a buffer is open |
+//! | `in_voltage<N>_scale` | Millivolts per code at the current gain and reference |
+//! | `in_voltage<N>_label` | Channel name from the [`ChannelMap`] |
+//!
+//! A buffer holds the enabled channels as little-endian `s32` codes in
+//! channel order, followed by the timestamp in nanoseconds since the Unix
+//! epoch as an `s64` aligned to 8 bytes. Opening a buffer starts a
+//! background acquisition of the enabled channels, closing it stops the
+//! acquisition; only one buffer can be open at a time. Scans the client
+//! does not fetch in time are dropped.
+//!
+//! The protocol has no authentication; serve trusted networks only.
+
+use crate::acquisition::{Acquisition, AcquisitionConfig, Reconfiguration};
+use crate::ads1263::Ads1263;
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{Adc1Config, DataRate};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// TCP port of `iiod`
+pub const IIOD_PORT: u16 = 30431;
+
+/// Device ID in the context
+const DEVICE_ID: &str = "iio:device0";
+
+/// Device name in the context
+const DEVICE_NAME: &str = "ads1263";
+
+/// libiio version reported to clients
+const VERSION: &str = "0.25.ads1263\n";
+
+/// Scans queued for the open buffer before further scans are dropped
+const QUEUE_SCANS: usize = 4096;
+
+/// How often the listener is polled for new connections
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often idle connections check for shutdown
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Default time `READBUF` waits for data, changed by `TIMEOUT`
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest accepted attribute value
+const MAX_ATTR_BYTES: usize = 4096;
+
+// Negated errno values of the replies
+const ENOENT: i32 = 2;
+const EIO: i32 = 5;
+const EBUSY: i32 = 16;
+const ENODEV: i32 = 19;
+const EINVAL: i32 = 22;
+const ETIMEDOUT: i32 = 110;
+
+/// The board and its acquisition while a buffer is open
+struct Device {
+    /// The driver while no buffer is open
+    adc: Option<Ads1263>,
+    acquisition: Option<Acquisition>,
+    adc1: Adc1Config,
+}
+
+impl Device {
+    /// The driver, unless a buffer is open
+    fn adc(&mut self) -> std::result::Result<&mut Ads1263, i32> {
+        self.adc.as_mut().ok_or(EBUSY)
+    }
+
+    /// Stop the acquisition and take the driver back
+    fn stop(&mut self) -> Result<()> {
+        if let Some(acquisition) = self.acquisition.take() {
+            self.adc = Some(acquisition.join()?);
+            log::info!("IIO buffer closed");
+        }
+        Ok(())
+    }
+}
+
+/// State shared by the server, the listener, and the connections
+struct Shared {
+    device: Mutex<Device>,
+    map: ChannelMap,
+    /// ADC1 inputs served, in scan index order
+    channels: Vec<u8>,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn device(&self) -> MutexGuard<'_, Device> {
+        self.device.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_shut_down(&self) -> bool {
+        self.shutdown.load(Ordering::Relaxed)
+    }
+
+    /// Scan index of the channel `id`; the timestamp follows the inputs
+    fn channel_index(&self, id: &str) -> Option<usize> {
+        if id == "timestamp" {
+            return Some(self.channels.len());
+        }
+        let input = id.strip_prefix("voltage")?.parse::<u8>().ok()?;
+        self.channels.iter().position(|&c| c == input)
+    }
+
+    /// Millivolts per code at the current gain
+    fn scale(&self, adc1: &Adc1Config) -> f64 {
+        let gain = f64::from(1u32 << (adc1.gain as u32));
+        self.map.reference() * 1000.0 / gain / f64::from(1u32 << 31)
+    }
+}
+
+/// Server exposing the HAT through the libiio network protocol
+///
+/// Each client connection is served on its own thread. Dropping the
+/// server, or [`shutdown`](Self::shutdown), stops a running acquisition
+/// and stops accepting connections.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, ChannelMap, DataRate, Hal, IIOD_PORT, IioServer};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps400)?;
+///
+/// let server = IioServer::bind(("0.0.0.0", IIOD_PORT), adc, ChannelMap::new(5.0), &[0, 1, 2, 3])?;
+/// // iio_info -u ip:<pi> now lists the device
+/// let adc = server.shutdown()?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct IioServer {
+    shared: Arc<Shared>,
+    local_addr: SocketAddr,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl IioServer {
+    /// Serve the ADC1 inputs `channels` of `adc` on `addr`, labelling
+    /// them and taking the reference voltage from `map`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidChannel` for an input beyond `AIN9`,
+    /// or `Ads1263Error::Io` if `addr` cannot be bound
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        adc: Ads1263,
+        map: ChannelMap,
+        channels: &[u8],
+    ) -> Result<Self> {
+        if let Some(&channel) = channels.iter().find(|&&c| c > 9) {
+            return Err(Ads1263Error::InvalidChannel(channel, 9));
+        }
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            device: Mutex::new(Device {
+                adc1: adc.adc1_config(),
+                adc: Some(adc),
+                acquisition: None,
+            }),
+            map,
+            channels: channels.to_vec(),
+            shutdown: AtomicBool::new(false),
+        });
+        let listener = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("ads1263-iio".to_string())
+                .spawn(move || listen(listener, shared))?
+        };
+        log::info!("IIO server listening on {}", local_addr);
+        Ok(Self {
+            shared,
+            local_addr,
+            listener: Some(listener),
+        })
+    }
+
+    /// Address the server listens on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop serving and return the driver
+    ///
+    /// # Errors
+    ///
+    /// Returns the error that stopped an open buffer's acquisition
+    pub fn shutdown(mut self) -> Result<Ads1263> {
+        self.stop_listener();
+        let mut device = self.shared.device();
+        device.stop()?;
+        Ok(device.adc.take().expect("driver present when stopped"))
+    }
+
+    fn stop_listener(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+    }
+}
+
+impl Drop for IioServer {
+    fn drop(&mut self) {
+        self.stop_listener();
+        if let Err(e) = self.shared.device().stop() {
+            log::warn!("IIO acquisition failed: {}", e);
+        }
+    }
+}
+
+/// Accept connections until shutdown, serving each on its own thread
+fn listen(listener: TcpListener, shared: Arc<Shared>) {
+    while !shared.is_shut_down() {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let shared = shared.clone();
+                let spawned = thread::Builder::new()
+                    .name("ads1263-iio-client".to_string())
+                    .spawn(move || {
+                        log::info!("IIO client {} connected", peer);
+                        let mut connection = Connection {
+                            shared,
+                            buffer: None,
+                            timeout: READ_TIMEOUT,
+                        };
+                        match connection.serve(stream) {
+                            Ok(()) => log::info!("IIO client {} disconnected", peer),
+                            Err(e) => log::info!("IIO client {} dropped: {}", peer, e),
+                        }
+                        connection.close();
+                    });
+                if let Err(e) = spawned {
+                    log::warn!("Failed to start IIO client thread: {}", e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+            Err(e) => {
+                log::warn!("Failed to accept IIO connection: {}", e);
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Buffer opened by a connection
+struct Buffer {
+    scans: Receiver<Vec<u8>>,
+    /// Bytes of a scan not yet sent
+    pending: Vec<u8>,
+    /// Channel mask as sent with the data
+    mask: String,
+}
+
+/// One client connection
+struct Connection {
+    shared: Arc<Shared>,
+    buffer: Option<Buffer>,
+    timeout: Duration,
+}
+
+impl Connection {
+    /// Answer commands until the client exits or the server shuts down
+    fn serve(&mut self, stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        stream.set_nodelay(true)?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut writer = stream;
+        let mut line = String::new();
+        while !self.shared.is_shut_down() {
+            match reader.read_line(&mut line) {
+                Ok(0) => return Ok(()),
+                Ok(_) if !line.ends_with('\n') => continue,
+                Ok(_) => {}
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+            let words: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+            line.clear();
+            let Some(command) = words.first() else {
+                continue;
+            };
+            log::debug!("IIO command {:?}", words);
+            match command.to_ascii_uppercase().as_str() {
+                "EXIT" => return Ok(()),
+                "VERSION" => writer.write_all(VERSION.as_bytes())?,
+                "PRINT" => {
+                    let xml = context_xml(&self.shared);
+                    write!(writer, "{}\n{}\n", xml.len(), xml)?;
+                }
+                "TIMEOUT" => {
+                    let code = match words.get(1).and_then(|ms| ms.parse().ok()) {
+                        Some(ms) => {
+                            self.timeout = match ms {
+                                0 => Duration::MAX,
+                                ms => Duration::from_millis(ms),
+                            };
+                            0
+                        }
+                        None => -EINVAL,
+                    };
+                    reply(&mut writer, code)?;
+                }
+                "OPEN" => {
+                    let code = self.open(&words[1..]).map_or_else(|e| -e, |()| 0);
+                    reply(&mut writer, code)?;
+                }
+                "CLOSE" => {
+                    let code = match self.device(words.get(1)) {
+                        Ok(()) => self.close(),
+                        Err(e) => -e,
+                    };
+                    reply(&mut writer, code)?;
+                }
+                "READBUF" => self.read_buffer(&words[1..], &mut writer)?,
+                "READ" => {
+                    let value = self.read_attr(&words[1..]);
+                    match value {
+                        Ok(value) => {
+                            // The length counts the terminating NUL, as in sysfs reads
+                            write!(writer, "{}\n{}\0\n", value.len() + 1, value)?;
+                        }
+                        Err(e) => reply(&mut writer, -e)?,
+                    }
+                }
+                "WRITE" => {
+                    let code = self.write_attr(&words[1..], &mut reader);
+                    reply(&mut writer, code)?;
+                }
+                "GETTRIG" => reply(&mut writer, -ENOENT)?,
+                "SET" => reply(&mut writer, 0)?,
+                _ => reply(&mut writer, -EINVAL)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the device named in a command
+    fn device(&self, name: Option<&String>) -> std::result::Result<(), i32> {
+        match name.map(String::as_str) {
+            Some(DEVICE_ID | DEVICE_NAME) => Ok(()),
+            _ => Err(ENODEV),
+        }
+    }
+
+    /// `OPEN <device> <samples> <mask> [CYCLIC]`: start an acquisition of
+    /// the channels in the mask
+    fn open(&mut self, args: &[String]) -> std::result::Result<(), i32> {
+        self.device(args.first())?;
+        let mask = args.get(2).ok_or(EINVAL)?;
+        if self.buffer.is_some() || args.get(3).is_some_and(|a| a == "CYCLIC") {
+            return Err(EINVAL);
+        }
+        let enabled = parse_mask(mask, self.shared.channels.len() + 1).ok_or(EINVAL)?;
+        let inputs: Vec<u8> = enabled
+            .iter()
+            .filter_map(|&index| self.shared.channels.get(index).copied())
+            .collect();
+        if inputs.is_empty() {
+            return Err(EINVAL);
+        }
+        let timestamp = enabled.contains(&self.shared.channels.len());
+        let (sender, scans) = mpsc::sync_channel(QUEUE_SCANS);
+        let sink = BufferSink {
+            sender,
+            inputs: inputs.len(),
+            timestamp,
+            clock: WallClock::new(),
+        };
+        let mut device = self.shared.device();
+        let adc = device.adc.take().ok_or(EBUSY)?;
+        match Acquisition::try_start_sink(adc, &inputs, AcquisitionConfig::default(), sink) {
+            Ok(acquisition) => device.acquisition = Some(acquisition),
+            Err((e, adc)) => {
+                device.adc = Some(adc);
+                log::warn!("Failed to open IIO buffer: {}", e);
+                return Err(EIO);
+            }
+        }
+        log::info!(
+            "IIO buffer opened on AIN{:?}{}",
+            inputs,
+            if timestamp { " with timestamps" } else { "" }
+        );
+        self.buffer = Some(Buffer {
+            scans,
+            pending: Vec::new(),
+            mask: format!("{}\n", mask),
+        });
+        Ok(())
+    }
+
+    /// Close this connection's buffer, stopping its acquisition
+    fn close(&mut self) -> i32 {
+        if self.buffer.take().is_none() {
+            return 0;
+        }
+        match self.shared.device().stop() {
+            Ok(()) => 0,
+            Err(e) => {
+                log::warn!("IIO acquisition failed: {}", e);
+                -EIO
+            }
+        }
+    }
+
+    /// `READBUF <device> <bytes>`: send `bytes` of buffered scans
+    fn read_buffer(&mut self, args: &[String], writer: &mut impl Write) -> io::Result<()> {
+        if let Err(e) = self.device(args.first()) {
+            return reply(writer, -e);
+        }
+        let (Some(buffer), Some(Ok(len))) = (&mut self.buffer, args.get(1).map(|b| b.parse()))
+        else {
+            return reply(writer, -EINVAL);
+        };
+        let mut data = Vec::with_capacity(len);
+        while data.len() < len {
+            if buffer.pending.is_empty() {
+                match buffer.scans.recv_timeout(self.timeout) {
+                    Ok(scan) => buffer.pending = scan,
+                    Err(RecvTimeoutError::Timeout) => return reply(writer, -ETIMEDOUT),
+                    Err(RecvTimeoutError::Disconnected) => return reply(writer, -EIO),
+                }
+            }
+            let take = buffer.pending.len().min(len - data.len());
+            data.extend(buffer.pending.drain(..take));
+        }
+        write!(writer, "{}\n{}", len, buffer.mask)?;
+        writer.write_all(&data)
+    }
+
+    /// `READ <device> [INPUT <channel>] <attribute>`
+    fn read_attr(&mut self, args: &[String]) -> std::result::Result<String, i32> {
+        self.device(args.first())?;
+        let shared = &self.shared;
+        match &args[1..] {
+            [attr] => {
+                let adc1 = shared.device().adc1;
+                match attr.as_str() {
+                    "sampling_frequency" => Ok(adc1.data_rate.sps().to_string()),
+                    "sampling_frequency_available" => Ok(DataRate::ALL
+                        .iter()
+                        .map(|rate| rate.sps().to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")),
+                    _ => Err(ENOENT),
+                }
+            }
+            [direction, id, attr] if direction == "INPUT" => {
+                let index = shared.channel_index(id).ok_or(ENODEV)?;
+                let Some(&input) = shared.channels.get(index) else {
+                    return Err(ENOENT);
+                };
+                match attr.as_str() {
+                    "raw" => {
+                        let mut device = shared.device();
+                        let sample = device.adc()?.get_sample(input).map_err(|e| {
+                            log::warn!("IIO read of AIN{} failed: {}", input, e);
+                            EIO
+                        })?;
+                        Ok(sample.raw.to_string())
+                    }
+                    "scale" => Ok(shared.scale(&shared.device().adc1).to_string()),
+                    "label" => Ok(shared.map.name(input).into_owned()),
+                    _ => Err(ENOENT),
+                }
+            }
+            _ => Err(EINVAL),
+        }
+    }
+
+    /// `WRITE <device> <attribute> <bytes>` followed by the value
+    fn write_attr(&mut self, args: &[String], reader: &mut impl Read) -> i32 {
+        let (Some(attr), Some(Ok(len))) = (args.get(1), args.last().map(|b| b.parse::<usize>()))
+        else {
+            return -EINVAL;
+        };
+        if len > MAX_ATTR_BYTES {
+            return -EINVAL;
+        }
+        let mut value = vec![0; len];
+        if read_exactly(reader, &mut value).is_err() {
+            return -EIO;
+        }
+        if let Err(e) = self.device(args.first()) {
+            return -e;
+        }
+        if args.len() != 3 || attr != "sampling_frequency" {
+            return -ENOENT;
+        }
+        let value = String::from_utf8_lossy(&value);
+        let Some(rate) = value
+            .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+            .parse()
+            .ok()
+            .and_then(DataRate::from_sps)
+        else {
+            return -EINVAL;
+        };
+        let mut guard = self.shared.device();
+        let device = &mut *guard;
+        let adc1 = Adc1Config {
+            data_rate: rate,
+            ..device.adc1
+        };
+        let result = match (&device.acquisition, device.adc.as_mut()) {
+            (Some(acquisition), _) => acquisition.reconfigure(Reconfiguration::Adc1(adc1)),
+            (None, Some(adc)) => adc.configure_adc1(adc1),
+            (None, None) => return -EBUSY,
+        };
+        match result {
+            Ok(()) => {
+                device.adc1 = adc1;
+                len as i32
+            }
+            Err(e) => {
+                log::warn!("IIO data rate change failed: {}", e);
+                -EIO
+            }
+        }
+    }
+}
+
+/// Sink encoding scans in the IIO buffer layout
+struct BufferSink {
+    sender: SyncSender<Vec<u8>>,
+    /// Enabled inputs, the samples of one scan
+    inputs: usize,
+    timestamp: bool,
+    clock: WallClock,
+}
+
+impl Sink for BufferSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if samples.len() != self.inputs {
+            return Ok(());
+        }
+        let mut scan = Vec::with_capacity(samples.len() * 4 + 16);
+        for sample in samples {
+            scan.extend_from_slice(&sample.raw.to_le_bytes());
+        }
+        if self.timestamp {
+            self.clock.update();
+            // The s64 timestamp is aligned to its size
+            scan.resize(scan.len().next_multiple_of(8), 0);
+            let nanos = self
+                .clock
+                .to_utc(samples[0].timestamp)
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as i64;
+            scan.extend_from_slice(&nanos.to_le_bytes());
+        }
+        match self.sender.try_send(scan) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(_)) => log::debug!("IIO client lagging, scan dropped"),
+        }
+        Ok(())
+    }
+}
+
+/// Send a numeric reply
+fn reply(writer: &mut impl Write, code: i32) -> io::Result<()> {
+    writeln!(writer, "{}", code)
+}
+
+/// Read exactly `buf.len()` bytes, retrying after read timeouts
+fn read_exactly(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<()> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::Interrupted
+                ) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Enabled scan indices of a hex channel mask, lowest word last, or `None`
+/// if it is malformed or enables channels beyond `channels`
+fn parse_mask(mask: &str, channels: usize) -> Option<Vec<usize>> {
+    if mask.is_empty() || !mask.len().is_multiple_of(8) {
+        return None;
+    }
+    let mut enabled = Vec::new();
+    for (word, chunk) in mask.as_bytes().rchunks(8).enumerate() {
+        let bits = u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok()?;
+        for bit in 0..32 {
+            if bits & (1 << bit) != 0 {
+                enabled.push(word * 32 + bit);
+            }
+        }
+    }
+    enabled
+        .iter()
+        .all(|&index| index < channels)
+        .then_some(enabled)
+}
+
+/// Document type of libiio context descriptions
+const XML_HEADER: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+    "<!DOCTYPE context [",
+    "<!ELEMENT context (device | context-attribute)*>",
+    "<!ELEMENT context-attribute EMPTY>",
+    "<!ELEMENT device (channel | attribute | debug-attribute | buffer-attribute)*>",
+    "<!ELEMENT channel (scan-element?, attribute*)>",
+    "<!ELEMENT attribute EMPTY>",
+    "<!ELEMENT scan-element EMPTY>",
+    "<!ELEMENT debug-attribute EMPTY>",
+    "<!ELEMENT buffer-attribute EMPTY>",
+    "<!ATTLIST context name CDATA #REQUIRED version-major CDATA #REQUIRED ",
+    "version-minor CDATA #REQUIRED version-git CDATA #REQUIRED description CDATA #IMPLIED>",
+    "<!ATTLIST context-attribute name CDATA #REQUIRED value CDATA #REQUIRED>",
+    "<!ATTLIST device id CDATA #REQUIRED name CDATA #IMPLIED label CDATA #IMPLIED>",
+    "<!ATTLIST channel id CDATA #REQUIRED type (input|output) #REQUIRED name CDATA #IMPLIED>",
+    "<!ATTLIST scan-element index CDATA #REQUIRED format CDATA #REQUIRED scale CDATA #IMPLIED>",
+    "<!ATTLIST attribute name CDATA #REQUIRED filename CDATA #IMPLIED value CDATA #IMPLIED>",
+    "<!ATTLIST debug-attribute name CDATA #REQUIRED value CDATA #IMPLIED>",
+    "<!ATTLIST buffer-attribute name CDATA #REQUIRED value CDATA #IMPLIED>",
+    "]>",
+);
+
+/// Context description answering `PRINT`
+fn context_xml(shared: &Shared) -> String {
+    let scale = shared.scale(&shared.device().adc1);
+    let mut xml = String::from(XML_HEADER);
+    let _ = write!(
+        xml,
+        "<context name=\"local\" version-major=\"0\" version-minor=\"25\" version-git=\"ads1263\" \
+         description=\"Waveshare High-Precision AD HAT\">\
+         <context-attribute name=\"hw_model\" value=\"Waveshare High-Precision AD HAT\" />\
+         <context-attribute name=\"driver_version\" value=\"{}\" />\
+         <device id=\"{}\" name=\"{}\">",
+        env!("CARGO_PKG_VERSION"),
+        DEVICE_ID,
+        DEVICE_NAME
+    );
+    for (index, &input) in shared.channels.iter().enumerate() {
+        // Channels named in the map show their name in iio_info
+        let name = shared
+            .map
+            .get(input)
+            .map(|info| format!(" name=\"{}\"", escape(&info.name)))
+            .unwrap_or_default();
+        let _ = write!(
+            xml,
+            "<channel id=\"voltage{input}\" type=\"input\"{name}>\
+             <scan-element index=\"{index}\" format=\"le:s32/32&gt;&gt;0\" scale=\"{scale}\" />\
+             <attribute name=\"raw\" filename=\"in_voltage{input}_raw\" />\
+             <attribute name=\"scale\" filename=\"in_voltage{input}_scale\" />\
+             <attribute name=\"label\" filename=\"in_voltage{input}_label\" />\
+             </channel>",
+        );
+    }
+    let _ = write!(
+        xml,
+        "<channel id=\"timestamp\" type=\"input\">\
+         <scan-element index=\"{}\" format=\"le:s64/64&gt;&gt;0\" />\
+         </channel>\
+         <attribute name=\"sampling_frequency\" />\
+         <attribute name=\"sampling_frequency_available\" />\
+         </device></context>",
+        shared.channels.len()
+    );
+    xml
+}
+
+/// `text` escaped for an XML attribute value
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
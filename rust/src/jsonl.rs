@@ -0,0 +1,299 @@
+//! JSON Lines output
+//!
+//! [`JsonLinesSink`] writes one JSON object per line, ready to pipe into
+//! `jq`, Vector, or Fluent Bit:
+//!
+//! ```text
+//! {"timestamp":0.000000,"channel":"inlet","raw":1073741824,"value":50,"unit":"kPa"}
+//! {"timestamp":0.000833,"channel":"ch1","raw":-2147484,"value":-0.005000001,"unit":"V"}
+//! ```
+//!
+//! The fields are selected with [`JsonLinesSink::fields`]. With
+//! [`JsonLinesSink::per_scan`] each write becomes one object holding the
+//! timestamp of its first sample and the selected fields of every sample:
+//!
+//! ```text
+//! {"timestamp":0.000000,"samples":[{"channel":"inlet","value":50},{"channel":"ch1","value":-0.005000001}]}
+//! ```
+//!
+//! Timestamps are seconds since the first sample, or Unix time if a
+//! [`WallClock`] is attached. Values that are not finite are written as
+//! `null`.
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Field of a JSON Lines object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonField {
+    /// `timestamp`: seconds, relative or Unix time
+    Timestamp,
+    /// `channel`: channel name from the [`ChannelMap`]
+    Channel,
+    /// `raw`: ADC1 code
+    Raw,
+    /// `value`: value converted by the [`ChannelMap`]
+    Value,
+    /// `unit`: unit of the converted value
+    Unit,
+    /// `sequence`: acquisition sequence number
+    Sequence,
+    /// `status`: status byte
+    Status,
+    /// `crc_ok`: CRC check result
+    CrcOk,
+}
+
+impl JsonField {
+    /// Key of the field in the JSON object
+    pub fn key(self) -> &'static str {
+        match self {
+            JsonField::Timestamp => "timestamp",
+            JsonField::Channel => "channel",
+            JsonField::Raw => "raw",
+            JsonField::Value => "value",
+            JsonField::Unit => "unit",
+            JsonField::Sequence => "sequence",
+            JsonField::Status => "status",
+            JsonField::CrcOk => "crc_ok",
+        }
+    }
+}
+
+/// Sink writing samples as JSON Lines
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelInfo, ChannelMap, DataRate, Hal, JsonField, JsonLinesSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps20)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let sink = JsonLinesSink::new(std::io::stdout(), map)
+///     .fields(&[JsonField::Timestamp, JsonField::Channel, JsonField::Value])
+///     .per_scan(true);
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct JsonLinesSink<W: Write> {
+    writer: W,
+    map: ChannelMap,
+    fields: Vec<JsonField>,
+    per_scan: bool,
+    clock: Option<WallClock>,
+    /// Timestamp of the first object; relative times are measured from it
+    start: Option<Instant>,
+    line: String,
+}
+
+impl JsonLinesSink<BufWriter<File>> {
+    /// Create (or truncate) a JSON Lines file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created
+    pub fn create(path: impl AsRef<Path>, map: ChannelMap) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), map))
+    }
+}
+
+impl<W: Write> JsonLinesSink<W> {
+    /// Write to `writer`, naming and converting channels with `map`
+    ///
+    /// Defaults to one object per sample with timestamp, channel, raw,
+    /// value, and unit.
+    pub fn new(writer: W, map: ChannelMap) -> Self {
+        Self {
+            writer,
+            map,
+            fields: vec![
+                JsonField::Timestamp,
+                JsonField::Channel,
+                JsonField::Raw,
+                JsonField::Value,
+                JsonField::Unit,
+            ],
+            per_scan: false,
+            clock: None,
+            start: None,
+            line: String::new(),
+        }
+    }
+
+    /// Select the fields written, in order
+    pub fn fields(mut self, fields: &[JsonField]) -> Self {
+        self.fields = fields.to_vec();
+        self
+    }
+
+    /// Write one object per scan instead of per sample
+    ///
+    /// Each call to [`Sink::write`] is one scan, as delivered by an
+    /// acquisition.
+    pub fn per_scan(mut self, per_scan: bool) -> Self {
+        self.per_scan = per_scan;
+        self
+    }
+
+    /// Write Unix timestamps from a [`WallClock`] instead of relative times
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Channel map used for names and conversion
+    pub fn map(&self) -> &ChannelMap {
+        &self.map
+    }
+
+    /// Flush and return the underlying writer
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if flushing fails
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Write one line of `samples`, one object per sample or per scan
+    fn write_objects(&mut self, samples: &[Sample]) -> Result<()> {
+        let Some(first) = samples.first() else {
+            return Ok(());
+        };
+        let mut line = std::mem::take(&mut self.line);
+        line.clear();
+        if self.per_scan {
+            line.push('{');
+            if self.fields.contains(&JsonField::Timestamp) {
+                let time = self.timestamp(first.timestamp);
+                let _ = write!(
+                    line,
+                    "\"timestamp\":{}.{:06},",
+                    time.as_secs(),
+                    time.subsec_micros()
+                );
+            }
+            line.push_str("\"samples\":[");
+            for (i, sample) in samples.iter().enumerate() {
+                if i > 0 {
+                    line.push(',');
+                }
+                self.object(&mut line, sample, None);
+            }
+            line.push_str("]}\n");
+        } else {
+            let timestamp = self.fields.contains(&JsonField::Timestamp);
+            for sample in samples {
+                let time = timestamp.then(|| self.timestamp(sample.timestamp));
+                self.object(&mut line, sample, time);
+                line.push('\n');
+            }
+        }
+        let result = self.writer.write_all(line.as_bytes());
+        self.line = line;
+        Ok(result?)
+    }
+
+    /// Append the object of one sample to `line`, with `time` as timestamp
+    fn object(&self, line: &mut String, sample: &Sample, time: Option<Duration>) {
+        line.push('{');
+        let mut first = true;
+        for &field in &self.fields {
+            if field == JsonField::Timestamp && time.is_none() {
+                continue;
+            }
+            if !first {
+                line.push(',');
+            }
+            first = false;
+            let _ = write!(line, "\"{}\":", field.key());
+            match field {
+                JsonField::Timestamp => {
+                    let time = time.unwrap_or_default();
+                    let _ = write!(line, "{}.{:06}", time.as_secs(), time.subsec_micros());
+                }
+                JsonField::Channel => string(line, &self.map.name(sample.channel)),
+                JsonField::Raw => {
+                    let _ = write!(line, "{}", sample.raw);
+                }
+                JsonField::Value => number(line, self.map.convert_sample(sample)),
+                JsonField::Unit => string(line, self.map.unit(sample.channel)),
+                JsonField::Sequence => {
+                    let _ = write!(line, "{}", sample.sequence);
+                }
+                JsonField::Status => {
+                    let _ = write!(line, "{}", sample.status.bits());
+                }
+                JsonField::CrcOk => {
+                    let _ = write!(line, "{}", sample.crc_ok);
+                }
+            }
+        }
+        line.push('}');
+    }
+
+    /// Seconds since the first object, or since the Unix epoch with a clock
+    fn timestamp(&mut self, at: Instant) -> Duration {
+        match &mut self.clock {
+            Some(clock) => {
+                clock.update();
+                clock
+                    .to_utc(at)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+            }
+            None => at.saturating_duration_since(*self.start.get_or_insert(at)),
+        }
+    }
+}
+
+impl<W: Write + Send> Sink for JsonLinesSink<W> {
+    /// Write one object per sample, or one per call with `per_scan`
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.write_objects(samples)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Append a JSON number, or `null` if it is not finite
+pub(crate) fn number(line: &mut String, value: f64) {
+    if value.is_finite() {
+        let _ = write!(line, "{}", value);
+    } else {
+        line.push_str("null");
+    }
+}
+
+/// Append a quoted and escaped JSON string
+pub(crate) fn string(line: &mut String, value: &str) {
+    line.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(line, "\\u{:04x}", u32::from(c));
+            }
+            c => line.push(c),
+        }
+    }
+    line.push('"');
+}
@@ -11,7 +11,70 @@
 //! - Configurable gain, data rate, and digital filters
 //! - RTD (Resistance Temperature Detector) measurement support
 //! - DAC output control
+//! - Die temperature and supply voltage monitors via [`Ads1263::read_monitor`]
+//! - On-chip GPIO pins (AIN3 to AIN9, AINCOM) via [`Ads1263::gpio`]
+//! - Timestamped [`Sample`]s with status byte and CRC result from the streaming APIs
+//! - Missed-conversion counters for continuous reads via [`OverrunStats`]
+//! - Drift-corrected UTC timestamps with optional PTP/NTP offset via [`WallClock`]
+//! - Blocking iterator over a channel's conversions via [`Ads1263::samples`]
+//! - Post-processing [`Pipeline`] with per-channel filter stages
+//! - Per-channel names, units, and linear scaling via [`ChannelMap`]
+//! - Polynomial and lookup-table linearization of nonlinear sensors via [`Curve`]
+//! - Sensors with their own ADC setup and conversion to physical units, bound to channels, via [`Sensor`] and [`SensorRegistry`]
+//! - Thermocouples with cold-junction compensation from the internal temperature sensor via [`Thermocouple`]
+//! - PT100, PT500, and PT1000 RTDs with α = 0.00385, 0.003911, or 0.003916 via [`RtdSensor`]
+//! - NTC thermistors in a voltage divider with beta or Steinhart–Hart coefficients via [`Thermistor`]
+//! - Ratiometric conversions against the sensor excitation via [`Ads1263::read_ratio`] and [`RatiometricSensor`]
+//! - Ratiometric load cells with mV/V sensitivity, tare, and known-weight calibration via [`LoadCell`]
+//! - Quarter-, half-, and full-bridge strain gauges in µε via [`StrainGauge`]
+//! - Shunt current measurement with burden-voltage checks via [`ShuntCurrent`]
+//! - 4–20 mA current loop transmitters with zero/span scaling and NE 43 fault limits via [`LoopCurrent`]
+//! - Windowed statistics (mean, std dev, min/max, p-p, RMS) via [`WindowStats`] and [`Ads1263::measure_stats`]
+//! - FFT amplitude spectrum with THD and SNR via [`Spectrum`] (`analysis` feature)
+//! - Code histograms with percentiles and missing-code detection via [`Histogram`]
+//! - Burst capture of consecutive conversions with timing statistics via [`Ads1263::capture`]
+//! - Scan sequences with per-channel gain, data rate, and settling via [`ScanPlan`]
+//! - One fast ADC1 channel alongside ADC2 round-robin scanning, merged in time order, via [`DualScan`]
+//! - Several boards on one SPI bus via [`Ads1263Bank`]
+//! - Phase-coherent multi-board conversions on a shared START line via [`SyncGroup`]
+//! - Thread-safe shared access via [`Ads1263Handle`]
+//! - Triggered capture with pre/post-trigger scans via [`Trigger`]
+//! - Threshold alarms with hysteresis and debounce via [`AlarmMonitor`]
+//! - Background sampler thread via [`Acquisition`], optionally into a lock-free [`RingBuffer`]
+//! - Gap-free double-buffered block capture via [`BlockAcquisition`]
+//! - Acquisition watchdog re-initializing the device after DRDY stalls or SPI errors via [`WatchdogConfig`]
+//! - Pause, resume, and runtime reconfiguration of running acquisitions via [`Reconfiguration`]
+//! - Sampler thread writing straight into a [`Sink`], e.g. a memory-mapped file (`mmap` feature)
+//! - CSV export with channel names and converted values via [`CsvSink`]
+//! - JSON Lines output per sample or per scan with selectable fields via [`JsonLinesSink`]
+//! - Lossless self-describing binary captures via [`CaptureWriter`] and [`CaptureReader`]
+//! - Frame-wise zstd compression of captures and CSV via [`ZstdWriter`] (`zstd` feature)
+//! - Size- and age-based file rotation with retention via [`RotatingSink`]
+//! - Queryable history in a SQLite database via [`SqliteSink`] (`sqlite` feature)
+//! - Home Assistant MQTT discovery of channels as sensor entities via [`HaDiscovery`]
+//! - HTTP API for latest values, configuration, start/stop, and capture downloads via [`RestApi`] (`rest` feature)
+//! - D-Bus service with latest values, configuration properties, and alarm signals via [`DbusService`] (`dbus` feature)
+//! - gRPC service with configure, start/stop, sample streaming, and calibration RPCs via [`GrpcServer`] (`grpc` feature)
+//! - Live streaming to browser dashboards with per-client subscriptions via [`WebSocketSink`] (`websocket` feature)
+//! - systemd readiness, watchdog, and socket activation via [`SystemdNotifier`] and [`listen_fds`]
+//! - Length-prefixed binary frames over a Unix domain socket for local consumers via [`UnixSocketSink`]
+//! - Sequence-numbered sample packets over UDP unicast or multicast via [`UdpSink`]
+//! - Lab Streaming Layer outlet with XDF channel metadata and LSL-clock timestamps via [`LslOutlet`] (`lsl` feature)
+//! - libiio network protocol for `iio_info`, `iio_readdev`, and other IIO clients via [`IioServer`]
+//! - Node.js addon with promise-based reads and async-iterator streams via napi-rs (`node` feature)
+//! - Batched inserts into PostgreSQL or a TimescaleDB hypertable with reconnects and spill buffering via [`TimescaleSink`] (`postgres` feature)
+//! - Declarative TOML/YAML configuration of pins, ADCs, channels, sensors, and sinks via [`Ads1263Config`] (`config` feature)
+//! - Replay of recorded captures through the same sample and pipeline APIs via [`ReplaySource`]
+//! - 32-bit float WAV export of a channel for audio tooling via [`export_wav`]
+//! - HDF5 files with per-channel groups and configuration attributes (`hdf5` feature)
+//! - Parquet files of Arrow record batches for pandas, Polars, and data lakes via [`ParquetSink`] (`parquet` feature)
+//! - ADC1 self and system offset/gain calibration via [`Ads1263::calibrate_adc1`]
 //! - Automatic CRC checksum verification
+//! - Optional async API on tokio (`tokio` feature)
+//! - `futures::Stream` of samples driven by the DRDY interrupt (`stream` feature)
+//! - Generic async driver over `embedded-hal-async` (`embedded-hal-async` feature), usable
+//!   without std with `default-features = false`
+//! - Real-time thread priority, CPU pinning, and jitter measurement (`realtime` feature)
 //!
 //! ## Hardware Requirements
 //!
@@ -80,16 +143,259 @@
 //! }
 //! ```
 
+#![cfg_attr(not(feature = "rpi"), no_std)]
+
+#[cfg(feature = "rpi")]
+pub mod acquisition;
+#[cfg(feature = "rpi")]
 pub mod ads1263;
+#[cfg(feature = "rpi")]
+pub mod alarm;
+#[cfg(feature = "analysis")]
+pub mod analysis;
+#[cfg(feature = "parquet")]
+pub mod arrow;
+#[cfg(feature = "tokio")]
+pub mod asynch;
+#[cfg(feature = "rpi")]
+pub mod bank;
+#[cfg(feature = "rpi")]
+pub mod bridge;
+#[cfg(feature = "rpi")]
+pub mod burst;
+#[cfg(feature = "rpi")]
+pub mod capture;
+#[cfg(feature = "rpi")]
+pub mod channel_map;
+#[cfg(feature = "rpi")]
+pub mod chip_gpio;
+#[cfg(feature = "rpi")]
+pub mod clock;
+#[cfg(feature = "zstd")]
+pub mod compress;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "rpi")]
+pub mod csv;
+#[cfg(feature = "rpi")]
+pub mod current;
+#[cfg(feature = "dbus")]
+pub mod dbus;
+#[cfg(feature = "rpi")]
+pub mod decimate;
+#[cfg(feature = "rpi")]
+pub mod delay;
+#[cfg(feature = "rpi")]
+pub mod dual;
+#[cfg(feature = "embedded-hal-async")]
+pub mod eh_async;
 pub mod error;
+#[cfg(feature = "rpi")]
+pub mod filter;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "rpi")]
 pub mod hal;
+#[cfg(feature = "rpi")]
+pub mod handle;
+#[cfg(feature = "hdf5")]
+pub mod hdf5;
+#[cfg(feature = "rpi")]
+pub mod histogram;
+#[cfg(feature = "rpi")]
+pub mod homeassistant;
+#[cfg(feature = "rpi")]
+pub mod iio;
+#[cfg(feature = "rpi")]
+pub mod jsonl;
+#[cfg(feature = "rpi")]
+pub mod linearize;
+#[cfg(feature = "lsl")]
+pub mod lsl;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "node")]
+pub mod node;
+#[cfg(feature = "rpi")]
+pub mod overrun;
+#[cfg(feature = "rpi")]
+pub mod pipeline;
+#[cfg(any(feature = "rpi", feature = "embedded-hal-async"))]
+mod protocol;
+#[cfg(feature = "realtime")]
+pub mod realtime;
 pub mod registers;
+#[cfg(feature = "rpi")]
+pub mod replay;
+#[cfg(feature = "rest")]
+pub mod rest;
+#[cfg(feature = "rpi")]
+pub mod ring;
+#[cfg(feature = "rpi")]
+pub mod rotate;
+#[cfg(feature = "rpi")]
+pub mod rtd;
+#[cfg(feature = "rpi")]
+pub mod sample;
+#[cfg(feature = "rpi")]
+pub mod scan;
+#[cfg(feature = "rpi")]
+pub mod sensors;
+#[cfg(feature = "rpi")]
+pub mod sink;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "rpi")]
+pub mod stats;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(feature = "rpi")]
+pub mod sync;
+#[cfg(feature = "rpi")]
+pub mod systemd;
+#[cfg(feature = "rpi")]
+pub mod thermistor;
+#[cfg(feature = "rpi")]
+pub mod thermocouple;
+#[cfg(feature = "postgres")]
+pub mod timescale;
+#[cfg(feature = "rpi")]
+pub mod trigger;
+#[cfg(feature = "rpi")]
+pub mod udp;
+#[cfg(feature = "rpi")]
+pub mod unix_socket;
+#[cfg(feature = "rpi")]
+pub mod wav;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 // Re-export main types for convenience
-pub use ads1263::Ads1263;
+#[cfg(feature = "rpi")]
+pub use acquisition::{
+    Acquisition, AcquisitionConfig, AcquisitionEvent, Block, BlockAcquisition, Reconfiguration,
+    WatchdogConfig,
+};
+#[cfg(feature = "rpi")]
+pub use ads1263::{Adc1Calibration, Ads1263, Calibration, ConversionControl, RtdWiring};
+#[cfg(feature = "rpi")]
+pub use alarm::{AlarmEvent, AlarmKind, AlarmLimits, AlarmMonitor};
+#[cfg(feature = "analysis")]
+pub use analysis::{Spectrum, Window};
+#[cfg(feature = "parquet")]
+pub use arrow::ParquetSink;
+#[cfg(feature = "tokio")]
+pub use asynch::Ads1263Async;
+#[cfg(feature = "rpi")]
+pub use bank::{Ads1263Bank, BankChannel};
+#[cfg(feature = "rpi")]
+pub use bridge::{BridgeType, LoadCell, StrainGauge};
+#[cfg(feature = "rpi")]
+pub use burst::{Burst, BurstTiming};
+#[cfg(feature = "rpi")]
+pub use capture::{CaptureFile, CaptureHeader, CaptureReader, CaptureSamples, CaptureWriter};
+#[cfg(feature = "rpi")]
+pub use channel_map::{ChannelInfo, ChannelMap};
+#[cfg(feature = "rpi")]
+pub use chip_gpio::{ChipGpio, GpioDirection};
+#[cfg(feature = "rpi")]
+pub use clock::{ClockConfig, WallClock};
+#[cfg(feature = "zstd")]
+pub use compress::ZstdWriter;
+#[cfg(feature = "config")]
+pub use config::{Adc2Config, Ads1263Config, Rotation, SensorConfig, SinkConfig};
+#[cfg(feature = "rpi")]
+pub use csv::CsvSink;
+#[cfg(feature = "rpi")]
+pub use current::{LoopCurrent, ShuntCurrent};
+#[cfg(feature = "dbus")]
+pub use dbus::{Bus, DbusService, DbusSink};
+#[cfg(feature = "rpi")]
+pub use decimate::{Decimator, DownsampleMode, Downsampler};
+#[cfg(feature = "rpi")]
+pub use delay::{DelayProvider, HybridDelay, SpinDelay, StdDelay};
+#[cfg(feature = "rpi")]
+pub use dual::{Converter, DualSample, DualScan, DualScanConfig};
+#[cfg(feature = "embedded-hal-async")]
+pub use eh_async::Ads1263EhAsync;
 pub use error::{Ads1263Error, Result};
-pub use hal::{Hal, PinConfig, SpiConfig};
+#[cfg(feature = "rpi")]
+pub use filter::{Despike, MedianFilter, MovingAverage};
+#[cfg(feature = "grpc")]
+pub use grpc::GrpcServer;
+#[cfg(feature = "rpi")]
+pub use hal::{CleanupPolicy, GpioChip, Hal, PinConfig, SpiConfig, WaitStrategy};
+#[cfg(feature = "rpi")]
+pub use handle::Ads1263Handle;
+#[cfg(feature = "hdf5")]
+pub use hdf5::{Hdf5Export, Hdf5Writer, export_hdf5};
+#[cfg(feature = "rpi")]
+pub use histogram::Histogram;
+#[cfg(feature = "rpi")]
+pub use homeassistant::{HaDiscovery, MqttMessage};
+#[cfg(feature = "rpi")]
+pub use iio::{IIOD_PORT, IioServer};
+#[cfg(feature = "rpi")]
+pub use jsonl::{JsonField, JsonLinesSink};
+#[cfg(feature = "rpi")]
+pub use linearize::{Curve, LookupTable};
+#[cfg(feature = "lsl")]
+pub use lsl::LslOutlet;
+#[cfg(feature = "mmap")]
+pub use mmap::{MmapCapture, MmapSink};
+#[cfg(feature = "node")]
+pub use node::NodeAds1263;
+#[cfg(feature = "rpi")]
+pub use overrun::OverrunStats;
+#[cfg(feature = "rpi")]
+pub use pipeline::{Pipeline, Processed, Reading, Stage};
+#[cfg(feature = "realtime")]
+pub use realtime::{JitterMeter, JitterStats, RealtimeConfig};
 pub use registers::{
-    Adc2DataRate, Adc2Gain, Command, DacVoltage, DataRate, Delay, DigitalFilter, Gain, InputMode,
-    ReferenceSource, Register,
+    Adc1Config, Adc2DataRate, Adc2Gain, Command, DacVoltage, DataRate, Delay, DigitalFilter, Gain,
+    IdacCurrent, InputMode, Monitor, MuxInput, ReferenceSource, Register, StatusFlags,
 };
+#[cfg(feature = "rpi")]
+pub use replay::ReplaySource;
+#[cfg(feature = "rest")]
+pub use rest::{RestApi, RestSink};
+#[cfg(feature = "rpi")]
+pub use ring::{OverflowPolicy, RingBuffer};
+#[cfg(feature = "rpi")]
+pub use rotate::RotatingSink;
+#[cfg(feature = "rpi")]
+pub use rtd::{RtdAlpha, RtdConfig, RtdElement, RtdPins, RtdSensor};
+#[cfg(feature = "rpi")]
+pub use sample::{Sample, Samples, SamplesConfig};
+#[cfg(feature = "rpi")]
+pub use scan::{ScanInput, ScanPlan, ScanStep};
+#[cfg(feature = "rpi")]
+pub use sensors::{RatiometricSensor, Sensor, SensorRegistry, SensorSetup, VoltageSensor};
+#[cfg(feature = "rpi")]
+pub use sink::Sink;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteSink;
+#[cfg(feature = "rpi")]
+pub use stats::{Statistic, Stats, StatsAccumulator, StatsWindow, WindowStats};
+#[cfg(feature = "stream")]
+pub use stream::SampleStream;
+#[cfg(feature = "rpi")]
+pub use sync::{SyncGroup, SyncSample};
+#[cfg(feature = "rpi")]
+pub use systemd::{ActivatedSocket, SystemdNotifier, listen_fds};
+#[cfg(feature = "rpi")]
+pub use thermistor::{Thermistor, ThermistorDivider, ThermistorModel};
+#[cfg(feature = "rpi")]
+pub use thermocouple::{Thermocouple, ThermocoupleType};
+#[cfg(feature = "postgres")]
+pub use timescale::{TimescaleConfig, TimescaleSink};
+#[cfg(feature = "rpi")]
+pub use trigger::{Capture, Edge, Trigger, TriggerCondition, TriggerConfig};
+#[cfg(feature = "rpi")]
+pub use udp::UdpSink;
+#[cfg(feature = "rpi")]
+pub use unix_socket::UnixSocketSink;
+#[cfg(feature = "rpi")]
+pub use wav::{WavExport, WavWriter, export_wav};
+#[cfg(feature = "websocket")]
+pub use websocket::WebSocketSink;
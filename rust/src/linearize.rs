@@ -0,0 +1,238 @@
+//! Linearization of nonlinear sensors
+//!
+//! A [`Curve`] maps a channel's linearly scaled value onto the physical
+//! quantity, either by a polynomial or by interpolating a monotonic
+//! [`LookupTable`]. Attach one to a channel with
+//! [`ChannelInfo::curve`](crate::ChannelInfo::curve); the
+//! [`ChannelMap`](crate::ChannelMap) stage then applies it in the pipeline.
+//!
+//! Curves also parse from a compact text form, for configuration files:
+//!
+//! ```
+//! use ads1263::Curve;
+//!
+//! let poly: Curve = "poly: -2.5, 31.2, 0.4".parse()?;   // c0 + c1·x + c2·x²
+//! let table: Curve = "table: 0=0, 1=50, 2=75, 2.5=100".parse()?;
+//! assert_eq!(table.apply(1.5), 62.5);
+//! # Ok::<(), ads1263::Ads1263Error>(())
+//! ```
+
+use crate::error::{Ads1263Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Nonlinear transfer function
+#[derive(Debug, Clone, PartialEq)]
+pub enum Curve {
+    /// Polynomial with coefficients in ascending order: c0 + c1·x + c2·x² + ...
+    Polynomial(Vec<f64>),
+    /// Piecewise-linear interpolation between table points
+    Table(LookupTable),
+}
+
+impl Curve {
+    /// Polynomial with coefficients in ascending order
+    pub fn polynomial(coefficients: impl Into<Vec<f64>>) -> Self {
+        Curve::Polynomial(coefficients.into())
+    }
+
+    /// Lookup table through `points`; see [`LookupTable::new`]
+    pub fn table(points: impl IntoIterator<Item = (f64, f64)>) -> Result<Self> {
+        LookupTable::new(points).map(Curve::Table)
+    }
+
+    /// Evaluate the curve at `x`
+    pub fn apply(&self, x: f64) -> f64 {
+        match self {
+            // Horner's scheme
+            Curve::Polynomial(c) => c.iter().rev().fold(0.0, |acc, &c| acc * x + c),
+            Curve::Table(table) => table.apply(x),
+        }
+    }
+}
+
+impl FromStr for Curve {
+    type Err = Ads1263Error;
+
+    /// Parse `poly: c0, c1, ...` or `table: x0=y0, x1=y1, ...`
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |msg: &str| Ads1263Error::InvalidCurve(format!("{}: '{}'", msg, s));
+        let number = |v: &str| v.trim().parse::<f64>().map_err(|_| invalid("bad number"));
+
+        let (kind, body) = s
+            .split_once(':')
+            .ok_or_else(|| invalid("expected 'poly:' or 'table:'"))?;
+        let items = body
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty());
+        match kind.trim() {
+            "poly" | "polynomial" => {
+                let coefficients = items.map(number).collect::<Result<Vec<_>>>()?;
+                if coefficients.is_empty() {
+                    return Err(invalid("no coefficients"));
+                }
+                Ok(Curve::Polynomial(coefficients))
+            }
+            "table" => {
+                let points = items
+                    .map(|item| {
+                        let (x, y) = item
+                            .split_once('=')
+                            .ok_or_else(|| invalid("expected x=y"))?;
+                        Ok((number(x)?, number(y)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Curve::table(points)
+            }
+            _ => Err(invalid("expected 'poly:' or 'table:'")),
+        }
+    }
+}
+
+impl fmt::Display for Curve {
+    /// Format in the form accepted by `parse`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Curve::Polynomial(c) => {
+                write!(f, "poly:")?;
+                for (i, c) in c.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { " " } else { ", " }, c)?;
+                }
+                Ok(())
+            }
+            Curve::Table(table) => {
+                write!(f, "table:")?;
+                for (i, (x, y)) in table.points().iter().enumerate() {
+                    write!(f, "{}{}={}", if i == 0 { " " } else { ", " }, x, y)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Monotonic lookup table with linear interpolation
+///
+/// Inputs outside the table are extrapolated from the first or last
+/// segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupTable {
+    points: Vec<(f64, f64)>,
+}
+
+impl LookupTable {
+    /// Create a table from (input, output) points
+    ///
+    /// # Errors
+    ///
+    /// [`Ads1263Error::InvalidCurve`] unless there are at least two points,
+    /// all finite, with strictly increasing inputs and monotonic
+    /// (non-decreasing or non-increasing) outputs.
+    pub fn new(points: impl IntoIterator<Item = (f64, f64)>) -> Result<Self> {
+        let points: Vec<(f64, f64)> = points.into_iter().collect();
+        let invalid = |msg: &str| Err(Ads1263Error::InvalidCurve(msg.to_string()));
+
+        if points.len() < 2 {
+            return invalid("lookup table needs at least two points");
+        }
+        if points.iter().any(|(x, y)| !x.is_finite() || !y.is_finite()) {
+            return invalid("lookup table contains non-finite values");
+        }
+        if points.windows(2).any(|w| w[1].0 <= w[0].0) {
+            return invalid("lookup table inputs must be strictly increasing");
+        }
+        let rising = points.windows(2).all(|w| w[1].1 >= w[0].1);
+        let falling = points.windows(2).all(|w| w[1].1 <= w[0].1);
+        if !rising && !falling {
+            return invalid("lookup table outputs must be monotonic");
+        }
+        Ok(Self { points })
+    }
+
+    /// Table points in ascending input order
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+
+    /// Interpolate the output at `x`
+    pub fn apply(&self, x: f64) -> f64 {
+        let last = self.points.len() - 1;
+        // Index of the segment containing x, clamped to the end segments
+        let segment = self
+            .points
+            .partition_point(|&(px, _)| px <= x)
+            .clamp(1, last)
+            - 1;
+        let (x0, y0) = self.points[segment];
+        let (x1, y1) = self.points[segment + 1];
+        y0 + (x - x0) * (y1 - y0) / (x1 - x0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> LookupTable {
+        LookupTable::new([(0.0, 0.0), (1.0, 50.0), (2.0, 75.0), (2.5, 100.0)]).unwrap()
+    }
+
+    #[test]
+    fn interpolates_within_segments() {
+        let table = table();
+        assert_eq!(table.apply(0.5), 25.0);
+        assert_eq!(table.apply(1.5), 62.5);
+        assert_eq!(table.apply(2.25), 87.5);
+        for &(x, y) in table.points() {
+            assert_eq!(table.apply(x), y);
+        }
+    }
+
+    #[test]
+    fn extrapolates_outside_the_table() {
+        let table = table();
+        // First segment has slope 50, last segment 50 per unit
+        assert_eq!(table.apply(-1.0), -50.0);
+        assert_eq!(table.apply(3.0), 125.0);
+
+        let falling = LookupTable::new([(0.0, 10.0), (1.0, 0.0)]).unwrap();
+        assert_eq!(falling.apply(0.25), 7.5);
+        assert_eq!(falling.apply(2.0), -10.0);
+    }
+
+    #[test]
+    fn rejects_malformed_tables() {
+        let cases: [&[(f64, f64)]; 6] = [
+            &[(0.0, 0.0)],
+            &[(1.0, 10.0), (0.0, 0.0)],
+            &[(0.0, 0.0), (2.0, 20.0), (1.0, 10.0)],
+            &[(0.0, 0.0), (0.0, 10.0)],
+            &[(0.0, 0.0), (1.0, 10.0), (2.0, 5.0)],
+            &[(0.0, 0.0), (f64::NAN, 10.0)],
+        ];
+        for points in cases {
+            assert!(
+                matches!(
+                    LookupTable::new(points.iter().copied()),
+                    Err(Ads1263Error::InvalidCurve(_))
+                ),
+                "{:?} accepted",
+                points
+            );
+        }
+    }
+
+    #[test]
+    fn text_form_round_trips() {
+        for text in ["poly: -2.5, 31.2, 0.4", "table: 0=0, 1=50, 2=75, 2.5=100"] {
+            let curve: Curve = text.parse().unwrap();
+            assert_eq!(curve.to_string(), text);
+        }
+        let poly: Curve = "poly: 1, 2, 3".parse().unwrap();
+        assert_eq!(poly.apply(2.0), 17.0);
+        assert!("poly:".parse::<Curve>().is_err());
+        assert!("table: 0=0, 1".parse::<Curve>().is_err());
+        assert!("spline: 0=0".parse::<Curve>().is_err());
+    }
+}
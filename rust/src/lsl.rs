@@ -0,0 +1,293 @@
+//! Lab Streaming Layer outlet
+//!
+//! [`LslOutlet`] publishes the scanned channels as one multi-channel LSL
+//! stream, so LabRecorder and other LSL tools record the HAT together with
+//! EEG amplifiers, eye trackers, or marker streams on a common clock. Each
+//! scan becomes one LSL sample of converted `double` values, stamped with
+//! the acquisition time of its first conversion on the LSL clock.
+//!
+//! The stream description follows the XDF meta-data conventions:
+//!
+//! ```text
+//! <desc>
+//!   <channels>
+//!     <channel><label>inlet</label><unit>kPa</unit><type>Analog</type><input>AIN1</input></channel>
+//!   </channels>
+//!   <acquisition>
+//!     <manufacturer>Texas Instruments</manufacturer><model>ADS1263</model>
+//!     <gain>1</gain><data_rate>400</data_rate><filter>Fir</filter><reference>5</reference>
+//!   </acquisition>
+//! </desc>
+//! ```
+//!
+//! Requires the `lsl` feature, which links the system liblsl (1.14 or
+//! newer, e.g. the `.deb` packages of the liblsl releases).
+
+use crate::capture::CaptureHeader;
+use crate::channel_map::ChannelMap;
+use crate::error::{Ads1263Error, Result};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::ffi::{CString, c_int};
+use std::time::Instant;
+
+/// LSL stream type of the outlet
+pub const STREAM_TYPE: &str = "ADC";
+
+/// Seconds of data liblsl buffers for slow consumers
+const MAX_BUFFERED: c_int = 360;
+
+/// Declarations from liblsl's `lsl_c.h`
+mod ffi {
+    use std::ffi::{c_char, c_double, c_int, c_void};
+
+    pub type StreamInfo = *mut c_void;
+    pub type Outlet = *mut c_void;
+    pub type XmlPtr = *mut c_void;
+
+    /// `cft_double64`
+    pub const CF_DOUBLE64: c_int = 2;
+
+    /// `LSL_IRREGULAR_RATE`
+    pub const IRREGULAR_RATE: c_double = 0.0;
+
+    #[link(name = "lsl")]
+    unsafe extern "C" {
+        pub fn lsl_local_clock() -> c_double;
+        pub fn lsl_create_streaminfo(
+            name: *const c_char,
+            kind: *const c_char,
+            channel_count: c_int,
+            nominal_srate: c_double,
+            channel_format: c_int,
+            source_id: *const c_char,
+        ) -> StreamInfo;
+        pub fn lsl_destroy_streaminfo(info: StreamInfo);
+        pub fn lsl_get_desc(info: StreamInfo) -> XmlPtr;
+        pub fn lsl_append_child(e: XmlPtr, name: *const c_char) -> XmlPtr;
+        pub fn lsl_append_child_value(
+            e: XmlPtr,
+            name: *const c_char,
+            value: *const c_char,
+        ) -> XmlPtr;
+        pub fn lsl_create_outlet(
+            info: StreamInfo,
+            chunk_size: c_int,
+            max_buffered: c_int,
+        ) -> Outlet;
+        pub fn lsl_destroy_outlet(out: Outlet);
+        pub fn lsl_push_sample_dtp(
+            out: Outlet,
+            data: *const c_double,
+            timestamp: c_double,
+            pushthrough: c_int,
+        ) -> c_int;
+        pub fn lsl_have_consumers(out: Outlet) -> c_int;
+    }
+}
+
+/// C string of `text`, with interior NULs removed
+fn c_string(text: &str) -> CString {
+    CString::new(text.replace('\0', "")).expect("no interior NUL")
+}
+
+/// Stream description being filled, destroyed when dropped
+struct StreamInfo(ffi::StreamInfo);
+
+impl StreamInfo {
+    /// Append `<name>` to `parent` and return it
+    fn append(&self, parent: ffi::XmlPtr, name: &str) -> ffi::XmlPtr {
+        let name = c_string(name);
+        // SAFETY: `parent` belongs to this info, `name` outlives the call
+        unsafe { ffi::lsl_append_child(parent, name.as_ptr()) }
+    }
+
+    /// Append `<name>value</name>` to `parent`
+    fn append_value(&self, parent: ffi::XmlPtr, name: &str, value: &str) {
+        let (name, value) = (c_string(name), c_string(value));
+        // SAFETY: `parent` belongs to this info, the strings outlive the call
+        unsafe { ffi::lsl_append_child_value(parent, name.as_ptr(), value.as_ptr()) };
+    }
+}
+
+impl Drop for StreamInfo {
+    fn drop(&mut self) {
+        // SAFETY: the handle is valid and not used afterwards
+        unsafe { ffi::lsl_destroy_streaminfo(self.0) };
+    }
+}
+
+/// Sink publishing scans as an LSL stream
+///
+/// The stream is discoverable as soon as the outlet is created. Its source
+/// ID is `ads1263-<name>`, so recorders reconnect to it after a restart;
+/// give every board its own name. A single-channel stream has the ADC data
+/// rate as nominal rate; scans of several channels are irregular, as their
+/// rate depends on settling.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, ChannelMap, DataRate, Hal, LslOutlet};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let channels = [0, 1, 2, 3];
+/// let header = CaptureHeader::from_device(&mut adc, ChannelMap::new(5.0))?;
+/// let outlet = LslOutlet::new("HAT", &header, &channels)?;
+/// let acq = adc.start_acquisition_sink(&channels, AcquisitionConfig::default(), outlet)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct LslOutlet {
+    outlet: ffi::Outlet,
+    channels: Vec<u8>,
+    map: ChannelMap,
+    /// Values of the scan being assembled, in stream channel order
+    values: Vec<f64>,
+    /// Which of `values` the scan has set
+    filled: Vec<bool>,
+    /// Acquisition time of the first sample in `values`
+    started: Option<Instant>,
+}
+
+// SAFETY: liblsl outlets may be used from any thread; the handle is only
+// used through `&mut self` or for thread-safe queries
+unsafe impl Send for LslOutlet {}
+
+impl LslOutlet {
+    /// Publish `channels` of the device described by `header` as stream
+    /// `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Lsl` if liblsl cannot create the outlet
+    pub fn new(name: &str, header: &CaptureHeader, channels: &[u8]) -> Result<Self> {
+        let adc1 = &header.adc1;
+        let rate = match channels.len() {
+            1 => adc1.data_rate.sps(),
+            _ => ffi::IRREGULAR_RATE,
+        };
+        let (c_name, c_type) = (c_string(name), c_string(STREAM_TYPE));
+        let source_id = c_string(&format!("ads1263-{}", name));
+        // SAFETY: the strings outlive the call
+        let info = unsafe {
+            ffi::lsl_create_streaminfo(
+                c_name.as_ptr(),
+                c_type.as_ptr(),
+                channels.len() as c_int,
+                rate,
+                ffi::CF_DOUBLE64,
+                source_id.as_ptr(),
+            )
+        };
+        if info.is_null() {
+            return Err(Ads1263Error::Lsl(
+                "failed to create stream info".to_string(),
+            ));
+        }
+        let info = StreamInfo(info);
+
+        // SAFETY: `info` is valid
+        let desc = unsafe { ffi::lsl_get_desc(info.0) };
+        let list = info.append(desc, "channels");
+        for &channel in channels {
+            let entry = info.append(list, "channel");
+            info.append_value(entry, "label", &header.map.name(channel));
+            info.append_value(entry, "unit", header.map.unit(channel));
+            info.append_value(entry, "type", "Analog");
+            info.append_value(entry, "input", &format!("AIN{}", channel));
+        }
+        let acquisition = info.append(desc, "acquisition");
+        info.append_value(acquisition, "manufacturer", "Texas Instruments");
+        info.append_value(acquisition, "model", "ADS1263");
+        info.append_value(
+            acquisition,
+            "gain",
+            &(1u32 << (adc1.gain as u32)).to_string(),
+        );
+        info.append_value(acquisition, "data_rate", &adc1.data_rate.sps().to_string());
+        info.append_value(acquisition, "filter", &format!("{:?}", adc1.filter));
+        info.append_value(
+            acquisition,
+            "reference",
+            &header.map.reference().to_string(),
+        );
+
+        // SAFETY: `info` is valid; the outlet keeps its own copy
+        let outlet = unsafe { ffi::lsl_create_outlet(info.0, 0, MAX_BUFFERED) };
+        if outlet.is_null() {
+            return Err(Ads1263Error::Lsl("failed to create outlet".to_string()));
+        }
+        log::info!(
+            "LSL outlet {} publishing {} channels at {} Hz",
+            name,
+            channels.len(),
+            rate
+        );
+        Ok(Self {
+            outlet,
+            channels: channels.to_vec(),
+            map: header.map.clone(),
+            values: vec![f64::NAN; channels.len()],
+            filled: vec![false; channels.len()],
+            started: None,
+        })
+    }
+
+    /// true if at least one inlet is connected
+    pub fn have_consumers(&self) -> bool {
+        // SAFETY: the outlet is valid until drop
+        unsafe { ffi::lsl_have_consumers(self.outlet) != 0 }
+    }
+
+    /// Current time of the LSL clock in seconds
+    pub fn local_clock() -> f64 {
+        // SAFETY: no preconditions
+        unsafe { ffi::lsl_local_clock() }
+    }
+
+    /// Push the assembled scan, if any
+    fn push(&mut self) -> Result<()> {
+        let Some(started) = self.started.take() else {
+            return Ok(());
+        };
+        // Acquisition time on the LSL clock
+        let timestamp = Self::local_clock() - started.elapsed().as_secs_f64();
+        // SAFETY: the outlet is valid and `values` has one entry per channel
+        let code =
+            unsafe { ffi::lsl_push_sample_dtp(self.outlet, self.values.as_ptr(), timestamp, 1) };
+        self.values.fill(f64::NAN);
+        self.filled.fill(false);
+        match code {
+            0 => Ok(()),
+            code => Err(Ads1263Error::Lsl(format!("push failed with code {}", code))),
+        }
+    }
+}
+
+impl Sink for LslOutlet {
+    /// Push one LSL sample per scan; channels missing from a scan are NaN
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        for sample in samples {
+            let Some(index) = self.channels.iter().position(|&c| c == sample.channel) else {
+                continue;
+            };
+            // A channel repeating starts the next scan
+            if self.filled[index] {
+                self.push()?;
+            }
+            self.started.get_or_insert(sample.timestamp);
+            self.values[index] = self.map.convert_sample(sample);
+            self.filled[index] = true;
+        }
+        self.push()
+    }
+}
+
+impl Drop for LslOutlet {
+    fn drop(&mut self) {
+        // SAFETY: the handle is valid and not used afterwards
+        unsafe { ffi::lsl_destroy_outlet(self.outlet) };
+    }
+}
@@ -0,0 +1,351 @@
+//! Memory-mapped capture files
+//!
+//! [`MmapSink`] writes samples into a file that is allocated to its full
+//! size up front and mapped into memory, so a multi-hour capture costs one
+//! copy per sample and no heap growth. The kernel writes the pages back in
+//! the background. [`MmapCapture`] maps a finished (or interrupted) file
+//! for reading.
+//!
+//! The file starts with a 64-byte header followed by fixed 32-byte records,
+//! all little-endian:
+//!
+//! | Offset | Header field | Record field |
+//! |--------|--------------|--------------|
+//! | 0 | magic `ADS1263M` | time since the first sample, ns (u64) |
+//! | 8 | version (u16), record size (u16), reserved (u32) | raw code (i32), dropped since last (u32) |
+//! | 16 | wall clock of the first sample, ns since the Unix epoch (u64) | channel, status byte, CRC ok (u8 each), reserved (u8), configuration generation (u32) |
+//! | 24 | capacity in records (u64) | sequence number (u64) |
+//! | 32 | records written (u64) | |
+//! | 40 | system clock drift, ppm (f64) | |
+//!
+//! The record count is updated after every write, so a file left behind
+//! by a killed process is readable up to the last completed write.
+//!
+//! With a [`WallClock`] attached, the header's wall clock and drift come
+//! from its correlation, so [`MmapCapture::wall_time`] yields drift-corrected
+//! UTC; otherwise the wall clock is read once and the drift is 0.
+//! Version 1 files, with 24-byte records and no sequence numbers, are
+//! still readable; their samples are numbered in file order.
+//!
+//! Requires the `mmap` feature.
+
+use crate::clock::WallClock;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::StatusFlags;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use memmap2::{Mmap, MmapMut};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// File magic of memory-mapped captures
+pub const MAGIC: [u8; 8] = *b"ADS1263M";
+/// Current file format version
+pub const VERSION: u16 = 2;
+/// Size of the file header in bytes
+pub const HEADER_LEN: usize = 64;
+/// Size of one sample record in bytes
+pub const RECORD_LEN: usize = 32;
+
+/// Record size of version 1 files
+const RECORD_LEN_V1: usize = 24;
+
+const CAPACITY_OFFSET: usize = 24;
+const WALL_CLOCK_OFFSET: usize = 16;
+const COUNT_OFFSET: usize = 32;
+const DRIFT_OFFSET: usize = 40;
+
+/// Sink writing samples into a pre-allocated memory-mapped file
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, DataRate, Hal, MmapSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps38400)?;
+///
+/// // Two hours at 38400 SPS
+/// let sink = MmapSink::create("capture.bin", 2 * 3600 * 38400)?;
+/// let acq = adc.start_acquisition_sink(&[0], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct MmapSink {
+    map: MmapMut,
+    file: File,
+    capacity: u64,
+    count: u64,
+    /// Timestamp of the first sample; record times are relative to it
+    start: Option<Instant>,
+    clock: Option<WallClock>,
+}
+
+impl MmapSink {
+    /// Create (or truncate) `path` with room for `capacity` samples
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created, sized, or mapped
+    pub fn create(path: impl AsRef<Path>, capacity: u64) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(HEADER_LEN as u64 + capacity * RECORD_LEN as u64)?;
+        // SAFETY: the file was just created by us and is only modified through this map
+        let mut map = unsafe { MmapMut::map_mut(&file)? };
+
+        map[..8].copy_from_slice(&MAGIC);
+        map[8..10].copy_from_slice(&VERSION.to_le_bytes());
+        map[10..12].copy_from_slice(&(RECORD_LEN as u16).to_le_bytes());
+        map[CAPACITY_OFFSET..CAPACITY_OFFSET + 8].copy_from_slice(&capacity.to_le_bytes());
+        Ok(Self {
+            map,
+            file,
+            capacity,
+            count: 0,
+            start: None,
+            clock: None,
+        })
+    }
+
+    /// Align the file's wall clock with a [`WallClock`]
+    ///
+    /// The clock is updated on every write and the header keeps its latest
+    /// estimate of the first sample's UTC time and of the drift.
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    /// Number of samples the file can hold
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Number of samples written
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    /// true if nothing has been written
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Flush and shrink the file to the samples actually written
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if flushing or truncating fails
+    pub fn finish(mut self) -> Result<()> {
+        self.finalize()
+    }
+
+    fn write_wall_clock(&mut self, wall: SystemTime, drift_ppm: f64) {
+        let nanos = wall
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        self.map[WALL_CLOCK_OFFSET..WALL_CLOCK_OFFSET + 8].copy_from_slice(&nanos.to_le_bytes());
+        self.map[DRIFT_OFFSET..DRIFT_OFFSET + 8].copy_from_slice(&drift_ppm.to_le_bytes());
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        self.map.flush()?;
+        self.file
+            .set_len(HEADER_LEN as u64 + self.count * RECORD_LEN as u64)?;
+        Ok(())
+    }
+}
+
+impl Sink for MmapSink {
+    /// Append samples
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::CaptureFull` if the samples don't fit; none
+    /// of them are written in that case
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if self.count + samples.len() as u64 > self.capacity {
+            return Err(Ads1263Error::CaptureFull {
+                capacity: self.capacity,
+            });
+        }
+        let Some(first) = samples.first() else {
+            return Ok(());
+        };
+        if let Some(clock) = &mut self.clock {
+            clock.update();
+        }
+        let start = match self.start {
+            Some(start) => start,
+            None => {
+                let wall = match &self.clock {
+                    Some(clock) => Some(clock.to_utc(first.timestamp)),
+                    None => SystemTime::now().checked_sub(first.timestamp.elapsed()),
+                };
+                self.write_wall_clock(wall.unwrap_or(UNIX_EPOCH), 0.0);
+                *self.start.insert(first.timestamp)
+            }
+        };
+
+        for sample in samples {
+            let at = HEADER_LEN + self.count as usize * RECORD_LEN;
+            let record = &mut self.map[at..at + RECORD_LEN];
+            let offset = sample.timestamp.saturating_duration_since(start).as_nanos() as u64;
+            record[..8].copy_from_slice(&offset.to_le_bytes());
+            record[8..12].copy_from_slice(&sample.raw.to_le_bytes());
+            record[12..16].copy_from_slice(&sample.dropped_since_last.to_le_bytes());
+            record[16] = sample.channel;
+            record[17] = sample.status.bits();
+            record[18] = u8::from(sample.crc_ok);
+            record[20..24].copy_from_slice(&sample.generation.to_le_bytes());
+            record[24..32].copy_from_slice(&sample.sequence.to_le_bytes());
+            self.count += 1;
+        }
+        self.map[COUNT_OFFSET..COUNT_OFFSET + 8].copy_from_slice(&self.count.to_le_bytes());
+        if let Some(clock) = &self.clock {
+            let (wall, drift) = (clock.to_utc(start), clock.drift_ppm());
+            self.write_wall_clock(wall, drift);
+        }
+        Ok(())
+    }
+
+    /// Write dirty pages back to the file
+    fn flush(&mut self) -> Result<()> {
+        self.map.flush_async()?;
+        Ok(())
+    }
+}
+
+impl Drop for MmapSink {
+    fn drop(&mut self) {
+        if let Err(e) = self.finalize() {
+            log::warn!("Failed to finalize capture file: {}", e);
+        }
+    }
+}
+
+/// Read-only view of a memory-mapped capture file
+pub struct MmapCapture {
+    map: Mmap,
+    record_len: usize,
+    count: usize,
+    wall_clock: SystemTime,
+    drift_ppm: f64,
+}
+
+impl MmapCapture {
+    /// Map a capture file written by [`MmapSink`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidCapture` if the header is not
+    /// recognized and `Ads1263Error::Io` if the file cannot be mapped
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only; a concurrent writer can only change record contents
+        let map = unsafe { Mmap::map(&file)? };
+        if map.len() < HEADER_LEN || map[..8] != MAGIC {
+            return Err(Ads1263Error::InvalidCapture(
+                "not a memory-mapped capture".into(),
+            ));
+        }
+        let version = u16::from_le_bytes([map[8], map[9]]);
+        let record_len = usize::from(u16::from_le_bytes([map[10], map[11]]));
+        match (version, record_len) {
+            (1, RECORD_LEN_V1) | (VERSION, RECORD_LEN) => {}
+            _ => {
+                return Err(Ads1263Error::InvalidCapture(format!(
+                    "unsupported version {} with {}-byte records",
+                    version, record_len
+                )));
+            }
+        }
+        let read_u64 =
+            |at: usize| u64::from_le_bytes(map[at..at + 8].try_into().unwrap_or_default());
+        let stored = (map.len() - HEADER_LEN) / record_len;
+        let count = (read_u64(COUNT_OFFSET) as usize).min(stored);
+        let wall_clock = UNIX_EPOCH + Duration::from_nanos(read_u64(WALL_CLOCK_OFFSET));
+        let drift_ppm = f64::from_bits(read_u64(DRIFT_OFFSET));
+        Ok(Self {
+            map,
+            record_len,
+            count,
+            wall_clock,
+            drift_ppm: if drift_ppm.is_finite() {
+                drift_ppm
+            } else {
+                0.0
+            },
+        })
+    }
+
+    /// Number of samples in the file
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// true if the file holds no samples
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Wall-clock time of the first sample
+    pub fn wall_clock(&self) -> SystemTime {
+        self.wall_clock
+    }
+
+    /// Drift of the recording system's clock in ppm, 0 if not measured
+    pub fn drift_ppm(&self) -> f64 {
+        self.drift_ppm
+    }
+
+    /// Drift-corrected wall-clock time of the sample at `index`
+    pub fn wall_time(&self, index: usize) -> Option<SystemTime> {
+        if index >= self.count {
+            return None;
+        }
+        let at = HEADER_LEN + index * self.record_len;
+        let offset = u64::from_le_bytes(self.map[at..at + 8].try_into().ok()?);
+        let seconds = offset as f64 * 1e-9 * (1.0 + self.drift_ppm * 1e-6);
+        self.wall_clock
+            .checked_add(Duration::try_from_secs_f64(seconds).ok()?)
+    }
+
+    /// Samples in recorded order, with timestamps relative to `start`
+    ///
+    /// Recorded times are offsets from the first sample, which is
+    /// timestamped `start`. Samples are yielded as fast as they are read;
+    /// [`ReplaySource`](crate::ReplaySource) paces binary captures only.
+    pub fn samples(&self, start: Instant) -> impl Iterator<Item = Sample> + '_ {
+        self.map[HEADER_LEN..HEADER_LEN + self.count * self.record_len]
+            .chunks_exact(self.record_len)
+            .enumerate()
+            .map(move |(index, record)| {
+                let word = |at: usize| [record[at], record[at + 1], record[at + 2], record[at + 3]];
+                let long = |at: usize| {
+                    u64::from_le_bytes(record[at..at + 8].try_into().unwrap_or_default())
+                };
+                let offset = long(0);
+                Sample {
+                    raw: i32::from_le_bytes(word(8)),
+                    channel: record[16],
+                    timestamp: start + Duration::from_nanos(offset),
+                    status: StatusFlags::from_bits(record[17]),
+                    crc_ok: record[18] != 0,
+                    dropped_since_last: u32::from_le_bytes(word(12)),
+                    sequence: if record.len() >= RECORD_LEN {
+                        long(24)
+                    } else {
+                        index as u64
+                    },
+                    generation: u32::from_le_bytes(word(20)),
+                }
+            })
+    }
+}
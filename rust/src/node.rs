@@ -0,0 +1,402 @@
+//! Node.js addon
+//!
+//! With the `node` feature the library is also an N-API addon built with
+//! napi-rs, so Electron dashboards and Node-RED nodes on the Pi use the
+//! driver without a server in between. Build it as a shared library and
+//! load it under a `.node` name:
+//!
+//! ```text
+//! cargo rustc --lib --release --features node --crate-type cdylib
+//! cp target/release/libads1263.so ads1263.node
+//! ```
+//!
+//! The addon exports the class `Ads1263`. Everything that talks to the
+//! board returns a promise and runs on a worker thread, so the event loop
+//! never waits for a conversion:
+//!
+//! ```text
+//! const { Ads1263 } = require('./ads1263.node')
+//!
+//! const adc = new Ads1263(5.0)
+//! await adc.init(400)
+//! await adc.configure({ gain: 4, filter: 'sinc4' })
+//! adc.setChannel(0, { name: 'inlet', unit: 'kPa', scale: 20, offset: 0 })
+//! console.log(await adc.read(0))
+//!
+//! for await (const scan of adc.stream([0, 1])) {
+//!   for (const { channel, value, unit } of scan) console.log(channel, value, unit)
+//! }
+//! ```
+//!
+//! A stream yields one array of readings per scan and ends when the
+//! acquisition stops; leaving the `for await` loop stops it. Up to 64
+//! scans are buffered for a slow consumer, later ones are dropped.
+
+use crate::acquisition::{Acquisition, AcquisitionConfig, Reconfiguration};
+use crate::ads1263::Ads1263;
+use crate::channel_map::{ChannelInfo, ChannelMap};
+use crate::clock::WallClock;
+use crate::error::{Ads1263Error, Result};
+use crate::hal::Hal;
+use crate::registers::{Adc1Config, DataRate, DigitalFilter, Gain};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use napi::bindgen_prelude::AsyncGenerator;
+use napi_derive::napi;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::UNIX_EPOCH;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// Scans buffered per stream before further scans are dropped
+const STREAM_SCANS: usize = 64;
+
+/// ADC1 settings as seen from JavaScript; unset fields are left unchanged
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// PGA gain factor: 1, 2, 4, 8, 16, 32, or 64
+    pub gain: Option<u32>,
+    /// Samples per second, one of the ADS1263 data rates
+    pub data_rate: Option<f64>,
+    /// Digital filter: `sinc1` to `sinc4` or `fir`
+    pub filter: Option<String>,
+}
+
+/// Name, unit, and linear scaling of a channel
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelOptions {
+    /// Display name
+    pub name: String,
+    /// Unit of the scaled value (default `V`)
+    pub unit: Option<String>,
+    /// Factor applied to the input voltage (default 1)
+    pub scale: Option<f64>,
+    /// Added after scaling (default 0)
+    pub offset: Option<f64>,
+}
+
+/// One conversion
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    /// Input channel
+    pub channel: u8,
+    /// Signed raw code
+    pub raw: i32,
+    /// Value converted by the channel's scaling
+    pub value: f64,
+    /// Unit of `value`
+    pub unit: String,
+    /// Milliseconds since the Unix epoch, as `Date.now()`
+    pub timestamp: f64,
+    /// Position among all samples read by the driver
+    pub sequence: i64,
+    /// Status byte sent with the conversion
+    pub status: u8,
+    /// true if the CRC byte matched the data
+    pub crc_ok: bool,
+}
+
+/// The board and its acquisition, if one is running
+struct Device {
+    /// The driver while no acquisition holds it
+    adc: Option<Ads1263>,
+    acquisition: Option<Acquisition>,
+    adc1: Adc1Config,
+    map: ChannelMap,
+    clock: WallClock,
+}
+
+impl Device {
+    /// The driver, if no acquisition is running
+    fn adc(&mut self) -> napi::Result<&mut Ads1263> {
+        if self.acquisition.as_ref().is_some_and(|a| !a.is_running()) {
+            self.stop()?;
+        }
+        self.adc
+            .as_mut()
+            .ok_or_else(|| napi::Error::from_reason("acquisition running"))
+    }
+
+    /// Stop the acquisition and take the driver back
+    fn stop(&mut self) -> napi::Result<()> {
+        if let Some(acquisition) = self.acquisition.take() {
+            self.adc = Some(acquisition.join()?);
+            log::info!("Node.js acquisition stopped");
+        }
+        Ok(())
+    }
+}
+
+/// Lock the device, ignoring poisoning
+fn lock(device: &Mutex<Device>) -> MutexGuard<'_, Device> {
+    device.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Run `f` with the device on a blocking worker thread
+async fn with_device<T: Send + 'static>(
+    device: &Arc<Mutex<Device>>,
+    f: impl FnOnce(&mut Device) -> napi::Result<T> + Send + 'static,
+) -> napi::Result<T> {
+    let device = device.clone();
+    tokio::task::spawn_blocking(move || f(&mut lock(&device)))
+        .await
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?
+}
+
+/// The `Ads1263` class of the addon
+#[napi(js_name = "Ads1263")]
+pub struct NodeAds1263 {
+    device: Arc<Mutex<Device>>,
+}
+
+#[napi]
+impl NodeAds1263 {
+    /// Open the HAT with its default pins and SPI settings; `reference` is
+    /// the reference voltage (default 5.0)
+    #[napi(constructor)]
+    pub fn new(reference: Option<f64>) -> napi::Result<Self> {
+        let adc = Ads1263::new(Hal::new()?);
+        Ok(Self {
+            device: Arc::new(Mutex::new(Device {
+                adc1: adc.adc1_config(),
+                adc: Some(adc),
+                acquisition: None,
+                map: ChannelMap::new(reference.unwrap_or(5.0)),
+                clock: WallClock::new(),
+            })),
+        })
+    }
+
+    /// Reset the chip and start ADC1 at `dataRate` samples per second
+    /// (default 400)
+    #[napi]
+    pub async fn init(&self, data_rate: Option<f64>) -> napi::Result<Config> {
+        let rate = data_rate.map_or(Ok(DataRate::Sps400), data_rate_of)?;
+        with_device(&self.device, move |device| {
+            let adc = device.adc()?;
+            adc.init_adc1(rate)?;
+            device.adc1 = adc.adc1_config();
+            Ok(config(&device.adc1))
+        })
+        .await
+    }
+
+    /// Change gain, data rate, or filter, also while streaming
+    #[napi]
+    pub async fn configure(&self, changes: Config) -> napi::Result<Config> {
+        let adc1 = merge(lock(&self.device).adc1, &changes)?;
+        with_device(&self.device, move |device| {
+            match &device.acquisition {
+                Some(acquisition) if acquisition.is_running() => {
+                    acquisition.reconfigure(Reconfiguration::Adc1(adc1))?
+                }
+                _ => device.adc()?.configure_adc1(adc1)?,
+            }
+            device.adc1 = adc1;
+            Ok(config(&adc1))
+        })
+        .await
+    }
+
+    /// Current ADC1 settings
+    #[napi(getter)]
+    pub fn config(&self) -> Config {
+        config(&lock(&self.device).adc1)
+    }
+
+    /// Name, unit, and scale `channel`; applies to later reads and streams
+    #[napi]
+    pub fn set_channel(&self, channel: u8, options: ChannelOptions) {
+        let mut info = ChannelInfo::new(options.name)
+            .linear(options.scale.unwrap_or(1.0), options.offset.unwrap_or(0.0));
+        if let Some(unit) = options.unit {
+            info = info.unit(unit);
+        }
+        lock(&self.device).map.insert(channel, info);
+    }
+
+    /// Read one conversion of `channel`
+    #[napi]
+    pub async fn read(&self, channel: u8) -> napi::Result<Reading> {
+        with_device(&self.device, move |device| {
+            let sample = device.adc()?.get_sample(channel)?;
+            device.clock.update();
+            Ok(reading(&device.map, &device.clock, &sample))
+        })
+        .await
+    }
+
+    /// Scan `channels` continuously, yielding one array of readings per scan
+    #[napi]
+    pub fn stream(&self, channels: Vec<u8>) -> napi::Result<ScanStream> {
+        if channels.is_empty() {
+            return Err(napi::Error::from_reason("no channels"));
+        }
+        let (sender, receiver) = mpsc::channel(STREAM_SCANS);
+        let mut device = lock(&self.device);
+        device.adc()?;
+        let sink = ScanSink {
+            map: device.map.clone(),
+            clock: WallClock::new(),
+            sender,
+        };
+        let adc = device.adc.take().expect("driver present when stopped");
+        let acquisition =
+            Acquisition::try_start_sink(adc, &channels, AcquisitionConfig::default(), sink)
+                .map_err(|(e, adc)| {
+                    device.adc = Some(adc);
+                    e
+                })?;
+        log::info!("Node.js acquisition started on channels {:?}", channels);
+        device.acquisition = Some(acquisition);
+        Ok(ScanStream {
+            receiver: Arc::new(tokio::sync::Mutex::new(receiver)),
+            device: self.device.clone(),
+        })
+    }
+
+    /// Stop the running stream, if any
+    #[napi]
+    pub async fn stop(&self) -> napi::Result<()> {
+        with_device(&self.device, Device::stop).await
+    }
+}
+
+/// Async iterator over the scans of a stream
+#[napi(async_iterator)]
+pub struct ScanStream {
+    receiver: Arc<tokio::sync::Mutex<mpsc::Receiver<Vec<Reading>>>>,
+    device: Arc<Mutex<Device>>,
+}
+
+#[napi]
+impl AsyncGenerator for ScanStream {
+    type Yield = Vec<Reading>;
+    type Next = ();
+    type Return = ();
+
+    /// The next scan, or the end once the acquisition stopped; rejects
+    /// with the error that stopped it
+    fn next(
+        &mut self,
+        _: Option<()>,
+    ) -> impl Future<Output = napi::Result<Option<Vec<Reading>>>> + Send + 'static {
+        let (receiver, device) = (self.receiver.clone(), self.device.clone());
+        async move {
+            match receiver.lock().await.recv().await {
+                Some(scan) => Ok(Some(scan)),
+                None => with_device(&device, Device::stop).await.map(|()| None),
+            }
+        }
+    }
+
+    /// Stop the acquisition when the loop is left
+    fn complete(
+        &mut self,
+        _: Option<()>,
+    ) -> impl Future<Output = napi::Result<Option<Vec<Reading>>>> + Send + 'static {
+        let device = self.device.clone();
+        async move { with_device(&device, Device::stop).await.map(|()| None) }
+    }
+}
+
+/// Sink handing scans to a stream
+struct ScanSink {
+    map: ChannelMap,
+    clock: WallClock,
+    sender: mpsc::Sender<Vec<Reading>>,
+}
+
+impl Sink for ScanSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.clock.update();
+        let scan = samples
+            .iter()
+            .map(|s| reading(&self.map, &self.clock, s))
+            .collect();
+        match self.sender.try_send(scan) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => log::debug!("Node.js stream lagging, scan dropped"),
+            Err(TrySendError::Closed(_)) => {}
+        }
+        Ok(())
+    }
+}
+
+impl From<Ads1263Error> for napi::Error {
+    fn from(e: Ads1263Error) -> Self {
+        napi::Error::from_reason(e.to_string())
+    }
+}
+
+/// Reading of `sample`
+fn reading(map: &ChannelMap, clock: &WallClock, sample: &Sample) -> Reading {
+    Reading {
+        channel: sample.channel,
+        raw: sample.raw,
+        value: map.convert_sample(sample),
+        unit: map.unit(sample.channel).to_string(),
+        timestamp: clock
+            .to_utc(sample.timestamp)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * 1000.0,
+        sequence: sample.sequence as i64,
+        status: sample.status.bits(),
+        crc_ok: sample.crc_ok,
+    }
+}
+
+/// `Config` of an ADC1 configuration
+fn config(adc1: &Adc1Config) -> Config {
+    Config {
+        gain: Some(1 << (adc1.gain as u32)),
+        data_rate: Some(adc1.data_rate.sps()),
+        filter: Some(format!("{:?}", adc1.filter).to_lowercase()),
+    }
+}
+
+/// Data rate of `sps` samples per second
+fn data_rate_of(sps: f64) -> napi::Result<DataRate> {
+    DataRate::from_sps(sps)
+        .ok_or_else(|| napi::Error::from_reason(format!("invalid data rate {}", sps)))
+}
+
+/// `adc1` with the fields set in `changes` replaced
+fn merge(mut adc1: Adc1Config, changes: &Config) -> napi::Result<Adc1Config> {
+    if let Some(gain) = changes.gain {
+        adc1.gain = match gain {
+            1 => Gain::Gain1,
+            2 => Gain::Gain2,
+            4 => Gain::Gain4,
+            8 => Gain::Gain8,
+            16 => Gain::Gain16,
+            32 => Gain::Gain32,
+            64 => Gain::Gain64,
+            gain => return Err(napi::Error::from_reason(format!("invalid gain {}", gain))),
+        };
+    }
+    if let Some(sps) = changes.data_rate {
+        adc1.data_rate = data_rate_of(sps)?;
+    }
+    if let Some(filter) = &changes.filter {
+        adc1.filter = match filter.to_ascii_lowercase().as_str() {
+            "sinc1" => DigitalFilter::Sinc1,
+            "sinc2" => DigitalFilter::Sinc2,
+            "sinc3" => DigitalFilter::Sinc3,
+            "sinc4" => DigitalFilter::Sinc4,
+            "fir" => DigitalFilter::Fir,
+            _ => {
+                return Err(napi::Error::from_reason(format!(
+                    "invalid filter {}",
+                    filter
+                )));
+            }
+        };
+    }
+    Ok(adc1)
+}
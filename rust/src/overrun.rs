@@ -0,0 +1,66 @@
+//! Missed-conversion detection for continuous reads
+//!
+//! When one channel is read back-to-back, DRDY fires once per data rate
+//! period. If the reader is late (preempted, blocked on a full channel,
+//! ...), the ADC overwrites the unread result and DRDY fires again before
+//! the previous conversion was fetched. The ADS1263 has no sequence
+//! counter, so misses are inferred from the DRDY timestamps: an interval of
+//! about `k` periods means `k - 1` conversions were lost.
+//!
+//! Reads that switch the multiplexer restart the conversion and are not
+//! counted.
+
+use std::time::{Duration, Instant};
+
+/// Counters of missed conversions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverrunStats {
+    /// Conversions read in continuous mode
+    pub samples: u64,
+    /// Conversions estimated to have been lost
+    pub dropped: u64,
+    /// Reads that followed one or more lost conversions
+    pub overruns: u64,
+}
+
+/// Infers lost conversions from the spacing of DRDY timestamps
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct OverrunDetector {
+    last: Option<Instant>,
+    stats: OverrunStats,
+}
+
+impl OverrunDetector {
+    /// Start a new sequence, e.g. after the multiplexer changed
+    pub(crate) fn restart(&mut self) {
+        self.last = None;
+    }
+
+    /// Record a conversion signalled at `at`
+    ///
+    /// # Returns
+    ///
+    /// The number of conversions lost since the previous one
+    pub(crate) fn record(&mut self, at: Instant, period: Duration) -> u32 {
+        self.stats.samples += 1;
+        let Some(last) = self.last.replace(at) else {
+            return 0;
+        };
+        let periods = at.saturating_duration_since(last).as_secs_f64() / period.as_secs_f64();
+        let dropped = (periods.round() as u32).saturating_sub(1);
+        if dropped > 0 {
+            self.stats.dropped += u64::from(dropped);
+            self.stats.overruns += 1;
+            log::debug!("{} conversion(s) missed", dropped);
+        }
+        dropped
+    }
+
+    pub(crate) fn stats(&self) -> OverrunStats {
+        self.stats
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
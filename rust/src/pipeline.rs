@@ -0,0 +1,265 @@
+//! Post-processing pipeline for sample streams
+//!
+//! A [`Pipeline`] is a chain of [`Stage`]s that turns [`Sample`]s into
+//! [`Reading`]s. Stages keep separate state for every channel, so one
+//! pipeline can sit behind a multi-channel scan. A stage may emit zero,
+//! one, or several readings per input (e.g. a decimator emits one per N).
+//!
+//! Feed samples one at a time with [`Pipeline::process`], run a recorded
+//! capture through [`Pipeline::process_all`], or wrap a live stream such as
+//! [`Ads1263::samples`](crate::Ads1263::samples) with [`Pipeline::apply`].
+
+use crate::channel_map::ChannelMap;
+use crate::decimate::{Decimator, DownsampleMode, Downsampler};
+use crate::error::Result;
+use crate::filter::{Despike, MedianFilter, MovingAverage};
+use crate::sample::Sample;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Processed value of one channel at one instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    /// Channel the value belongs to
+    pub channel: u8,
+    /// Time of the (last) conversion the value was derived from
+    pub timestamp: Instant,
+    /// Value; raw ADC codes unless a stage converted it
+    pub value: f64,
+}
+
+impl From<Sample> for Reading {
+    fn from(sample: Sample) -> Self {
+        Self {
+            channel: sample.channel,
+            timestamp: sample.timestamp,
+            value: f64::from(sample.raw),
+        }
+    }
+}
+
+/// One processing step of a [`Pipeline`]
+pub trait Stage: Send {
+    /// Process one reading and push any outputs to `out`
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>);
+
+    /// Forget all history, e.g. after a gap in the data
+    fn reset(&mut self);
+}
+
+/// Per-channel state of a stage, created on first use
+#[derive(Debug, Clone)]
+pub(crate) struct PerChannel<T> {
+    slots: Vec<Option<T>>,
+}
+
+impl<T> Default for PerChannel<T> {
+    fn default() -> Self {
+        Self { slots: Vec::new() }
+    }
+}
+
+impl<T> PerChannel<T> {
+    /// State of `channel`, created with `init` if it does not exist yet
+    pub(crate) fn get_or_insert_with(&mut self, channel: u8, init: impl FnOnce() -> T) -> &mut T {
+        let index = usize::from(channel);
+        if self.slots.len() <= index {
+            self.slots.resize_with(index + 1, || None);
+        }
+        self.slots[index].get_or_insert_with(init)
+    }
+
+    /// Drop the state of all channels
+    pub(crate) fn clear(&mut self) {
+        self.slots.clear();
+    }
+}
+
+/// Chain of processing stages
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{Pipeline, SamplesConfig};
+///
+/// let pipeline = Pipeline::new().average(64);
+/// for reading in pipeline.apply(adc.samples(0, SamplesConfig::default())).take(1000) {
+///     println!("{:.1}", reading?.value);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Stage>>,
+    /// Outputs of the last `process` call
+    output: Vec<Reading>,
+    /// Scratch buffer between stages
+    scratch: Vec<Reading>,
+}
+
+impl Pipeline {
+    /// Create a pipeline that passes samples through unchanged
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a stage
+    pub fn stage(mut self, stage: impl Stage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Append a moving-average (boxcar) filter over `window` samples
+    ///
+    /// See [`MovingAverage`].
+    pub fn average(self, window: usize) -> Self {
+        self.stage(MovingAverage::new(window))
+    }
+
+    /// Append a rolling-median filter over `window` samples
+    ///
+    /// See [`MedianFilter`].
+    pub fn median(self, window: usize) -> Self {
+        self.stage(MedianFilter::new(window))
+    }
+
+    /// Append a Hampel despike filter
+    ///
+    /// See [`Despike`].
+    pub fn despike(self, window: usize, threshold: f64) -> Self {
+        self.stage(Despike::new(window, threshold))
+    }
+
+    /// Append a block-average decimator by `factor`
+    ///
+    /// See [`Decimator`].
+    pub fn decimate(self, factor: usize) -> Self {
+        self.stage(Decimator::new(factor))
+    }
+
+    /// Append a sinc^`order` decimator by `factor`
+    ///
+    /// See [`Decimator::sinc`].
+    pub fn sinc_decimate(self, factor: usize, order: u32) -> Self {
+        self.stage(Decimator::sinc(factor, order))
+    }
+
+    /// Append a rate limiter forwarding at most one value per `interval`
+    ///
+    /// See [`Downsampler`].
+    pub fn downsample(self, interval: Duration, mode: DownsampleMode) -> Self {
+        self.stage(Downsampler::new(interval, mode))
+    }
+
+    /// Append a conversion from ADC codes to each channel's unit
+    ///
+    /// See [`ChannelMap`].
+    pub fn channel_map(self, map: ChannelMap) -> Self {
+        self.stage(map)
+    }
+
+    /// Number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// true if the pipeline has no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Run one sample through all stages
+    ///
+    /// # Returns
+    ///
+    /// The readings that came out of the last stage, valid until the next call
+    pub fn process(&mut self, sample: Sample) -> &[Reading] {
+        self.process_reading(Reading::from(sample))
+    }
+
+    /// Run one reading through all stages
+    pub fn process_reading(&mut self, reading: Reading) -> &[Reading] {
+        self.output.clear();
+        self.output.push(reading);
+        for stage in &mut self.stages {
+            self.scratch.clear();
+            for input in self.output.drain(..) {
+                stage.process(input, &mut self.scratch);
+            }
+            std::mem::swap(&mut self.output, &mut self.scratch);
+            if self.output.is_empty() {
+                break;
+            }
+        }
+        &self.output
+    }
+
+    /// Run a recorded capture through the pipeline
+    pub fn process_all(&mut self, samples: impl IntoIterator<Item = Sample>) -> Vec<Reading> {
+        let mut readings = Vec::new();
+        for sample in samples {
+            readings.extend_from_slice(self.process(sample));
+        }
+        readings
+    }
+
+    /// Reset every stage
+    pub fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+
+    /// Wrap a sample stream, yielding the processed readings
+    ///
+    /// Errors from the stream are passed through unchanged.
+    pub fn apply<I>(self, samples: I) -> Processed<I>
+    where
+        I: Iterator<Item = Result<Sample>>,
+    {
+        Processed {
+            pipeline: self,
+            samples,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+/// Iterator of readings from a sample stream run through a [`Pipeline`]
+///
+/// Created by [`Pipeline::apply`].
+pub struct Processed<I> {
+    pipeline: Pipeline,
+    samples: I,
+    pending: VecDeque<Reading>,
+}
+
+impl<I> Processed<I> {
+    /// Recover the pipeline and the underlying stream
+    pub fn into_parts(self) -> (Pipeline, I) {
+        (self.pipeline, self.samples)
+    }
+}
+
+impl<I> Iterator for Processed<I>
+where
+    I: Iterator<Item = Result<Sample>>,
+{
+    type Item = Result<Reading>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(reading) = self.pending.pop_front() {
+                return Some(Ok(reading));
+            }
+            match self.samples.next()? {
+                Ok(sample) => {
+                    let readings = self.pipeline.process(sample);
+                    self.pending.extend(readings);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Register-level protocol shared by the drivers
+//!
+//! [`Ads1263`](crate::Ads1263) and [`Ads1263EhAsync`](crate::Ads1263EhAsync)
+//! program the chip with the same register values and reset sequence; only
+//! the transport differs. Everything here is `core`-only so the
+//! `embedded-hal-async` driver builds without std.
+
+use crate::error::{Ads1263Error, Result};
+use crate::registers::*;
+
+/// Time RST is held in each state of the high-low-high reset sequence
+pub(crate) const RESET_HOLD_MS: u32 = 300;
+
+/// Chip ID in the top bits of the ID register of an ADS1263
+pub(crate) const CHIP_ID: u8 = 1;
+
+/// Time after which an ADC2 status poll is considered failed
+///
+/// Several conversion periods at the slowest ADC2 rate (10 SPS), including
+/// the sinc3 filter latency after START2.
+pub(crate) const ADC2_TIMEOUT_MS: u32 = 500;
+
+/// Pause between ADC2 status polls
+pub(crate) const ADC2_POLL_INTERVAL_US: u32 = 100;
+
+/// A register write with the name logged on readback
+pub(crate) type RegisterWrite = (Register, u8, &'static str);
+
+/// Chip ID from the value of the ID register
+pub(crate) fn chip_id(id_reg: u8) -> u8 {
+    id_reg >> 5
+}
+
+/// Check the chip ID read after a reset
+///
+/// # Errors
+///
+/// Returns `Ads1263Error::InvalidChipId` if it is not [`CHIP_ID`]
+pub(crate) fn verify_chip_id(chip_id: u8) -> Result<()> {
+    if chip_id == CHIP_ID {
+        log::info!("Chip ID verified: {}", chip_id);
+        Ok(())
+    } else {
+        log::error!("Invalid chip ID: {} (expected {})", chip_id, CHIP_ID);
+        Err(Ads1263Error::InvalidChipId(chip_id))
+    }
+}
+
+/// Verify checksum for ADC data
+///
+/// The ADS1263 appends a CRC byte to each data read.
+///
+/// # Arguments
+///
+/// * `val` - The data value to check
+/// * `crc` - The CRC byte received from the ADC
+///
+/// # Returns
+///
+/// true if checksum is valid, false otherwise
+pub(crate) fn checksum(val: u32, crc: u8) -> bool {
+    let mut sum: u8 = 0;
+    let mut v = val;
+    while v != 0 {
+        sum = sum.wrapping_add((v & 0xFF) as u8);
+        v >>= 8;
+    }
+    sum = sum.wrapping_add(0x9B);
+    sum == crc
+}
+
+/// Compute the multiplexer value for a channel in the given input mode
+///
+/// Single-ended channels are measured against AINCOM (0x0A); differential
+/// channels map to the pairs AIN0-AIN1 through AIN8-AIN9. The same
+/// encoding is used by INPMUX (ADC1) and ADC2MUX (ADC2).
+#[allow(clippy::identity_op)]
+pub(crate) fn channel_mux(mode: InputMode, channel: u8) -> Result<u8> {
+    match mode {
+        InputMode::SingleEnded => {
+            if channel > 10 {
+                return Err(Ads1263Error::InvalidChannel(channel, 10));
+            }
+            // Channel as positive, VCOM (0x0A) as negative
+            Ok((channel << 4) | 0x0A)
+        }
+        InputMode::Differential => match channel {
+            0 => Ok((0 << 4) | 1), // AIN0 - AIN1
+            1 => Ok((2 << 4) | 3), // AIN2 - AIN3
+            2 => Ok((4 << 4) | 5), // AIN4 - AIN5
+            3 => Ok((6 << 4) | 7), // AIN6 - AIN7
+            4 => Ok((8 << 4) | 9), // AIN8 - AIN9
+            _ => Err(Ads1263Error::InvalidChannel(channel, 4)),
+        },
+    }
+}
+
+/// MODE2 value for an ADC1 gain and data rate
+pub(crate) fn adc1_mode2(gain: Gain, rate: DataRate) -> u8 {
+    // PGA bypassed (0x80) at unity gain | gain | data rate
+    let bypass = if gain == Gain::Gain1 { 0x80 } else { 0x00 };
+    bypass | ((gain as u8) << 4) | (rate as u8)
+}
+
+/// ADC2CFG value for an ADC2 gain and data rate
+pub(crate) fn adc2_cfg(gain: Adc2Gain, rate: Adc2DataRate) -> u8 {
+    // VAVDD/VAVSS reference (0x20) | data rate | gain
+    0x20 | ((rate as u8) << 6) | (gain as u8)
+}
+
+/// Register writes that apply an ADC1 configuration, in order
+pub(crate) fn adc1_writes(config: &Adc1Config) -> [RegisterWrite; 4] {
+    [
+        (
+            Register::Mode2,
+            adc1_mode2(config.gain, config.data_rate),
+            "REG_MODE2",
+        ),
+        // REFMUX: reference selection (VDD, VSS by default: 0x24)
+        (Register::RefMux, config.reference as u8, "REG_REFMUX"),
+        // MODE0: Conversion delay
+        (Register::Mode0, config.delay as u8, "REG_MODE0"),
+        // MODE1: Digital filter (FIR by default: 0x84)
+        (Register::Mode1, config.filter as u8, "REG_MODE1"),
+    ]
+}
+
+/// Register writes that configure ADC2, in order
+pub(crate) fn adc2_writes(gain: Adc2Gain, rate: Adc2DataRate, delay: Delay) -> [RegisterWrite; 2] {
+    [
+        (Register::Adc2Cfg, adc2_cfg(gain, rate), "REG_ADC2CFG"),
+        // MODE0: Conversion delay
+        (Register::Mode0, delay as u8, "REG_MODE0"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_adds_bytes_and_seed() {
+        // 0x12 + 0x34 + 0x56 + 0x78 + 0x9B = 0x1AF
+        assert!(checksum(0x1234_5678, 0xAF));
+        assert!(!checksum(0x1234_5678, 0xAE));
+        assert!(checksum(0, 0x9B));
+    }
+
+    #[test]
+    fn channel_mux_encodes_inputs() {
+        assert_eq!(channel_mux(InputMode::SingleEnded, 3).unwrap(), 0x3A);
+        assert_eq!(channel_mux(InputMode::Differential, 2).unwrap(), 0x45);
+        assert!(channel_mux(InputMode::SingleEnded, 11).is_err());
+        assert!(channel_mux(InputMode::Differential, 5).is_err());
+    }
+
+    #[test]
+    fn default_adc1_writes_bypass_pga() {
+        let writes = adc1_writes(&Adc1Config::default());
+        assert_eq!(
+            writes[0],
+            (Register::Mode2, 0x80 | DataRate::Sps400 as u8, "REG_MODE2")
+        );
+        assert_eq!(writes[1].1, 0x24);
+    }
+}
@@ -0,0 +1,227 @@
+//! Real-time scheduling helpers for acquisition threads
+//!
+//! At high data rates a conversion is only valid until the next one
+//! completes, so a sampling thread preempted for a few hundred microseconds
+//! drops samples. [`RealtimeConfig`] raises a thread to `SCHED_FIFO`, pins
+//! it to chosen CPUs, and optionally locks memory to avoid page faults.
+//! [`JitterMeter`] measures how far the actual sample times drift from the
+//! expected period.
+//!
+//! Setting a real-time priority needs root or `CAP_SYS_NICE`. For best
+//! results, reserve a core with `isolcpus=3` on the kernel command line and
+//! pin the acquisition thread to it.
+//!
+//! Requires the `realtime` feature (Linux only).
+
+use crate::error::{Ads1263Error, Result};
+use std::io;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Scheduling settings applied to an acquisition thread
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RealtimeConfig {
+    /// `SCHED_FIFO` priority (1-99); `None` keeps the normal scheduler
+    pub priority: Option<i32>,
+    /// CPUs the thread may run on; empty keeps the inherited affinity
+    pub cpus: Vec<usize>,
+    /// Lock current and future pages into RAM (`mlockall`)
+    pub lock_memory: bool,
+}
+
+impl RealtimeConfig {
+    /// `SCHED_FIFO` at `priority` on a single CPU with memory locked
+    pub fn fifo_on(priority: i32, cpu: usize) -> Self {
+        Self {
+            priority: Some(priority),
+            cpus: vec![cpu],
+            lock_memory: true,
+        }
+    }
+
+    /// Apply the settings to the calling thread
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if a CPU index does not fit
+    /// in a `cpu_set_t`, and `Ads1263Error::Io` with the OS error if any
+    /// setting is rejected, e.g. `EPERM` without `CAP_SYS_NICE`
+    pub fn apply(&self) -> Result<()> {
+        if let Some(&cpu) = self
+            .cpus
+            .iter()
+            .find(|&&cpu| cpu >= libc::CPU_SETSIZE as usize)
+        {
+            return Err(Ads1263Error::InvalidConfig(format!(
+                "CPU {} is outside the affinity mask (max {})",
+                cpu,
+                libc::CPU_SETSIZE - 1
+            )));
+        }
+
+        if self.lock_memory {
+            // SAFETY: mlockall takes only flags and has no memory-safety preconditions
+            if unsafe { libc::mlockall(libc::MCL_CURRENT | libc::MCL_FUTURE) } != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+
+        if !self.cpus.is_empty() {
+            // SAFETY: cpu_set_t is plain data; all-zero is the empty set
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            for &cpu in &self.cpus {
+                // SAFETY: CPU_SET only writes into `set`; `cpu` was checked
+                // against CPU_SETSIZE above, past which it would panic
+                unsafe { libc::CPU_SET(cpu, &mut set) };
+            }
+            // SAFETY: `set` is a valid cpu_set_t of the size passed; pid 0 is the calling thread
+            let ret =
+                unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+            if ret != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+
+        if let Some(priority) = self.priority {
+            let param = libc::sched_param {
+                sched_priority: priority,
+            };
+            // SAFETY: `param` is a valid sched_param for the calling thread
+            let ret = unsafe {
+                libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param)
+            };
+            if ret != 0 {
+                return Err(io::Error::from_raw_os_error(ret).into());
+            }
+        }
+
+        log::info!(
+            "Real-time settings applied: priority {:?}, cpus {:?}, memory locked: {}",
+            self.priority,
+            self.cpus,
+            self.lock_memory
+        );
+        Ok(())
+    }
+
+    /// Spawn a named thread with these settings applied
+    ///
+    /// If the settings cannot be applied the thread still runs with normal
+    /// scheduling and a warning is logged, so captures work without root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the thread cannot be created
+    pub fn spawn<F, T>(&self, name: &str, f: F) -> io::Result<JoinHandle<T>>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let config = self.clone();
+        thread::Builder::new().name(name.into()).spawn(move || {
+            if let Err(e) = config.apply() {
+                log::warn!("Running without real-time scheduling: {}", e);
+            }
+            f()
+        })
+    }
+}
+
+/// Summary of sample timing jitter
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct JitterStats {
+    /// Number of intervals measured
+    pub count: u64,
+    /// Shortest interval seen
+    pub min: Duration,
+    /// Longest interval seen
+    pub max: Duration,
+    /// Mean interval
+    pub mean: Duration,
+    /// Standard deviation of the interval
+    pub std_dev: Duration,
+    /// Intervals longer than twice the expected period (likely missed samples)
+    pub late: u64,
+}
+
+/// Measures the spacing of sample timestamps against an expected period
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::JitterMeter;
+///
+/// let mut jitter = JitterMeter::new(adc.conversion_period());
+/// for _ in 0..1000 {
+///     adc.get_channel_value(0)?;
+///     jitter.record(std::time::Instant::now());
+/// }
+/// println!("{:?}", jitter.stats());
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct JitterMeter {
+    expected: Duration,
+    last: Option<Instant>,
+    count: u64,
+    min: Duration,
+    max: Duration,
+    /// Running mean and sum of squared deviations in seconds (Welford)
+    mean: f64,
+    m2: f64,
+    late: u64,
+}
+
+impl JitterMeter {
+    /// Create a meter for samples expected every `expected`
+    pub fn new(expected: Duration) -> Self {
+        Self {
+            expected,
+            last: None,
+            count: 0,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            mean: 0.0,
+            m2: 0.0,
+            late: 0,
+        }
+    }
+
+    /// Record the time a sample was taken
+    pub fn record(&mut self, at: Instant) {
+        if let Some(last) = self.last.replace(at) {
+            let interval = at.saturating_duration_since(last);
+            self.count += 1;
+            self.min = self.min.min(interval);
+            self.max = self.max.max(interval);
+            if interval > self.expected * 2 {
+                self.late += 1;
+            }
+            let x = interval.as_secs_f64();
+            let delta = x - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (x - self.mean);
+        }
+    }
+
+    /// Forget all recorded samples
+    pub fn reset(&mut self) {
+        *self = Self::new(self.expected);
+    }
+
+    /// Timing statistics over all recorded intervals
+    pub fn stats(&self) -> JitterStats {
+        if self.count == 0 {
+            return JitterStats::default();
+        }
+        JitterStats {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: Duration::from_secs_f64(self.mean),
+            std_dev: Duration::from_secs_f64((self.m2 / self.count as f64).sqrt()),
+            late: self.late,
+        }
+    }
+}
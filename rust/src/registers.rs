@@ -5,6 +5,8 @@
 
 #![allow(dead_code)]
 
+use core::time::Duration;
+
 /// ADS1263 Register addresses
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -65,6 +67,382 @@ pub enum Register {
     Adc2Fsc1 = 0x1A,
 }
 
+impl Register {
+    /// All registers in address order
+    pub const ALL: [Register; 27] = [
+        Register::Id,
+        Register::Power,
+        Register::Interface,
+        Register::Mode0,
+        Register::Mode1,
+        Register::Mode2,
+        Register::InpMux,
+        Register::OfCal0,
+        Register::OfCal1,
+        Register::OfCal2,
+        Register::FsCal0,
+        Register::FsCal1,
+        Register::FsCal2,
+        Register::IdacMux,
+        Register::IdacMag,
+        Register::RefMux,
+        Register::TdacP,
+        Register::TdacN,
+        Register::GpioCon,
+        Register::GpioDir,
+        Register::GpioDat,
+        Register::Adc2Cfg,
+        Register::Adc2Mux,
+        Register::Adc2Ofc0,
+        Register::Adc2Ofc1,
+        Register::Adc2Fsc0,
+        Register::Adc2Fsc1,
+    ];
+
+    /// Register name as printed in the datasheet, e.g. `MODE2`
+    pub fn name(self) -> &'static str {
+        match self {
+            Register::Id => "ID",
+            Register::Power => "POWER",
+            Register::Interface => "INTERFACE",
+            Register::Mode0 => "MODE0",
+            Register::Mode1 => "MODE1",
+            Register::Mode2 => "MODE2",
+            Register::InpMux => "INPMUX",
+            Register::OfCal0 => "OFCAL0",
+            Register::OfCal1 => "OFCAL1",
+            Register::OfCal2 => "OFCAL2",
+            Register::FsCal0 => "FSCAL0",
+            Register::FsCal1 => "FSCAL1",
+            Register::FsCal2 => "FSCAL2",
+            Register::IdacMux => "IDACMUX",
+            Register::IdacMag => "IDACMAG",
+            Register::RefMux => "REFMUX",
+            Register::TdacP => "TDACP",
+            Register::TdacN => "TDACN",
+            Register::GpioCon => "GPIOCON",
+            Register::GpioDir => "GPIODIR",
+            Register::GpioDat => "GPIODAT",
+            Register::Adc2Cfg => "ADC2CFG",
+            Register::Adc2Mux => "ADC2MUX",
+            Register::Adc2Ofc0 => "ADC2OFC0",
+            Register::Adc2Ofc1 => "ADC2OFC1",
+            Register::Adc2Fsc0 => "ADC2FSC0",
+            Register::Adc2Fsc1 => "ADC2FSC1",
+        }
+    }
+
+    /// Decode `value` into the register's named bit fields
+    ///
+    /// Fields are returned MSB first as `(name, meaning)` pairs. The GPIO
+    /// registers list the pins whose bit is set, i.e. connected, input, or
+    /// high. The calibration bytes have no fields of their own and return
+    /// an empty list.
+    #[cfg(feature = "rpi")]
+    pub fn fields(self, value: u8) -> Vec<(&'static str, String)> {
+        let on = |bit: u8| if value & (1 << bit) != 0 { "on" } else { "off" }.to_string();
+        match self {
+            Register::Id => vec![
+                (
+                    "DEV_ID",
+                    match value >> 5 {
+                        0b000 => "ADS1262".to_string(),
+                        0b001 => "ADS1263".to_string(),
+                        id => format!("unknown ({:#05b})", id),
+                    },
+                ),
+                ("REV_ID", (value & 0x1F).to_string()),
+            ],
+            Register::Power => vec![
+                (
+                    "RESET",
+                    if value & 0x10 != 0 {
+                        "occurred"
+                    } else {
+                        "cleared"
+                    }
+                    .to_string(),
+                ),
+                ("VBIAS", on(1)),
+                ("INTREF", on(0)),
+            ],
+            Register::Interface => vec![
+                ("TIMEOUT", on(3)),
+                ("STATUS", on(2)),
+                (
+                    "CRC",
+                    match value & 0x03 {
+                        0b00 => "off",
+                        0b01 => "checksum",
+                        0b10 => "crc",
+                        _ => "reserved",
+                    }
+                    .to_string(),
+                ),
+            ],
+            Register::Mode0 => vec![
+                (
+                    "REFREV",
+                    if value & 0x80 != 0 {
+                        "reversed"
+                    } else {
+                        "normal"
+                    }
+                    .to_string(),
+                ),
+                (
+                    "RUNMODE",
+                    if value & 0x40 != 0 {
+                        "pulse"
+                    } else {
+                        "continuous"
+                    }
+                    .to_string(),
+                ),
+                (
+                    "CHOP",
+                    match (value >> 4) & 0x03 {
+                        0b00 => "off",
+                        0b01 => "input chop",
+                        0b10 => "idac rotation",
+                        _ => "input chop + idac rotation",
+                    }
+                    .to_string(),
+                ),
+                (
+                    "DELAY",
+                    [
+                        "0", "8.7 µs", "17 µs", "35 µs", "69 µs", "139 µs", "278 µs", "555 µs",
+                        "1.1 ms", "2.2 ms", "4.4 ms", "8.8 ms",
+                    ]
+                    .get(usize::from(value & 0x0F))
+                    .unwrap_or(&"reserved")
+                    .to_string(),
+                ),
+            ],
+            Register::Mode1 => vec![
+                (
+                    "FILTER",
+                    match value >> 5 {
+                        0b000 => "sinc1",
+                        0b001 => "sinc2",
+                        0b010 => "sinc3",
+                        0b011 => "sinc4",
+                        0b100 => "fir",
+                        _ => "reserved",
+                    }
+                    .to_string(),
+                ),
+                (
+                    "SBADC",
+                    if value & 0x10 != 0 { "adc2" } else { "adc1" }.to_string(),
+                ),
+                (
+                    "SBPOL",
+                    if value & 0x08 != 0 {
+                        "pull-down"
+                    } else {
+                        "pull-up"
+                    }
+                    .to_string(),
+                ),
+                (
+                    "SBMAG",
+                    match value & 0x07 {
+                        0b000 => "off",
+                        0b001 => "0.5 µA",
+                        0b010 => "2 µA",
+                        0b011 => "10 µA",
+                        0b100 => "50 µA",
+                        0b101 => "200 µA",
+                        0b110 => "10 MΩ",
+                        _ => "reserved",
+                    }
+                    .to_string(),
+                ),
+            ],
+            Register::Mode2 => vec![
+                (
+                    "BYPASS",
+                    if value & 0x80 != 0 {
+                        "pga bypassed"
+                    } else {
+                        "pga"
+                    }
+                    .to_string(),
+                ),
+                (
+                    "GAIN",
+                    match (value >> 4) & 0x07 {
+                        gain @ 0..=5 => format!("{}", 1u32 << gain),
+                        _ => "reserved".to_string(),
+                    },
+                ),
+                (
+                    "DR",
+                    format!(
+                        "{} SPS",
+                        [
+                            "2.5", "5", "10", "16.6", "20", "50", "60", "100", "400", "1200",
+                            "2400", "4800", "7200", "14400", "19200", "38400",
+                        ][usize::from(value & 0x0F)]
+                    ),
+                ),
+            ],
+            Register::InpMux | Register::Adc2Mux => vec![
+                ("MUXP", mux_input(value >> 4)),
+                ("MUXN", mux_input(value & 0x0F)),
+            ],
+            Register::IdacMux => vec![
+                ("MUX2", idac_pin(value >> 4)),
+                ("MUX1", idac_pin(value & 0x0F)),
+            ],
+            Register::IdacMag => vec![
+                ("MAG2", idac_current(value >> 4)),
+                ("MAG1", idac_current(value & 0x0F)),
+            ],
+            Register::RefMux => vec![
+                (
+                    "RMUXP",
+                    ["internal", "AIN0", "AIN2", "AIN4", "AVDD"]
+                        .get(usize::from((value >> 3) & 0x07))
+                        .unwrap_or(&"reserved")
+                        .to_string(),
+                ),
+                (
+                    "RMUXN",
+                    ["internal", "AIN1", "AIN3", "AIN5", "AVSS"]
+                        .get(usize::from(value & 0x07))
+                        .unwrap_or(&"reserved")
+                        .to_string(),
+                ),
+            ],
+            Register::TdacP => vec![("OUTP", on(7)), ("MAGP", tdac_voltage(value & 0x1F))],
+            Register::TdacN => vec![("OUTN", on(7)), ("MAGN", tdac_voltage(value & 0x1F))],
+            Register::GpioCon | Register::GpioDir | Register::GpioDat => {
+                let pins: Vec<&str> = GPIO_PINS
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| value & (1 << bit) != 0)
+                    .map(|(_, pin)| *pin)
+                    .collect();
+                let name = match self {
+                    Register::GpioCon => "CON",
+                    Register::GpioDir => "DIR",
+                    _ => "DAT",
+                };
+                let pins = if pins.is_empty() {
+                    "none".to_string()
+                } else {
+                    pins.join(",")
+                };
+                vec![(name, pins)]
+            }
+            Register::Adc2Cfg => vec![
+                (
+                    "DR2",
+                    ["10 SPS", "100 SPS", "400 SPS", "800 SPS"][usize::from(value >> 6)]
+                        .to_string(),
+                ),
+                (
+                    "REF2",
+                    match (value >> 3) & 0x07 {
+                        0b000 => "internal",
+                        0b001 => "AIN0/AIN1",
+                        0b010 => "AIN2/AIN3",
+                        0b011 => "AIN4/AIN5",
+                        0b100 => "AVDD/AVSS",
+                        _ => "reserved",
+                    }
+                    .to_string(),
+                ),
+                ("GAIN2", format!("{}", 1u32 << (value & 0x07))),
+            ],
+            Register::OfCal0
+            | Register::OfCal1
+            | Register::OfCal2
+            | Register::FsCal0
+            | Register::FsCal1
+            | Register::FsCal2
+            | Register::Adc2Ofc0
+            | Register::Adc2Ofc1
+            | Register::Adc2Fsc0
+            | Register::Adc2Fsc1 => Vec::new(),
+        }
+    }
+}
+
+/// Pins of GPIO bits 0 to 7
+const GPIO_PINS: [&str; 8] = [
+    "AIN3", "AIN4", "AIN5", "AIN6", "AIN7", "AIN8", "AIN9", "AINCOM",
+];
+
+/// Input selected by a 4-bit INPMUX or ADC2MUX field
+#[cfg(feature = "rpi")]
+fn mux_input(bits: u8) -> String {
+    match bits {
+        0..=9 => format!("AIN{}", bits),
+        0x0A => "AINCOM".to_string(),
+        0x0B => "temperature".to_string(),
+        0x0C => "analog supply".to_string(),
+        0x0D => "digital supply".to_string(),
+        0x0E => "tdac test".to_string(),
+        _ => "open".to_string(),
+    }
+}
+
+/// Pin selected by a 4-bit IDACMUX field
+#[cfg(feature = "rpi")]
+fn idac_pin(bits: u8) -> String {
+    match bits {
+        0..=9 => format!("AIN{}", bits),
+        0x0A => "AINCOM".to_string(),
+        0x0B => "none".to_string(),
+        _ => "reserved".to_string(),
+    }
+}
+
+/// Current selected by a 4-bit IDACMAG field
+#[cfg(feature = "rpi")]
+fn idac_current(bits: u8) -> String {
+    const MICROAMPS: [u32; 11] = [0, 50, 100, 250, 500, 750, 1000, 1500, 2000, 2500, 3000];
+    match MICROAMPS.get(usize::from(bits)) {
+        Some(0) => "off".to_string(),
+        Some(ua) => format!("{} µA", ua),
+        None => "reserved".to_string(),
+    }
+}
+
+/// Output voltage selected by a 5-bit TDACP/TDACN field, on the HAT's 5 V supply
+#[cfg(feature = "rpi")]
+fn tdac_voltage(bits: u8) -> String {
+    const VOLTS: [(u8, &str); 19] = [
+        (0b01001, "4.5"),
+        (0b01000, "3.5"),
+        (0b00111, "3.0"),
+        (0b00110, "2.75"),
+        (0b00101, "2.625"),
+        (0b00100, "2.5625"),
+        (0b00011, "2.53125"),
+        (0b00010, "2.515625"),
+        (0b00001, "2.5078125"),
+        (0b00000, "2.5"),
+        (0b10001, "2.4921875"),
+        (0b10010, "2.484375"),
+        (0b10011, "2.46875"),
+        (0b10100, "2.4375"),
+        (0b10101, "2.375"),
+        (0b10110, "2.25"),
+        (0b10111, "2.0"),
+        (0b11000, "1.5"),
+        (0b11001, "0.5"),
+    ];
+    match VOLTS.iter().find(|(code, _)| *code == bits) {
+        Some((_, volts)) => format!("{} V", volts),
+        None => "reserved".to_string(),
+    }
+}
+
 /// ADS1263 Command codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -161,6 +539,62 @@ pub enum DataRate {
     Sps38400 = 15,
 }
 
+impl DataRate {
+    /// Nominal output data rate in samples per second
+    pub fn sps(self) -> f64 {
+        match self {
+            DataRate::Sps2_5 => 2.5,
+            DataRate::Sps5 => 5.0,
+            DataRate::Sps10 => 10.0,
+            DataRate::Sps16_6 => 50.0 / 3.0,
+            DataRate::Sps20 => 20.0,
+            DataRate::Sps50 => 50.0,
+            DataRate::Sps60 => 60.0,
+            DataRate::Sps100 => 100.0,
+            DataRate::Sps400 => 400.0,
+            DataRate::Sps1200 => 1200.0,
+            DataRate::Sps2400 => 2400.0,
+            DataRate::Sps4800 => 4800.0,
+            DataRate::Sps7200 => 7200.0,
+            DataRate::Sps14400 => 14400.0,
+            DataRate::Sps19200 => 19200.0,
+            DataRate::Sps38400 => 38400.0,
+        }
+    }
+
+    /// Every data rate, slowest first
+    pub const ALL: [DataRate; 16] = [
+        DataRate::Sps2_5,
+        DataRate::Sps5,
+        DataRate::Sps10,
+        DataRate::Sps16_6,
+        DataRate::Sps20,
+        DataRate::Sps50,
+        DataRate::Sps60,
+        DataRate::Sps100,
+        DataRate::Sps400,
+        DataRate::Sps1200,
+        DataRate::Sps2400,
+        DataRate::Sps4800,
+        DataRate::Sps7200,
+        DataRate::Sps14400,
+        DataRate::Sps19200,
+        DataRate::Sps38400,
+    ];
+
+    /// Data rate nearest to `sps` within 0.1, e.g. `16.6` for [`DataRate::Sps16_6`]
+    pub fn from_sps(sps: f64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|rate| (rate.sps() - sps).abs() < 0.1)
+    }
+
+    /// Time between conversions in continuous mode
+    pub fn period(self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.sps())
+    }
+}
+
 /// ADC1 Conversion delay settings
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
@@ -174,7 +608,7 @@ pub enum Delay {
     /// 35 µs delay (default)
     #[default]
     Delay35us = 3,
-    /// 169 µs delay
+    /// 69 µs delay (datasheet value; the variant name is kept for compatibility)
     Delay169us = 4,
     /// 139 µs delay
     Delay139us = 5,
@@ -192,6 +626,27 @@ pub enum Delay {
     Delay8_8ms = 11,
 }
 
+impl Delay {
+    /// Programmed delay before each conversion starts
+    pub fn duration(self) -> Duration {
+        let ns = match self {
+            Delay::Delay0 => 0,
+            Delay::Delay8_7us => 8_700,
+            Delay::Delay17us => 17_000,
+            Delay::Delay35us => 35_000,
+            Delay::Delay169us => 69_000,
+            Delay::Delay139us => 139_000,
+            Delay::Delay278us => 278_000,
+            Delay::Delay555us => 555_000,
+            Delay::Delay1_1ms => 1_100_000,
+            Delay::Delay2_2ms => 2_200_000,
+            Delay::Delay4_4ms => 4_400_000,
+            Delay::Delay8_8ms => 8_800_000,
+        };
+        Duration::from_nanos(ns)
+    }
+}
+
 /// ADC2 Programmable Gain Amplifier settings
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
@@ -274,6 +729,76 @@ pub enum DacVoltage {
     Volt0_5 = 0b11001,
 }
 
+/// Excitation current of the IDACs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum IdacCurrent {
+    /// Current source off
+    Off = 0,
+    /// 50 µA
+    Ua50 = 1,
+    /// 100 µA
+    Ua100 = 2,
+    /// 250 µA (default, used by [`read_rtd`](crate::Ads1263::read_rtd))
+    #[default]
+    Ua250 = 3,
+    /// 500 µA
+    Ua500 = 4,
+    /// 750 µA
+    Ua750 = 5,
+    /// 1 mA
+    Ua1000 = 6,
+    /// 1.5 mA
+    Ua1500 = 7,
+    /// 2 mA
+    Ua2000 = 8,
+    /// 2.5 mA
+    Ua2500 = 9,
+    /// 3 mA
+    Ua3000 = 10,
+}
+
+impl IdacCurrent {
+    /// Every current, smallest first
+    pub const ALL: [IdacCurrent; 11] = [
+        IdacCurrent::Off,
+        IdacCurrent::Ua50,
+        IdacCurrent::Ua100,
+        IdacCurrent::Ua250,
+        IdacCurrent::Ua500,
+        IdacCurrent::Ua750,
+        IdacCurrent::Ua1000,
+        IdacCurrent::Ua1500,
+        IdacCurrent::Ua2000,
+        IdacCurrent::Ua2500,
+        IdacCurrent::Ua3000,
+    ];
+
+    /// Current in microamps
+    pub fn microamps(self) -> u32 {
+        match self {
+            IdacCurrent::Off => 0,
+            IdacCurrent::Ua50 => 50,
+            IdacCurrent::Ua100 => 100,
+            IdacCurrent::Ua250 => 250,
+            IdacCurrent::Ua500 => 500,
+            IdacCurrent::Ua750 => 750,
+            IdacCurrent::Ua1000 => 1000,
+            IdacCurrent::Ua1500 => 1500,
+            IdacCurrent::Ua2000 => 2000,
+            IdacCurrent::Ua2500 => 2500,
+            IdacCurrent::Ua3000 => 3000,
+        }
+    }
+
+    /// Current of exactly `microamps`, e.g. `250` for [`IdacCurrent::Ua250`]
+    pub fn from_microamps(microamps: u32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|current| current.microamps() == microamps)
+    }
+}
+
 /// Input mode selection for ADC channels
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum InputMode {
@@ -284,6 +809,89 @@ pub enum InputMode {
     Differential,
 }
 
+/// Input of the ADC1 and ADC2 multiplexers
+///
+/// The internal monitors connect to both sides of the multiplexer: select
+/// them as positive and negative input together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MuxInput {
+    /// Analog input AIN0-AIN9
+    Ain(u8),
+    /// Common input AINCOM
+    AinCom,
+    /// Internal temperature sensor
+    Temperature,
+    /// Analog supply monitor, (AVDD - AVSS) / 4
+    AnalogSupply,
+    /// Digital supply monitor, (DVDD - DGND) / 4
+    DigitalSupply,
+    /// TDAC test signal
+    TdacTest,
+    /// No connection
+    Float,
+}
+
+impl MuxInput {
+    /// 4-bit MUXP/MUXN code
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            MuxInput::Ain(n) => n,
+            MuxInput::AinCom => 0x0A,
+            MuxInput::Temperature => 0x0B,
+            MuxInput::AnalogSupply => 0x0C,
+            MuxInput::DigitalSupply => 0x0D,
+            MuxInput::TdacTest => 0x0E,
+            MuxInput::Float => 0x0F,
+        }
+    }
+}
+
+/// Internal measurement read with [`Ads1263::read_monitor`](crate::Ads1263::read_monitor)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monitor {
+    /// Die temperature in °C
+    Temperature,
+    /// AVDD - AVSS in volts
+    AnalogSupply,
+    /// DVDD - DGND in volts
+    DigitalSupply,
+}
+
+impl Monitor {
+    /// Every monitor
+    pub const ALL: [Monitor; 3] = [
+        Monitor::Temperature,
+        Monitor::AnalogSupply,
+        Monitor::DigitalSupply,
+    ];
+
+    /// Multiplexer input of the monitor
+    pub fn input(self) -> MuxInput {
+        match self {
+            Monitor::Temperature => MuxInput::Temperature,
+            Monitor::AnalogSupply => MuxInput::AnalogSupply,
+            Monitor::DigitalSupply => MuxInput::DigitalSupply,
+        }
+    }
+
+    /// Unit of the converted value
+    pub fn unit(self) -> &'static str {
+        match self {
+            Monitor::Temperature => "°C",
+            Monitor::AnalogSupply | Monitor::DigitalSupply => "V",
+        }
+    }
+
+    /// Physical value of the monitor output `volts`
+    pub fn convert(self, volts: f64) -> f64 {
+        match self {
+            // 122.4 mV at 25 °C, 420 µV/°C
+            Monitor::Temperature => (volts - 0.1224) / 0.000420 + 25.0,
+            Monitor::AnalogSupply | Monitor::DigitalSupply => volts * 4.0,
+        }
+    }
+}
+
 /// Digital filter selection for ADC1
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
@@ -301,6 +909,20 @@ pub enum DigitalFilter {
     Fir = 0x84,
 }
 
+impl DigitalFilter {
+    /// Number of conversion periods until the first settled result
+    ///
+    /// Applies after a start, restart, or input multiplexer change.
+    pub fn settling_periods(self) -> u32 {
+        match self {
+            DigitalFilter::Sinc1 | DigitalFilter::Fir => 1,
+            DigitalFilter::Sinc2 => 2,
+            DigitalFilter::Sinc3 => 3,
+            DigitalFilter::Sinc4 => 4,
+        }
+    }
+}
+
 /// Reference voltage source selection
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[repr(u8)]
@@ -317,3 +939,105 @@ pub enum ReferenceSource {
     #[default]
     AvddAvss = 0x24,
 }
+
+impl ReferenceSource {
+    /// Positive and negative input of an external reference pair
+    pub fn inputs(self) -> Option<(MuxInput, MuxInput)> {
+        match self {
+            ReferenceSource::ExternalAin01 => Some((MuxInput::Ain(0), MuxInput::Ain(1))),
+            ReferenceSource::ExternalAin23 => Some((MuxInput::Ain(2), MuxInput::Ain(3))),
+            ReferenceSource::ExternalAin45 => Some((MuxInput::Ain(4), MuxInput::Ain(5))),
+            ReferenceSource::Internal2_5V | ReferenceSource::AvddAvss => None,
+        }
+    }
+
+    /// Whether the reference can be the excitation of a sensor, making its
+    /// conversions ratiometric: AVDD or an external pair
+    pub fn is_ratiometric(self) -> bool {
+        self != ReferenceSource::Internal2_5V
+    }
+}
+
+/// ADC1 configuration
+///
+/// The default matches [`Ads1263::init_adc1`](crate::Ads1263::init_adc1):
+/// unity gain (PGA bypassed), 400 SPS, FIR filter, 35 µs delay, and
+/// AVDD/AVSS reference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Adc1Config {
+    /// PGA gain (the PGA is bypassed at unity gain)
+    pub gain: Gain,
+    /// Output data rate
+    pub data_rate: DataRate,
+    /// Digital filter
+    pub filter: DigitalFilter,
+    /// Conversion start delay
+    pub delay: Delay,
+    /// Reference voltage source
+    pub reference: ReferenceSource,
+}
+
+/// Status byte returned ahead of every conversion result
+///
+/// Bit layout follows the ADS1263 datasheet (STATUS byte).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusFlags(u8);
+
+impl StatusFlags {
+    /// New ADC2 data available
+    pub const ADC2: u8 = 0x80;
+    /// New ADC1 data available
+    pub const ADC1: u8 = 0x40;
+    /// External clock detected
+    pub const EXTCLK: u8 = 0x20;
+    /// Low reference voltage alarm
+    pub const REF_ALM: u8 = 0x10;
+    /// PGA output low alarm
+    pub const PGAL_ALM: u8 = 0x08;
+    /// PGA output high alarm
+    pub const PGAH_ALM: u8 = 0x04;
+    /// PGA differential output range alarm
+    pub const PGAD_ALM: u8 = 0x02;
+    /// Device reset occurred
+    pub const RESET: u8 = 0x01;
+
+    /// Create flags from a raw status byte
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Get the raw status byte
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// true if ADC1 reported a new conversion
+    pub const fn adc1_new_data(self) -> bool {
+        self.0 & Self::ADC1 != 0
+    }
+
+    /// true if ADC2 reported a new conversion
+    pub const fn adc2_new_data(self) -> bool {
+        self.0 & Self::ADC2 != 0
+    }
+
+    /// true if the device is running from an external clock
+    pub const fn external_clock(self) -> bool {
+        self.0 & Self::EXTCLK != 0
+    }
+
+    /// true if the reference voltage is below the alarm threshold
+    pub const fn reference_alarm(self) -> bool {
+        self.0 & Self::REF_ALM != 0
+    }
+
+    /// true if any PGA output alarm (low, high, differential) is set
+    pub const fn pga_alarm(self) -> bool {
+        self.0 & (Self::PGAL_ALM | Self::PGAH_ALM | Self::PGAD_ALM) != 0
+    }
+
+    /// true if a device reset was detected since the flag was last cleared
+    pub const fn reset(self) -> bool {
+        self.0 & Self::RESET != 0
+    }
+}
@@ -0,0 +1,132 @@
+//! Replay of recorded captures
+//!
+//! A [`ReplaySource`] reads a binary capture (see [`CaptureWriter`](crate::CaptureWriter))
+//! and yields its samples as `Result<Sample>`, the same items as the live
+//! [`Ads1263::samples`](crate::Ads1263::samples) iterator, so a
+//! [`Pipeline`](crate::Pipeline), alarm, or trigger can be developed and
+//! regression-tested against recorded data without a HAT. Samples come out
+//! as fast as they can be read, or paced at the recorded rate.
+
+use crate::capture::{CaptureFile, CaptureHeader, CaptureReader, CaptureSamples};
+use crate::error::Result;
+use crate::sample::Sample;
+use std::io::Read;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Sample source reading a recorded capture
+///
+/// Timestamps keep the recorded spacing and start at the instant the
+/// replay was created.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Pipeline, ReplaySource};
+///
+/// let replay = ReplaySource::open("capture.ads")?.realtime();
+/// let map = replay.header().map.clone();
+/// for reading in Pipeline::new().average(16).stage(map).apply(replay) {
+///     println!("{:.3}", reading?.value);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct ReplaySource<R: Read> {
+    samples: CaptureSamples<R>,
+    start: Instant,
+    /// Playback speed relative to the recording; `None` is unpaced
+    speed: Option<f64>,
+    /// When the first sample was delivered, and its recorded offset
+    anchor: Option<(Instant, Duration)>,
+}
+
+impl ReplaySource<CaptureFile> {
+    /// Open a capture file for replay
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be read and
+    /// `Ads1263Error::InvalidCapture` if it is not a binary capture
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(CaptureReader::open(path)?))
+    }
+}
+
+impl<R: Read> ReplaySource<R> {
+    /// Replay the samples of `reader`
+    pub fn new(reader: CaptureReader<R>) -> Self {
+        let start = Instant::now();
+        Self {
+            samples: reader.samples(start),
+            start,
+            speed: None,
+            anchor: None,
+        }
+    }
+
+    /// Pace samples at the recorded rate
+    pub fn realtime(self) -> Self {
+        self.speed(1.0)
+    }
+
+    /// Pace samples at `speed` times the recorded rate
+    ///
+    /// Only the pacing changes; timestamps keep the recorded spacing.
+    /// Non-positive speeds replay unpaced.
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = (speed > 0.0).then_some(speed);
+        self
+    }
+
+    /// Header of the replayed capture
+    pub fn header(&self) -> &CaptureHeader {
+        self.samples.header()
+    }
+
+    /// Group the samples into scans of `channels` samples
+    ///
+    /// Yields the same items as [`Acquisition::iter`](crate::Acquisition::iter);
+    /// a trailing partial scan is dropped.
+    pub fn scans(mut self, channels: usize) -> impl Iterator<Item = Result<Vec<Sample>>> {
+        let channels = channels.max(1);
+        std::iter::from_fn(move || {
+            let mut scan = Vec::with_capacity(channels);
+            while scan.len() < channels {
+                match self.next()? {
+                    Ok(sample) => scan.push(sample),
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            Some(Ok(scan))
+        })
+    }
+
+    /// Sleep until the sample is due at the configured speed
+    fn pace(&mut self, sample: &Sample) {
+        let Some(speed) = self.speed else {
+            return;
+        };
+        let offset = sample.timestamp.saturating_duration_since(self.start);
+        let now = Instant::now();
+        let (at, first) = *self.anchor.get_or_insert((now, offset));
+        let due = at + offset.saturating_sub(first).div_f64(speed);
+        if due > now {
+            thread::sleep(due - now);
+        }
+    }
+}
+
+impl<R: Read> Iterator for ReplaySource<R> {
+    type Item = Result<Sample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.samples.next()?;
+        if let Ok(sample) = &sample {
+            self.pace(sample);
+        }
+        Some(sample)
+    }
+}
+
+impl<R: Read> std::iter::FusedIterator for ReplaySource<R> {}
@@ -0,0 +1,479 @@
+//! Embedded REST API
+//!
+//! [`RestApi`] runs a small HTTP server for controlling the HAT from other
+//! machines on the network. All responses are JSON, except file downloads:
+//!
+//! | Endpoint | Action |
+//! |----------|--------|
+//! | `GET /latest` | Latest sample of every channel, converted by the [`ChannelMap`] |
+//! | `GET /config` | Chip ID, input mode, and ADC1 configuration |
+//! | `PUT /config?gain=4&rate=400` | Change gain and data rate of the running acquisition |
+//! | `PUT /config?channels=0,inlet,2` | Change the scanned channels, by number or name |
+//! | `GET /status` | Whether the acquisition is running or paused |
+//! | `POST /start` | Resume a paused acquisition |
+//! | `POST /stop` | Pause the acquisition |
+//! | `GET /captures` | Files in the capture directory, newest first |
+//! | `GET /captures/<name>` | Download one of them |
+//!
+//! The latest values come from the [`RestSink`] of [`RestApi::sink`], and
+//! control goes to the [`Acquisition`] given to [`RestApi::attach`].
+//! Errors are answered as `{"error":"..."}` with a 4xx status.
+//!
+//! The API has no authentication; bind it to a trusted network only.
+//!
+//! Requires the `rest` feature.
+
+use crate::acquisition::{Acquisition, Reconfiguration};
+use crate::capture::CaptureHeader;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::jsonl::{number, string};
+use crate::registers::{DataRate, Gain};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tiny_http::{Header, Method, Request, Response, Server, StatusCode};
+
+/// State shared by the API, its sink, and the server thread
+struct State {
+    header: Mutex<CaptureHeader>,
+    /// Latest sample and its UTC time, indexed by channel
+    latest: Mutex<Vec<Option<(Sample, SystemTime)>>>,
+    acquisition: Mutex<Option<Acquisition>>,
+    captures: Mutex<Option<PathBuf>>,
+}
+
+/// Lock a mutex, ignoring poisoning
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// HTTP server controlling an acquisition
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, CaptureWriter, ChannelMap, DataRate, Hal, RestApi};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps400)?;
+///
+/// let header = CaptureHeader::from_device(&mut adc, ChannelMap::new(5.0))?;
+/// let api = RestApi::bind("0.0.0.0:8000", &header)?.captures("/data");
+/// let sink = (CaptureWriter::create("/data/capture.ads", &header)?, api.sink());
+/// api.attach(adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?);
+/// // curl http://raspberrypi.local:8000/latest
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct RestApi {
+    state: Arc<State>,
+    server: Arc<Server>,
+    local_addr: SocketAddr,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl RestApi {
+    /// Listen on `addr`, describing the device with `header`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the address cannot be bound
+    pub fn bind(addr: impl ToSocketAddrs, header: &CaptureHeader) -> Result<Self> {
+        let server = Arc::new(Server::http(addr).map_err(io::Error::other)?);
+        let local_addr = server
+            .server_addr()
+            .to_ip()
+            .ok_or_else(|| io::Error::other("not an IP listener"))?;
+        let state = Arc::new(State {
+            header: Mutex::new(header.clone()),
+            latest: Mutex::new(Vec::new()),
+            acquisition: Mutex::new(None),
+            captures: Mutex::new(None),
+        });
+        let worker = {
+            let (server, state) = (server.clone(), state.clone());
+            thread::Builder::new()
+                .name("ads1263-rest".to_string())
+                .spawn(move || serve(&server, &state))?
+        };
+        log::info!("REST API listening on {}", local_addr);
+        Ok(Self {
+            state,
+            server,
+            local_addr,
+            worker: Some(worker),
+        })
+    }
+
+    /// Offer the files in `dir` for download
+    pub fn captures(self, dir: impl Into<PathBuf>) -> Self {
+        *lock(&self.state.captures) = Some(dir.into());
+        self
+    }
+
+    /// Sink recording the latest sample of every channel for `GET /latest`
+    ///
+    /// Combine it with a file sink as a tuple `(file, api.sink())`.
+    pub fn sink(&self) -> RestSink {
+        RestSink {
+            state: self.state.clone(),
+            clock: WallClock::new(),
+        }
+    }
+
+    /// Control `acquisition` through the API, replacing any attached before
+    pub fn attach(&self, acquisition: Acquisition) -> Option<Acquisition> {
+        lock(&self.state.acquisition).replace(acquisition)
+    }
+
+    /// Take the attached acquisition back, e.g. to join it
+    pub fn detach(&self) -> Option<Acquisition> {
+        lock(&self.state.acquisition).take()
+    }
+
+    /// Address the server listens on, e.g. to find the port bound for port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for RestApi {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Sink feeding `GET /latest` of a [`RestApi`]
+pub struct RestSink {
+    state: Arc<State>,
+    clock: WallClock,
+}
+
+impl RestSink {
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl Sink for RestSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.clock.update();
+        let mut latest = lock(&self.state.latest);
+        for sample in samples {
+            let index = usize::from(sample.channel);
+            if latest.len() <= index {
+                latest.resize(index + 1, None);
+            }
+            latest[index] = Some((*sample, self.clock.to_utc(sample.timestamp)));
+        }
+        Ok(())
+    }
+}
+
+/// Answer requests until the server is unblocked
+fn serve(server: &Server, state: &State) {
+    for request in server.incoming_requests() {
+        let path = request.url().split('?').next().unwrap_or_default();
+        if *request.method() == Method::Get
+            && let Some(name) = path.strip_prefix("/captures/")
+        {
+            // Downloads may be slow; keep answering other requests meanwhile
+            let file = capture_file(state, name);
+            let spawned = thread::Builder::new()
+                .name("ads1263-rest-download".to_string())
+                .spawn(move || {
+                    let result = match file {
+                        Ok((disposition, file)) => request.respond(
+                            Response::from_file(file)
+                                .with_header(header("Content-Type", "application/octet-stream"))
+                                .with_header(disposition),
+                        ),
+                        Err((status, message)) => request.respond(error(status, &message)),
+                    };
+                    if let Err(e) = result {
+                        log::debug!("Failed to send capture file: {}", e);
+                    }
+                });
+            if let Err(e) = spawned {
+                log::warn!("Failed to start download thread: {}", e);
+            }
+            continue;
+        }
+        let response = match handle(&request, state) {
+            Ok(body) => json(200, body),
+            Err((status, message)) => error(status, &message),
+        };
+        if let Err(e) = request.respond(response) {
+            log::debug!("Failed to send REST response: {}", e);
+        }
+    }
+}
+
+/// Status and message of a failed request
+type Failure = (u16, String);
+
+/// Route a request, returning the JSON body
+fn handle(request: &Request, state: &State) -> std::result::Result<String, Failure> {
+    let (path, query) = request.url().split_once('?').unwrap_or((request.url(), ""));
+    match (request.method(), path) {
+        (Method::Get, "/latest") => Ok(latest(state)),
+        (Method::Get, "/config") => Ok(config(&lock(&state.header))),
+        (Method::Put, "/config") => reconfigure(state, query),
+        (Method::Get, "/status") => Ok(status(state)),
+        (Method::Post, "/start") => {
+            with_acquisition(state, Acquisition::resume)?;
+            Ok(status(state))
+        }
+        (Method::Post, "/stop") => {
+            with_acquisition(state, Acquisition::pause)?;
+            Ok(status(state))
+        }
+        (Method::Get, "/captures") => captures(state),
+        (_, "/latest" | "/config" | "/status" | "/start" | "/stop" | "/captures") => {
+            Err((405, "method not allowed".to_string()))
+        }
+        _ => Err((404, format!("no endpoint {}", path))),
+    }
+}
+
+/// Run `f` on the attached acquisition
+fn with_acquisition<T>(
+    state: &State,
+    f: impl FnOnce(&Acquisition) -> T,
+) -> std::result::Result<T, Failure> {
+    match lock(&state.acquisition).as_ref() {
+        Some(acquisition) => Ok(f(acquisition)),
+        None => Err((409, "no acquisition attached".to_string())),
+    }
+}
+
+/// `PUT /config`: apply gain, rate, and channel changes
+fn reconfigure(state: &State, query: &str) -> std::result::Result<String, Failure> {
+    let mut adc1 = lock(&state.header).adc1;
+    let mut channels = None;
+    for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        let invalid = || (400, format!("invalid {} '{}'", key, value));
+        match key {
+            "gain" => adc1.gain = parse_gain(value).ok_or_else(invalid)?,
+            "rate" => adc1.data_rate = parse_data_rate(value).ok_or_else(invalid)?,
+            "channels" => {
+                let header = lock(&state.header);
+                let list = value
+                    .split(',')
+                    .map(|c| header.map.lookup(c).ok_or_else(invalid))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                channels = Some(list);
+            }
+            _ => return Err((400, format!("unknown parameter '{}'", key))),
+        }
+    }
+    let changed = adc1 != lock(&state.header).adc1;
+    let applied = with_acquisition(state, |acquisition| -> Result<()> {
+        if changed {
+            acquisition.reconfigure(Reconfiguration::Adc1(adc1))?;
+        }
+        if let Some(channels) = channels {
+            acquisition.reconfigure(Reconfiguration::Channels(channels))?;
+        }
+        Ok(())
+    })?;
+    applied.map_err(|e| (409, e.to_string()))?;
+    let mut header = lock(&state.header);
+    header.adc1 = adc1;
+    Ok(config(&header))
+}
+
+/// `GET /latest`
+fn latest(state: &State) -> String {
+    let header = lock(&state.header);
+    let map = &header.map;
+    let mut body = String::from("[");
+    for (sample, time) in lock(&state.latest).iter().flatten() {
+        if body.len() > 1 {
+            body.push(',');
+        }
+        let _ = write!(body, "{{\"channel\":{},\"name\":", sample.channel);
+        string(&mut body, &map.name(sample.channel));
+        let time = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let _ = write!(
+            body,
+            ",\"timestamp\":{}.{:06},\"raw\":{},\"value\":",
+            time.as_secs(),
+            time.subsec_micros(),
+            sample.raw
+        );
+        number(&mut body, map.convert_sample(sample));
+        body.push_str(",\"unit\":");
+        string(&mut body, map.unit(sample.channel));
+        let _ = write!(body, ",\"generation\":{}}}", sample.generation);
+    }
+    body.push(']');
+    body
+}
+
+/// `GET /config`
+fn config(header: &CaptureHeader) -> String {
+    let adc1 = &header.adc1;
+    let mut body = format!("{{\"chip_id\":{},\"driver_version\":", header.chip_id);
+    string(&mut body, &header.driver_version);
+    let _ = write!(
+        body,
+        ",\"input_mode\":\"{:?}\",\"gain\":{},\"rate\":",
+        header.input_mode,
+        gain_factor(adc1.gain)
+    );
+    number(&mut body, adc1.data_rate.sps());
+    let _ = write!(
+        body,
+        ",\"filter\":\"{:?}\",\"delay\":\"{:?}\",\"reference_source\":\"{:?}\",\"reference\":",
+        adc1.filter, adc1.delay, adc1.reference
+    );
+    number(&mut body, header.map.reference());
+    body.push_str(",\"channels\":[");
+    for (i, (channel, info)) in header.map.iter().enumerate() {
+        if i > 0 {
+            body.push(',');
+        }
+        let _ = write!(body, "{{\"channel\":{},\"name\":", channel);
+        string(&mut body, &info.name);
+        body.push_str(",\"unit\":");
+        string(&mut body, &info.unit);
+        body.push('}');
+    }
+    body.push_str("]}");
+    body
+}
+
+/// `GET /status`, and the answer to start and stop
+fn status(state: &State) -> String {
+    match lock(&state.acquisition).as_ref() {
+        Some(acquisition) => format!(
+            "{{\"attached\":true,\"running\":{},\"paused\":{},\"generation\":{},\"dropped\":{}}}",
+            acquisition.is_running(),
+            acquisition.is_paused(),
+            acquisition.generation(),
+            acquisition.dropped()
+        ),
+        None => "{\"attached\":false}".to_string(),
+    }
+}
+
+/// `GET /captures`: regular files of the capture directory, newest first
+fn captures(state: &State) -> std::result::Result<String, Failure> {
+    let dir = lock(&state.captures)
+        .clone()
+        .ok_or_else(|| (404, "no capture directory".to_string()))?;
+    let entries = fs::read_dir(&dir).map_err(|e| (500, e.to_string()))?;
+    let mut files: Vec<(String, u64, SystemTime)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let meta = entry.metadata().ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            (meta.is_file() && !name.starts_with('.')).then(|| {
+                let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+                (name, meta.len(), modified)
+            })
+        })
+        .collect();
+    files.sort_by_key(|&(_, _, modified)| std::cmp::Reverse(modified));
+    let mut body = String::from("[");
+    for (i, (name, bytes, modified)) in files.iter().enumerate() {
+        if i > 0 {
+            body.push(',');
+        }
+        body.push_str("{\"name\":");
+        string(&mut body, name);
+        let modified = modified.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let _ = write!(
+            body,
+            ",\"bytes\":{},\"modified\":{}}}",
+            bytes,
+            modified.as_secs()
+        );
+    }
+    body.push(']');
+    Ok(body)
+}
+
+/// Open a file of the capture directory for download, with its
+/// Content-Disposition header
+fn capture_file(state: &State, name: &str) -> std::result::Result<(Header, File), Failure> {
+    let dir = lock(&state.captures)
+        .clone()
+        .ok_or_else(|| (404, "no capture directory".to_string()))?;
+    // Header values must be printable ASCII
+    if name.is_empty()
+        || name.starts_with('.')
+        || name.contains(['/', '\\', '"'])
+        || !name.chars().all(|c| c.is_ascii_graphic() || c == ' ')
+    {
+        return Err((400, format!("invalid file name '{}'", name)));
+    }
+    let path = dir.join(name);
+    if !path.is_file() {
+        return Err((404, format!("no capture '{}'", name)));
+    }
+    let disposition = Header::from_bytes(
+        &b"Content-Disposition"[..],
+        format!("attachment; filename=\"{}\"", name),
+    )
+    .map_err(|()| (500, format!("invalid file name '{}'", name)))?;
+    let file = File::open(&path).map_err(|e| (500, e.to_string()))?;
+    Ok((disposition, file))
+}
+
+/// Gain from its factor, e.g. `4`
+fn parse_gain(value: &str) -> Option<Gain> {
+    Some(match value {
+        "1" => Gain::Gain1,
+        "2" => Gain::Gain2,
+        "4" => Gain::Gain4,
+        "8" => Gain::Gain8,
+        "16" => Gain::Gain16,
+        "32" => Gain::Gain32,
+        "64" => Gain::Gain64,
+        _ => return None,
+    })
+}
+
+/// Factor of a gain
+fn gain_factor(gain: Gain) -> u8 {
+    1 << (gain as u8)
+}
+
+/// Data rate from its samples per second, e.g. `400` or `16.6`
+fn parse_data_rate(value: &str) -> Option<DataRate> {
+    DataRate::from_sps(value.parse().ok()?)
+}
+
+/// Response header from name and value
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+/// JSON response
+fn json(status: u16, body: String) -> Response<io::Cursor<Vec<u8>>> {
+    Response::from_string(body)
+        .with_status_code(StatusCode(status))
+        .with_header(header("Content-Type", "application/json"))
+}
+
+/// JSON error response
+fn error(status: u16, message: &str) -> Response<io::Cursor<Vec<u8>>> {
+    let mut body = String::from("{\"error\":");
+    string(&mut body, message);
+    body.push('}');
+    json(status, body)
+}
@@ -0,0 +1,222 @@
+//! Lock-free ring buffer for scans
+//!
+//! [`RingBuffer`] stores fixed-size frames (one scan of `frame_len`
+//! channels) in atomic slots, so a sampler thread can keep writing while a
+//! UI thread takes [`snapshot`](RingBuffer::snapshot)s of the most recent
+//! data and a consumer [`drain`](RingBuffer::drain)s it, without any of
+//! them taking a lock.
+//!
+//! There must be a single producer calling [`push`](RingBuffer::push) and
+//! at most one consumer calling [`drain`](RingBuffer::drain); snapshots may
+//! be taken from any number of threads.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering, fence};
+
+/// What [`RingBuffer::push`] does when the buffer is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Replace the oldest undrained frame (default)
+    #[default]
+    Overwrite,
+    /// Drop the new frame and keep the undrained data
+    Reject,
+}
+
+/// Fixed-capacity ring of scan frames
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, DataRate, Hal, OverflowPolicy, RingBuffer};
+/// use std::sync::Arc;
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// // Last 10 s of 4 channels scanned at ~300 Hz
+/// let ring = Arc::new(RingBuffer::new(3000, 4, OverflowPolicy::Overwrite));
+/// let acq = adc.start_acquisition_ring(&[0, 1, 2, 3], AcquisitionConfig::default(), ring.clone())?;
+///
+/// // From the UI thread: the most recent 300 scans, oldest first
+/// let recent = ring.snapshot(300);
+/// for frame in recent.chunks(ring.frame_len()) {
+///     println!("{:?}", frame);
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct RingBuffer {
+    slots: Box<[AtomicU32]>,
+    frame_len: usize,
+    capacity: usize,
+    policy: OverflowPolicy,
+    /// Total frames ever written
+    head: AtomicU64,
+    /// Total frames ever drained (or skipped because they were overwritten)
+    tail: AtomicU64,
+    /// Frames lost to `Reject` or overwritten before being drained
+    dropped: AtomicU64,
+}
+
+impl RingBuffer {
+    /// Create a ring holding `capacity` frames of `frame_len` values
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `frame_len` is zero
+    pub fn new(capacity: usize, frame_len: usize, policy: OverflowPolicy) -> Self {
+        assert!(
+            capacity > 0 && frame_len > 0,
+            "ring dimensions must be non-zero"
+        );
+        Self {
+            slots: (0..capacity * frame_len)
+                .map(|_| AtomicU32::new(0))
+                .collect(),
+            frame_len,
+            capacity,
+            policy,
+            head: AtomicU64::new(0),
+            tail: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum number of frames held
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of values per frame
+    pub fn frame_len(&self) -> usize {
+        self.frame_len
+    }
+
+    /// Overflow policy
+    pub fn policy(&self) -> OverflowPolicy {
+        self.policy
+    }
+
+    /// Frames waiting to be drained
+    pub fn len(&self) -> usize {
+        // Tail first: it never passes a head loaded afterwards
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        (head - tail).min(self.capacity as u64) as usize
+    }
+
+    /// true if no frames are waiting to be drained
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total frames accepted since creation
+    pub fn total_written(&self) -> u64 {
+        self.head.load(Ordering::Acquire)
+    }
+
+    /// Frames rejected or overwritten before they were drained
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Append a frame (producer only)
+    ///
+    /// Extra values beyond [`frame_len`](Self::frame_len) are ignored and
+    /// missing ones are written as zero.
+    ///
+    /// # Returns
+    ///
+    /// false if the frame was rejected because the ring is full
+    pub fn push(&self, frame: &[u32]) -> bool {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if self.policy == OverflowPolicy::Reject && head - tail >= self.capacity as u64 {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+
+        let base = self.slot(head);
+        for (i, slot) in self.slots[base..base + self.frame_len].iter().enumerate() {
+            slot.store(frame.get(i).copied().unwrap_or(0), Ordering::Relaxed);
+        }
+        self.head.store(head + 1, Ordering::Release);
+        true
+    }
+
+    /// Copy of the most recent frames without consuming them
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - Maximum number of frames to return
+    ///
+    /// # Returns
+    ///
+    /// Values frame by frame, oldest first
+    pub fn snapshot(&self, frames: usize) -> Vec<u32> {
+        let mut out = Vec::new();
+        self.snapshot_into(frames, &mut out);
+        out
+    }
+
+    /// Copy the most recent frames into `out`, replacing its contents
+    ///
+    /// # Returns
+    ///
+    /// The number of frames copied
+    pub fn snapshot_into(&self, frames: usize, out: &mut Vec<u32>) -> usize {
+        out.clear();
+        let head = self.head.load(Ordering::Acquire);
+        let start = head.saturating_sub(frames.min(self.capacity) as u64);
+        self.copy_range(start, head, out).0
+    }
+
+    /// Move all undrained frames into `out`, appending (consumer only)
+    ///
+    /// # Returns
+    ///
+    /// The number of frames drained
+    pub fn drain(&self, out: &mut Vec<u32>) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Relaxed);
+        let start = tail.max(head.saturating_sub(self.capacity as u64));
+        let (copied, torn) = self.copy_range(start, head, out);
+        let lost = start - tail + torn as u64;
+        if lost > 0 {
+            self.dropped.fetch_add(lost, Ordering::Relaxed);
+            log::debug!("Ring drain skipped {} overwritten frames", lost);
+        }
+        self.tail.store(head, Ordering::Release);
+        copied
+    }
+
+    /// Append frames `start..end` to `out`, discarding any overwritten
+    /// during the copy
+    ///
+    /// Returns the number of frames kept and discarded.
+    fn copy_range(&self, start: u64, end: u64, out: &mut Vec<u32>) -> (usize, usize) {
+        let offset = out.len();
+        for frame in start..end {
+            let base = self.slot(frame);
+            out.extend(
+                self.slots[base..base + self.frame_len]
+                    .iter()
+                    .map(|slot| slot.load(Ordering::Relaxed)),
+            );
+        }
+
+        // The producer may be rewriting the slot of frame `head + 1 - capacity`
+        fence(Ordering::Acquire);
+        let head = self.head.load(Ordering::Relaxed);
+        let valid_from = (head + 1).saturating_sub(self.capacity as u64);
+        let torn = valid_from.clamp(start, end) - start;
+        if torn > 0 {
+            out.drain(offset..offset + torn as usize * self.frame_len);
+        }
+        ((end - start - torn) as usize, torn as usize)
+    }
+
+    /// Index of the first slot of a frame
+    fn slot(&self, frame: u64) -> usize {
+        (frame % self.capacity as u64) as usize * self.frame_len
+    }
+}
@@ -0,0 +1,244 @@
+//! File rotation for unattended logging
+//!
+//! [`RotatingSink`] wraps any file sink and starts a new file once the
+//! current one reaches a size or age limit. Files are numbered in the
+//! order they are written, `capture.ads` becoming `capture.0001.ads`,
+//! `capture.0002.ads`, ...; numbering continues after files left by an
+//! earlier run. Each file is created by the same constructor, so it
+//! starts with its own header (the capture header, or the CSV header
+//! line) and can be read on its own. Optionally only the newest files are
+//! kept.
+
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// How often the size of the current file is checked
+const SIZE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Sink writing into a series of files
+///
+/// Rotation happens between two writes, so a scan is never split across
+/// files. The size limit is checked once a second against the size on
+/// disk, so a file may exceed it by what is written in that second plus
+/// what the sink still buffers.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, CaptureWriter, ChannelMap, DataRate, Hal, RotatingSink};
+/// use std::time::Duration;
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps38400)?;
+///
+/// let header = CaptureHeader::from_device(&mut adc, ChannelMap::new(5.0))?;
+/// let sink = RotatingSink::new("/data/capture.ads", move |path| CaptureWriter::create(path, &header))?
+///     .max_duration(Duration::from_secs(3600))
+///     .max_bytes(1 << 30)
+///     .keep_files(48);
+/// let acq = adc.start_acquisition_sink(&[0], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct RotatingSink<S, F> {
+    make: F,
+    sink: S,
+    base: PathBuf,
+    index: u32,
+    /// Files written so far, oldest first, including the current one
+    files: VecDeque<PathBuf>,
+    opened: Instant,
+    last_size_check: Instant,
+    max_bytes: Option<u64>,
+    max_duration: Option<Duration>,
+    keep: Option<usize>,
+}
+
+impl<S, F> RotatingSink<S, F>
+where
+    S: Sink,
+    F: FnMut(&Path) -> Result<S> + Send,
+{
+    /// Open the first file, creating each file with `make`
+    ///
+    /// File names are derived from `path` by inserting a four-digit file
+    /// number before the extension. Without limits every write goes to
+    /// the first file.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `make` if the first file cannot be created
+    pub fn new(path: impl Into<PathBuf>, mut make: F) -> Result<Self> {
+        let base = path.into();
+        let index = last_index(&base) + 1;
+        let first = numbered(&base, index);
+        let sink = make(&first)?;
+        log::info!("Logging to {}", first.display());
+        let now = Instant::now();
+        Ok(Self {
+            make,
+            sink,
+            base,
+            index,
+            files: VecDeque::from([first]),
+            opened: now,
+            last_size_check: now,
+            max_bytes: None,
+            max_duration: None,
+            keep: None,
+        })
+    }
+
+    /// Start a new file once the current one reaches `bytes`
+    pub fn max_bytes(mut self, bytes: u64) -> Self {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Start a new file once the current one has been written for `duration`
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Delete the oldest files written by this sink beyond the newest `files`
+    ///
+    /// Files left by earlier runs are not touched.
+    pub fn keep_files(mut self, files: usize) -> Self {
+        self.keep = Some(files.max(1));
+        self
+    }
+
+    /// Path of the file being written
+    pub fn current_path(&self) -> &Path {
+        self.files
+            .back()
+            .map_or(self.base.as_path(), PathBuf::as_path)
+    }
+
+    /// The sink writing the current file
+    pub fn get_ref(&self) -> &S {
+        &self.sink
+    }
+
+    /// Close the current file and continue in the next one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current file cannot be flushed or the next
+    /// one created
+    pub fn rotate(&mut self) -> Result<()> {
+        self.sink.flush()?;
+        let next = numbered(&self.base, self.index + 1);
+        // The previous sink finishes its file when dropped
+        self.sink = (self.make)(&next)?;
+        self.index += 1;
+        log::info!("Rotated log to {}", next.display());
+        self.files.push_back(next);
+        self.opened = Instant::now();
+        self.last_size_check = self.opened;
+        self.prune();
+        Ok(())
+    }
+
+    /// true if the current file has reached a limit
+    fn due(&mut self) -> bool {
+        if let Some(max) = self.max_duration
+            && self.opened.elapsed() >= max
+        {
+            return true;
+        }
+        if let Some(max) = self.max_bytes
+            && self.last_size_check.elapsed() >= SIZE_CHECK_INTERVAL
+        {
+            self.last_size_check = Instant::now();
+            let size = fs::metadata(self.current_path()).map_or(0, |m| m.len());
+            return size >= max;
+        }
+        false
+    }
+
+    /// Delete files beyond the retention count
+    fn prune(&mut self) {
+        let Some(keep) = self.keep else {
+            return;
+        };
+        while self.files.len() > keep {
+            let Some(old) = self.files.pop_front() else {
+                break;
+            };
+            match fs::remove_file(&old) {
+                Ok(()) => log::info!("Removed old log {}", old.display()),
+                Err(e) => log::warn!("Failed to remove old log {}: {}", old.display(), e),
+            }
+        }
+    }
+}
+
+impl<S, F> Sink for RotatingSink<S, F>
+where
+    S: Sink,
+    F: FnMut(&Path) -> Result<S> + Send,
+{
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if self.due() {
+            self.rotate()?;
+        }
+        self.sink.write(samples)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// File name of `path` split into stem and extension(s)
+fn split(path: &Path) -> (String, Option<String>) {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.split_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), Some(ext.to_string())),
+        _ => (name, None),
+    }
+}
+
+/// `path` with `index` inserted before its extension(s)
+///
+/// `capture.csv.zst` becomes `capture.0001.csv.zst`.
+fn numbered(path: &Path, index: u32) -> PathBuf {
+    let numbered = match split(path) {
+        (stem, Some(ext)) => format!("{}.{:04}.{}", stem, index, ext),
+        (stem, None) => format!("{}.{:04}", stem, index),
+    };
+    path.with_file_name(numbered)
+}
+
+/// Highest file number of `path` already on disk, 0 if none
+fn last_index(path: &Path) -> u32 {
+    let (stem, ext) = split(path);
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let rest = name.strip_prefix(&stem)?.strip_prefix('.')?;
+            let number = match &ext {
+                Some(ext) => rest.strip_suffix(ext.as_str())?.strip_suffix('.')?,
+                None => rest,
+            };
+            number.parse::<u32>().ok()
+        })
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,627 @@
+//! Platinum RTDs
+//!
+//! [`RtdElement`] converts between resistance and temperature with the
+//! Callendar–Van Dusen equation for an element's R0 and temperature
+//! coefficient. [`RtdSensor`] measures an element on the HAT's RTD circuit
+//! or on another [`RtdConfig`], see [`Ads1263::read_rtd_config`], and
+//! reports its temperature.
+
+use crate::ads1263::{Ads1263, RtdWiring};
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{DataRate, Delay, Gain, IdacCurrent, MuxInput, ReferenceSource};
+use crate::sensors::{Sensor, SensorSetup};
+
+/// Standardized temperature coefficient of a platinum RTD
+///
+/// Each is named after the digits of the mean coefficient α between 0 and
+/// 100 °C (385 for α = 0.00385/K, 3911 for α = 0.003911/K) and selects its
+/// Callendar–Van Dusen coefficients.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RtdAlpha {
+    /// α = 0.00385, IEC 60751 (default)
+    #[default]
+    Alpha385,
+    /// α = 0.003911, US industrial
+    Alpha3911,
+    /// α = 0.003916, JIS C 1604
+    Alpha3916,
+}
+
+impl RtdAlpha {
+    /// All coefficients
+    pub const ALL: [RtdAlpha; 3] = [RtdAlpha::Alpha385, RtdAlpha::Alpha3911, RtdAlpha::Alpha3916];
+
+    /// Mean temperature coefficient between 0 and 100 °C, per kelvin
+    pub fn alpha(self) -> f64 {
+        match self {
+            RtdAlpha::Alpha385 => 0.00385,
+            RtdAlpha::Alpha3911 => 0.003911,
+            RtdAlpha::Alpha3916 => 0.003916,
+        }
+    }
+
+    /// Callendar–Van Dusen coefficients A, B, and C
+    pub fn coefficients(self) -> (f64, f64, f64) {
+        match self {
+            RtdAlpha::Alpha385 => (3.9083e-3, -5.775e-7, -4.183e-12),
+            RtdAlpha::Alpha3911 => (3.9692e-3, -5.8495e-7, -4.2325e-12),
+            RtdAlpha::Alpha3916 => (3.9739e-3, -5.870e-7, -4.4e-12),
+        }
+    }
+}
+
+/// Platinum RTD element: resistance at 0 °C and temperature coefficient
+///
+/// # Example
+///
+/// ```
+/// use ads1263::{RtdAlpha, RtdElement};
+///
+/// let pt1000 = RtdElement::PT1000;
+/// assert!((pt1000.resistance(100.0) - 1385.055).abs() < 1e-3);
+/// assert!((pt1000.celsius(1385.055) - 100.0).abs() < 1e-3);
+///
+/// let pt500 = RtdElement::new(500.0, RtdAlpha::Alpha3916);
+/// assert_eq!(pt500.resistance(0.0), 500.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtdElement {
+    /// Resistance at 0 °C in ohms
+    pub r0: f64,
+    /// Temperature coefficient
+    pub alpha: RtdAlpha,
+}
+
+impl RtdElement {
+    /// PT100 with α = 0.00385
+    pub const PT100: RtdElement = RtdElement::new(100.0, RtdAlpha::Alpha385);
+    /// PT500 with α = 0.00385
+    pub const PT500: RtdElement = RtdElement::new(500.0, RtdAlpha::Alpha385);
+    /// PT1000 with α = 0.00385
+    pub const PT1000: RtdElement = RtdElement::new(1000.0, RtdAlpha::Alpha385);
+
+    /// Element with `r0` ohms at 0 °C
+    pub const fn new(r0: f64, alpha: RtdAlpha) -> Self {
+        Self { r0, alpha }
+    }
+
+    /// Resistance in ohms at `celsius`
+    ///
+    /// R(t) = R0·(1 + A·t + B·t² + C·(t - 100)·t³), without the C term
+    /// from 0 °C.
+    pub fn resistance(&self, celsius: f64) -> f64 {
+        let (a, b, c) = self.alpha.coefficients();
+        let t = celsius;
+        let c = if t < 0.0 { c } else { 0.0 };
+        self.r0 * (1.0 + a * t + b * t * t + c * (t - 100.0) * t.powi(3))
+    }
+
+    /// Temperature in °C at `resistance` ohms
+    ///
+    /// Inverts [`resistance`](Self::resistance): in closed form from 0 °C,
+    /// and by Newton's method below 0 °C, where the C term makes the
+    /// equation quartic. Accurate to well below 1 mK from -200 to 850 °C.
+    pub fn celsius(&self, resistance: f64) -> f64 {
+        let (a, b, c) = self.alpha.coefficients();
+        let ratio = resistance / self.r0;
+        // Quadratic solution, exact from 0 °C
+        let mut t = (-a + (a * a - 4.0 * b * (1.0 - ratio)).sqrt()) / (2.0 * b);
+        if ratio < 1.0 {
+            for _ in 0..10 {
+                let f = 1.0 + a * t + b * t * t + c * (t - 100.0) * t.powi(3) - ratio;
+                let slope = a + 2.0 * b * t + c * (4.0 * t.powi(3) - 300.0 * t * t);
+                let step = f / slope;
+                t -= step;
+                if step.abs() < 1e-9 {
+                    break;
+                }
+            }
+        }
+        t
+    }
+}
+
+impl Default for RtdElement {
+    /// PT100 with α = 0.00385
+    fn default() -> Self {
+        Self::PT100
+    }
+}
+
+/// Inputs of an RTD circuit
+///
+/// # Example
+///
+/// ```
+/// use ads1263::{MuxInput, RtdPins, RtdWiring};
+///
+/// // Force current out of AIN0, sensed on AIN1 and AIN2
+/// let kelvin = RtdPins::new(MuxInput::Ain(0), MuxInput::Ain(1), MuxInput::Ain(2));
+/// assert!(kelvin.validate(RtdWiring::FourWire).is_ok());
+///
+/// // The force lead must not be a sense lead
+/// let shared = RtdPins::new(MuxInput::Ain(1), MuxInput::Ain(1), MuxInput::Ain(2));
+/// assert!(shared.validate(RtdWiring::FourWire).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RtdPins {
+    /// IDAC1 output exciting the RTD, the force lead in 4-wire connection
+    pub excitation: MuxInput,
+    /// IDAC2 output into the compensation lead in 3-wire connection
+    pub compensation: MuxInput,
+    /// Positive sense input
+    pub positive: MuxInput,
+    /// Negative sense input
+    pub negative: MuxInput,
+}
+
+impl RtdPins {
+    /// The HAT's RTD circuit: IDAC1 on AIN3, IDAC2 on AINCOM, sensed on
+    /// AIN7 and AIN6
+    pub const HAT: RtdPins = RtdPins {
+        excitation: MuxInput::Ain(3),
+        compensation: MuxInput::AinCom,
+        positive: MuxInput::Ain(7),
+        negative: MuxInput::Ain(6),
+    };
+
+    /// Excitation on `excitation`, sensed between `positive` and
+    /// `negative`, with the compensation current on AINCOM
+    pub fn new(excitation: MuxInput, positive: MuxInput, negative: MuxInput) -> Self {
+        Self {
+            excitation,
+            compensation: MuxInput::AinCom,
+            positive,
+            negative,
+        }
+    }
+
+    /// Set the IDAC2 output for 3-wire connection
+    pub fn compensation(mut self, pin: MuxInput) -> Self {
+        self.compensation = pin;
+        self
+    }
+
+    /// The same pins with the IDAC outputs exchanged
+    pub fn swapped(self) -> Self {
+        Self {
+            excitation: self.compensation,
+            compensation: self.excitation,
+            ..self
+        }
+    }
+
+    /// Check the pins against the multiplexers for `wiring`
+    ///
+    /// Every pin must be AIN0-AIN9 or AINCOM, which both the IDAC and the
+    /// input multiplexer reach. The sense inputs must differ, the 3-wire
+    /// excitation and compensation outputs must differ, and in 4-wire
+    /// connection the force output must not be a sense input.
+    /// [`RtdConfig::validate`] also checks them against the reference
+    /// inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` naming the offending pin
+    pub fn validate(&self, wiring: RtdWiring) -> Result<()> {
+        let invalid = |message: String| {
+            Err(Ads1263Error::InvalidConfig(format!(
+                "RTD pins: {}",
+                message
+            )))
+        };
+
+        let mut pins = vec![
+            ("excitation", self.excitation),
+            ("positive sense", self.positive),
+            ("negative sense", self.negative),
+        ];
+        if wiring == RtdWiring::ThreeWire {
+            pins.push(("compensation", self.compensation));
+        }
+        for (role, pin) in pins {
+            if !matches!(pin, MuxInput::Ain(0..=9) | MuxInput::AinCom) {
+                return invalid(format!("{} {:?} is not AIN0-AIN9 or AINCOM", role, pin));
+            }
+        }
+        if self.positive == self.negative {
+            return invalid(format!("both sense inputs are {:?}", self.positive));
+        }
+        if wiring == RtdWiring::ThreeWire && self.excitation == self.compensation {
+            return invalid(format!(
+                "excitation and compensation both on {:?}",
+                self.excitation
+            ));
+        }
+        if wiring == RtdWiring::FourWire
+            && (self.excitation == self.positive || self.excitation == self.negative)
+        {
+            return invalid(format!(
+                "4-wire force output {:?} is also a sense input",
+                self.excitation
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RtdPins {
+    /// The HAT's RTD circuit
+    fn default() -> Self {
+        Self::HAT
+    }
+}
+
+/// Complete RTD circuit: wiring, pins, reference, excitation current, and
+/// reference resistor
+///
+/// The default is the HAT's circuit: 3-wire on [`RtdPins::HAT`], 250 µA,
+/// and the 2 kΩ reference resistor between AIN4 and AIN5. Boards wired
+/// differently describe their circuit here and read it with
+/// [`Ads1263::read_rtd_config`].
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{
+///     DataRate, Delay, Gain, IdacCurrent, MuxInput, ReferenceSource, RtdConfig, RtdPins,
+///     RtdWiring,
+/// };
+///
+/// // 4-wire, forced from AIN0, sensed on AIN8/AIN9, 4.99 kΩ on AIN2/AIN3
+/// let circuit = RtdConfig::default()
+///     .wiring(RtdWiring::FourWire)
+///     .pins(RtdPins::new(MuxInput::Ain(0), MuxInput::Ain(8), MuxInput::Ain(9)))
+///     .reference(ReferenceSource::ExternalAin23)
+///     .idac(IdacCurrent::Ua100)
+///     .r_ref(4990.0);
+///
+/// let raw = adc.read_rtd_config(&circuit, Delay::Delay8_8ms, Gain::Gain1, DataRate::Sps20)?;
+/// println!("{:.3} Ω", circuit.resistance(raw, Gain::Gain1));
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtdConfig {
+    /// Lead connection
+    pub wiring: RtdWiring,
+    /// Excitation and sense inputs
+    pub pins: RtdPins,
+    /// External reference across the reference resistor
+    pub reference: ReferenceSource,
+    /// Excitation current
+    pub idac: IdacCurrent,
+    /// Reference resistor in ohms
+    pub r_ref: f64,
+}
+
+impl RtdConfig {
+    /// Set the lead connection
+    pub fn wiring(mut self, wiring: RtdWiring) -> Self {
+        self.wiring = wiring;
+        self
+    }
+
+    /// Set the excitation and sense inputs
+    pub fn pins(mut self, pins: RtdPins) -> Self {
+        self.pins = pins;
+        self
+    }
+
+    /// Set the external reference input pair across the reference resistor
+    pub fn reference(mut self, reference: ReferenceSource) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    /// Set the excitation current
+    pub fn idac(mut self, idac: IdacCurrent) -> Self {
+        self.idac = idac;
+        self
+    }
+
+    /// Set the reference resistor in ohms
+    pub fn r_ref(mut self, ohms: f64) -> Self {
+        self.r_ref = ohms;
+        self
+    }
+
+    /// Voltage across the reference resistor in volts
+    pub fn vref(&self) -> f64 {
+        f64::from(self.idac.microamps()) * 1e-6 * self.wiring.currents() * self.r_ref
+    }
+
+    /// RTD resistance in ohms of a raw conversion at `gain`
+    pub fn resistance(&self, raw: u32, gain: Gain) -> f64 {
+        self.wiring.resistance(raw, self.r_ref, gain)
+    }
+
+    /// Check the circuit
+    ///
+    /// The reference must be one of the external input pairs, no RTD pin
+    /// may be one of its inputs, the pins must fit the wiring (see
+    /// [`RtdPins::validate`]), the excitation current must be on, and the
+    /// reference resistor positive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` describing the first problem
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |message: String| {
+            Err(Ads1263Error::InvalidConfig(format!(
+                "RTD circuit: {}",
+                message
+            )))
+        };
+
+        let Some((reference_p, reference_n)) = self.reference.inputs() else {
+            return invalid(format!(
+                "reference {:?} is not an external input pair",
+                self.reference
+            ));
+        };
+        self.pins.validate(self.wiring)?;
+        let mut pins = vec![
+            ("excitation", self.pins.excitation),
+            ("positive sense", self.pins.positive),
+            ("negative sense", self.pins.negative),
+        ];
+        if self.wiring == RtdWiring::ThreeWire {
+            pins.push(("compensation", self.pins.compensation));
+        }
+        for (role, pin) in pins {
+            if pin == reference_p || pin == reference_n {
+                return invalid(format!("{} {:?} is a reference input", role, pin));
+            }
+        }
+        if self.idac == IdacCurrent::Off {
+            return invalid("excitation current is off".to_string());
+        }
+        if !self.r_ref.is_finite() || self.r_ref <= 0.0 {
+            return invalid(format!(
+                "reference resistor {} Ω must be positive",
+                self.r_ref
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for RtdConfig {
+    /// The HAT's 3-wire circuit at 250 µA against 2 kΩ on AIN4/AIN5
+    fn default() -> Self {
+        Self {
+            wiring: RtdWiring::ThreeWire,
+            pins: RtdPins::HAT,
+            reference: ReferenceSource::ExternalAin45,
+            idac: IdacCurrent::Ua250,
+            r_ref: 2000.0,
+        }
+    }
+}
+
+/// RTD reported in °C, on the HAT's RTD circuit unless given another
+/// [`RtdConfig`]
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{IdacCurrent, MuxInput, RtdElement, RtdPins, RtdSensor, RtdWiring, Sensor};
+///
+/// let probe = RtdSensor::new(RtdElement::PT1000)
+///     .wiring(RtdWiring::FourWire)
+///     .pins(RtdPins::new(MuxInput::Ain(0), MuxInput::Ain(1), MuxInput::Ain(2)))
+///     .idac(IdacCurrent::Ua100);
+/// println!("{:.3} °C", probe.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtdSensor {
+    element: RtdElement,
+    config: RtdConfig,
+    gain: Gain,
+    data_rate: DataRate,
+    delay: Delay,
+}
+
+impl RtdSensor {
+    /// `element` in 3-wire connection at 250 µA against the 2 kΩ reference
+    /// resistor, at unity gain, 20 SPS, and 8.8 ms delay
+    pub fn new(element: RtdElement) -> Self {
+        Self {
+            element,
+            config: RtdConfig::default(),
+            gain: Gain::Gain1,
+            data_rate: DataRate::Sps20,
+            delay: Delay::Delay8_8ms,
+        }
+    }
+
+    /// Set the whole circuit, checked when reading
+    pub fn config(mut self, config: RtdConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the lead connection
+    pub fn wiring(mut self, wiring: RtdWiring) -> Self {
+        self.config.wiring = wiring;
+        self
+    }
+
+    /// Set the excitation and sense inputs, checked when reading
+    pub fn pins(mut self, pins: RtdPins) -> Self {
+        self.config.pins = pins;
+        self
+    }
+
+    /// Set the external reference input pair
+    pub fn reference(mut self, reference: ReferenceSource) -> Self {
+        self.config.reference = reference;
+        self
+    }
+
+    /// Set the excitation current
+    pub fn idac(mut self, idac: IdacCurrent) -> Self {
+        self.config.idac = idac;
+        self
+    }
+
+    /// Set the reference resistor in ohms
+    pub fn r_ref(mut self, ohms: f64) -> Self {
+        self.config.r_ref = ohms;
+        self
+    }
+
+    /// Set the PGA gain
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Set the data rate
+    pub fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.data_rate = data_rate;
+        self
+    }
+
+    /// Set the conversion start delay
+    pub fn delay(mut self, delay: Delay) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// RTD element
+    pub fn element(&self) -> RtdElement {
+        self.element
+    }
+
+    /// RTD circuit
+    pub fn circuit(&self) -> &RtdConfig {
+        &self.config
+    }
+
+    /// RTD resistance in ohms of a conversion result
+    pub fn resistance(&self, code: i32) -> f64 {
+        self.config.resistance(code.max(0) as u32, self.gain)
+    }
+
+    /// Read the lead-compensated resistance in ohms
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`Ads1263::read_rtd_config`]
+    pub fn read_resistance(&self, adc: &mut Ads1263) -> Result<f64> {
+        let raw = adc.read_rtd_config(&self.config, self.delay, self.gain, self.data_rate)?;
+        Ok(self.resistance(raw as i32))
+    }
+}
+
+impl Sensor for RtdSensor {
+    fn unit(&self) -> &str {
+        "°C"
+    }
+
+    /// Sense inputs and the reference; the reference voltage depends on
+    /// the excitation current
+    fn setup(&self) -> SensorSetup {
+        let pins = &self.config.pins;
+        SensorSetup::new(pins.positive, pins.negative)
+            .gain(self.gain)
+            .data_rate(self.data_rate)
+            .delay(self.delay)
+            .reference(self.config.reference, self.config.vref())
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.element.celsius(self.resistance(code))
+    }
+
+    /// Read with [`Ads1263::read_rtd_config`], which compensates 3-wire
+    /// leads
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        Ok(self.element.celsius(self.read_resistance(adc)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Code of `ohms` on the HAT circuit (2 × 250 µA into 2 kΩ) at `gain`
+    fn hat_code(ohms: f64, gain: Gain) -> i32 {
+        let factor = f64::from(1u32 << gain as u32);
+        (ohms / 4000.0 * factor * 2147483647.0).round() as i32
+    }
+
+    /// IEC 60751 Pt100 reference values: °C and ohms
+    const IEC_60751: [(f64, f64); 6] = [
+        (-200.0, 18.5201),
+        (-100.0, 60.2558),
+        (0.0, 100.0),
+        (100.0, 138.5055),
+        (500.0, 280.9775),
+        (850.0, 390.4811),
+    ];
+
+    #[test]
+    fn callendar_van_dusen_matches_iec_60751() {
+        for (celsius, ohms) in IEC_60751 {
+            let r = RtdElement::PT100.resistance(celsius);
+            // Values rounded to 0.1 mΩ
+            assert!(
+                (r - ohms).abs() <= 0.00005,
+                "{} °C: {} Ω, expected {} Ω",
+                celsius,
+                r,
+                ohms
+            );
+            // 0.05 mΩ is about 0.15 mK at the flattest point of the curve
+            let t = RtdElement::PT100.celsius(ohms);
+            assert!(
+                (t - celsius).abs() < 0.0002,
+                "{} Ω: {} °C, expected {} °C",
+                ohms,
+                t,
+                celsius
+            );
+        }
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        for alpha in RtdAlpha::ALL {
+            let element = RtdElement::new(1000.0, alpha);
+            for celsius in [-200.0, -150.0, -40.0, -0.5, 0.0, 0.5, 100.0, 420.0, 850.0] {
+                let back = element.celsius(element.resistance(celsius));
+                assert!(
+                    (back - celsius).abs() < 1e-6,
+                    "{:?} at {} °C: inverse gives {} °C",
+                    alpha,
+                    celsius,
+                    back
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn resistance_divides_out_the_gain() {
+        let code = hat_code(100.0, Gain::Gain16);
+        let sensor = RtdSensor::new(RtdElement::PT100).gain(Gain::Gain16);
+        assert!((sensor.resistance(code) - 100.0).abs() < 1e-6);
+        assert!(sensor.convert(code).abs() < 1e-4);
+
+        let code = hat_code(138.5055, Gain::Gain8);
+        let circuit = RtdConfig::default();
+        assert!((circuit.resistance(code as u32, Gain::Gain8) - 138.5055).abs() < 1e-6);
+        assert!((circuit.resistance(code as u32, Gain::Gain1) - 8.0 * 138.5055).abs() < 1e-5);
+    }
+
+    #[test]
+    fn negative_codes_read_as_zero_ohms() {
+        let sensor = RtdSensor::new(RtdElement::PT100).gain(Gain::Gain8);
+        assert_eq!(sensor.resistance(-1000), 0.0);
+    }
+}
@@ -0,0 +1,149 @@
+//! Timestamped conversion results
+//!
+//! A [`Sample`] carries everything known about one ADC1 conversion: the
+//! value, the channel it was taken on, when DRDY signalled it, the status
+//! byte, whether the CRC matched, and its sequence number and
+//! configuration generation, so loss, reordering, and reconfigurations
+//! can be detected after the fact. The streaming APIs
+//! ([`Acquisition`](crate::Acquisition), burst capture, and the async
+//! stream) deliver samples so consumers don't have to infer timing.
+//!
+//! [`Ads1263::samples`] turns a channel into a blocking iterator of
+//! samples, so the usual adapters (`take`, `map`, `filter`, ...) work on a
+//! live stream.
+
+use crate::ads1263::Ads1263;
+use crate::error::Result;
+use crate::registers::StatusFlags;
+use std::time::Instant;
+
+/// One ADC1 conversion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// Signed raw conversion result
+    pub raw: i32,
+    /// Channel the conversion was taken on
+    pub channel: u8,
+    /// Time DRDY signalled the conversion
+    pub timestamp: Instant,
+    /// Status byte sent with the conversion
+    pub status: StatusFlags,
+    /// true if the CRC byte matched the data
+    pub crc_ok: bool,
+    /// Conversions lost between the previous sample on this channel and
+    /// this one; always 0 for reads that switch the multiplexer
+    pub dropped_since_last: u32,
+    /// Position among all samples read by the driver, starting at 0
+    ///
+    /// The driver numbers every successful read without gaps, so a gap
+    /// downstream means samples were lost after reading and a decrease
+    /// means they were reordered.
+    pub sequence: u64,
+    /// Configuration generation of the acquisition that produced the
+    /// sample, incremented by every reconfiguration; 0 for direct reads
+    pub generation: u32,
+}
+
+impl Sample {
+    /// Convert the raw value to volts; see [`Ads1263::raw_to_voltage_adc1`]
+    pub fn voltage(&self, reference: f64) -> f64 {
+        Ads1263::raw_to_voltage_adc1(self.raw as u32, reference)
+    }
+}
+
+/// Settings for [`Ads1263::samples`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SamplesConfig {
+    /// End the iterator after this many samples; `None` runs forever
+    pub limit: Option<u64>,
+    /// End the iterator after yielding the first error (default)
+    ///
+    /// When false, errors are yielded and reading continues.
+    pub stop_on_error: bool,
+}
+
+impl Default for SamplesConfig {
+    fn default() -> Self {
+        Self {
+            limit: None,
+            stop_on_error: true,
+        }
+    }
+}
+
+/// Blocking iterator over the conversions of one channel
+///
+/// Created by [`Ads1263::samples`]. The channel is routed on the first
+/// call to `next`; each call then blocks until the next conversion.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::SamplesConfig;
+///
+/// let volts: Vec<f64> = adc
+///     .samples(0, SamplesConfig::default())
+///     .take(1000)
+///     .map(|s| s.map(|s| s.voltage(5.0)))
+///     .collect::<ads1263::Result<_>>()?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct Samples<'a> {
+    adc: &'a mut Ads1263,
+    channel: u8,
+    config: SamplesConfig,
+    read: u64,
+    routed: bool,
+    done: bool,
+}
+
+impl<'a> Samples<'a> {
+    pub(crate) fn new(adc: &'a mut Ads1263, channel: u8, config: SamplesConfig) -> Self {
+        Self {
+            adc,
+            channel,
+            config,
+            read: 0,
+            routed: false,
+            done: false,
+        }
+    }
+
+    fn read(&mut self) -> Result<Sample> {
+        if !self.routed {
+            self.adc.select_channel(self.channel)?;
+            self.routed = true;
+        }
+        self.adc.read_next_sample(self.channel)
+    }
+}
+
+impl Iterator for Samples<'_> {
+    type Item = Result<Sample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.config.limit.is_some_and(|limit| self.read >= limit) {
+            return None;
+        }
+        let sample = self.read();
+        self.read += 1;
+        if sample.is_err() && self.config.stop_on_error {
+            self.done = true;
+        }
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.config.limit {
+            _ if self.done => (0, Some(0)),
+            Some(limit) => {
+                let left = limit.saturating_sub(self.read) as usize;
+                (0, Some(left))
+            }
+            None => (0, None),
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Samples<'_> {}
@@ -0,0 +1,153 @@
+//! Scan sequences with per-channel settings
+//!
+//! A [`ScanPlan`] lists the inputs to convert in order, each with its own
+//! gain, data rate, and settling delay.
+//! [`Ads1263::run_scan`](crate::Ads1263::run_scan) executes the plan and
+//! only rewrites the registers that differ from the previous step, so a
+//! plan where most channels share settings costs no more than
+//! [`get_all`](crate::Ads1263::get_all).
+
+use crate::registers::{DataRate, Gain, InputMode};
+use std::time::Duration;
+
+/// Input routed to ADC1 for one step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanInput {
+    /// AINx against AINCOM (0-10)
+    Single(u8),
+    /// Differential pair AIN(2n)-AIN(2n+1) (0-4)
+    Differential(u8),
+}
+
+impl ScanInput {
+    /// Input mode and channel number for the multiplexer
+    pub(crate) fn mode_channel(self) -> (InputMode, u8) {
+        match self {
+            ScanInput::Single(ch) => (InputMode::SingleEnded, ch),
+            ScanInput::Differential(ch) => (InputMode::Differential, ch),
+        }
+    }
+}
+
+/// One conversion in a scan plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanStep {
+    /// Input to convert
+    pub input: ScanInput,
+    /// PGA gain for this input
+    pub gain: Gain,
+    /// Output data rate for this input
+    pub data_rate: DataRate,
+    /// Extra delay after reprogramming, before waiting for the conversion
+    ///
+    /// For sources that need time to settle after the multiplexer switches
+    /// (e.g. high-impedance sensors). Skipped if nothing was reprogrammed.
+    pub settling: Duration,
+}
+
+impl ScanStep {
+    /// Single-ended step at unity gain and 400 SPS
+    pub fn single(channel: u8) -> Self {
+        Self::new(ScanInput::Single(channel))
+    }
+
+    /// Differential step at unity gain and 400 SPS
+    pub fn differential(pair: u8) -> Self {
+        Self::new(ScanInput::Differential(pair))
+    }
+
+    fn new(input: ScanInput) -> Self {
+        Self {
+            input,
+            gain: Gain::default(),
+            data_rate: DataRate::default(),
+            settling: Duration::ZERO,
+        }
+    }
+
+    /// Set the PGA gain
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Set the data rate
+    pub fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.data_rate = data_rate;
+        self
+    }
+
+    /// Set the settling delay
+    pub fn settling(mut self, settling: Duration) -> Self {
+        self.settling = settling;
+        self
+    }
+}
+
+/// Ordered list of conversions with per-step settings
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{DataRate, Gain, ScanPlan, ScanStep};
+/// use std::time::Duration;
+///
+/// let plan = ScanPlan::new()
+///     .step(ScanStep::single(0))
+///     .step(ScanStep::single(1))
+///     .step(
+///         ScanStep::differential(2)
+///             .gain(Gain::Gain32)
+///             .data_rate(DataRate::Sps20)
+///             .settling(Duration::from_millis(2)),
+///     );
+///
+/// let values = adc.scan(&plan)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanPlan {
+    steps: Vec<ScanStep>,
+}
+
+impl ScanPlan {
+    /// Create an empty plan
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a step
+    pub fn step(mut self, step: ScanStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Append a step in place
+    pub fn push(&mut self, step: ScanStep) {
+        self.steps.push(step);
+    }
+
+    /// Steps in execution order
+    pub fn steps(&self) -> &[ScanStep] {
+        &self.steps
+    }
+
+    /// Number of steps (values per scan)
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// true if the plan has no steps
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl FromIterator<ScanStep> for ScanPlan {
+    fn from_iter<I: IntoIterator<Item = ScanStep>>(iter: I) -> Self {
+        Self {
+            steps: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,385 @@
+//! Sensors on top of the raw conversions
+//!
+//! A [`Sensor`] knows how it has to be measured (a [`SensorSetup`]:
+//! multiplexer inputs, gain, data rate, and reference) and how to turn the
+//! conversion result into a physical value. A [`SensorRegistry`] binds
+//! sensors to channel numbers, so code can ask for the temperature on
+//! channel 3 instead of its voltage.
+//!
+//! [`VoltageSensor`] reports the input voltage of any input pair, and
+//! [`RatiometricSensor`] the input as a fraction of the sensor excitation;
+//! the other sensor types build on the same trait.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::{DataRate, Delay, Gain, MuxInput, ReferenceSource};
+use std::fmt;
+
+/// ADC1 configuration a sensor is measured with
+///
+/// [`Ads1263::read_sensor`] applies it for one conversion and then
+/// restores the previous ADC1 configuration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensorSetup {
+    /// Positive multiplexer input
+    pub positive: MuxInput,
+    /// Negative multiplexer input
+    pub negative: MuxInput,
+    /// PGA gain
+    pub gain: Gain,
+    /// Output data rate
+    pub data_rate: DataRate,
+    /// Conversion start delay
+    pub delay: Delay,
+    /// Reference voltage source
+    pub reference: ReferenceSource,
+    /// Reference voltage in volts, to convert codes to volts
+    pub vref: f64,
+    /// Bias AINCOM to mid-supply (VBIAS) during the conversion
+    pub vbias: bool,
+}
+
+impl SensorSetup {
+    /// `positive` against `negative` at unity gain, 20 SPS, 8.8 ms delay,
+    /// and the 5 V AVDD/AVSS reference, without VBIAS
+    pub fn new(positive: MuxInput, negative: MuxInput) -> Self {
+        Self {
+            positive,
+            negative,
+            gain: Gain::Gain1,
+            data_rate: DataRate::Sps20,
+            delay: Delay::Delay8_8ms,
+            reference: ReferenceSource::AvddAvss,
+            vref: 5.0,
+            vbias: false,
+        }
+    }
+
+    /// Set the PGA gain
+    pub fn gain(mut self, gain: Gain) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Set the data rate
+    pub fn data_rate(mut self, data_rate: DataRate) -> Self {
+        self.data_rate = data_rate;
+        self
+    }
+
+    /// Set the conversion start delay
+    pub fn delay(mut self, delay: Delay) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the reference source and its voltage
+    pub fn reference(mut self, reference: ReferenceSource, vref: f64) -> Self {
+        self.reference = reference;
+        self.vref = vref;
+        self
+    }
+
+    /// Use the sensor excitation as the reference, on AVDD or an external
+    /// input pair, so conversions are ratiometric
+    ///
+    /// The reference voltage is set to 1, which makes
+    /// [`volts`](Self::volts) equal to [`ratio`](Self::ratio): the input
+    /// in units of the excitation.
+    pub fn ratiometric(self, excitation: ReferenceSource) -> Self {
+        self.reference(excitation, 1.0)
+    }
+
+    /// Enable the VBIAS level shift of AINCOM, for floating sensors such
+    /// as thermocouples
+    pub fn vbias(mut self, enable: bool) -> Self {
+        self.vbias = enable;
+        self
+    }
+
+    /// Input voltage of a conversion result, divided by the PGA gain
+    pub fn volts(&self, code: i32) -> f64 {
+        self.ratio(code) * self.vref
+    }
+
+    /// Input voltage of a conversion result as a fraction of the reference
+    pub fn ratio(&self, code: i32) -> f64 {
+        f64::from(code) / 2147483648.0 / gain_factor(self.gain)
+    }
+}
+
+/// PGA gain as a factor
+pub(crate) fn gain_factor(gain: Gain) -> f64 {
+    f64::from(1u32 << gain as u32)
+}
+
+/// Something measured by ADC1 and reported in a physical unit
+///
+/// Implement [`setup`](Self::setup) and [`convert`](Self::convert); the
+/// default [`read`](Self::read) takes one conversion with the setup.
+/// Sensors that need more than one conversion override `read`.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{MuxInput, Sensor, SensorSetup};
+///
+/// /// Potentiometer between AVDD and AVSS, wiper on AIN2
+/// #[derive(Debug)]
+/// struct Position;
+///
+/// impl Sensor for Position {
+///     fn unit(&self) -> &str {
+///         "%"
+///     }
+///
+///     fn setup(&self) -> SensorSetup {
+///         SensorSetup::new(MuxInput::Ain(2), MuxInput::AinCom)
+///     }
+///
+///     fn convert(&self, code: i32) -> f64 {
+///         self.setup().ratio(code) * 100.0
+///     }
+/// }
+///
+/// println!("{:.1} %", Position.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub trait Sensor: fmt::Debug + Send {
+    /// Unit of the converted value, e.g. "°C"
+    fn unit(&self) -> &str;
+
+    /// ADC1 configuration the sensor is measured with
+    fn setup(&self) -> SensorSetup;
+
+    /// Convert a conversion result taken with [`setup`](Self::setup) to
+    /// the sensor's unit
+    fn convert(&self, code: i32) -> f64;
+
+    /// Take one reading in the sensor's unit
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`Ads1263::read_sensor`]
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        let code = adc.read_sensor(&self.setup())?;
+        Ok(self.convert(code))
+    }
+}
+
+/// Voltage across two inputs in volts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VoltageSensor {
+    setup: SensorSetup,
+}
+
+impl VoltageSensor {
+    /// `positive` against `negative`, with the defaults of [`SensorSetup::new`]
+    pub fn new(positive: MuxInput, negative: MuxInput) -> Self {
+        Self::with_setup(SensorSetup::new(positive, negative))
+    }
+
+    /// Voltage measured with a custom setup
+    pub fn with_setup(setup: SensorSetup) -> Self {
+        Self { setup }
+    }
+}
+
+impl Sensor for VoltageSensor {
+    fn unit(&self) -> &str {
+        "V"
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.setup.volts(code)
+    }
+}
+
+/// Input as a fraction of the sensor excitation, optionally scaled
+///
+/// The reference is the excitation itself, so a drifting excitation moves
+/// the input and the reference together and the ratio stays put: the
+/// usual way to read bridges and potentiometers. The value is
+/// `ratio × scale + offset`, in V/V unless scaled.
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{MuxInput, RatiometricSensor, ReferenceSource, Sensor};
+///
+/// // Potentiometer fed from AIN0/AIN1, wiper on AIN2, 0 to 270°
+/// let excitation = ReferenceSource::ExternalAin01;
+/// let angle = RatiometricSensor::new(MuxInput::Ain(2), MuxInput::Ain(1), excitation)
+///     .linear(270.0, 0.0, "°");
+/// println!("{:.1} °", angle.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatiometricSensor {
+    setup: SensorSetup,
+    scale: f64,
+    offset: f64,
+    unit: String,
+}
+
+impl RatiometricSensor {
+    /// `positive` against `negative` relative to `excitation`, with the
+    /// other defaults of [`SensorSetup::new`]
+    pub fn new(positive: MuxInput, negative: MuxInput, excitation: ReferenceSource) -> Self {
+        Self::with_setup(SensorSetup::new(positive, negative).ratiometric(excitation))
+    }
+
+    /// Ratio measured with a custom setup, whose reference must be the
+    /// excitation
+    pub fn with_setup(setup: SensorSetup) -> Self {
+        Self {
+            setup,
+            scale: 1.0,
+            offset: 0.0,
+            unit: "V/V".to_string(),
+        }
+    }
+
+    /// Report `ratio × scale + offset` in `unit`
+    pub fn linear(mut self, scale: f64, offset: f64, unit: &str) -> Self {
+        self.scale = scale;
+        self.offset = offset;
+        self.unit = unit.to_string();
+        self
+    }
+
+    /// Map the ratio range `ratios` linearly onto `values` in `unit`
+    ///
+    /// An empty ratio range leaves the scaling unchanged.
+    pub fn range(self, ratios: (f64, f64), values: (f64, f64), unit: &str) -> Self {
+        let span = ratios.1 - ratios.0;
+        if span == 0.0 {
+            return self;
+        }
+        let scale = (values.1 - values.0) / span;
+        self.linear(scale, values.0 - ratios.0 * scale, unit)
+    }
+}
+
+impl Sensor for RatiometricSensor {
+    fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.setup.ratio(code) * self.scale + self.offset
+    }
+
+    /// Take one conversion against the excitation
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if the reference is the
+    /// internal 2.5 V reference, which does not track any excitation, or
+    /// the error of the conversion
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        let ratio = adc.read_ratio(&self.setup)?;
+        Ok(ratio * self.scale + self.offset)
+    }
+}
+
+/// Sensors bound to channel numbers
+///
+/// Channel numbers are labels here: each sensor routes its own inputs, so
+/// a sensor may sit on any channel of a [`ChannelMap`](crate::ChannelMap).
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{MuxInput, SensorRegistry, VoltageSensor};
+///
+/// let sensors = SensorRegistry::new()
+///     .sensor(3, VoltageSensor::new(MuxInput::Ain(6), MuxInput::Ain(7)));
+///
+/// let value = sensors.read(&mut adc, 3)?;
+/// println!("channel 3: {:.6} {}", value, sensors.unit(3).unwrap_or("?"));
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct SensorRegistry {
+    sensors: Vec<Option<Box<dyn Sensor>>>,
+}
+
+impl SensorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a sensor to a channel, replacing any previous one
+    pub fn sensor(mut self, channel: u8, sensor: impl Sensor + 'static) -> Self {
+        self.insert(channel, Box::new(sensor));
+        self
+    }
+
+    /// Bind a sensor to a channel, replacing any previous one
+    pub fn insert(&mut self, channel: u8, sensor: Box<dyn Sensor>) {
+        let index = usize::from(channel);
+        if self.sensors.len() <= index {
+            self.sensors.resize_with(index + 1, || None);
+        }
+        self.sensors[index] = Some(sensor);
+    }
+
+    /// Remove the sensor of a channel
+    pub fn remove(&mut self, channel: u8) -> Option<Box<dyn Sensor>> {
+        self.sensors.get_mut(usize::from(channel))?.take()
+    }
+
+    /// Sensor of a channel, if bound
+    pub fn get(&self, channel: u8) -> Option<&dyn Sensor> {
+        self.sensors.get(usize::from(channel))?.as_deref()
+    }
+
+    /// Bound channels in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &dyn Sensor)> {
+        self.sensors
+            .iter()
+            .enumerate()
+            .filter_map(|(i, sensor)| Some((i as u8, sensor.as_deref()?)))
+    }
+
+    /// Unit of a channel's sensor
+    pub fn unit(&self, channel: u8) -> Option<&str> {
+        self.get(channel).map(Sensor::unit)
+    }
+
+    /// Read the sensor of a channel
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::NoSensor` if no sensor is bound to the
+    /// channel, or the error of the sensor's read
+    pub fn read(&self, adc: &mut Ads1263, channel: u8) -> Result<f64> {
+        self.get(channel)
+            .ok_or(Ads1263Error::NoSensor(channel))?
+            .read(adc)
+    }
+
+    /// Read every bound sensor in channel order
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first sensor that fails
+    pub fn read_all(&self, adc: &mut Ads1263) -> Result<Vec<(u8, f64)>> {
+        self.iter()
+            .map(|(channel, sensor)| Ok((channel, sensor.read(adc)?)))
+            .collect()
+    }
+}
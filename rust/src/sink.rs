@@ -0,0 +1,54 @@
+//! Destinations for captured samples
+//!
+//! A [`Sink`] consumes scans of [`Sample`]s, e.g. by writing them to a
+//! file. Hand one to [`Ads1263::start_acquisition_sink`](crate::Ads1263::start_acquisition_sink)
+//! and the sampler thread writes every scan itself, so the capture does
+//! not depend on a consumer thread keeping up.
+
+use crate::error::Result;
+use crate::sample::Sample;
+
+/// Destination for samples
+pub trait Sink: Send {
+    /// Write one scan (or any run) of samples
+    fn write(&mut self, samples: &[Sample]) -> Result<()>;
+
+    /// Push buffered data to the underlying storage
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: Sink + ?Sized> Sink for Box<S> {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        (**self).write(samples)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+/// Write every scan to both sinks, e.g. a file and a live view
+impl<A: Sink, B: Sink> Sink for (A, B) {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.0.write(samples)?;
+        self.1.write(samples)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.0.flush()?;
+        self.1.flush()
+    }
+}
+
+/// Write every scan to each sink in turn, e.g. the sinks of a configuration file
+impl<S: Sink> Sink for Vec<S> {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.iter_mut().try_for_each(|sink| sink.write(samples))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.iter_mut().try_for_each(Sink::flush)
+    }
+}
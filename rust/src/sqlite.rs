@@ -0,0 +1,262 @@
+//! SQLite logging
+//!
+//! [`SqliteSink`] keeps a queryable history in a single database file.
+//! Every sink opened on a database starts a new session:
+//!
+//! ```text
+//! sessions (id, started, chip_id, driver_version, input_mode,
+//!           gain, data_rate, filter, delay, reference_source, reference)
+//! channels (session, channel, name, unit, scale, offset, curve)
+//! samples  (session, time, channel, sequence, raw, value)
+//! ```
+//!
+//! `started` and `time` are Unix seconds (UTC), `value` is converted by
+//! the session's [`ChannelMap`]; a channel's row is added with its first
+//! sample. Samples are inserted in batches, one transaction per batch.
+//!
+//! ```sql
+//! SELECT datetime(time, 'unixepoch'), value FROM samples
+//! JOIN channels USING (session, channel)
+//! WHERE name = 'inlet' ORDER BY time DESC LIMIT 10;
+//! ```
+
+use crate::capture::CaptureHeader;
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use rusqlite::{Connection, params};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of samples per transaction
+const BATCH_ROWS: usize = 1000;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id INTEGER PRIMARY KEY,
+    started REAL NOT NULL,
+    chip_id INTEGER NOT NULL,
+    driver_version TEXT NOT NULL,
+    input_mode TEXT NOT NULL,
+    gain TEXT NOT NULL,
+    data_rate REAL NOT NULL,
+    filter TEXT NOT NULL,
+    delay TEXT NOT NULL,
+    reference_source TEXT NOT NULL,
+    reference REAL NOT NULL
+);
+CREATE TABLE IF NOT EXISTS channels (
+    session INTEGER NOT NULL REFERENCES sessions (id),
+    channel INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    unit TEXT NOT NULL,
+    scale REAL NOT NULL,
+    offset REAL NOT NULL,
+    curve TEXT,
+    PRIMARY KEY (session, channel)
+);
+CREATE TABLE IF NOT EXISTS samples (
+    session INTEGER NOT NULL REFERENCES sessions (id),
+    time REAL NOT NULL,
+    channel INTEGER NOT NULL,
+    sequence INTEGER NOT NULL,
+    raw INTEGER NOT NULL,
+    value REAL NOT NULL
+);
+CREATE INDEX IF NOT EXISTS samples_by_time ON samples (session, channel, time);
+";
+
+/// Sink logging samples into a SQLite database
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, CaptureHeader, ChannelInfo, ChannelMap, DataRate, Hal, SqliteSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps10)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let header = CaptureHeader::from_device(&mut adc, map)?;
+/// let sink = SqliteSink::open("history.db", &header)?.batch_rows(100);
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct SqliteSink {
+    connection: Connection,
+    session: i64,
+    map: ChannelMap,
+    clock: WallClock,
+    batch_rows: usize,
+    pending: Vec<Sample>,
+    /// Channels with a row in `channels`
+    recorded: HashSet<u8>,
+}
+
+impl SqliteSink {
+    /// Open (or create) the database at `path` and start a session
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Sqlite` if the database cannot be opened or
+    /// the session recorded
+    pub fn open(path: impl AsRef<Path>, header: &CaptureHeader) -> Result<Self> {
+        Self::new(Connection::open(path)?, header)
+    }
+
+    /// Start a session on an open connection
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Sqlite` if the schema cannot be created or
+    /// the session recorded
+    pub fn new(connection: Connection, header: &CaptureHeader) -> Result<Self> {
+        connection.execute_batch(SCHEMA)?;
+        let session = record_session(&connection, header)?;
+        log::info!("SQLite session {} started", session);
+        Ok(Self {
+            connection,
+            session,
+            map: header.map.clone(),
+            clock: WallClock::new(),
+            batch_rows: BATCH_ROWS,
+            pending: Vec::new(),
+            recorded: HashSet::new(),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Set the number of samples per transaction (default 1000)
+    pub fn batch_rows(mut self, rows: usize) -> Self {
+        self.batch_rows = rows.max(1);
+        self
+    }
+
+    /// Id of this sink's session
+    pub fn session(&self) -> i64 {
+        self.session
+    }
+
+    /// The database connection, e.g. for queries
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Insert the pending samples in one transaction
+    fn write_batch(&mut self) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        self.clock.update();
+        let tx = self.connection.transaction()?;
+        for sample in &self.pending {
+            if self.recorded.insert(sample.channel) {
+                record_channel(&tx, self.session, &self.map, sample.channel)?;
+            }
+        }
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT INTO samples (session, time, channel, sequence, raw, value)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?;
+            for sample in &self.pending {
+                insert.execute(params![
+                    self.session,
+                    unix_seconds(self.clock.to_utc(sample.timestamp)),
+                    sample.channel,
+                    sample.sequence as i64,
+                    sample.raw,
+                    self.map.convert_sample(sample),
+                ])?;
+            }
+        }
+        tx.commit()?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl Sink for SqliteSink {
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.pending.extend_from_slice(samples);
+        if self.pending.len() >= self.batch_rows {
+            self.write_batch()?;
+        }
+        Ok(())
+    }
+
+    /// Commit the pending samples
+    fn flush(&mut self) -> Result<()> {
+        self.write_batch()
+    }
+}
+
+impl Drop for SqliteSink {
+    fn drop(&mut self) {
+        if let Err(e) = self.write_batch() {
+            log::warn!("Failed to commit last SQLite batch: {}", e);
+        }
+    }
+}
+
+/// Insert the session, returning its id
+fn record_session(connection: &Connection, header: &CaptureHeader) -> Result<i64> {
+    let adc1 = &header.adc1;
+    connection.execute(
+        "INSERT INTO sessions (started, chip_id, driver_version, input_mode, gain,
+                               data_rate, filter, delay, reference_source, reference)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            unix_seconds(SystemTime::now()),
+            header.chip_id,
+            header.driver_version,
+            format!("{:?}", header.input_mode),
+            format!("{:?}", adc1.gain),
+            adc1.data_rate.sps(),
+            format!("{:?}", adc1.filter),
+            format!("{:?}", adc1.delay),
+            format!("{:?}", adc1.reference),
+            header.map.reference(),
+        ],
+    )?;
+    Ok(connection.last_insert_rowid())
+}
+
+/// Insert the name, unit, and scaling of a channel
+fn record_channel(
+    connection: &Connection,
+    session: i64,
+    map: &ChannelMap,
+    channel: u8,
+) -> Result<()> {
+    let info = map.get(channel);
+    connection.execute(
+        "INSERT OR IGNORE INTO channels (session, channel, name, unit, scale, offset, curve)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            session,
+            channel,
+            map.name(channel),
+            map.unit(channel),
+            info.map_or(1.0, |i| i.scale),
+            info.map_or(0.0, |i| i.offset),
+            info.and_then(|i| i.curve.as_ref()).map(ToString::to_string),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Seconds since the Unix epoch
+fn unix_seconds(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
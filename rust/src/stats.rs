@@ -0,0 +1,331 @@
+//! Windowed statistics
+//!
+//! [`StatsAccumulator`] collects mean, standard deviation, min/max,
+//! peak-to-peak, and RMS in a single pass (Welford's method, so long
+//! windows don't lose precision). [`WindowStats`] applies it per channel
+//! over sample-count or time windows as a [`Pipeline`](crate::Pipeline)
+//! stage, and [`Ads1263::measure_stats`](crate::Ads1263::measure_stats)
+//! measures one channel for a fixed time.
+
+use crate::pipeline::{PerChannel, Reading, Stage};
+use std::time::{Duration, Instant};
+
+/// Summary of a window of values
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    /// Number of values
+    pub count: u64,
+    /// Arithmetic mean
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Root mean square
+    pub rms: f64,
+}
+
+impl Stats {
+    /// Difference between the largest and smallest value
+    pub fn peak_to_peak(&self) -> f64 {
+        self.max - self.min
+    }
+
+    /// Value of one statistic
+    pub fn get(&self, statistic: Statistic) -> f64 {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::StdDev => self.std_dev,
+            Statistic::Min => self.min,
+            Statistic::Max => self.max,
+            Statistic::PeakToPeak => self.peak_to_peak(),
+            Statistic::Rms => self.rms,
+        }
+    }
+}
+
+/// Selects one value of [`Stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Statistic {
+    /// Arithmetic mean (default)
+    #[default]
+    Mean,
+    /// Population standard deviation
+    StdDev,
+    /// Smallest value
+    Min,
+    /// Largest value
+    Max,
+    /// Largest minus smallest value
+    PeakToPeak,
+    /// Root mean square
+    Rms,
+}
+
+/// Single-pass accumulator for [`Stats`]
+///
+/// Non-finite values are ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct StatsAccumulator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    sum_sq: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for StatsAccumulator {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            sum_sq: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl StatsAccumulator {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value
+    pub fn push(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.sum_sq += value * value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Number of values added
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Statistics of the values added so far (all zero if none)
+    pub fn stats(&self) -> Stats {
+        if self.count == 0 {
+            return Stats::default();
+        }
+        let n = self.count as f64;
+        Stats {
+            count: self.count,
+            mean: self.mean,
+            std_dev: (self.m2 / n).sqrt(),
+            min: self.min,
+            max: self.max,
+            rms: (self.sum_sq / n).sqrt(),
+        }
+    }
+
+    /// Forget all values
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Extend<f64> for StatsAccumulator {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl FromIterator<f64> for StatsAccumulator {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut acc = Self::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+/// Length of a statistics window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsWindow {
+    /// A fixed number of readings per channel
+    Samples(u64),
+    /// A fixed time span, measured from the window's first reading
+    Duration(Duration),
+}
+
+/// Callback invoked with the channel and statistics of each completed window
+type StatsCallback = Box<dyn FnMut(u8, &Stats) + Send>;
+
+#[derive(Debug, Clone, Copy)]
+struct WindowState {
+    acc: StatsAccumulator,
+    started: Instant,
+}
+
+/// Pipeline stage computing statistics over consecutive windows
+///
+/// Emits one reading per completed window of each channel, holding the
+/// selected [`Statistic`] (the mean by default), timestamped with the
+/// window's last reading. Register [`on_window`](Self::on_window) to
+/// receive the full [`Stats`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Pipeline, Statistic, StatsWindow, WindowStats};
+/// use std::time::Duration;
+///
+/// let pipeline = Pipeline::new().stage(
+///     WindowStats::new(StatsWindow::Duration(Duration::from_secs(1)))
+///         .emit(Statistic::StdDev)
+///         .on_window(|channel, stats| {
+///             println!("ch{}: mean {:.1}, p-p {:.1}", channel, stats.mean, stats.peak_to_peak())
+///         }),
+/// );
+/// ```
+pub struct WindowStats {
+    window: StatsWindow,
+    emit: Statistic,
+    channels: PerChannel<WindowState>,
+    callbacks: Vec<StatsCallback>,
+}
+
+impl WindowStats {
+    /// Statistics over consecutive windows of the given length
+    pub fn new(window: StatsWindow) -> Self {
+        Self {
+            window,
+            emit: Statistic::default(),
+            channels: PerChannel::default(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Select the statistic emitted as reading value
+    pub fn emit(mut self, statistic: Statistic) -> Self {
+        self.emit = statistic;
+        self
+    }
+
+    /// Register a callback for every completed window
+    pub fn on_window(mut self, callback: impl FnMut(u8, &Stats) + Send + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+}
+
+impl Stage for WindowStats {
+    fn process(&mut self, input: Reading, out: &mut Vec<Reading>) {
+        let state = self
+            .channels
+            .get_or_insert_with(input.channel, || WindowState {
+                acc: StatsAccumulator::new(),
+                started: input.timestamp,
+            });
+        if state.acc.count() == 0 {
+            state.started = input.timestamp;
+        }
+        state.acc.push(input.value);
+
+        let complete = match self.window {
+            StatsWindow::Samples(n) => state.acc.count() >= n.max(1),
+            StatsWindow::Duration(d) => {
+                input.timestamp.saturating_duration_since(state.started) >= d
+            }
+        };
+        if !complete || state.acc.count() == 0 {
+            return;
+        }
+
+        let stats = state.acc.stats();
+        state.acc.reset();
+        for callback in &mut self.callbacks {
+            callback(input.channel, &stats);
+        }
+        out.push(Reading {
+            value: stats.get(self.emit),
+            ..input
+        });
+    }
+
+    fn reset(&mut self) {
+        self.channels.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welford_matches_direct_computation() {
+        // Large offset, small spread: a naive sum of squares loses the variance
+        let values: Vec<f64> = (0..1000).map(|n| 1.0e9 + f64::from(n % 7) - 3.0).collect();
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        let stats = values.iter().copied().collect::<StatsAccumulator>().stats();
+        assert_eq!(stats.count, 1000);
+        assert!((stats.mean - mean).abs() < 1e-6);
+        assert!((stats.std_dev - variance.sqrt()).abs() < 1e-6);
+        assert_eq!((stats.min, stats.max), (1.0e9 - 3.0, 1.0e9 + 3.0));
+    }
+
+    #[test]
+    fn accumulator_skips_non_finite_values() {
+        let stats = [1.0, f64::NAN, 3.0, f64::INFINITY]
+            .into_iter()
+            .collect::<StatsAccumulator>()
+            .stats();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.std_dev, 1.0);
+        assert_eq!(stats.rms, 5.0f64.sqrt());
+    }
+
+    #[test]
+    fn sample_windows_close_after_n_readings() {
+        let mut stage = WindowStats::new(StatsWindow::Samples(3)).emit(Statistic::Max);
+        let timestamp = Instant::now();
+        let mut out = Vec::new();
+        for value in [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0] {
+            let reading = Reading {
+                channel: 0,
+                timestamp,
+                value,
+            };
+            stage.process(reading, &mut out);
+        }
+        let maxima: Vec<f64> = out.iter().map(|r| r.value).collect();
+        assert_eq!(maxima, [3.0, 6.0]);
+    }
+
+    #[test]
+    fn duration_windows_close_at_the_first_late_reading() {
+        let mut stage = WindowStats::new(StatsWindow::Duration(Duration::from_millis(10)))
+            .emit(Statistic::PeakToPeak);
+        let start = Instant::now();
+        let mut out = Vec::new();
+        for ms in 0..25u32 {
+            let reading = Reading {
+                channel: 0,
+                timestamp: start + Duration::from_millis(u64::from(ms)),
+                value: f64::from(ms),
+            };
+            stage.process(reading, &mut out);
+        }
+        // 0..=10, then 11..=21
+        let spans: Vec<f64> = out.iter().map(|r| r.value).collect();
+        assert_eq!(spans, [10.0, 10.0]);
+    }
+}
@@ -0,0 +1,126 @@
+//! `futures::Stream` of samples
+//!
+//! [`SampleStream`] delivers conversions from an [`Ads1263Async`] as a
+//! [`Stream`], so they can be piped into async sinks with the usual
+//! combinators (`StreamExt::map`, `forward`, ...). A background task awaits
+//! each conversion through the DRDY interrupt and feeds a bounded channel;
+//! when the consumer falls behind the task waits for room.
+//!
+//! Requires the `stream` feature (implies `tokio`).
+
+use crate::asynch::Ads1263Async;
+use crate::error::Result;
+use crate::sample::Sample;
+use futures_core::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Stream of samples from a background scan task
+///
+/// Created by [`Ads1263Async::sample_stream`]. Ends after yielding the
+/// first error; dropping it stops the task.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{Ads1263, Ads1263Async, DataRate, Hal};
+/// use futures_core::Stream;
+///
+/// async fn run() -> ads1263::Result<()> {
+///     let adc = Ads1263Async::new(Ads1263::new(Hal::new()?))?;
+///     adc.init_adc1(DataRate::Sps1200).await?;
+///
+///     let mut samples = std::pin::pin!(adc.sample_stream(vec![0], 64));
+///     while let Some(sample) =
+///         std::future::poll_fn(|cx| samples.as_mut().poll_next(cx)).await
+///     {
+///         println!("{:?}", sample?);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct SampleStream {
+    receiver: mpsc::Receiver<Result<Sample>>,
+}
+
+impl Stream for SampleStream {
+    type Item = Result<Sample>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+impl Ads1263Async {
+    /// Stream samples of ADC1 channels from a background task
+    ///
+    /// Channels are read in turn, one sample each. With a single channel the
+    /// multiplexer is set once and conversions are read back-to-back, with
+    /// missed conversions reported in [`Sample::dropped_since_last`]; reads
+    /// on other clones wait until such a stream is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Channel numbers to scan
+    /// * `capacity` - Number of samples buffered before the task applies backpressure
+    ///
+    /// Without channels the stream ends right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside a tokio runtime or if `capacity` is 0
+    pub fn sample_stream(&self, channels: Vec<u8>, capacity: usize) -> SampleStream {
+        let (tx, rx) = mpsc::channel(capacity);
+        if channels.is_empty() {
+            return SampleStream { receiver: rx };
+        }
+        let adc = self.clone();
+
+        tokio::spawn(async move {
+            let result = match *channels {
+                [channel] => stream_continuous(&adc, channel, &tx).await,
+                _ => stream_scans(&adc, &channels, &tx).await,
+            };
+            if let Err(e) = result {
+                let _ = tx.send(Err(e)).await;
+            }
+            log::debug!("Sample stream stopped");
+        });
+
+        SampleStream { receiver: rx }
+    }
+}
+
+/// Read one channel back-to-back until a read fails or the stream is dropped
+async fn stream_continuous(
+    adc: &Ads1263Async,
+    channel: u8,
+    tx: &mpsc::Sender<Result<Sample>>,
+) -> Result<()> {
+    // Other clones wait until the stream ends instead of moving the input
+    let _session = adc.session().await;
+    adc.run(move |adc| adc.select_channel(channel)).await?;
+    loop {
+        let sample = adc.read_next_sample(channel).await?;
+        if tx.send(Ok(sample)).await.is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// Scan channels in turn until a read fails or the stream is dropped
+async fn stream_scans(
+    adc: &Ads1263Async,
+    channels: &[u8],
+    tx: &mpsc::Sender<Result<Sample>>,
+) -> Result<()> {
+    loop {
+        for &channel in channels {
+            let sample = adc.read_sample(channel).await?;
+            if tx.send(Ok(sample)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Synchronized conversions on several boards sharing one START line
+//!
+//! Route a single GPIO to the START input of every ADS1263 and let
+//! [`SyncGroup`] own it. Each trigger stops all boards, selects the
+//! requested inputs, and raises START once, so every board begins its
+//! conversion on the same edge. The boards' own [`PinConfig`] must not name
+//! the shared START pin.
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::hal::{Hal, PinConfig, SpiConfig};
+use crate::registers::{DataRate, InputMode};
+use rppal::gpio::{Gpio, OutputPin};
+use std::time::Instant;
+
+/// One synchronized conversion across all boards of a [`SyncGroup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSample {
+    /// Instant START was raised on all boards
+    pub timestamp: Instant,
+    /// Raw 32-bit value per board, in group order
+    pub values: Vec<u32>,
+}
+
+/// Several ADS1263 devices triggered by a shared START line
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{DataRate, PinConfig, SyncGroup};
+///
+/// let mut group = SyncGroup::with_pins(
+///     &[
+///         PinConfig::default(),
+///         PinConfig { rst: 23, cs: 24, drdy: 25, ..PinConfig::default() },
+///     ],
+///     27, // START of both boards
+/// )?;
+/// group.init_adc1(DataRate::Sps1200)?;
+///
+/// // Channel 0 on both boards, converted on the same START edge
+/// let sample = group.trigger(0)?;
+/// println!("{:?}: {:?}", sample.timestamp, sample.values);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct SyncGroup {
+    devices: Vec<Ads1263>,
+    start_pin: OutputPin,
+}
+
+impl SyncGroup {
+    /// Create a group from already constructed drivers
+    ///
+    /// # Arguments
+    ///
+    /// * `devices` - Drivers whose START inputs are wired together
+    /// * `start` - BCM number of the shared START pin
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the START pin cannot be claimed
+    pub fn new(devices: Vec<Ads1263>, start: u8) -> Result<Self> {
+        // START low; conversions only begin on a trigger
+        let start_pin = Gpio::new()?.get(start)?.into_output_low();
+        log::info!(
+            "Sync group created with {} devices, START: BCM{}",
+            devices.len(),
+            start
+        );
+        Ok(Self { devices, start_pin })
+    }
+
+    /// Create a group with one device per pin configuration on the default SPI bus
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if GPIO or SPI initialization fails for any device
+    pub fn with_pins(pins: &[PinConfig], start: u8) -> Result<Self> {
+        Self::with_config(pins, SpiConfig::default(), start)
+    }
+
+    /// Create a group with one device per pin configuration on a custom SPI bus
+    ///
+    /// All devices share a single SPI device handle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if GPIO or SPI initialization fails for any device
+    pub fn with_config(pins: &[PinConfig], spi_config: SpiConfig, start: u8) -> Result<Self> {
+        let spi = Hal::open_shared_spi(spi_config)?;
+        let devices = pins
+            .iter()
+            .map(|&pin_config| {
+                Hal::with_shared_spi(pin_config, spi_config, spi.clone()).map(Ads1263::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(devices, start)
+    }
+
+    /// Number of devices in the group
+    pub fn len(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// true if the group has no devices
+    pub fn is_empty(&self) -> bool {
+        self.devices.is_empty()
+    }
+
+    /// Get a device by index
+    pub fn device(&mut self, index: usize) -> Option<&mut Ads1263> {
+        self.devices.get_mut(index)
+    }
+
+    /// Release the individual drivers
+    pub fn into_devices(self) -> Vec<Ads1263> {
+        self.devices
+    }
+
+    /// Set the input mode on every device
+    pub fn set_mode(&mut self, mode: InputMode) {
+        for adc in &mut self.devices {
+            adc.set_mode(mode);
+        }
+    }
+
+    /// Initialize ADC1 on every device and leave it stopped
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first device that fails to initialize
+    pub fn init_adc1(&mut self, rate: DataRate) -> Result<()> {
+        self.start_pin.set_low();
+        for (i, adc) in self.devices.iter_mut().enumerate() {
+            log::info!("Initializing sync device {}", i);
+            adc.init_adc1(rate)?;
+            // A START1 command keeps converting even with START low
+            adc.stop_adc1()?;
+        }
+        Ok(())
+    }
+
+    /// Convert the same channel on every device on one START edge
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - Channel number (0-10 for single-ended, 0-4 for differential)
+    pub fn trigger(&mut self, channel: u8) -> Result<SyncSample> {
+        let mut values = vec![0u32; self.devices.len()];
+        let timestamp = self.trigger_with(|_| channel, &mut values)?;
+        Ok(SyncSample { timestamp, values })
+    }
+
+    /// Convert one channel per device on one START edge
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - Channel for each device, in group order
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::ChannelCountMismatch` unless there is exactly
+    /// one channel per device
+    pub fn trigger_each(&mut self, channels: &[u8]) -> Result<SyncSample> {
+        if channels.len() != self.devices.len() {
+            return Err(Ads1263Error::ChannelCountMismatch {
+                expected: self.devices.len(),
+                got: channels.len(),
+            });
+        }
+        let mut values = vec![0u32; self.devices.len()];
+        let timestamp = self.trigger_with(|i| channels[i], &mut values)?;
+        Ok(SyncSample { timestamp, values })
+    }
+
+    /// Run one synchronized conversion and return the trigger instant
+    fn trigger_with(
+        &mut self,
+        channel: impl Fn(usize) -> u8,
+        values: &mut [u32],
+    ) -> Result<Instant> {
+        self.start_pin.set_low();
+        for (i, adc) in self.devices.iter_mut().enumerate() {
+            adc.select_channel(channel(i))?;
+        }
+
+        let timestamp = Instant::now();
+        self.start_pin.set_high();
+
+        let result = self
+            .devices
+            .iter_mut()
+            .zip(values.iter_mut())
+            .try_for_each(|(adc, value)| {
+                adc.wait_conversion()?;
+                *value = adc.read_adc1_data()?;
+                Ok(())
+            });
+        self.start_pin.set_low();
+        result.map(|()| timestamp)
+    }
+}
@@ -0,0 +1,219 @@
+//! systemd service integration
+//!
+//! [`SystemdNotifier`] implements the `sd_notify` protocol: it reports
+//! readiness, reloads, and status text, and pings the service watchdog.
+//! [`listen_fds`] takes over the sockets systemd passed to a
+//! socket-activated service, so [`UnixSocketSink::from_listener`](crate::UnixSocketSink::from_listener)
+//! and `WebSocketSink::from_listener` (`websocket` feature) can serve on
+//! them. Both talk to systemd through environment variables and a datagram
+//! socket, without linking libsystemd, and do nothing outside a service.
+//!
+//! A unit running `ads1263 daemon`:
+//!
+//! ```text
+//! [Service]
+//! Type=notify
+//! ExecStart=/usr/local/bin/ads1263 --config /etc/ads1263.toml daemon
+//! ExecReload=/bin/kill -HUP $MAINPID
+//! WatchdogSec=10
+//! ```
+
+use crate::error::Result;
+use std::net::TcpListener;
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram, UnixListener};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// First file descriptor passed by socket activation
+const LISTEN_FDS_START: i32 = 3;
+
+/// Set once the activated sockets have been taken
+static LISTEN_FDS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Sender of service notifications to systemd
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::SystemdNotifier;
+///
+/// if let Some(notifier) = SystemdNotifier::from_env() {
+///     notifier.ready()?;
+///     notifier.status("Acquiring 4 channels")?;
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SystemdNotifier {
+    socket: UnixDatagram,
+    addr: SocketAddr,
+    watchdog: Option<Duration>,
+}
+
+impl SystemdNotifier {
+    /// Notifier for the socket in `NOTIFY_SOCKET`, `None` outside a
+    /// `Type=notify` service
+    ///
+    /// Reads the watchdog interval from `WATCHDOG_USEC` if `WATCHDOG_PID`
+    /// is unset or names this process.
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os("NOTIFY_SOCKET")?;
+        let bytes = path.as_bytes();
+        // A leading '@' names a socket in the abstract namespace
+        let addr = match bytes.strip_prefix(b"@") {
+            Some(name) => SocketAddr::from_abstract_name(name),
+            None => SocketAddr::from_pathname(&path),
+        };
+        let result = addr.and_then(|addr| Ok((UnixDatagram::unbound()?, addr)));
+        let (socket, addr) = match result {
+            Ok(pair) => pair,
+            Err(e) => {
+                log::warn!("Ignoring NOTIFY_SOCKET {:?}: {}", path, e);
+                return None;
+            }
+        };
+        Some(Self {
+            socket,
+            addr,
+            watchdog: watchdog_from_env(),
+        })
+    }
+
+    /// Send newline-separated `KEY=VALUE` assignments
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the datagram cannot be sent
+    pub fn notify(&self, state: &str) -> Result<()> {
+        self.socket.send_to_addr(state.as_bytes(), &self.addr)?;
+        Ok(())
+    }
+
+    /// Report that startup, or a reload, has finished
+    pub fn ready(&self) -> Result<()> {
+        self.notify("READY=1")
+    }
+
+    /// Report that the service is reloading its configuration
+    ///
+    /// Follow with [`ready`](Self::ready) once the new configuration is in
+    /// effect.
+    pub fn reloading(&self) -> Result<()> {
+        self.notify("RELOADING=1")
+    }
+
+    /// Report that the service is shutting down
+    pub fn stopping(&self) -> Result<()> {
+        self.notify("STOPPING=1")
+    }
+
+    /// Set the status line shown by `systemctl status`
+    pub fn status(&self, status: &str) -> Result<()> {
+        // A newline would start another assignment
+        self.notify(&format!("STATUS={}", status.replace('\n', " ")))
+    }
+
+    /// Ping the service watchdog
+    pub fn watchdog(&self) -> Result<()> {
+        self.notify("WATCHDOG=1")
+    }
+
+    /// `WatchdogSec=` of the unit, `None` if the watchdog is off
+    ///
+    /// Ping at about half this interval.
+    pub fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog
+    }
+}
+
+/// Watchdog interval from `WATCHDOG_USEC`, if meant for this process
+fn watchdog_from_env() -> Option<Duration> {
+    if !meant_for_us("WATCHDOG_PID") {
+        return None;
+    }
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+/// Whether the PID in `variable` is unset or this process
+fn meant_for_us(variable: &str) -> bool {
+    match std::env::var(variable) {
+        Ok(pid) => pid.parse() == Ok(std::process::id()),
+        Err(_) => true,
+    }
+}
+
+/// Socket passed by systemd socket activation
+///
+/// Sorted by address family only, so a `ListenDatagram=` socket shows up
+/// as `Unix` or `Tcp` too.
+#[derive(Debug)]
+pub enum ActivatedSocket {
+    /// Unix domain socket, e.g. `ListenStream=/run/ads1263.sock`
+    Unix(UnixListener),
+    /// IP socket, e.g. `ListenStream=8080`
+    Tcp(TcpListener),
+    /// Any other descriptor, e.g. a FIFO
+    Other(OwnedFd),
+}
+
+/// Take the sockets passed by socket activation, in `LISTEN_FDS` order
+///
+/// Each comes with its `FileDescriptorName=` from `LISTEN_FDNAMES`, or an
+/// empty name. Returns nothing unless `LISTEN_PID` names this process, and
+/// nothing on every call after the first, so a descriptor is never owned
+/// twice.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{ActivatedSocket, ChannelMap, UnixSocketSink, listen_fds};
+///
+/// for (_name, socket) in listen_fds() {
+///     if let ActivatedSocket::Unix(listener) = socket {
+///         let sink = UnixSocketSink::from_listener(listener, ChannelMap::new(5.0))?;
+///     }
+/// }
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub fn listen_fds() -> Vec<(String, ActivatedSocket)> {
+    if std::env::var_os("LISTEN_PID").is_none() || !meant_for_us("LISTEN_PID") {
+        return Vec::new();
+    }
+    let Some(count) = std::env::var("LISTEN_FDS")
+        .ok()
+        .and_then(|count| count.parse::<i32>().ok())
+    else {
+        return Vec::new();
+    };
+    if LISTEN_FDS_TAKEN.swap(true, Ordering::SeqCst) {
+        return Vec::new();
+    }
+    let names = std::env::var("LISTEN_FDNAMES").unwrap_or_default();
+    let mut names = names.split(':').map(str::to_string);
+
+    (LISTEN_FDS_START..LISTEN_FDS_START + count.max(0))
+        .map(|fd| {
+            // Systemd hands these descriptors to the process, and the flag
+            // above makes sure they are taken only once
+            let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+            (names.next().unwrap_or_default(), classify(fd))
+        })
+        .collect()
+}
+
+/// Sort a descriptor by the address family its local address has
+fn classify(fd: OwnedFd) -> ActivatedSocket {
+    let unix = UnixListener::from(fd);
+    if unix.local_addr().is_ok() {
+        return ActivatedSocket::Unix(unix);
+    }
+    let tcp = TcpListener::from(OwnedFd::from(unix));
+    if tcp.local_addr().is_ok() {
+        return ActivatedSocket::Tcp(tcp);
+    }
+    ActivatedSocket::Other(tcp.into())
+}
@@ -0,0 +1,275 @@
+//! NTC thermistors
+//!
+//! [`ThermistorModel`] converts between resistance and temperature with
+//! either the beta equation of a data sheet or the three Steinhart–Hart
+//! coefficients of a calibrated part. [`Thermistor`] measures one in a
+//! voltage divider with a fixed resistor and reports its temperature, so
+//! an inexpensive NTC can sit in the same [`SensorRegistry`] as RTDs and
+//! thermocouples.
+//!
+//! [`SensorRegistry`]: crate::SensorRegistry
+
+use crate::ads1263::Ads1263;
+use crate::error::{Ads1263Error, Result};
+use crate::registers::MuxInput;
+use crate::sensors::{Sensor, SensorSetup};
+
+/// Offset between °C and K
+const KELVIN: f64 = 273.15;
+
+/// Resistance-temperature relation of an NTC thermistor
+///
+/// # Example
+///
+/// ```
+/// use ads1263::ThermistorModel;
+///
+/// // 10 kΩ at 25 °C, B25/85 = 3950 K
+/// let ntc = ThermistorModel::beta(10_000.0, 3950.0);
+/// assert!((ntc.celsius(10_000.0) - 25.0).abs() < 1e-9);
+///
+/// // Fitted from three calibration points (°C, Ω)
+/// let fitted =
+///     ThermistorModel::from_points([(0.0, 32_650.0), (25.0, 10_000.0), (50.0, 3_603.0)])?;
+/// assert!((fitted.celsius(10_000.0) - 25.0).abs() < 1e-6);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThermistorModel {
+    /// 1/T = 1/T0 + ln(R/R0)/β
+    Beta {
+        /// Resistance at `t0` in ohms
+        r0: f64,
+        /// Temperature of `r0` in °C, usually 25
+        t0: f64,
+        /// β in kelvins
+        beta: f64,
+    },
+    /// 1/T = A + B·ln(R) + C·ln(R)³, with T in kelvins and R in ohms
+    SteinhartHart {
+        /// Coefficient A
+        a: f64,
+        /// Coefficient B
+        b: f64,
+        /// Coefficient C
+        c: f64,
+    },
+}
+
+impl ThermistorModel {
+    /// Beta model with `r25` ohms at 25 °C
+    pub fn beta(r25: f64, beta: f64) -> Self {
+        ThermistorModel::Beta {
+            r0: r25,
+            t0: 25.0,
+            beta,
+        }
+    }
+
+    /// Steinhart–Hart model from its coefficients
+    pub fn steinhart_hart(a: f64, b: f64, c: f64) -> Self {
+        ThermistorModel::SteinhartHart { a, b, c }
+    }
+
+    /// Steinhart–Hart model through three (°C, Ω) points
+    ///
+    /// Points spread over the range of interest, e.g. its ends and middle,
+    /// give the best fit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if a resistance is not
+    /// positive or two points share a resistance
+    pub fn from_points(points: [(f64, f64); 3]) -> Result<Self> {
+        if points
+            .iter()
+            .any(|&(_, ohms)| !(ohms.is_finite() && ohms > 0.0))
+        {
+            return Err(Ads1263Error::InvalidConfig(
+                "thermistor points: resistances must be positive".to_string(),
+            ));
+        }
+        let [(l1, y1), (l2, y2), (l3, y3)] =
+            points.map(|(celsius, ohms)| (ohms.ln(), 1.0 / (celsius + KELVIN)));
+        let g2 = (y2 - y1) / (l2 - l1);
+        let g3 = (y3 - y1) / (l3 - l1);
+        let c = (g3 - g2) / (l3 - l2) / (l1 + l2 + l3);
+        let b = g2 - c * (l1 * l1 + l1 * l2 + l2 * l2);
+        let a = y1 - (b + l1 * l1 * c) * l1;
+        if ![a, b, c].iter().all(|x| x.is_finite()) {
+            return Err(Ads1263Error::InvalidConfig(
+                "thermistor points: resistances must differ".to_string(),
+            ));
+        }
+        Ok(Self::steinhart_hart(a, b, c))
+    }
+
+    /// Temperature in °C at `ohms`
+    pub fn celsius(&self, ohms: f64) -> f64 {
+        let ln = ohms.ln();
+        let inverse = match *self {
+            ThermistorModel::Beta { r0, t0, beta } => 1.0 / (t0 + KELVIN) + (ln - r0.ln()) / beta,
+            ThermistorModel::SteinhartHart { a, b, c } => a + b * ln + c * ln * ln * ln,
+        };
+        1.0 / inverse - KELVIN
+    }
+
+    /// Resistance in ohms at `celsius`
+    pub fn resistance(&self, celsius: f64) -> f64 {
+        let inverse = 1.0 / (celsius + KELVIN);
+        match *self {
+            ThermistorModel::Beta { r0, t0, beta } => {
+                r0 * (beta * (inverse - 1.0 / (t0 + KELVIN))).exp()
+            }
+            ThermistorModel::SteinhartHart { a, b, c } => {
+                // Cardano's solution of C·x³ + B·x + (A - 1/T) = 0
+                let y = (a - inverse) / c;
+                let x = ((b / (3.0 * c)).powi(3) + y * y / 4.0).sqrt();
+                ((x - y / 2.0).cbrt() - (x + y / 2.0).cbrt()).exp()
+            }
+        }
+    }
+}
+
+/// Voltage divider of a thermistor and a fixed resistor
+///
+/// The divider hangs between the excitation and ground, and the ADC
+/// measures its midpoint against ground.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThermistorDivider {
+    /// Thermistor from the midpoint to ground, `r_fixed` ohms from the
+    /// excitation; the voltage falls as the temperature rises
+    LowSide {
+        /// Fixed resistor in ohms
+        r_fixed: f64,
+    },
+    /// Thermistor from the excitation to the midpoint, `r_fixed` ohms to
+    /// ground; the voltage rises with the temperature
+    HighSide {
+        /// Fixed resistor in ohms
+        r_fixed: f64,
+    },
+}
+
+impl ThermistorDivider {
+    /// Thermistor resistance in ohms with the midpoint at `ratio` of the
+    /// excitation
+    pub fn resistance(self, ratio: f64) -> f64 {
+        match self {
+            ThermistorDivider::LowSide { r_fixed } => r_fixed * ratio / (1.0 - ratio),
+            ThermistorDivider::HighSide { r_fixed } => r_fixed * (1.0 - ratio) / ratio,
+        }
+    }
+}
+
+/// NTC thermistor in a voltage divider, reported in °C
+///
+/// By default the divider is fed from the reference of the setup (AVDD for
+/// [`SensorSetup::new`]), which makes the reading ratiometric: supply
+/// drift cancels. A divider on another supply names its voltage with
+/// [`excitation`](Self::excitation).
+///
+/// # Example
+///
+/// ```no_run
+/// # let mut adc = ads1263::Ads1263::new(ads1263::Hal::new()?);
+/// use ads1263::{MuxInput, Sensor, Thermistor, ThermistorDivider, ThermistorModel};
+///
+/// // 10 kΩ NTC from AIN2 to ground, 10 kΩ from AVDD to AIN2
+/// let ntc = Thermistor::new(
+///     ThermistorModel::beta(10_000.0, 3950.0),
+///     ThermistorDivider::LowSide { r_fixed: 10_000.0 },
+///     MuxInput::Ain(2),
+/// );
+/// println!("{:.2} °C", ntc.read(&mut adc)?);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thermistor {
+    model: ThermistorModel,
+    divider: ThermistorDivider,
+    setup: SensorSetup,
+    excitation: Option<f64>,
+}
+
+impl Thermistor {
+    /// Divider midpoint on `input` against AINCOM, with the defaults of
+    /// [`SensorSetup::new`]
+    pub fn new(model: ThermistorModel, divider: ThermistorDivider, input: MuxInput) -> Self {
+        Self::with_setup(model, divider, SensorSetup::new(input, MuxInput::AinCom))
+    }
+
+    /// Thermistor measured with a custom setup
+    pub fn with_setup(
+        model: ThermistorModel,
+        divider: ThermistorDivider,
+        setup: SensorSetup,
+    ) -> Self {
+        Self {
+            model,
+            divider,
+            setup,
+            excitation: None,
+        }
+    }
+
+    /// Set the divider supply in volts, when it is not the reference
+    pub fn excitation(mut self, volts: f64) -> Self {
+        self.excitation = Some(volts);
+        self
+    }
+
+    /// Resistance-temperature relation
+    pub fn model(&self) -> ThermistorModel {
+        self.model
+    }
+
+    /// Thermistor resistance in ohms of a conversion result
+    pub fn resistance(&self, code: i32) -> f64 {
+        self.divider.resistance(self.ratio(code))
+    }
+
+    /// Divider midpoint as a fraction of the excitation
+    fn ratio(&self, code: i32) -> f64 {
+        match self.excitation {
+            Some(volts) => self.setup.volts(code) / volts,
+            None => self.setup.ratio(code),
+        }
+    }
+}
+
+impl Sensor for Thermistor {
+    fn unit(&self) -> &str {
+        "°C"
+    }
+
+    fn setup(&self) -> SensorSetup {
+        self.setup
+    }
+
+    fn convert(&self, code: i32) -> f64 {
+        self.model.celsius(self.resistance(code))
+    }
+
+    /// Take one conversion and check the divider
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::SensorFault` if the midpoint sits at either
+    /// end of the divider, i.e. an open or shorted thermistor, or the error
+    /// of the conversion
+    fn read(&self, adc: &mut Ads1263) -> Result<f64> {
+        let code = adc.read_sensor(&self.setup)?;
+        let ratio = self.ratio(code);
+        if !(ratio > 0.0 && ratio < 1.0) {
+            return Err(Ads1263Error::SensorFault(format!(
+                "thermistor divider at {:.1} % of the excitation (open or shorted?)",
+                ratio * 100.0
+            )));
+        }
+        let ohms = self.divider.resistance(ratio);
+        let celsius = self.model.celsius(ohms);
+        log::debug!("Thermistor: {:.1} Ω -> {:.2} °C", ohms, celsius);
+        Ok(celsius)
+    }
+}
@@ -0,0 +1,704 @@
+//! PostgreSQL and TimescaleDB logging
+//!
+//! [`TimescaleSink`] writes samples into a table of a PostgreSQL database,
+//! for setups that already collect their sensor data there. By default it
+//! creates the table
+//!
+//! ```sql
+//! CREATE TABLE ads1263_samples (
+//!     time timestamptz NOT NULL,
+//!     channel smallint NOT NULL,
+//!     raw integer NOT NULL,
+//!     value double precision NOT NULL,
+//!     PRIMARY KEY (time, channel)
+//! );
+//! ```
+//!
+//! and turns it into a hypertable partitioned by `time` if the TimescaleDB
+//! extension is installed in the database. Table and column names are set
+//! in [`TimescaleConfig`]; an existing table needs a unique constraint on
+//! the time and channel columns for upserts.
+//!
+//! A background thread inserts the rows in batches, so a slow or
+//! unreachable server never stalls the acquisition. While the server is
+//! unreachable, rows are kept in memory and, beyond
+//! [`buffer_rows`](TimescaleConfig::buffer_rows), appended to a spill file
+//! that is replayed once the connection is back.
+//!
+//! Requires the `postgres` feature. Connections are unencrypted; use a
+//! Unix socket or an SSH or WireGuard tunnel to reach remote servers.
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use postgres::types::ToSql;
+use postgres::{Client, NoTls, Statement};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Connect timeout unless the connection parameters set one
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most bind parameters of one statement
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+/// Size of a row in the spill file
+const SPILL_RECORD_BYTES: usize = 21;
+
+/// Table, columns, batching, and outage handling of a [`TimescaleSink`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimescaleConfig {
+    /// Table the samples are inserted into, optionally schema-qualified
+    /// (`schema.table`)
+    pub table: String,
+    /// `timestamptz` column of the sample time
+    pub time_column: String,
+    /// `smallint` column of the channel number
+    pub channel_column: String,
+    /// `integer` column of the raw code
+    pub raw_column: String,
+    /// `double precision` column of the converted value
+    pub value_column: String,
+    /// `text` column receiving the channel name; `None` stores no names
+    pub name_column: Option<String>,
+    /// Create the table, and a hypertable if TimescaleDB is installed,
+    /// unless it exists
+    pub create_table: bool,
+    /// Update the raw code and value of rows whose time and channel
+    /// already exist instead of failing the batch
+    pub upsert: bool,
+    /// Rows per `INSERT` statement
+    pub batch_rows: usize,
+    /// Longest time a row waits for its batch to fill
+    pub flush_interval: Duration,
+    /// Pause between connection attempts while the server is unreachable
+    pub retry_delay: Duration,
+    /// Rows kept in memory while the server is unreachable; older rows
+    /// move to the spill file or, without one, are dropped
+    pub buffer_rows: usize,
+    /// File holding rows beyond `buffer_rows` until they are written
+    pub spill_path: Option<PathBuf>,
+}
+
+impl Default for TimescaleConfig {
+    fn default() -> Self {
+        Self {
+            table: "ads1263_samples".into(),
+            time_column: "time".into(),
+            channel_column: "channel".into(),
+            raw_column: "raw".into(),
+            value_column: "value".into(),
+            name_column: None,
+            create_table: true,
+            upsert: true,
+            batch_rows: 1000,
+            flush_interval: Duration::from_secs(1),
+            retry_delay: Duration::from_secs(5),
+            buffer_rows: 100_000,
+            spill_path: None,
+        }
+    }
+}
+
+/// A sample ready for insertion
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    time: SystemTime,
+    channel: u8,
+    raw: i32,
+    value: f64,
+}
+
+/// Message from the sink to its writer thread
+enum Message {
+    Rows(Vec<Row>),
+    Flush,
+}
+
+/// Counters shared by the sink and its writer thread
+#[derive(Default)]
+struct Stats {
+    written: AtomicU64,
+    dropped: AtomicU64,
+    connected: AtomicBool,
+}
+
+/// Sink inserting samples into a PostgreSQL or TimescaleDB table
+///
+/// Writing only hands the rows to the writer thread; connection and
+/// insertion errors are logged and never stop the acquisition. Dropping
+/// the sink writes the remaining rows, or spills them if the server is
+/// unreachable.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelMap, DataRate, Hal, TimescaleConfig, TimescaleSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps100)?;
+///
+/// let config = TimescaleConfig {
+///     table: "lab.pressure".into(),
+///     spill_path: Some("/var/lib/ads1263/spill.bin".into()),
+///     ..TimescaleConfig::default()
+/// };
+/// let sink = TimescaleSink::connect(
+///     "host=db.lab user=sensors dbname=telemetry",
+///     ChannelMap::new(5.0),
+///     config,
+/// )?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct TimescaleSink {
+    sender: Option<Sender<Message>>,
+    writer: Option<JoinHandle<()>>,
+    stats: Arc<Stats>,
+    map: ChannelMap,
+    clock: WallClock,
+}
+
+impl TimescaleSink {
+    /// Write to the database given by `params`, a libpq-style connection
+    /// string (`host=... user=... dbname=...`) or URL
+    /// (`postgresql://user@host/dbname`), converting channels with `map`
+    ///
+    /// The connection is made by the writer thread, so an unreachable
+    /// server is not an error here.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Postgres` if `params` cannot be parsed, or
+    /// `Ads1263Error::Io` if the writer thread cannot be started
+    pub fn connect(params: &str, map: ChannelMap, config: TimescaleConfig) -> Result<Self> {
+        let mut params: postgres::Config = params.parse()?;
+        if params.get_connect_timeout().is_none() {
+            params.connect_timeout(CONNECT_TIMEOUT);
+        }
+        if params.get_application_name().is_none() {
+            params.application_name("ads1263");
+        }
+        let columns = 4 + usize::from(config.name_column.is_some());
+        let stats = Arc::new(Stats::default());
+        let writer = Writer {
+            params,
+            batch_rows: config.batch_rows.clamp(1, MAX_PARAMETERS / columns),
+            config,
+            map: map.clone(),
+            client: None,
+            statements: HashMap::new(),
+            next_attempt: Instant::now(),
+            outage: false,
+            buffer: VecDeque::new(),
+            spill_offset: 0,
+            stats: stats.clone(),
+        };
+        let (sender, receiver) = mpsc::channel();
+        let writer = thread::Builder::new()
+            .name("ads1263-timescale".to_string())
+            .spawn(move || writer.run(&receiver))?;
+        Ok(Self {
+            sender: Some(sender),
+            writer: Some(writer),
+            stats,
+            map,
+            clock: WallClock::new(),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Number of rows inserted so far
+    pub fn written(&self) -> u64 {
+        self.stats.written.load(Ordering::Relaxed)
+    }
+
+    /// Number of rows lost, because neither memory nor a spill file could
+    /// hold them or the server rejected them
+    pub fn dropped(&self) -> u64 {
+        self.stats.dropped.load(Ordering::Relaxed)
+    }
+
+    /// true while the writer thread is connected to the server
+    pub fn is_connected(&self) -> bool {
+        self.stats.connected.load(Ordering::Relaxed)
+    }
+
+    fn send(&self, message: Message) {
+        if let Some(sender) = &self.sender
+            && sender.send(message).is_err()
+        {
+            log::warn!("PostgreSQL writer thread stopped, rows dropped");
+        }
+    }
+}
+
+impl Sink for TimescaleSink {
+    /// Queue the samples for the writer thread
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        if samples.is_empty() {
+            return Ok(());
+        }
+        self.clock.update();
+        let rows = samples
+            .iter()
+            .map(|sample| Row {
+                time: self.clock.to_utc(sample.timestamp),
+                channel: sample.channel,
+                raw: sample.raw,
+                value: self.map.convert_sample(sample),
+            })
+            .collect();
+        self.send(Message::Rows(rows));
+        Ok(())
+    }
+
+    /// Ask the writer thread to insert the queued rows now
+    fn flush(&mut self) -> Result<()> {
+        self.send(Message::Flush);
+        Ok(())
+    }
+}
+
+impl Drop for TimescaleSink {
+    fn drop(&mut self) {
+        // Closing the channel makes the writer write or spill the rest and exit
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+/// State of the writer thread
+struct Writer {
+    params: postgres::Config,
+    config: TimescaleConfig,
+    batch_rows: usize,
+    map: ChannelMap,
+    client: Option<Client>,
+    /// Prepared inserts by row count
+    statements: HashMap<usize, Statement>,
+    next_attempt: Instant,
+    /// true once a connection attempt failed, until the next success
+    outage: bool,
+    /// Rows not yet written, oldest first
+    buffer: VecDeque<Row>,
+    /// Bytes of the spill file already written to the database
+    spill_offset: u64,
+    stats: Arc<Stats>,
+}
+
+impl Writer {
+    /// Write batches as they fill or age until the sink is dropped
+    fn run(mut self, receiver: &Receiver<Message>) {
+        let mut deadline = Instant::now() + self.config.flush_interval;
+        loop {
+            let due =
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(Message::Rows(rows)) => {
+                        self.buffer.extend(rows);
+                        self.buffer.len() >= self.batch_rows
+                    }
+                    Ok(Message::Flush) | Err(RecvTimeoutError::Timeout) => true,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+            if due {
+                self.write();
+                deadline = Instant::now() + self.config.flush_interval;
+            }
+            self.limit_buffer(self.config.buffer_rows);
+        }
+        self.next_attempt = Instant::now();
+        self.write();
+        self.limit_buffer(0);
+        if let Some(client) = self.client.take() {
+            let _ = client.close();
+        }
+    }
+
+    /// Write the spill file and the buffer, if connected
+    fn write(&mut self) {
+        if self.buffer.is_empty() && !self.spill_pending() {
+            return;
+        }
+        if !self.connected() {
+            return;
+        }
+        if let Err(e) = self.replay_spill() {
+            self.disconnect(&e);
+            return;
+        }
+        while !self.buffer.is_empty() {
+            let count = self.buffer.len().min(self.batch_rows);
+            let rows: Vec<Row> = self.buffer.range(..count).copied().collect();
+            match self.insert(&rows) {
+                Ok(()) => {}
+                Err(e) if is_permanent(&e) => self.reject(count, &e),
+                Err(e) => {
+                    self.disconnect(&e);
+                    return;
+                }
+            }
+            self.buffer.drain(..count);
+        }
+    }
+
+    /// true if connected, connecting if the retry delay has passed
+    fn connected(&mut self) -> bool {
+        if self
+            .client
+            .as_ref()
+            .is_some_and(|client| !client.is_closed())
+        {
+            return true;
+        }
+        if Instant::now() < self.next_attempt {
+            return false;
+        }
+        self.statements.clear();
+        match self.open() {
+            Ok(client) => {
+                log::info!("Connected to PostgreSQL, writing to {}", self.config.table);
+                self.client = Some(client);
+                self.outage = false;
+                self.stats.connected.store(true, Ordering::Relaxed);
+                true
+            }
+            Err(e) => {
+                if self.outage {
+                    log::debug!("PostgreSQL still unreachable: {}", e);
+                } else {
+                    log::warn!(
+                        "Failed to connect to PostgreSQL, retrying every {:?}: {}",
+                        self.config.retry_delay,
+                        e
+                    );
+                }
+                self.client = None;
+                self.outage = true;
+                self.stats.connected.store(false, Ordering::Relaxed);
+                self.next_attempt = Instant::now() + self.config.retry_delay;
+                false
+            }
+        }
+    }
+
+    /// Connect and create the table if configured
+    fn open(&self) -> std::result::Result<Client, postgres::Error> {
+        let mut client = self.params.connect(NoTls)?;
+        if self.config.create_table {
+            client.batch_execute(&self.create_sql())?;
+            let timescale: bool = client
+                .query_one(
+                    "SELECT EXISTS (SELECT 1 FROM pg_extension WHERE extname = 'timescaledb')",
+                    &[],
+                )?
+                .get(0);
+            if timescale {
+                client.batch_execute(&format!(
+                    "SELECT create_hypertable({}, {}, if_not_exists => TRUE)",
+                    literal(&table(&self.config.table)),
+                    literal(&self.config.time_column)
+                ))?;
+            }
+        }
+        Ok(client)
+    }
+
+    /// Drop the connection after `error`, keeping the rows for a retry
+    fn disconnect(&mut self, error: &postgres::Error) {
+        log::warn!(
+            "PostgreSQL write failed, retrying in {:?}: {}",
+            self.config.retry_delay,
+            error
+        );
+        self.client = None;
+        self.stats.connected.store(false, Ordering::Relaxed);
+        self.next_attempt = Instant::now() + self.config.retry_delay;
+    }
+
+    /// Count `rows` rejected by the server with `error` as dropped
+    fn reject(&self, rows: usize, error: &postgres::Error) {
+        log::error!("PostgreSQL rejected {} rows: {}", rows, error);
+        self.stats.dropped.fetch_add(rows as u64, Ordering::Relaxed);
+    }
+
+    /// Insert `rows` with one statement
+    fn insert(&mut self, rows: &[Row]) -> std::result::Result<(), postgres::Error> {
+        let Some(client) = self.client.as_mut() else {
+            return Ok(());
+        };
+        let statement = match self.statements.get(&rows.len()) {
+            Some(statement) => statement.clone(),
+            None => {
+                let statement = client.prepare(&insert_sql(&self.config, rows.len()))?;
+                self.statements.insert(rows.len(), statement.clone());
+                statement
+            }
+        };
+        let values: Vec<(i16, String)> = rows
+            .iter()
+            .map(|row| {
+                (
+                    i16::from(row.channel),
+                    self.map.name(row.channel).into_owned(),
+                )
+            })
+            .collect();
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::with_capacity(rows.len() * 5);
+        for (row, (channel, name)) in rows.iter().zip(&values) {
+            params.extend([
+                &row.time as &(dyn ToSql + Sync),
+                channel,
+                &row.raw,
+                &row.value,
+            ]);
+            if self.config.name_column.is_some() {
+                params.push(name);
+            }
+        }
+        client.execute(&statement, &params)?;
+        self.stats
+            .written
+            .fetch_add(rows.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Keep at most `limit` rows in memory, spilling or dropping the oldest
+    fn limit_buffer(&mut self, limit: usize) {
+        let excess = self.buffer.len().saturating_sub(limit);
+        if excess == 0 {
+            return;
+        }
+        let spilled = match &self.config.spill_path {
+            Some(path) => match spill(path, self.buffer.range(..excess)) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::error!("Failed to spill rows to {}: {}", path.display(), e);
+                    false
+                }
+            },
+            None => false,
+        };
+        if !spilled {
+            log::warn!("PostgreSQL unreachable, dropped {} rows", excess);
+            self.stats
+                .dropped
+                .fetch_add(excess as u64, Ordering::Relaxed);
+        }
+        self.buffer.drain(..excess);
+    }
+
+    /// true if the spill file holds rows not yet written
+    fn spill_pending(&self) -> bool {
+        self.config
+            .spill_path
+            .as_ref()
+            .is_some_and(|path| fs::metadata(path).is_ok_and(|m| m.len() > self.spill_offset))
+    }
+
+    /// Insert the rows of the spill file, removing it when done
+    ///
+    /// Rows left over from an earlier run are replayed, too.
+    fn replay_spill(&mut self) -> std::result::Result<(), postgres::Error> {
+        if !self.spill_pending() {
+            return Ok(());
+        }
+        let Some(path) = self.config.spill_path.clone() else {
+            return Ok(());
+        };
+        let mut reader = match File::open(&path).and_then(|mut file| {
+            file.seek(SeekFrom::Start(self.spill_offset))?;
+            Ok(BufReader::new(file))
+        }) {
+            Ok(reader) => reader,
+            Err(e) => {
+                log::error!("Failed to read spill file {}: {}", path.display(), e);
+                return Ok(());
+            }
+        };
+        log::info!("Replaying spilled rows from {}", path.display());
+        let mut chunk = vec![0; self.batch_rows * SPILL_RECORD_BYTES];
+        loop {
+            let bytes = match read_records(&mut reader, &mut chunk) {
+                Ok(0) => break,
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    log::error!("Failed to read spill file {}: {}", path.display(), e);
+                    return Ok(());
+                }
+            };
+            let rows: Vec<Row> = chunk[..bytes]
+                .chunks_exact(SPILL_RECORD_BYTES)
+                .map(decode)
+                .collect();
+            match self.insert(&rows) {
+                Ok(()) => {}
+                Err(e) if is_permanent(&e) => self.reject(rows.len(), &e),
+                Err(e) => return Err(e),
+            }
+            self.spill_offset += bytes as u64;
+        }
+        match fs::remove_file(&path) {
+            Ok(()) => self.spill_offset = 0,
+            Err(e) => log::warn!("Failed to remove spill file {}: {}", path.display(), e),
+        }
+        Ok(())
+    }
+
+    /// Statement creating the table unless it exists
+    fn create_sql(&self) -> String {
+        let config = &self.config;
+        let name = config
+            .name_column
+            .as_deref()
+            .map(|name| format!("{} text, ", identifier(name)))
+            .unwrap_or_default();
+        format!(
+            "CREATE TABLE IF NOT EXISTS {} ({} timestamptz NOT NULL, {} smallint NOT NULL, \
+             {} integer NOT NULL, {} double precision NOT NULL, {}PRIMARY KEY ({}, {}))",
+            table(&config.table),
+            identifier(&config.time_column),
+            identifier(&config.channel_column),
+            identifier(&config.raw_column),
+            identifier(&config.value_column),
+            name,
+            identifier(&config.time_column),
+            identifier(&config.channel_column),
+        )
+    }
+}
+
+/// Statement inserting `rows` rows
+fn insert_sql(config: &TimescaleConfig, rows: usize) -> String {
+    let mut columns = vec![
+        identifier(&config.time_column),
+        identifier(&config.channel_column),
+        identifier(&config.raw_column),
+        identifier(&config.value_column),
+    ];
+    if let Some(name) = &config.name_column {
+        columns.push(identifier(name));
+    }
+    let values = (0..rows)
+        .map(|row| {
+            let params: Vec<String> = (1..=columns.len())
+                .map(|column| format!("${}", row * columns.len() + column))
+                .collect();
+            format!("({})", params.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut sql = format!(
+        "INSERT INTO {} ({}) VALUES {}",
+        table(&config.table),
+        columns.join(", "),
+        values
+    );
+    if config.upsert {
+        let updates: Vec<String> = columns[2..]
+            .iter()
+            .map(|column| format!("{} = EXCLUDED.{}", column, column))
+            .collect();
+        sql.push_str(&format!(
+            " ON CONFLICT ({}, {}) DO UPDATE SET {}",
+            columns[0],
+            columns[1],
+            updates.join(", ")
+        ));
+    }
+    sql
+}
+
+/// true for errors retrying cannot fix: invalid data or constraint
+/// violations
+fn is_permanent(error: &postgres::Error) -> bool {
+    error
+        .as_db_error()
+        .is_some_and(|e| matches!(&e.code().code()[..2], "22" | "23"))
+}
+
+/// `table` as a quoted, possibly schema-qualified SQL name
+fn table(table: &str) -> String {
+    table
+        .split('.')
+        .map(identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// `name` as a quoted SQL identifier
+fn identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// `text` as a SQL string literal
+fn literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Append `rows` to the spill file at `path`
+///
+/// A record is the time (`i64`, Unix microseconds), the channel (`u8`),
+/// the raw code (`i32`), and the value (`f64`), little-endian.
+fn spill<'a>(path: &Path, rows: impl Iterator<Item = &'a Row>) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    let mut count = 0;
+    for row in rows {
+        let micros = row
+            .time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as i64;
+        writer.write_all(&micros.to_le_bytes())?;
+        writer.write_all(&[row.channel])?;
+        writer.write_all(&row.raw.to_le_bytes())?;
+        writer.write_all(&row.value.to_le_bytes())?;
+        count += 1;
+    }
+    writer.flush()?;
+    log::debug!("Spilled {} rows to {}", count, path.display());
+    Ok(())
+}
+
+/// Decode a spill file record
+fn decode(record: &[u8]) -> Row {
+    let micros = i64::from_le_bytes(record[..8].try_into().expect("8 bytes"));
+    Row {
+        time: UNIX_EPOCH + Duration::from_micros(micros.max(0) as u64),
+        channel: record[8],
+        raw: i32::from_le_bytes(record[9..13].try_into().expect("4 bytes")),
+        value: f64::from_le_bytes(record[13..21].try_into().expect("8 bytes")),
+    }
+}
+
+/// Fill `buf` with whole records, returning the bytes read; a truncated
+/// record at the end of the file is ignored
+fn read_records(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled - filled % SPILL_RECORD_BYTES)
+}
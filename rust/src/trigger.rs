@@ -0,0 +1,340 @@
+//! Oscilloscope-style triggered capture
+//!
+//! A [`Trigger`] watches a stream of scans for a [`TriggerCondition`]
+//! while keeping the most recent scans in a pre-trigger buffer. When the
+//! condition fires it collects the post-trigger scans and returns a
+//! [`Capture`] holding both, so transients can be seen together with the
+//! signal leading up to them.
+//!
+//! Feed scans from any loop (e.g. an [`Acquisition`](crate::Acquisition))
+//! with [`Trigger::feed`], or let
+//! [`Ads1263::capture_triggered`](crate::Ads1263::capture_triggered) run the
+//! loop.
+
+use crate::error::{Ads1263Error, Result};
+use rppal::gpio::{Gpio, InputPin, Trigger as GpioTrigger};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Edge direction for level and external triggers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Edge {
+    /// Low to high (default)
+    #[default]
+    Rising,
+    /// High to low
+    Falling,
+    /// Either direction
+    Both,
+}
+
+/// Condition that fires a trigger
+///
+/// Levels are signed raw ADC1 codes; `channel` is the index of the value
+/// within a scan, not the ADC input number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerCondition {
+    /// The value crosses `level` in the given direction
+    Level {
+        /// Index of the value within a scan
+        channel: usize,
+        /// Threshold in raw codes
+        level: i32,
+        /// Crossing direction
+        edge: Edge,
+    },
+    /// The value enters (or leaves) the range `low..=high`
+    Window {
+        /// Index of the value within a scan
+        channel: usize,
+        /// Lower bound in raw codes
+        low: i32,
+        /// Upper bound in raw codes
+        high: i32,
+        /// true to fire on entering the window, false on leaving it
+        enter: bool,
+    },
+    /// An edge on an external GPIO (BCM numbering)
+    External {
+        /// Trigger input pin
+        pin: u8,
+        /// Edge that fires the trigger
+        edge: Edge,
+    },
+}
+
+/// Trigger settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerConfig {
+    /// Condition that fires the trigger
+    pub condition: TriggerCondition,
+    /// Scans kept from before the trigger
+    pub pre_trigger: usize,
+    /// Scans captured from the trigger on, including the triggering scan
+    pub post_trigger: usize,
+    /// Give up waiting for the trigger after this long
+    pub timeout: Option<Duration>,
+}
+
+/// Scans surrounding one trigger event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// Values per scan
+    pub frame_len: usize,
+    /// Index of the triggering scan; scans before it are pre-trigger data.
+    /// Less than the configured `pre_trigger` if the trigger fired early.
+    pub trigger_index: usize,
+    /// Time the triggering scan was fed
+    pub timestamp: Instant,
+    /// Raw values scan by scan, oldest first
+    pub data: Vec<u32>,
+}
+
+impl Capture {
+    /// Number of scans in the capture
+    pub fn len(&self) -> usize {
+        self.data.len() / self.frame_len
+    }
+
+    /// true if the capture holds no scans
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterate over the scans
+    pub fn scans(&self) -> impl Iterator<Item = &[u32]> {
+        self.data.chunks(self.frame_len)
+    }
+}
+
+/// Trigger state machine fed with scans
+pub struct Trigger {
+    config: TriggerConfig,
+    frame_len: usize,
+    /// Pre-trigger scans, flattened
+    history: VecDeque<u32>,
+    /// Previous value of the watched channel
+    previous: Option<i32>,
+    /// Capture being filled after the trigger fired
+    capture: Option<Capture>,
+    /// Set by the GPIO interrupt for external triggers
+    external: Option<(InputPin, Arc<AtomicBool>)>,
+}
+
+impl Trigger {
+    /// Create a trigger for scans of `frame_len` values
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::InvalidConfig` if `frame_len` is zero,
+    /// `Ads1263Error::InvalidChannel` if the condition watches a value
+    /// outside the scan, or a GPIO error if an external trigger pin cannot
+    /// be configured
+    pub fn new(config: TriggerConfig, frame_len: usize) -> Result<Self> {
+        if frame_len == 0 {
+            return Err(Ads1263Error::InvalidConfig(
+                "trigger needs at least one value per scan".to_string(),
+            ));
+        }
+        let external = match config.condition {
+            TriggerCondition::Level { channel, .. } | TriggerCondition::Window { channel, .. } => {
+                if channel >= frame_len {
+                    let index = u8::try_from(channel).unwrap_or(u8::MAX);
+                    let max = u8::try_from(frame_len.saturating_sub(1)).unwrap_or(u8::MAX);
+                    return Err(Ads1263Error::InvalidChannel(index, max));
+                }
+                None
+            }
+            TriggerCondition::External { pin, edge } => {
+                let mut input = Gpio::new()?.get(pin)?.into_input();
+                let fired = Arc::new(AtomicBool::new(false));
+                let flag = fired.clone();
+                let trigger = match edge {
+                    Edge::Rising => GpioTrigger::RisingEdge,
+                    Edge::Falling => GpioTrigger::FallingEdge,
+                    Edge::Both => GpioTrigger::Both,
+                };
+                input.set_async_interrupt(trigger, None, move |_| {
+                    flag.store(true, Ordering::Release);
+                })?;
+                log::info!("External trigger armed on BCM{} ({:?})", pin, edge);
+                Some((input, fired))
+            }
+        };
+
+        Ok(Self {
+            config,
+            frame_len,
+            history: VecDeque::with_capacity(config.pre_trigger * frame_len),
+            previous: None,
+            capture: None,
+            external,
+        })
+    }
+
+    /// true while post-trigger scans are being collected
+    pub fn is_triggered(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Discard the pre-trigger history and any capture in progress
+    pub fn rearm(&mut self) {
+        self.history.clear();
+        self.previous = None;
+        self.capture = None;
+        if let Some((_, fired)) = &self.external {
+            fired.store(false, Ordering::Release);
+        }
+    }
+
+    /// Process one scan
+    ///
+    /// # Returns
+    ///
+    /// A complete capture once the post-trigger scans have been collected;
+    /// the trigger then re-arms automatically
+    pub fn feed(&mut self, scan: &[u32]) -> Option<Capture> {
+        if self.capture.is_none() && self.fires(scan) {
+            let trigger_index = self.history.len() / self.frame_len;
+            let mut data =
+                Vec::with_capacity((trigger_index + self.config.post_trigger) * self.frame_len);
+            data.extend(self.history.drain(..));
+            self.capture = Some(Capture {
+                frame_len: self.frame_len,
+                trigger_index,
+                timestamp: Instant::now(),
+                data,
+            });
+            log::debug!("Trigger fired with {} pre-trigger scans", trigger_index);
+        }
+
+        let Some(capture) = &mut self.capture else {
+            self.remember(scan);
+            return None;
+        };
+        capture.data.extend(frame(scan, self.frame_len));
+        if capture.len() - capture.trigger_index < self.config.post_trigger.max(1) {
+            return None;
+        }
+
+        let capture = self.capture.take();
+        self.rearm();
+        capture
+    }
+
+    /// Keep a scan in the pre-trigger history
+    fn remember(&mut self, scan: &[u32]) {
+        if self.config.pre_trigger == 0 {
+            return;
+        }
+        if self.history.len() >= self.config.pre_trigger * self.frame_len {
+            self.history.drain(..self.frame_len);
+        }
+        self.history.extend(frame(scan, self.frame_len));
+    }
+
+    /// Evaluate the trigger condition for a new scan
+    fn fires(&mut self, scan: &[u32]) -> bool {
+        match self.config.condition {
+            TriggerCondition::Level {
+                channel,
+                level,
+                edge,
+            } => {
+                let value = code(scan, channel);
+                let previous = self.previous.replace(value);
+                previous.is_some_and(|prev| {
+                    let rising = prev < level && value >= level;
+                    let falling = prev > level && value <= level;
+                    match edge {
+                        Edge::Rising => rising,
+                        Edge::Falling => falling,
+                        Edge::Both => rising || falling,
+                    }
+                })
+            }
+            TriggerCondition::Window {
+                channel,
+                low,
+                high,
+                enter,
+            } => {
+                let value = code(scan, channel);
+                let previous = self.previous.replace(value);
+                let inside = |v: i32| (low..=high).contains(&v);
+                previous.is_some_and(|prev| inside(prev) != inside(value) && inside(value) == enter)
+            }
+            TriggerCondition::External { .. } => self
+                .external
+                .as_ref()
+                .is_some_and(|(_, fired)| fired.swap(false, Ordering::AcqRel)),
+        }
+    }
+}
+
+/// Signed raw code of one value in a scan
+fn code(scan: &[u32], channel: usize) -> i32 {
+    scan.get(channel).copied().unwrap_or(0) as i32
+}
+
+/// The first `len` values of a scan, zero-padded
+fn frame(scan: &[u32], len: usize) -> impl Iterator<Item = u32> + '_ {
+    (0..len).map(|i| scan.get(i).copied().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(condition: TriggerCondition) -> TriggerConfig {
+        TriggerConfig {
+            condition,
+            pre_trigger: 2,
+            post_trigger: 2,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn empty_frame_is_rejected() {
+        let level = config(TriggerCondition::Level {
+            channel: 0,
+            level: 0,
+            edge: Edge::Rising,
+        });
+        assert!(matches!(
+            Trigger::new(level, 0),
+            Err(Ads1263Error::InvalidConfig(_))
+        ));
+
+        // Rejected before the pin is touched
+        let external = config(TriggerCondition::External {
+            pin: 27,
+            edge: Edge::Rising,
+        });
+        assert!(matches!(
+            Trigger::new(external, 0),
+            Err(Ads1263Error::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn level_crossing_captures_surrounding_scans() {
+        let level = config(TriggerCondition::Level {
+            channel: 1,
+            level: 100,
+            edge: Edge::Rising,
+        });
+        let mut trigger = Trigger::new(level, 2).unwrap();
+        for value in [10, 20, 30] {
+            assert!(trigger.feed(&[0, value]).is_none());
+        }
+        assert!(trigger.feed(&[0, 200]).is_none());
+        let capture = trigger.feed(&[0, 300]).unwrap();
+        assert_eq!(capture.trigger_index, 2);
+        assert_eq!(capture.len(), 4);
+        assert_eq!(capture.data, vec![0, 20, 0, 30, 0, 200, 0, 300]);
+    }
+}
@@ -0,0 +1,182 @@
+//! UDP unicast and multicast streaming
+//!
+//! [`UdpSink`] sends every scan as one or more datagrams to a unicast or
+//! multicast address, so any number of listeners on a lab network can
+//! follow the acquisition with minimal latency. Datagrams are not
+//! acknowledged or repeated; listeners detect loss from gaps in the packet
+//! sequence number.
+//!
+//! A packet is a 12-byte little-endian header followed by up to 48 of the
+//! 30-byte sample records also used by
+//! [`UnixSocketSink`](crate::UnixSocketSink), so it fits into an Ethernet
+//! frame:
+//!
+//! | Offset | Field |
+//! |--------|-------|
+//! | 0 | Magic `b"AD"` |
+//! | 2 | Format version (`u8`, currently 1) |
+//! | 3 | Record count (`u8`) |
+//! | 4 | Packet sequence number (`u64`), counting every packet sent |
+//! | 12 | Records: channel (`u8`), status byte (`u8`), timestamp (`i64`, Unix microseconds), sample sequence number (`u64`), raw code (`i32`), converted value (`f64`) |
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use crate::unix_socket::{RECORD_BYTES, record};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::UNIX_EPOCH;
+
+/// Magic bytes starting every packet
+const MAGIC: &[u8; 2] = b"AD";
+
+/// Packet format version
+const VERSION: u8 = 1;
+
+/// Size of the packet header
+const HEADER_BYTES: usize = 12;
+
+/// Records per packet, keeping packets within a 1500-byte MTU
+const MAX_RECORDS: usize = 48;
+
+/// Sink sending fixed-format sample packets over UDP
+///
+/// Send errors, e.g. while the network is down, are logged and counted but
+/// do not stop the acquisition.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelMap, DataRate, Hal, UdpSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let sink = UdpSink::new("239.1.2.63:5263", ChannelMap::new(5.0))?.multicast_ttl(2)?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct UdpSink {
+    socket: UdpSocket,
+    target: SocketAddr,
+    map: ChannelMap,
+    clock: WallClock,
+    sequence: u64,
+    send_errors: u64,
+    packet: Vec<u8>,
+}
+
+impl UdpSink {
+    /// Send to `target`, a unicast or multicast address, converting
+    /// channels with `map`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if `target` does not resolve or no local
+    /// socket can be bound
+    pub fn new(target: impl ToSocketAddrs, map: ChannelMap) -> Result<Self> {
+        let target = target.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "no UDP target address")
+        })?;
+        let local: IpAddr = match target {
+            SocketAddr::V4(_) => Ipv4Addr::UNSPECIFIED.into(),
+            SocketAddr::V6(_) => Ipv6Addr::UNSPECIFIED.into(),
+        };
+        let socket = UdpSocket::bind((local, 0))?;
+        log::info!("Sending UDP sample packets to {}", target);
+        Ok(Self {
+            socket,
+            target,
+            map,
+            clock: WallClock::new(),
+            sequence: 0,
+            send_errors: 0,
+            packet: Vec::with_capacity(HEADER_BYTES + MAX_RECORDS * RECORD_BYTES),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Number of routers IPv4 multicast packets may cross (default: 1, the
+    /// local network only)
+    ///
+    /// IPv6 targets keep the system's multicast hop limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the socket option cannot be set
+    pub fn multicast_ttl(self, ttl: u32) -> Result<Self> {
+        if self.target.is_ipv4() {
+            self.socket.set_multicast_ttl_v4(ttl)?;
+        }
+        Ok(self)
+    }
+
+    /// Whether multicast packets are also delivered to listeners on this
+    /// host (default: true)
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the socket option cannot be set
+    pub fn multicast_loop(self, enabled: bool) -> Result<Self> {
+        match self.target {
+            SocketAddr::V4(_) => self.socket.set_multicast_loop_v4(enabled)?,
+            SocketAddr::V6(_) => self.socket.set_multicast_loop_v6(enabled)?,
+        }
+        Ok(self)
+    }
+
+    /// Address the packets are sent to
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+
+    /// Number of packets produced, which is the sequence number of the next one
+    pub fn packets(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Number of packets that could not be sent
+    pub fn send_errors(&self) -> u64 {
+        self.send_errors
+    }
+}
+
+impl Sink for UdpSink {
+    /// Send the samples in packets of up to 48 records
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        self.clock.update();
+        for chunk in samples.chunks(MAX_RECORDS) {
+            self.packet.clear();
+            self.packet.extend_from_slice(MAGIC);
+            self.packet.push(VERSION);
+            self.packet.push(chunk.len() as u8);
+            self.packet.extend_from_slice(&self.sequence.to_le_bytes());
+            for sample in chunk {
+                let time = self
+                    .clock
+                    .to_utc(sample.timestamp)
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros() as i64;
+                record(
+                    &mut self.packet,
+                    sample,
+                    time,
+                    self.map.convert_sample(sample),
+                );
+            }
+            self.sequence += 1;
+            if let Err(e) = self.socket.send_to(&self.packet, self.target) {
+                self.send_errors += 1;
+                log::debug!("Failed to send UDP packet to {}: {}", self.target, e);
+            }
+        }
+        Ok(())
+    }
+}
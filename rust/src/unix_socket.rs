@@ -0,0 +1,313 @@
+//! Unix domain socket streaming
+//!
+//! [`UnixSocketSink`] listens on a Unix domain socket and streams the
+//! samples written to it to every connected process, for local consumers in
+//! other languages that should not need an HTTP or WebSocket stack.
+//!
+//! The protocol is one-way. Every frame starts with its length as a
+//! little-endian `u32`, counting the bytes that follow, then a type byte:
+//!
+//! | Type | Frame | Payload |
+//! |------|-------|---------|
+//! | `1` | Header, sent once after connecting | Channel count (`u8`), then per channel: number (`u8`), name length (`u8`) and UTF-8 name, unit length (`u8`) and UTF-8 unit |
+//! | `2` | Scan | 30-byte records, one per sample |
+//!
+//! The header lists the channels of the [`ChannelMap`]. A record holds the
+//! channel (`u8`), the status byte (`u8`), the timestamp (`i64`, Unix
+//! microseconds), the sequence number (`u64`), the raw code (`i32`), and
+//! the converted value (`f64`), all little-endian. A reader in Python:
+//!
+//! ```text
+//! sock = socket.socket(socket.AF_UNIX); sock.connect("/run/ads1263.sock")
+//! while True:
+//!     length, kind = struct.unpack("<IB", recv_exactly(sock, 5))
+//!     payload = recv_exactly(sock, length - 1)
+//!     if kind == 2:
+//!         for record in struct.iter_unpack("<BBqQid", payload): ...
+//! ```
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Frames queued per client before further scans are dropped for it
+const QUEUE_FRAMES: usize = 256;
+
+/// How often the listener is polled for new connections
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Time after which a client not reading its frames is disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Size of one sample record
+pub(crate) const RECORD_BYTES: usize = 30;
+
+/// Type byte of the header frame
+const FRAME_HEADER: u8 = 1;
+
+/// Type byte of a scan frame
+const FRAME_SCAN: u8 = 2;
+
+/// State shared by the sink, the listener, and the client threads
+struct Shared {
+    /// Encoded header frame
+    header: Vec<u8>,
+    clients: Mutex<Vec<SyncSender<Arc<Vec<u8>>>>>,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn clients(&self) -> MutexGuard<'_, Vec<SyncSender<Arc<Vec<u8>>>>> {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_shut_down(&self) -> bool {
+        self.shutdown.load(Ordering::Relaxed)
+    }
+}
+
+/// Sink streaming length-prefixed sample frames over a Unix domain socket
+///
+/// Writing never waits for a client: each client has a queue of 256 scans,
+/// and scans are dropped for a client whose queue is full. Dropping the
+/// sink closes all connections and removes the socket file.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelMap, DataRate, Hal, UnixSocketSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps1200)?;
+///
+/// let sink = UnixSocketSink::bind("/run/ads1263.sock", ChannelMap::new(5.0))?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct UnixSocketSink {
+    shared: Arc<Shared>,
+    map: ChannelMap,
+    clock: WallClock,
+    path: PathBuf,
+    /// Whether the sink created the socket file and removes it
+    remove_on_drop: bool,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl UnixSocketSink {
+    /// Listen on the socket `path`, naming and converting channels with `map`
+    ///
+    /// A socket left behind at `path` by an earlier run is replaced; any
+    /// other file there is an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the socket cannot be bound
+    pub fn bind(path: impl AsRef<Path>, map: ChannelMap) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if fs::symlink_metadata(&path).is_ok_and(|m| m.file_type().is_socket()) {
+            fs::remove_file(&path)?;
+        }
+        let listener = UnixListener::bind(&path)?;
+        let mut sink = Self::from_listener(listener, map)?;
+        sink.remove_on_drop = true;
+        Ok(sink)
+    }
+
+    /// Serve on an already listening socket, e.g. one passed by systemd
+    /// socket activation (see [`listen_fds`](crate::listen_fds))
+    ///
+    /// Unlike with [`bind`](Self::bind), the socket file is left in place
+    /// when the sink is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the listener cannot be made
+    /// non-blocking
+    pub fn from_listener(listener: UnixListener, map: ChannelMap) -> Result<Self> {
+        listener.set_nonblocking(true)?;
+        let path = listener
+            .local_addr()?
+            .as_pathname()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let shared = Arc::new(Shared {
+            header: header(&map),
+            clients: Mutex::new(Vec::new()),
+            shutdown: AtomicBool::new(false),
+        });
+        let listener = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("ads1263-unix-socket".to_string())
+                .spawn(move || listen(listener, shared))?
+        };
+        log::info!("Unix socket server listening on {}", path.display());
+        Ok(Self {
+            shared,
+            map,
+            clock: WallClock::new(),
+            path,
+            remove_on_drop: false,
+            listener: Some(listener),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Path of the socket
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of connected clients
+    pub fn clients(&self) -> usize {
+        self.shared.clients().len()
+    }
+}
+
+impl Sink for UnixSocketSink {
+    /// Queue a scan frame for every client
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        let mut clients = self.shared.clients();
+        if clients.is_empty() || samples.is_empty() {
+            return Ok(());
+        }
+        self.clock.update();
+        let mut frame = Vec::with_capacity(5 + samples.len() * RECORD_BYTES);
+        frame.extend_from_slice(&((1 + samples.len() * RECORD_BYTES) as u32).to_le_bytes());
+        frame.push(FRAME_SCAN);
+        for sample in samples {
+            let time = self
+                .clock
+                .to_utc(sample.timestamp)
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_micros() as i64;
+            record(&mut frame, sample, time, self.map.convert_sample(sample));
+        }
+        let frame = Arc::new(frame);
+        clients.retain(|client| match client.try_send(frame.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log::debug!("Unix socket client lagging, scan dropped");
+                true
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+        Ok(())
+    }
+}
+
+impl Drop for UnixSocketSink {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        // Dropping the senders ends the client threads
+        self.shared.clients().clear();
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+        if self.remove_on_drop
+            && let Err(e) = fs::remove_file(&self.path)
+        {
+            log::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Append the record of `sample` with its Unix time in microseconds
+pub(crate) fn record(buf: &mut Vec<u8>, sample: &Sample, time: i64, value: f64) {
+    buf.push(sample.channel);
+    buf.push(sample.status.bits());
+    buf.extend_from_slice(&time.to_le_bytes());
+    buf.extend_from_slice(&sample.sequence.to_le_bytes());
+    buf.extend_from_slice(&sample.raw.to_le_bytes());
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Header frame listing the channels of `map`
+fn header(map: &ChannelMap) -> Vec<u8> {
+    let channels: Vec<_> = map.iter().collect();
+    let mut frame = vec![0; 4];
+    frame.push(FRAME_HEADER);
+    frame.push(channels.len() as u8);
+    for (channel, info) in channels {
+        frame.push(channel);
+        for text in [info.name.as_str(), info.unit.as_str()] {
+            let text = truncate(text);
+            frame.push(text.len() as u8);
+            frame.extend_from_slice(text.as_bytes());
+        }
+    }
+    let length = (frame.len() - 4) as u32;
+    frame[..4].copy_from_slice(&length.to_le_bytes());
+    frame
+}
+
+/// `text` cut to at most 255 bytes at a character boundary
+fn truncate(text: &str) -> &str {
+    let mut end = text.len().min(u8::MAX as usize);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Accept connections until shutdown, serving each on its own thread
+fn listen(listener: UnixListener, shared: Arc<Shared>) {
+    while !shared.is_shut_down() {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let (sender, receiver) = mpsc::sync_channel(QUEUE_FRAMES);
+                shared.clients().push(sender);
+                let header = shared.header.clone();
+                let spawned = thread::Builder::new()
+                    .name("ads1263-unix-socket-client".to_string())
+                    .spawn(move || match serve(stream, &header, &receiver) {
+                        Ok(()) => log::info!("Unix socket client disconnected"),
+                        Err(e) => log::info!("Unix socket client dropped: {}", e),
+                    });
+                if let Err(e) = spawned {
+                    log::warn!("Failed to start Unix socket client thread: {}", e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+            Err(e) => {
+                log::warn!("Failed to accept Unix socket connection: {}", e);
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Send the header, then the queued frames until the sink is dropped
+fn serve(
+    mut stream: UnixStream,
+    header: &[u8],
+    receiver: &Receiver<Arc<Vec<u8>>>,
+) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    log::info!("Unix socket client connected");
+    stream.write_all(header)?;
+    for frame in receiver {
+        stream.write_all(&frame)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+//! WAV export for audio-band analysis
+//!
+//! Vibration and acoustic measurements are easiest to inspect with audio
+//! tooling (Audacity, sox, ...). [`export_wav`] turns one channel of a
+//! binary capture into a mono 32-bit float WAV file at the channel's
+//! effective sample rate; [`WavWriter`] writes such files from any source.
+//!
+//! Samples are normalized to ADC full scale: 1.0 is the largest positive
+//! code, -1.0 the most negative one.
+
+use crate::capture::CaptureReader;
+use crate::error::Result;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Instant;
+
+/// Size of the RIFF/WAVE header written before the samples
+const HEADER_LEN: u32 = 44;
+
+/// Mono 32-bit float WAV writer
+///
+/// The header is written up front and completed by [`finish`](Self::finish)
+/// (or on drop), so the sample rate may still be changed while writing.
+pub struct WavWriter<W: Write + Seek> {
+    writer: Option<W>,
+    sample_rate: u32,
+    samples: u64,
+}
+
+impl WavWriter<BufWriter<File>> {
+    /// Create (or truncate) a WAV file at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the file cannot be created or written
+    pub fn create(path: impl AsRef<Path>, sample_rate: u32) -> Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), sample_rate)
+    }
+}
+
+impl<W: Write + Seek> WavWriter<W> {
+    /// Start a WAV stream on `writer`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the header cannot be written
+    pub fn new(mut writer: W, sample_rate: u32) -> Result<Self> {
+        writer.write_all(&header(sample_rate, 0))?;
+        Ok(Self {
+            writer: Some(writer),
+            sample_rate,
+            samples: 0,
+        })
+    }
+
+    /// Set the sample rate recorded in the header
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate;
+    }
+
+    /// Number of samples written
+    pub fn len(&self) -> u64 {
+        self.samples
+    }
+
+    /// true if no samples have been written
+    pub fn is_empty(&self) -> bool {
+        self.samples == 0
+    }
+
+    /// Append one sample
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails
+    pub fn push(&mut self, value: f32) -> Result<()> {
+        if let Some(writer) = self.writer.as_mut() {
+            writer.write_all(&value.to_le_bytes())?;
+            self.samples += 1;
+        }
+        Ok(())
+    }
+
+    /// Append an ADC1 code, normalized to full scale
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if writing fails
+    pub fn push_code(&mut self, raw: i32) -> Result<()> {
+        self.push((f64::from(raw) / 2147483648.0) as f32)
+    }
+
+    /// Complete the header and flush
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the header cannot be rewritten
+    pub fn finish(mut self) -> Result<()> {
+        self.complete()?;
+        self.writer = None;
+        Ok(())
+    }
+
+    fn complete(&mut self) -> Result<()> {
+        let Some(writer) = self.writer.as_mut() else {
+            return Ok(());
+        };
+        // The data chunk size field is 32 bits; longer files are truncated in the header
+        let data_len = (self.samples * 4).min(u64::from(u32::MAX - HEADER_LEN)) as u32;
+        writer.seek(SeekFrom::Start(0))?;
+        writer.write_all(&header(self.sample_rate, data_len))?;
+        writer.seek(SeekFrom::End(0))?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+impl<W: Write + Seek> Drop for WavWriter<W> {
+    fn drop(&mut self) {
+        if let Err(e) = self.complete() {
+            log::warn!("Failed to complete WAV header: {}", e);
+        }
+    }
+}
+
+/// Result of [`export_wav`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavExport {
+    /// Samples written
+    pub samples: u64,
+    /// Sample rate recorded in the file
+    pub sample_rate: u32,
+}
+
+/// Export one channel of a binary capture as a WAV file
+///
+/// The sample rate is the channel's effective rate, measured from the
+/// first and last timestamps; with fewer than two samples the capture's
+/// ADC1 data rate is used.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{CaptureReader, export_wav};
+///
+/// let wav = export_wav(CaptureReader::open("vibration.ads")?, 0, "vibration.wav")?;
+/// println!("{} samples at {} Hz", wav.samples, wav.sample_rate);
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the capture cannot be read or the file written
+pub fn export_wav<R: Read>(
+    capture: CaptureReader<R>,
+    channel: u8,
+    path: impl AsRef<Path>,
+) -> Result<WavExport> {
+    let nominal = capture.header().adc1.data_rate.sps().round() as u32;
+    let mut wav = WavWriter::create(path, nominal)?;
+    let mut span: Option<(Instant, Instant)> = None;
+
+    for sample in capture.samples(Instant::now()) {
+        let sample = sample?;
+        if sample.channel != channel {
+            continue;
+        }
+        wav.push_code(sample.raw)?;
+        let (first, _) = span.unwrap_or((sample.timestamp, sample.timestamp));
+        span = Some((first, sample.timestamp));
+    }
+
+    let samples = wav.len();
+    let elapsed = span.map_or(0.0, |(first, last)| (last - first).as_secs_f64());
+    let sample_rate = if samples >= 2 && elapsed > 0.0 {
+        ((samples - 1) as f64 / elapsed).round() as u32
+    } else {
+        nominal
+    }
+    .max(1);
+    wav.set_sample_rate(sample_rate);
+    wav.finish()?;
+    log::info!(
+        "Exported {} samples of ch{} at {} Hz",
+        samples,
+        channel,
+        sample_rate
+    );
+    Ok(WavExport {
+        samples,
+        sample_rate,
+    })
+}
+
+/// RIFF/WAVE header for mono IEEE float data of `data_len` bytes
+fn header(sample_rate: u32, data_len: u32) -> [u8; HEADER_LEN as usize] {
+    const CHANNELS: u16 = 1;
+    const BITS: u16 = 32;
+    const FORMAT_IEEE_FLOAT: u16 = 3;
+    let block_align = CHANNELS * BITS / 8;
+
+    let mut h = [0u8; HEADER_LEN as usize];
+    h[0..4].copy_from_slice(b"RIFF");
+    h[4..8].copy_from_slice(&(HEADER_LEN - 8 + data_len).to_le_bytes());
+    h[8..12].copy_from_slice(b"WAVE");
+    h[12..16].copy_from_slice(b"fmt ");
+    h[16..20].copy_from_slice(&16u32.to_le_bytes());
+    h[20..22].copy_from_slice(&FORMAT_IEEE_FLOAT.to_le_bytes());
+    h[22..24].copy_from_slice(&CHANNELS.to_le_bytes());
+    h[24..28].copy_from_slice(&sample_rate.to_le_bytes());
+    h[28..32].copy_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    h[32..34].copy_from_slice(&block_align.to_le_bytes());
+    h[34..36].copy_from_slice(&BITS.to_le_bytes());
+    h[36..40].copy_from_slice(b"data");
+    h[40..44].copy_from_slice(&data_len.to_le_bytes());
+    h
+}
@@ -0,0 +1,450 @@
+//! WebSocket streaming
+//!
+//! [`WebSocketSink`] runs a small WebSocket server and streams the samples
+//! written to it to every connected client, so a browser dashboard can
+//! show live data without a broker in between. Clients choose what they
+//! receive with query parameters of the URL, and can change them at any
+//! time by sending the same parameters as a text message:
+//!
+//! ```text
+//! ws://raspberrypi.local:8080/?channels=0,inlet&rate=10&format=binary
+//! ```
+//!
+//! | Parameter | Meaning | Default |
+//! |-----------|---------|---------|
+//! | `channels` | Channel numbers or [`ChannelMap`] names to send | all |
+//! | `rate` | Maximum messages per second; scans in between are skipped | unlimited |
+//! | `format` | `json` or `binary` | `json` |
+//!
+//! Each message holds one scan. A JSON message is an array with one object
+//! per sample:
+//!
+//! ```text
+//! [{"timestamp":1700000000.000123,"channel":"inlet","raw":1073741824,"value":50,"unit":"kPa"}]
+//! ```
+//!
+//! A binary message is a sequence of 21-byte little-endian records: channel
+//! (`u8`), timestamp (`i64`, Unix microseconds), raw code (`i32`), and
+//! converted value (`f64`).
+//!
+//! Requires the `websocket` feature.
+
+use crate::channel_map::ChannelMap;
+use crate::clock::WallClock;
+use crate::error::Result;
+use crate::jsonl::{number, string};
+use crate::sample::Sample;
+use crate::sink::Sink;
+use std::fmt::Write as _;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use tungstenite::handshake::HandshakeError;
+use tungstenite::handshake::server::{Request, Response};
+use tungstenite::{Message, WebSocket};
+
+/// Scans queued per client before further scans are dropped for it
+const QUEUE_SCANS: usize = 256;
+
+/// How long a client thread waits for a message from its client
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often the listener is polled for new connections
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Time after which a client not reading its messages is disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Size of one sample in a binary message
+const RECORD_BYTES: usize = 21;
+
+/// Encoding of the messages sent to a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Binary,
+}
+
+/// What a client receives
+#[derive(Debug, Clone)]
+struct Subscription {
+    /// Channels to send, all if `None`
+    channels: Option<Vec<u8>>,
+    /// Minimum time between two messages
+    interval: Option<Duration>,
+    format: Format,
+}
+
+impl Subscription {
+    /// Apply the parameters of a query string, ignoring invalid ones
+    fn update(&mut self, query: &str, map: &ChannelMap) {
+        for (key, value) in query
+            .trim_start_matches('?')
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+        {
+            match key {
+                "channels" if value.is_empty() || value == "all" => self.channels = None,
+                "channels" => {
+                    let channels: Option<Vec<u8>> =
+                        value.split(',').map(|name| map.lookup(name)).collect();
+                    match channels {
+                        Some(channels) => self.channels = Some(channels),
+                        None => log::warn!("Ignoring unknown WebSocket channels {:?}", value),
+                    }
+                }
+                "rate" => match value.parse::<f64>() {
+                    Ok(rate) if rate > 0.0 && rate.is_finite() => {
+                        self.interval = Some(Duration::from_secs_f64(1.0 / rate))
+                    }
+                    Ok(_) => self.interval = None,
+                    Err(_) => log::warn!("Ignoring invalid WebSocket rate {:?}", value),
+                },
+                "format" => match value {
+                    "json" => self.format = Format::Json,
+                    "binary" => self.format = Format::Binary,
+                    _ => log::warn!("Ignoring unknown WebSocket format {:?}", value),
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// true if `channel` is sent
+    fn wants(&self, channel: u8) -> bool {
+        self.channels.as_ref().is_none_or(|c| c.contains(&channel))
+    }
+}
+
+impl Default for Subscription {
+    fn default() -> Self {
+        Self {
+            channels: None,
+            interval: None,
+            format: Format::Json,
+        }
+    }
+}
+
+/// Scan shared by all clients, with Unix timestamps in microseconds
+struct Scan {
+    samples: Vec<Sample>,
+    times: Vec<i64>,
+}
+
+/// Connected client, as seen by the sink
+struct Client {
+    peer: SocketAddr,
+    subscription: Arc<Mutex<Subscription>>,
+    sender: SyncSender<Arc<Scan>>,
+    last_sent: Option<Instant>,
+}
+
+/// State shared by the sink, the listener, and the client threads
+struct Shared {
+    map: ChannelMap,
+    clients: Mutex<Vec<Client>>,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn clients(&self) -> MutexGuard<'_, Vec<Client>> {
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_shut_down(&self) -> bool {
+        self.shutdown.load(Ordering::Relaxed)
+    }
+}
+
+/// Sink streaming samples to WebSocket clients
+///
+/// Writing never waits for a client: each client has a queue of 256 scans,
+/// and scans are dropped for a client whose queue is full. Dropping the
+/// sink closes all connections.
+///
+/// # Example
+///
+/// ```no_run
+/// use ads1263::{AcquisitionConfig, Ads1263, ChannelInfo, ChannelMap, DataRate, Hal, WebSocketSink};
+///
+/// let mut adc = Ads1263::new(Hal::new()?);
+/// adc.init_adc1(DataRate::Sps100)?;
+///
+/// let map = ChannelMap::new(5.0).channel(0, ChannelInfo::new("inlet").unit("kPa").linear(20.0, 0.0));
+/// let sink = WebSocketSink::bind("0.0.0.0:8080", map)?;
+/// let acq = adc.start_acquisition_sink(&[0, 1], AcquisitionConfig::default(), sink)?;
+/// # Ok::<(), ads1263::Ads1263Error>(())
+/// ```
+pub struct WebSocketSink {
+    shared: Arc<Shared>,
+    clock: WallClock,
+    local_addr: SocketAddr,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl WebSocketSink {
+    /// Listen on `addr`, naming and converting channels with `map`
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the address cannot be bound
+    pub fn bind(addr: impl ToSocketAddrs, map: ChannelMap) -> Result<Self> {
+        Self::from_listener(TcpListener::bind(addr)?, map)
+    }
+
+    /// Serve on an already listening socket, e.g. one passed by systemd
+    /// socket activation (see [`listen_fds`](crate::listen_fds))
+    ///
+    /// # Errors
+    ///
+    /// Returns `Ads1263Error::Io` if the listener cannot be made
+    /// non-blocking
+    pub fn from_listener(listener: TcpListener, map: ChannelMap) -> Result<Self> {
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            map,
+            clients: Mutex::new(Vec::new()),
+            shutdown: AtomicBool::new(false),
+        });
+        let listener = {
+            let shared = shared.clone();
+            thread::Builder::new()
+                .name("ads1263-websocket".to_string())
+                .spawn(move || listen(listener, shared))?
+        };
+        log::info!("WebSocket server listening on {}", local_addr);
+        Ok(Self {
+            shared,
+            clock: WallClock::new(),
+            local_addr,
+            listener: Some(listener),
+        })
+    }
+
+    /// Timestamp samples with `clock` (default: a fresh [`WallClock`])
+    pub fn clock(mut self, clock: WallClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Address the server listens on, e.g. to find the port bound for port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of connected clients
+    pub fn clients(&self) -> usize {
+        self.shared.clients().len()
+    }
+}
+
+impl Sink for WebSocketSink {
+    /// Queue the samples for every client whose rate allows a message
+    fn write(&mut self, samples: &[Sample]) -> Result<()> {
+        let mut clients = self.shared.clients();
+        if clients.is_empty() || samples.is_empty() {
+            return Ok(());
+        }
+        self.clock.update();
+        let scan = Arc::new(Scan {
+            samples: samples.to_vec(),
+            times: samples
+                .iter()
+                .map(|s| {
+                    let utc = self.clock.to_utc(s.timestamp);
+                    utc.duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_micros() as i64
+                })
+                .collect(),
+        });
+        let now = Instant::now();
+        clients.retain_mut(|client| {
+            let interval = client
+                .subscription
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .interval;
+            if let (Some(interval), Some(last)) = (interval, client.last_sent)
+                && now.duration_since(last) < interval
+            {
+                return true;
+            }
+            match client.sender.try_send(scan.clone()) {
+                Ok(()) => {
+                    client.last_sent = Some(now);
+                    true
+                }
+                Err(TrySendError::Full(_)) => {
+                    log::debug!("WebSocket client {} lagging, scan dropped", client.peer);
+                    true
+                }
+                Err(TrySendError::Disconnected(_)) => false,
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Drop for WebSocketSink {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        // Dropping the senders ends the client threads
+        self.shared.clients().clear();
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+    }
+}
+
+/// Accept connections until shutdown, serving each on its own thread
+fn listen(listener: TcpListener, shared: Arc<Shared>) {
+    while !shared.is_shut_down() {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let shared = shared.clone();
+                let spawned = thread::Builder::new()
+                    .name("ads1263-websocket-client".to_string())
+                    .spawn(move || match serve(stream, peer, &shared) {
+                        Ok(()) => log::info!("WebSocket client {} disconnected", peer),
+                        Err(e) => log::info!("WebSocket client {} dropped: {}", peer, e),
+                    });
+                if let Err(e) = spawned {
+                    log::warn!("Failed to start WebSocket client thread: {}", e);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+            Err(e) => {
+                log::warn!("Failed to accept WebSocket connection: {}", e);
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Handshake with a client, then send its scans until either side closes
+// The handshake callback's error type is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+fn serve(stream: TcpStream, peer: SocketAddr, shared: &Shared) -> tungstenite::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut query = String::new();
+    let mut socket = tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+        query = request.uri().query().unwrap_or_default().to_string();
+        Ok(response)
+    })
+    .map_err(|e| match e {
+        HandshakeError::Failure(e) => e,
+        HandshakeError::Interrupted(_) => io::Error::from(io::ErrorKind::WouldBlock).into(),
+    })?;
+    socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+
+    let mut subscription = Subscription::default();
+    subscription.update(&query, &shared.map);
+    let subscription = Arc::new(Mutex::new(subscription));
+    let (sender, receiver) = mpsc::sync_channel(QUEUE_SCANS);
+    if shared.is_shut_down() {
+        return socket.close(None);
+    }
+    shared.clients().push(Client {
+        peer,
+        subscription: subscription.clone(),
+        sender,
+        last_sent: None,
+    });
+    log::info!("WebSocket client {} connected", peer);
+    run(&mut socket, &receiver, &subscription, &shared.map)
+}
+
+/// Forward queued scans and apply subscription changes
+fn run(
+    socket: &mut WebSocket<TcpStream>,
+    receiver: &Receiver<Arc<Scan>>,
+    subscription: &Mutex<Subscription>,
+    map: &ChannelMap,
+) -> tungstenite::Result<()> {
+    loop {
+        loop {
+            match receiver.try_recv() {
+                Ok(scan) => {
+                    let message = {
+                        let subscription =
+                            subscription.lock().unwrap_or_else(PoisonError::into_inner);
+                        encode(&scan, &subscription, map)
+                    };
+                    if let Some(message) = message {
+                        socket.send(message)?;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    socket.close(None)?;
+                    return socket.flush();
+                }
+            }
+        }
+        match socket.read() {
+            Ok(Message::Text(text)) => subscription
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .update(text.as_str(), map),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Message of the subscribed samples of a scan, `None` if there are none
+fn encode(scan: &Scan, subscription: &Subscription, map: &ChannelMap) -> Option<Message> {
+    let samples = scan
+        .samples
+        .iter()
+        .zip(&scan.times)
+        .filter(|(sample, _)| subscription.wants(sample.channel));
+    match subscription.format {
+        Format::Json => {
+            let mut text = String::from("[");
+            for (sample, &time) in samples {
+                if text.len() > 1 {
+                    text.push(',');
+                }
+                let _ = write!(
+                    text,
+                    "{{\"timestamp\":{}.{:06},\"channel\":",
+                    time / 1_000_000,
+                    time % 1_000_000
+                );
+                string(&mut text, &map.name(sample.channel));
+                let _ = write!(text, ",\"raw\":{},\"value\":", sample.raw);
+                number(&mut text, map.convert_sample(sample));
+                text.push_str(",\"unit\":");
+                string(&mut text, map.unit(sample.channel));
+                text.push('}');
+            }
+            text.push(']');
+            (text.len() > 2).then(|| Message::text(text))
+        }
+        Format::Binary => {
+            let mut data = Vec::with_capacity(scan.samples.len() * RECORD_BYTES);
+            for (sample, &time) in samples {
+                data.push(sample.channel);
+                data.extend_from_slice(&time.to_le_bytes());
+                data.extend_from_slice(&sample.raw.to_le_bytes());
+                data.extend_from_slice(&map.convert_sample(sample).to_le_bytes());
+            }
+            (!data.is_empty()).then(|| Message::binary(data))
+        }
+    }
+}
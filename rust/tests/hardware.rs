@@ -0,0 +1,110 @@
+//! Integration tests against a real AD HAT
+//!
+//! Require a Raspberry Pi with the HAT on the default pins:
+//!
+//! ```text
+//! cargo test --features hardware-tests --test hardware -- --test-threads=1
+//! ```
+
+#![cfg(feature = "hardware-tests")]
+
+use ads1263::{Ads1263, DataRate, GpioChip, Hal, WaitStrategy};
+
+#[test]
+fn detects_gpio_chip() {
+    GpioChip::detect().expect("board detection failed");
+}
+
+#[test]
+fn hal_reports_detected_chip() {
+    let detected = GpioChip::detect().unwrap();
+    let hal = Hal::new().expect("HAL initialization failed");
+    assert_eq!(hal.gpio_chip(), Some(detected));
+}
+
+#[test]
+fn reads_chip_id() {
+    let mut adc = Ads1263::new(Hal::new().unwrap());
+    adc.init_adc1(DataRate::Sps400).unwrap();
+    assert_eq!(adc.read_chip_id().unwrap(), 1);
+}
+
+#[test]
+fn drdy_with_every_wait_strategy() {
+    let mut adc = Ads1263::new(Hal::new().unwrap());
+    adc.init_adc1(DataRate::Sps400).unwrap();
+    for strategy in [
+        WaitStrategy::Interrupt,
+        WaitStrategy::BusySpin,
+        WaitStrategy::SpinThenSleep { spin_us: 200 },
+        WaitStrategy::Sleep {
+            poll_interval: std::time::Duration::from_micros(100),
+        },
+    ] {
+        adc.hal_mut().set_wait_strategy(strategy).unwrap();
+        adc.get_channel_value(0)
+            .unwrap_or_else(|e| panic!("{:?}: {}", strategy, e));
+    }
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn async_clones_read_their_own_channel() {
+    use ads1263::{Ads1263Async, DacVoltage};
+
+    // The DACs drive AIN6 and AIN7 far apart; a read that picked up the
+    // other clone's input would land on the wrong side
+    let mut adc = Ads1263::new(Hal::new().unwrap());
+    adc.set_dac(DacVoltage::Volt4_5, true, true).unwrap();
+    adc.set_dac(DacVoltage::Volt0_5, false, true).unwrap();
+    let adc = Ads1263Async::new(adc).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        adc.init_adc1(DataRate::Sps1200).await.unwrap();
+        let read = |adc: Ads1263Async, channel| {
+            tokio::spawn(async move {
+                let mut volts = Vec::new();
+                for _ in 0..50 {
+                    let raw = adc.read_channel(channel).await.unwrap();
+                    volts.push(Ads1263::raw_to_voltage_adc1(raw, 5.0));
+                }
+                volts
+            })
+        };
+        let high = read(adc.clone(), 6);
+        let low = read(adc.clone(), 7);
+        let (high, low) = (high.await.unwrap(), low.await.unwrap());
+        for (high, low) in high.iter().zip(&low) {
+            assert!(high - low > 3.0, "AIN6 {:.3} V, AIN7 {:.3} V", high, low);
+        }
+    });
+
+    let mut adc = adc.try_into_inner().ok().unwrap();
+    adc.set_dac(DacVoltage::Volt2_5, true, false).unwrap();
+    adc.set_dac(DacVoltage::Volt2_5, false, false).unwrap();
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn empty_sample_stream_ends() {
+    use ads1263::Ads1263Async;
+    use futures_core::Stream;
+    use std::time::Duration;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let adc = Ads1263Async::new(Ads1263::new(Hal::new().unwrap())).unwrap();
+        adc.init_adc1(DataRate::Sps1200).await.unwrap();
+        let mut samples = std::pin::pin!(adc.sample_stream(Vec::new(), 4));
+        let next = std::future::poll_fn(|cx| samples.as_mut().poll_next(cx));
+        let next = tokio::time::timeout(Duration::from_secs(1), next).await;
+        assert!(matches!(next, Ok(None)), "stream did not end");
+    });
+}